@@ -0,0 +1,13 @@
+/// This module provides script functions taking arguments of various types, for use in API tests
+module TestAccount::ArgumentTestData {
+    public(script) fun vector_args(
+        _account: signer,
+        _bytes: vector<u8>,
+        _addresses: vector<address>,
+        _nested: vector<vector<u64>>,
+    ) {
+    }
+
+    public(script) fun generic_args<T>(_account: signer, _amount: u64, _recipient: address) {
+    }
+}
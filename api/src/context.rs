@@ -18,7 +18,14 @@ use aptos_types::{
 use storage_interface::{DbReader, Order};
 
 use anyhow::{ensure, format_err, Result};
-use aptos_types::{state_store::state_key::StateKey, transaction::Version};
+use aptos_types::{
+    state_store::{
+        state_key::{StateKey, StateKeyTag},
+        state_key_prefix::StateKeyPrefix,
+        state_value::StateValue,
+    },
+    transaction::Version,
+};
 use aptos_vm::data_cache::{IntoMoveResolver, RemoteStorageOwned};
 use futures::{channel::oneshot, SinkExt};
 use std::{
@@ -70,6 +77,10 @@ impl Context {
         self.api_config.content_length_limit()
     }
 
+    pub fn view_max_gas_amount(&self) -> u64 {
+        self.api_config.view_max_gas_amount()
+    }
+
     pub fn filter(self) -> impl Filter<Extract = (Context,), Error = Infallible> + Clone {
         warp::any().map(move || self.clone())
     }
@@ -121,6 +132,23 @@ impl Context {
         }))
     }
 
+    pub fn get_table_items(
+        &self,
+        handle: u128,
+        start_after: Option<&StateKey>,
+        limit: u16,
+        version: u64,
+    ) -> Result<(Vec<(StateKey, StateValue)>, Option<StateKey>)> {
+        let table_prefix =
+            StateKeyPrefix::new(StateKeyTag::TableItem, handle.to_be_bytes().to_vec());
+        self.db
+            .get_state_values_by_key_prefix(&table_prefix, start_after, limit as u64, version)
+    }
+
+    pub fn get_table_size(&self, handle: u128, version: u64) -> Result<Option<u64>> {
+        self.db.get_table_size(handle, version)
+    }
+
     pub fn get_block_timestamp(&self, version: u64) -> Result<u64> {
         self.db.get_block_timestamp(version)
     }
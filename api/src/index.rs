@@ -37,12 +37,15 @@ pub fn routes(context: Context) -> impl Filter<Extract = impl Reply, Error = Inf
         .or(transactions::get_account_transactions(context.clone()))
         .or(transactions::submit_bcs_transactions(context.clone()))
         .or(transactions::submit_json_transactions(context.clone()))
+        .or(transactions::simulate_bcs_transactions(context.clone()))
         .or(transactions::create_signing_message(context.clone()))
         .or(events::get_events_by_event_key(context.clone()))
         .or(events::get_events_by_event_handle(context.clone()))
         .or(state::get_account_resource(context.clone()))
         .or(state::get_account_module(context.clone()))
         .or(state::get_table_item(context.clone()))
+        .or(state::get_table_items(context.clone()))
+        .or(state::view_function(context.clone()))
         .or(context.health_check_route().with(metrics("health_check")))
         .with(
             warp::cors()
@@ -5,24 +5,28 @@ use crate::{
     context::Context,
     failpoint::fail_point,
     metrics::metrics,
+    page::parse_limit,
     param::{
-        AddressParam, LedgerVersionParam, MoveIdentifierParam, MoveStructTagParam, TableHandleParam,
+        AddressParam, LedgerVersionParam, MoveIdentifierParam, MoveStructTagParam, Param,
+        TableHandleParam,
     },
     version::Version,
 };
 use anyhow::anyhow;
 use aptos_api_types::{
-    AsConverter, Error, LedgerInfo, MoveModuleBytecode, Response, TableItemRequest, TransactionId,
+    AsConverter, Error, HexEncodedBytes, LedgerInfo, MoveModuleBytecode, MoveType, Response,
+    TableItem, TableItemRequest, TableItems, TransactionId, ViewRequest, U64,
 };
 use aptos_state_view::StateView;
 use aptos_types::{access_path::AccessPath, state_store::state_key::StateKey};
-use aptos_vm::data_cache::AsMoveResolver;
+use aptos_vm::{data_cache::AsMoveResolver, logging::AdapterLogSchema, AptosVM};
 use move_core_types::{
     account_address::AccountAddress,
     identifier::Identifier,
     language_storage::{ModuleId, ResourceKey, StructTag},
 };
-use std::convert::TryInto;
+use serde::Deserialize;
+use std::{convert::TryInto, num::NonZeroU16};
 use storage_interface::state_view::DbStateView;
 use warp::{filters::BoxedFilter, Filter, Rejection, Reply};
 
@@ -71,6 +75,34 @@ pub fn get_table_item(context: Context) -> BoxedFilter<(impl Reply,)> {
         .boxed()
 }
 
+// GET /tables/<table_handle>/items?key_type=<key_type>&value_type=<value_type>
+pub fn get_table_items(context: Context) -> BoxedFilter<(impl Reply,)> {
+    warp::path!("tables" / TableHandleParam / "items")
+        .and(warp::get())
+        .and(warp::query::<TableItemsQuery>())
+        .and(context.filter())
+        .and_then(handle_get_table_items)
+        .with(metrics("get_table_items"))
+        .boxed()
+}
+
+// POST /view
+pub fn view_function(context: Context) -> BoxedFilter<(impl Reply,)> {
+    warp::path!("view")
+        .and(warp::post())
+        .and(warp::body::content_length_limit(
+            context.content_length_limit(),
+        ))
+        .and(warp::body::json::<ViewRequest>())
+        .and(context.filter())
+        .and(warp::query::<Version>())
+        .map(|body, ctx, version: Version| (version.version, body, ctx))
+        .untuple_one()
+        .and_then(handle_view_function)
+        .with(metrics("view_function"))
+        .boxed()
+}
+
 async fn handle_get_account_resource(
     ledger_version: Option<LedgerVersionParam>,
     address: AddressParam,
@@ -111,10 +143,41 @@ async fn handle_get_table_item(
     Ok(State::new(ledger_version, context)?.table_item(handle.parse("table handle")?, body)?)
 }
 
+async fn handle_get_table_items(
+    handle: TableHandleParam,
+    query: TableItemsQuery,
+    context: Context,
+) -> Result<impl Reply, Rejection> {
+    fail_point("endpoint_get_table_items")?;
+    let handle = handle.parse("table handle")?;
+    Ok(State::new(query.version.clone(), context)?.table_items(handle, query)?)
+}
+
+async fn handle_view_function(
+    ledger_version: Option<LedgerVersionParam>,
+    body: ViewRequest,
+    context: Context,
+) -> Result<impl Reply, Rejection> {
+    fail_point("endpoint_view_function")?;
+    let max_gas_amount = context.view_max_gas_amount();
+    Ok(State::new(ledger_version, context)?.view(body, max_gas_amount)?)
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct TableItemsQuery {
+    version: Option<LedgerVersionParam>,
+    key_type: Param<MoveType>,
+    value_type: Param<MoveType>,
+    /// BCS encoded key of the last item of the previous page.
+    start: Option<Param<HexEncodedBytes>>,
+    limit: Option<Param<NonZeroU16>>,
+}
+
 pub(crate) struct State {
     state_view: DbStateView,
     ledger_version: aptos_types::transaction::Version,
     latest_ledger_info: LedgerInfo,
+    context: Context,
 }
 
 impl State {
@@ -141,6 +204,7 @@ impl State {
             state_view,
             ledger_version,
             latest_ledger_info,
+            context,
         })
     }
 
@@ -209,4 +273,84 @@ impl State {
         let move_value = converter.try_into_move_value(&value_type, &bytes)?;
         Response::new(self.latest_ledger_info, &move_value)
     }
+
+    fn table_items(self, handle: u128, query: TableItemsQuery) -> Result<impl Reply, Error> {
+        let key_type = query.key_type.parse("key_type")?.try_into()?;
+        let value_type = query.value_type.parse("value_type")?.try_into()?;
+        let start_after = query
+            .start
+            .map(|start| start.parse("start"))
+            .transpose()?
+            .map(|key| StateKey::table_item(handle, key.into()));
+        let limit = parse_limit(query.limit)?;
+
+        let (entries, next_start_after) = self.context.get_table_items(
+            handle,
+            start_after.as_ref(),
+            limit,
+            self.ledger_version,
+        )?;
+        let next_start = match next_start_after {
+            Some(StateKey::TableItem { key, .. }) => Some(HexEncodedBytes::from(key)),
+            _ => None,
+        };
+
+        let resolver = self.state_view.as_move_resolver();
+        let converter = resolver.as_converter();
+        let items = entries
+            .into_iter()
+            .map(|(state_key, state_value)| match state_key {
+                StateKey::TableItem { key, .. } => Ok(TableItem {
+                    key: converter.try_into_move_value(&key_type, &key)?,
+                    value: converter.try_into_move_value(
+                        &value_type,
+                        &state_value.maybe_bytes.unwrap_or_default(),
+                    )?,
+                }),
+                _ => Err(anyhow!("Unexpected state key {:?} in table", state_key)),
+            })
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(Error::bad_request)?;
+
+        let table_items = TableItems {
+            length: self
+                .context
+                .get_table_size(handle, self.ledger_version)?
+                .map(U64::from),
+            items,
+            next_start,
+        };
+        Response::new(self.latest_ledger_info, &table_items)
+    }
+
+    pub fn view(self, request: ViewRequest, max_gas_amount: u64) -> Result<impl Reply, Error> {
+        let resolver = self.state_view.as_move_resolver();
+        let converter = resolver.as_converter();
+        let (function, return_types) = converter
+            .try_into_view_function(request)
+            .map_err(Error::bad_request)?;
+        let (module, function_name, ty_args, args) = function.into_inner();
+
+        let vm = AptosVM::new(&self.state_view);
+        let return_values = vm
+            .execute_view_function(
+                &resolver,
+                &module,
+                &function_name,
+                ty_args,
+                args,
+                max_gas_amount,
+                &AdapterLogSchema::new(self.state_view.id(), 0),
+            )
+            .map_err(|status| {
+                Error::bad_request(format!("view function execution failed: {:?}", status))
+            })?;
+
+        let values = return_types
+            .iter()
+            .zip(return_values.iter())
+            .map(|(typ, bytes)| converter.try_into_move_value(typ, bytes))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Response::new(self.latest_ledger_info, &values)
+    }
 }
@@ -1,15 +1,24 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{current_function_name, tests::new_test_context};
-use aptos_api_types::{new_vm_ascii_string, AsConverter, MoveConverter, MoveType};
+use crate::{
+    current_function_name,
+    tests::{build_test_module, new_test_context},
+};
+use aptos_api_types::{
+    new_vm_ascii_string, AsConverter, MoveConverter, MoveType, ScriptFunctionPayload,
+    TransactionPayload,
+};
+use aptos_types::transaction::{ScriptFunction, TransactionPayload as VmTransactionPayload};
 use aptos_vm::{data_cache::AsMoveResolver, move_vm_ext::MoveResolverExt};
 use move_core_types::{
     account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::ModuleId,
     value::{MoveStruct, MoveValue as VmMoveValue},
 };
 use serde::Serialize;
-use serde_json::json;
+use serde_json::{json, Value};
 use std::convert::TryInto;
 
 #[tokio::test]
@@ -55,6 +64,94 @@ async fn test_value_conversion() {
     );
 }
 
+#[tokio::test]
+async fn test_script_function_arguments_conversion() {
+    let mut context = new_test_context(current_function_name!());
+    let mut account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    context.commit_block(&vec![txn]).await;
+    let module = build_test_module(account.address(), "ArgumentTestData").await;
+    context
+        .api_publish_module(&mut account, module.try_into().unwrap())
+        .await;
+    let test_module = account.address().to_hex_literal() + "::ArgumentTestData";
+
+    let state_view = context.latest_state_view();
+    let resolver = state_view.as_move_resolver();
+    let converter = resolver.as_converter();
+
+    assert_script_function_arguments_conversion(
+        &converter,
+        "0x1::TestCoin::transfer",
+        json!([]),
+        json!(["0x1", "100"]),
+    );
+    assert_script_function_arguments_conversion(
+        &converter,
+        &format!("{}::vector_args", test_module),
+        json!([]),
+        json!(["0x0102", ["0x1", "0x2"], [["1", "2"], [], ["3"]]]),
+    );
+    assert_script_function_arguments_conversion(
+        &converter,
+        &format!("{}::generic_args", test_module),
+        json!(["0x1::GUID::ID"]),
+        json!(["100", "0x1"]),
+    );
+}
+
+#[tokio::test]
+async fn test_undecodable_script_function_arguments_rendered_as_hex() {
+    let context = new_test_context(current_function_name!());
+    let state_view = context.latest_state_view();
+    let resolver = state_view.as_move_resolver();
+    let converter = resolver.as_converter();
+
+    // the first argument is too short for an address, so the arguments can't be decoded with
+    // the signature of the function, and are rendered as their raw BCS bytes
+    let vm_payload = VmTransactionPayload::ScriptFunction(ScriptFunction::new(
+        ModuleId::new(
+            AccountAddress::from_hex_literal("0x1").unwrap(),
+            Identifier::new("TestCoin").unwrap(),
+        ),
+        Identifier::new("transfer").unwrap(),
+        vec![],
+        vec![vec![1, 2, 3], bcs::to_bytes(&100u64).unwrap()],
+    ));
+    let payload = converter.try_into_transaction_payload(vm_payload).unwrap();
+    match payload {
+        TransactionPayload::ScriptFunctionPayload(payload) => assert_eq!(
+            payload.arguments,
+            vec![json!("0x010203"), json!("0x6400000000000000")]
+        ),
+        payload => panic!("unexpected payload {:?}", payload),
+    }
+}
+
+fn assert_script_function_arguments_conversion<'r, R: MoveResolverExt>(
+    converter: &MoveConverter<'r, R>,
+    function: &str,
+    type_arguments: Value,
+    arguments: Value,
+) {
+    let payload: ScriptFunctionPayload = serde_json::from_value(json!({
+        "function": function,
+        "type_arguments": type_arguments,
+        "arguments": arguments,
+    }))
+    .unwrap();
+    let vm_payload = converter
+        .try_into_aptos_core_transaction_payload(TransactionPayload::ScriptFunctionPayload(
+            payload.clone(),
+        ))
+        .unwrap();
+    let payload_back = converter.try_into_transaction_payload(vm_payload).unwrap();
+    assert_eq!(
+        payload_back,
+        TransactionPayload::ScriptFunctionPayload(payload)
+    );
+}
+
 fn assert_value_conversion<'r, R: MoveResolverExt, V: Serialize>(
     converter: &MoveConverter<'r, R>,
     json_move_type: &str,
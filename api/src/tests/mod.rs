@@ -12,9 +12,42 @@ mod string_resource_test;
 mod test_context;
 mod transactions_test;
 
+use move_binary_format::access::ModuleAccess;
+use move_core_types::account_address::AccountAddress;
+use move_package::BuildConfig;
 use serde_json::Value;
+use std::path::PathBuf;
 pub use test_context::{new_test_context, TestContext};
 
+/// Builds the test package in `api/move-test-package` for the account, and returns the bytecode
+/// of its module of the given name.
+pub async fn build_test_module(account: AccountAddress, name: &str) -> Vec<u8> {
+    let package_dir = PathBuf::from(std::env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .join("api/move-test-package");
+    let build_config = BuildConfig {
+        generate_docs: false,
+        install_dir: Some(package_dir.clone()),
+        additional_named_addresses: [("TestAccount".to_string(), account)].into(),
+        ..Default::default()
+    };
+    let package = build_config
+        .compile_package(&package_dir, &mut Vec::new())
+        .unwrap();
+
+    let mut out = Vec::new();
+    package
+        .compiled_modules()
+        .iter_modules()
+        .into_iter()
+        .find(|module| module.self_id().name().as_str() == name)
+        .unwrap_or_else(|| panic!("could not find test module {}", name))
+        .serialize(&mut out)
+        .unwrap();
+    out
+}
+
 pub fn find_value(val: &Value, filter: for<'r> fn(&'r &Value) -> bool) -> Value {
     let resources = val
         .as_array()
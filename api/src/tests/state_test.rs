@@ -3,14 +3,12 @@
 
 use crate::{
     current_function_name,
-    tests::{new_test_context, TestContext},
+    tests::{build_test_module, new_test_context, TestContext},
 };
 use aptos_sdk::types::LocalAccount;
-use move_core_types::account_address::AccountAddress;
-use move_package::BuildConfig;
 use serde::Serialize;
 use serde_json::{json, Value};
-use std::{convert::TryInto, path::PathBuf};
+use std::convert::TryInto;
 
 #[tokio::test]
 async fn test_get_account_resource() {
@@ -177,6 +175,97 @@ async fn test_get_table_item() {
     assert_table_item(ctx, &nested_table, "u8", "u8", 2, 3).await;
 }
 
+#[tokio::test]
+async fn test_get_table_items() {
+    let mut context = new_test_context(current_function_name!());
+    let ctx = &mut context;
+    let mut account = ctx.gen_account();
+    let acc = &mut account;
+    let txn = ctx.create_user_account(acc);
+    ctx.commit_block(&vec![txn.clone()]).await;
+    make_test_tables(ctx, acc).await;
+
+    let tt = ctx
+        .api_get_account_resource(
+            acc,
+            format!(
+                "{}::TableTestData::TestTables",
+                acc.address().to_hex_literal()
+            ),
+        )
+        .await["data"]
+        .to_owned();
+    let handle: u128 = tt["u64_table"]["handle"].as_str().unwrap().parse().unwrap();
+
+    let resp = ctx
+        .get(&get_table_items(handle, "key_type=u64&value_type=u64"))
+        .await;
+    assert_eq!(
+        resp,
+        json!({"length": "1", "items": [{"key": "1", "value": "1"}]})
+    );
+
+    // A full page points to the next one, which is empty here.
+    let resp = ctx
+        .get(&get_table_items(
+            handle,
+            "key_type=u64&value_type=u64&limit=1",
+        ))
+        .await;
+    assert_eq!(resp["next_start"], json!("0x0100000000000000"));
+    let resp = ctx
+        .get(&get_table_items(
+            handle,
+            "key_type=u64&value_type=u64&limit=1&start=0x0100000000000000",
+        ))
+        .await;
+    assert_eq!(resp, json!({"length": "1", "items": []}));
+}
+
+#[tokio::test]
+async fn test_view_function() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .post(
+            "/view",
+            json!({
+                "function": "0x1::Account::exists_at",
+                "type_arguments": [],
+                "arguments": ["0xA550C18"],
+            }),
+        )
+        .await;
+    assert_eq!(resp, json!([true]));
+
+    let resp = context
+        .post(
+            "/view",
+            json!({
+                "function": "0x1::Account::exists_at",
+                "type_arguments": [],
+                "arguments": ["0xA550C19"],
+            }),
+        )
+        .await;
+    assert_eq!(resp, json!([false]));
+}
+
+#[tokio::test]
+async fn test_view_function_rejects_non_public_function() {
+    let context = new_test_context(current_function_name!());
+    context
+        .expect_status_code(400)
+        .post(
+            "/view",
+            json!({
+                "function": "0x1::TestCoin::claim_mint_capability",
+                "type_arguments": [],
+                "arguments": [],
+            }),
+        )
+        .await;
+}
+
 fn get_account_resource(address: &str, struct_tag: &str) -> String {
     format!("/accounts/{}/resource/{}", address, struct_tag)
 }
@@ -189,8 +278,12 @@ fn get_table_item(handle: u128) -> String {
     format!("/tables/{}/item", handle)
 }
 
+fn get_table_items(handle: u128, query: &str) -> String {
+    format!("/tables/{}/items?{}", handle, query)
+}
+
 async fn make_test_tables(ctx: &mut TestContext, account: &mut LocalAccount) {
-    let module = build_test_module(account.address()).await;
+    let module = build_test_module(account.address(), "TableTestData").await;
 
     ctx.api_publish_module(account, module.try_into().unwrap())
         .await;
@@ -203,32 +296,6 @@ async fn make_test_tables(ctx: &mut TestContext, account: &mut LocalAccount) {
     .await
 }
 
-async fn build_test_module(account: AccountAddress) -> Vec<u8> {
-    let package_dir = PathBuf::from(std::env!("CARGO_MANIFEST_DIR"))
-        .parent()
-        .unwrap()
-        .join("api/move-test-package");
-    let build_config = BuildConfig {
-        generate_docs: false,
-        install_dir: Some(package_dir.clone()),
-        additional_named_addresses: [("TestAccount".to_string(), account)].into(),
-        ..Default::default()
-    };
-    let package = build_config
-        .compile_package(&package_dir, &mut Vec::new())
-        .unwrap();
-
-    let mut out = Vec::new();
-    package
-        .compiled_modules()
-        .iter_modules()
-        .first()
-        .unwrap()
-        .serialize(&mut out)
-        .unwrap();
-    out
-}
-
 async fn api_get_table_item<T: Serialize>(
     ctx: &mut TestContext,
     table: &Value,
@@ -237,6 +237,25 @@ async fn test_post_bcs_format_transaction() {
         .await;
 }
 
+#[tokio::test]
+async fn test_simulate_bcs_format_transaction() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    let hash = txn.committed_hash().to_hex_literal();
+    let resp = context
+        .post_bcs_txn("/transactions/simulate", bcs::to_bytes(&txn).unwrap())
+        .await;
+    assert_eq!(resp["hash"], json!(hash));
+    assert!(resp["success"].as_bool().unwrap(), "{}", pretty(&resp));
+
+    // The simulated transaction is neither committed nor in mempool
+    context
+        .expect_status_code(404)
+        .get(&format!("/transactions/{}", hash))
+        .await;
+}
+
 #[tokio::test]
 async fn test_post_invalid_bcs_format_transaction() {
     let mut context = new_test_context(current_function_name!());
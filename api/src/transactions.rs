@@ -14,12 +14,14 @@ use aptos_api_types::{
     TransactionData, TransactionId, TransactionOnChainData, TransactionSigningMessage,
     UserTransactionRequest,
 };
+use aptos_crypto::HashValue;
 use aptos_types::{
     mempool_status::MempoolStatusCode,
-    transaction::{RawTransaction, SignedTransaction},
+    transaction::{RawTransaction, SignedTransaction, TransactionInfo, TransactionStatus},
 };
+use aptos_vm::AptosVM;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use warp::{
     filters::BoxedFilter,
     http::{header::CONTENT_TYPE, StatusCode},
@@ -95,6 +97,24 @@ pub fn submit_bcs_transactions(context: Context) -> BoxedFilter<(impl Reply,)> {
         .boxed()
 }
 
+// POST /transactions/simulate with BCS
+pub fn simulate_bcs_transactions(context: Context) -> BoxedFilter<(impl Reply,)> {
+    warp::path!("transactions" / "simulate")
+        .and(warp::post())
+        .and(warp::body::content_length_limit(
+            context.content_length_limit(),
+        ))
+        .and(warp::header::exact(
+            CONTENT_TYPE.as_str(),
+            BCS_SIGNED_TRANSACTION,
+        ))
+        .and(warp::body::bytes())
+        .and(context.filter())
+        .and_then(handle_simulate_bcs_transactions)
+        .with(metrics("simulate_bcs_transactions"))
+        .boxed()
+}
+
 // POST /transactions/signing_message
 pub fn create_signing_message(context: Context) -> BoxedFilter<(impl Reply,)> {
     warp::path!("transactions" / "signing_message")
@@ -153,6 +173,16 @@ async fn handle_submit_bcs_transactions(
     Ok(Transactions::new(context)?.create(txn).await?)
 }
 
+async fn handle_simulate_bcs_transactions(
+    body: bytes::Bytes,
+    context: Context,
+) -> Result<impl Reply, Rejection> {
+    fail_point("endpoint_simulate_bcs_transactions")?;
+    let txn = bcs::from_bytes(&body)
+        .map_err(|err| Error::invalid_request_body(format!("deserialize error: {}", err)))?;
+    Ok(Transactions::new(context)?.simulate(txn)?)
+}
+
 async fn handle_create_signing_message(
     body: UserTransactionRequest,
     context: Context,
@@ -215,6 +245,53 @@ impl Transactions {
         }
     }
 
+    /// Executes `txn` on top of the latest ledger version without committing it, and renders
+    /// it as if it was committed at that version.
+    pub fn simulate(self, txn: SignedTransaction) -> Result<impl Reply, Error> {
+        let version = self.ledger_info.version();
+        let state_view = self.context.state_view_at_version(version)?;
+        let (vm_status, output) = AptosVM::execute_block_and_keep_vm_status(
+            vec![aptos_types::transaction::Transaction::UserTransaction(
+                txn.clone(),
+            )],
+            &state_view,
+        )
+        .map_err(|status| Error::bad_request(format!("invalid transaction: {:?}", status)))?
+        .pop()
+        .ok_or_else(|| anyhow!("No output for the simulated transaction"))?;
+        let status = match output.status() {
+            TransactionStatus::Keep(status) => status.clone(),
+            _ => {
+                return Err(Error::bad_request(format!(
+                    "invalid transaction: {:?}",
+                    vm_status
+                )))
+            }
+        };
+
+        let info = TransactionInfo::new(
+            txn.clone().committed_hash(),
+            HashValue::zero(),
+            HashValue::zero(),
+            output.gas_used(),
+            status,
+        );
+        let data = TransactionOnChainData {
+            version,
+            transaction: aptos_types::transaction::Transaction::UserTransaction(txn),
+            info,
+            events: output.events().to_vec(),
+            accumulator_root_hash: HashValue::zero(),
+            changes: output.write_set().clone(),
+        };
+        let timestamp = self.context.get_block_timestamp(version)?;
+        let resolver = self.context.move_resolver()?;
+        let simulated_txn = resolver
+            .as_converter()
+            .try_into_onchain_transaction(timestamp, data)?;
+        Response::new(self.ledger_info, &simulated_txn)
+    }
+
     pub fn list(self, page: Page) -> Result<impl Reply, Error> {
         let ledger_version = self.ledger_info.version();
         let limit = page.limit()?;
@@ -4,9 +4,10 @@
 use crate::{
     transaction::{ModuleBundlePayload, StateCheckpointTransaction},
     Bytecode, DirectWriteSet, Event, HexEncodedBytes, MoveFunction, MoveModuleBytecode,
-    MoveResource, MoveScriptBytecode, MoveValue, ScriptFunctionId, ScriptFunctionPayload,
+    MoveResource, MoveScriptBytecode, MoveType, MoveValue, ScriptFunctionId, ScriptFunctionPayload,
     ScriptPayload, ScriptWriteSet, Transaction, TransactionInfo, TransactionOnChainData,
-    TransactionPayload, UserTransactionRequest, WriteSet, WriteSetChange, WriteSetPayload,
+    TransactionPayload, UserTransactionRequest, ViewRequest, WriteSet, WriteSetChange,
+    WriteSetPayload,
 };
 use anyhow::{bail, ensure, format_err, Result};
 use aptos_crypto::{hash::CryptoHash, HashValue};
@@ -23,7 +24,7 @@ use aptos_types::{
 use aptos_vm::move_vm_ext::MoveResolverExt;
 use move_binary_format::file_format::FunctionHandleIndex;
 use move_core_types::{
-    identifier::Identifier,
+    identifier::{IdentStr, Identifier},
     language_storage::{ModuleId, StructTag, TypeTag},
     value::{MoveStructLayout, MoveTypeLayout},
 };
@@ -94,6 +95,7 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
                 (info, payload, events).into()
             }
             BlockMetadata(txn) => (&txn, info).into(),
+            BlockMetadataV2(txn) => (txn.metadata(), info).into(),
             StateCheckpoint => {
                 Transaction::StateCheckpointTransaction(StateCheckpointTransaction {
                     info,
@@ -143,14 +145,13 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
             }),
             ScriptFunction(fun) => {
                 let (module, function, ty_args, args) = fun.into_inner();
-                let func_args = self
-                    .inner
-                    .view_function_arguments(&module, &function, &args);
-                let json_args = match func_args {
-                    Ok(values) => values
-                        .into_iter()
-                        .map(|v| MoveValue::try_from(v)?.json())
-                        .collect::<Result<_>>()?,
+                // Committed transactions may call a function that doesn't exist, or pass arguments
+                // that don't match its signature: their arguments are rendered as the raw BCS
+                // bytes, in hex, instead of failing the conversion of the whole transaction.
+                let json_args = match self
+                    .try_into_script_function_arguments(&module, &function, &ty_args, &args)
+                {
+                    Ok(values) => values,
                     Err(_e) => args
                         .into_iter()
                         .map(|arg| HexEncodedBytes::from(arg).json())
@@ -170,6 +171,39 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
         Ok(ret)
     }
 
+    /// Decodes the BCS arguments of a script function call into typed JSON values, using the
+    /// signature of the function in its on-chain module instantiated with `ty_args`.
+    pub fn try_into_script_function_arguments(
+        &self,
+        module: &ModuleId,
+        function: &IdentStr,
+        ty_args: &[TypeTag],
+        args: &[Vec<u8>],
+    ) -> Result<Vec<serde_json::Value>> {
+        let code = self.inner.get_module(module)? as Rc<dyn Bytecode>;
+        let func = code
+            .find_script_function(function)
+            .ok_or_else(|| format_err!("could not find script function by {}", function))?;
+        let arg_types = func
+            .params
+            .iter()
+            .filter(|p| !p.is_signer())
+            .map(|p| instantiate_type(p, ty_args))
+            .collect::<Result<Vec<_>>>()?;
+        ensure!(
+            arg_types.len() == args.len(),
+            "expected {} arguments for script function {}, but got {}",
+            arg_types.len(),
+            function,
+            args.len()
+        );
+        arg_types
+            .iter()
+            .zip(args)
+            .map(|(typ, arg)| self.try_into_move_value(typ, arg)?.json())
+            .collect()
+    }
+
     pub fn try_into_write_set_payload(
         &self,
         payload: aptos_types::transaction::WriteSetPayload,
@@ -357,8 +391,12 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
                     function,
                     type_arguments.len()
                 );
+                let type_arguments = type_arguments
+                    .into_iter()
+                    .map(|v| v.try_into())
+                    .collect::<Result<Vec<TypeTag>>>()?;
                 let args = self
-                    .try_into_vm_values(func, arguments)?
+                    .try_into_vm_values(func, &type_arguments, arguments)?
                     .iter()
                     .map(bcs::to_bytes)
                     .collect::<Result<_, bcs::Error>>()?;
@@ -366,10 +404,7 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
                 Target::ScriptFunction(ScriptFunction::new(
                     module.into(),
                     function.name,
-                    type_arguments
-                        .into_iter()
-                        .map(|v| v.try_into())
-                        .collect::<Result<_>>()?,
+                    type_arguments,
                     args,
                 ))
             }
@@ -392,13 +427,14 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
                 let MoveScriptBytecode { bytecode, abi } = code.try_parse_abi();
                 match abi {
                     Some(func) => {
-                        let args = self.try_into_vm_values(func, arguments)?;
+                        let type_arguments = type_arguments
+                            .into_iter()
+                            .map(|v| v.try_into())
+                            .collect::<Result<Vec<TypeTag>>>()?;
+                        let args = self.try_into_vm_values(func, &type_arguments, arguments)?;
                         Target::Script(Script::new(
                             bytecode.into(),
-                            type_arguments
-                                .into_iter()
-                                .map(|v| v.try_into())
-                                .collect::<Result<_>>()?,
+                            type_arguments,
                             args.into_iter()
                                 .map(|arg| arg.try_into())
                                 .collect::<Result<_>>()?,
@@ -416,9 +452,57 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
         Ok(ret)
     }
 
+    /// Converts a view request into a `ScriptFunction` carrying the BCS encoded arguments, along
+    /// with the types of the values returned by the function.
+    pub fn try_into_view_function(
+        &self,
+        request: ViewRequest,
+    ) -> Result<(ScriptFunction, Vec<TypeTag>)> {
+        let ViewRequest {
+            function,
+            type_arguments,
+            arguments,
+        } = request;
+
+        let module = function.module.clone();
+        let code = self.inner.get_module(&module.clone().into())? as Rc<dyn Bytecode>;
+        let func = code
+            .find_public_function(function.name.as_ident_str())
+            .ok_or_else(|| format_err!("could not find public function by {}", function))?;
+        ensure!(
+            func.generic_type_params.len() == type_arguments.len(),
+            "expect {} type arguments for function {}, but got {}",
+            func.generic_type_params.len(),
+            function,
+            type_arguments.len()
+        );
+        let type_arguments = type_arguments
+            .into_iter()
+            .map(|v| v.try_into())
+            .collect::<Result<Vec<TypeTag>>>()?;
+        let return_types = func
+            .return_
+            .iter()
+            .map(|typ| instantiate_type(typ, &type_arguments))
+            .collect::<Result<_>>()?;
+        let args = self
+            .try_into_vm_values(func, &type_arguments, arguments)?
+            .iter()
+            .map(bcs::to_bytes)
+            .collect::<Result<_, bcs::Error>>()?;
+
+        Ok((
+            ScriptFunction::new(module.into(), function.name, type_arguments, args),
+            return_types,
+        ))
+    }
+
+    /// Converts the JSON arguments of `func` into `MoveValue`s, using `type_args` to instantiate
+    /// the generic type parameters of the function's signature.
     pub fn try_into_vm_values(
         &self,
         func: MoveFunction,
+        type_args: &[TypeTag],
         args: Vec<serde_json::Value>,
     ) -> Result<Vec<move_core_types::value::MoveValue>> {
         let arg_types = func
@@ -443,7 +527,7 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
             .zip(args.into_iter())
             .enumerate()
             .map(|(i, (arg_type, arg))| {
-                self.try_into_vm_value(&arg_type.clone().try_into()?, arg)
+                self.try_into_vm_value(&instantiate_type(&arg_type, type_args)?, arg)
                     .map_err(|e| {
                         format_err!(
                             "parse arguments[{}] failed, expect {}, caused by error: {}",
@@ -627,6 +711,31 @@ impl<R: MoveResolverExt> AsConverter<R> for R {
     }
 }
 
+/// Converts a `MoveType` from a function signature into a `TypeTag`, substituting generic type
+/// parameters with the given type arguments.
+fn instantiate_type(typ: &MoveType, type_args: &[TypeTag]) -> Result<TypeTag> {
+    Ok(match typ {
+        MoveType::GenericTypeParam { index } => type_args
+            .get(*index as usize)
+            .cloned()
+            .ok_or_else(|| format_err!("missing type argument for T{}", index))?,
+        MoveType::Vector { items } => {
+            TypeTag::Vector(Box::new(instantiate_type(items, type_args)?))
+        }
+        MoveType::Struct(tag) => TypeTag::Struct(StructTag {
+            address: tag.address.into(),
+            module: tag.module.clone(),
+            name: tag.name.clone(),
+            type_params: tag
+                .generic_type_params
+                .iter()
+                .map(|param| instantiate_type(param, type_args))
+                .collect::<Result<_>>()?,
+        }),
+        _ => typ.clone().try_into()?,
+    })
+}
+
 pub fn new_vm_ascii_string(string: &str) -> move_core_types::value::MoveValue {
     use move_core_types::value::{MoveStruct, MoveValue};
 
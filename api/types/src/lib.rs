@@ -14,6 +14,7 @@ mod move_types;
 mod response;
 mod table;
 mod transaction;
+mod view;
 
 pub use account::AccountData;
 pub use address::Address;
@@ -31,7 +32,7 @@ pub use move_types::{
 pub use response::{
     Response, X_APTOS_CHAIN_ID, X_APTOS_EPOCH, X_APTOS_LEDGER_TIMESTAMP, X_APTOS_LEDGER_VERSION,
 };
-pub use table::TableItemRequest;
+pub use table::{TableItem, TableItemRequest, TableItems};
 pub use transaction::{
     BlockMetadataTransaction, DirectWriteSet, Event, GenesisTransaction, PendingTransaction,
     ScriptFunctionPayload, ScriptPayload, ScriptWriteSet, Transaction, TransactionData,
@@ -39,3 +40,4 @@ pub use transaction::{
     TransactionSigningMessage, UserTransaction, UserTransactionRequest, WriteSet, WriteSetChange,
     WriteSetPayload,
 };
+pub use view::ViewRequest;
@@ -1,7 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::MoveType;
+use crate::{HexEncodedBytes, MoveType, MoveValue, U64};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -11,3 +11,21 @@ pub struct TableItemRequest {
     pub value_type: MoveType,
     pub key: Value,
 }
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TableItem {
+    pub key: MoveValue,
+    pub value: MoveValue,
+}
+
+/// A page of the items of a table, ordered by the BCS encoding of their keys.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TableItems {
+    /// Total number of items in the table, absent if the node doesn't know the size of the table.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub length: Option<U64>,
+    pub items: Vec<TableItem>,
+    /// BCS encoded key to pass as `start` to fetch the next page, absent on the last page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_start: Option<HexEncodedBytes>,
+}
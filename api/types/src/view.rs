@@ -0,0 +1,13 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{MoveType, ScriptFunctionId};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ViewRequest {
+    pub function: ScriptFunctionId,
+    pub type_arguments: Vec<MoveType>,
+    pub arguments: Vec<Value>,
+}
@@ -3,9 +3,9 @@
 
 use anyhow::Result;
 use clap::StructOpt;
+use framework::natives::gas_schedule::INITIAL_APTOS_COST_SCHEDULE;
 use move_cli::{Command, Move};
 use move_core_types::errmap::ErrorMapping;
-use move_vm_types::gas_schedule::INITIAL_COST_SCHEDULE;
 
 #[derive(StructOpt)]
 pub struct AfCli {
@@ -29,7 +29,7 @@ fn main() -> Result<()> {
     match &args.cmd {
         AfCommands::Command(cmd) => move_cli::run_cli(
             aptos_vm::natives::aptos_natives(),
-            &INITIAL_COST_SCHEDULE,
+            &INITIAL_APTOS_COST_SCHEDULE,
             &error_descriptions,
             &args.move_args,
             cmd,
@@ -15,7 +15,7 @@ use aptos_types::{
     event::EventKey,
     on_chain_config::ValidatorSet,
     state_store::{state_key::StateKey, state_value::StateValue},
-    transaction::{Transaction, Version},
+    transaction::{Transaction, TransactionInfo, TransactionOutput, Version},
 };
 use move_binary_format::file_format::CompiledModule;
 
@@ -39,6 +39,14 @@ pub trait AptosValidatorInterface: Sync {
 
     fn get_committed_transactions(&self, start: Version, limit: u64) -> Result<Vec<Transaction>>;
 
+    /// Get the outputs (write set, events, gas and status) recorded in storage for the committed
+    /// transactions in `[start, start + limit)`, together with their `TransactionInfo`s.
+    fn get_committed_transaction_outputs(
+        &self,
+        start: Version,
+        limit: u64,
+    ) -> Result<Vec<(TransactionOutput, TransactionInfo)>>;
+
     fn get_latest_version(&self) -> Result<Version>;
 
     fn get_version_by_account_sequence(
@@ -10,7 +10,7 @@ use aptos_types::{
     contract_event::EventWithProof,
     event::EventKey,
     state_store::{state_key::StateKey, state_value::StateValue},
-    transaction::{Transaction, Version},
+    transaction::{Transaction, TransactionInfo, TransactionOutput, Version},
 };
 use aptosdb::AptosDB;
 use std::{convert::TryFrom, path::Path, sync::Arc};
@@ -19,6 +19,10 @@ use storage_interface::{DbReader, Order};
 pub struct DBDebuggerInterface(Arc<dyn DbReader>);
 
 impl DBDebuggerInterface {
+    pub fn new(db: Arc<dyn DbReader>) -> Self {
+        Self(db)
+    }
+
     pub fn open<P: AsRef<Path> + Clone>(db_root_path: P) -> Result<Self> {
         Ok(Self(Arc::new(AptosDB::open(
             db_root_path,
@@ -69,6 +73,22 @@ impl AptosValidatorInterface for DBDebuggerInterface {
             .transactions)
     }
 
+    fn get_committed_transaction_outputs(
+        &self,
+        start: Version,
+        limit: u64,
+    ) -> Result<Vec<(TransactionOutput, TransactionInfo)>> {
+        let output_list =
+            self.0
+                .get_transaction_outputs(start, limit, self.get_latest_version()?)?;
+        Ok(output_list
+            .transactions_and_outputs
+            .into_iter()
+            .map(|(_, output)| output)
+            .zip(output_list.proof.transaction_infos)
+            .collect())
+    }
+
     fn get_latest_version(&self) -> Result<Version> {
         let (version, _) = self
             .0
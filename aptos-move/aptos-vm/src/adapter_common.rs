@@ -17,7 +17,7 @@ use crate::{
 use aptos_logger::prelude::*;
 use aptos_types::{
     access_path::AccessPath,
-    block_metadata::BlockMetadata,
+    block_metadata::{BlockMetadata, BlockMetadataV2},
     state_store::state_key::StateKey,
     transaction::{
         Transaction, TransactionArgument, TransactionOutput, TransactionPayload, TransactionStatus,
@@ -242,6 +242,7 @@ pub enum PreprocessedTransaction {
     UserTransaction(Box<SignatureCheckedTransaction>),
     WaypointWriteSet(WriteSetPayload),
     BlockMetadata(BlockMetadata),
+    BlockMetadataV2(BlockMetadataV2),
     WriteSet(Box<SignatureCheckedTransaction>),
     InvalidSignature,
     StateCheckpoint,
@@ -254,6 +255,7 @@ pub enum PreprocessedTransaction {
 pub(crate) fn preprocess_transaction<A: VMAdapter>(txn: Transaction) -> PreprocessedTransaction {
     match txn {
         Transaction::BlockMetadata(b) => PreprocessedTransaction::BlockMetadata(b),
+        Transaction::BlockMetadataV2(b) => PreprocessedTransaction::BlockMetadataV2(b),
         Transaction::GenesisTransaction(ws) => PreprocessedTransaction::WaypointWriteSet(ws),
         Transaction::UserTransaction(txn) => {
             let checked_txn = match A::check_signature(txn) {
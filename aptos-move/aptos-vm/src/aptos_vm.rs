@@ -37,11 +37,14 @@ use aptos_types::{
     write_set::{WriteSet, WriteSetMut},
 };
 use fail::fail_point;
-use move_binary_format::{errors::VMResult, CompiledModule};
+use move_binary_format::{
+    access::ModuleAccess, errors::VMResult, file_format::Visibility, CompiledModule,
+};
 use move_core_types::{
     account_address::AccountAddress,
     gas_schedule::{GasAlgebra, GasUnits},
-    language_storage::ModuleId,
+    identifier::IdentStr,
+    language_storage::{ModuleId, TypeTag},
     transaction_argument::convert_txn_args,
     value::{serialize_values, MoveValue},
 };
@@ -190,6 +193,62 @@ impl AptosVM {
         self.0.load_module(module_id, state)
     }
 
+    /// Executes the public function `module_id::function_name` against `state` without a
+    /// transaction and returns its BCS serialized return values. No account is charged and any
+    /// changes made by the function are discarded; `max_gas_amount` only bounds the execution.
+    pub fn execute_view_function<S: MoveResolverExt>(
+        &self,
+        state: &S,
+        module_id: &ModuleId,
+        function_name: &IdentStr,
+        ty_args: Vec<TypeTag>,
+        args: Vec<Vec<u8>>,
+        max_gas_amount: u64,
+        log_context: &AdapterLogSchema,
+    ) -> Result<Vec<Vec<u8>>, VMStatus> {
+        let module = self
+            .load_module(module_id, state)
+            .map_err(|e| e.into_vm_status())?;
+        let is_public = module.function_defs().iter().any(|def| {
+            let handle = module.function_handle_at(def.function);
+            def.visibility == Visibility::Public
+                && module.identifier_at(handle.name) == function_name
+        });
+        if !is_public {
+            return Err(VMStatus::Error(StatusCode::FUNCTION_RESOLUTION_FAILURE));
+        }
+
+        let mut session = self.new_session(state, SessionId::void());
+        let function = session.load_function(module_id, function_name, &ty_args)?;
+        if function.parameters.iter().any(|ty| {
+            matches!(
+                ty,
+                Type::Signer | Type::Reference(_) | Type::MutableReference(_)
+            )
+        }) {
+            return Err(VMStatus::Error(StatusCode::INVALID_MAIN_FUNCTION_SIGNATURE));
+        }
+
+        let mut gas_status = GasStatus::new(
+            self.0.get_gas_schedule(log_context)?,
+            GasUnits::new(max_gas_amount),
+        );
+        let return_values = session
+            .execute_function_bypass_visibility(
+                module_id,
+                function_name,
+                ty_args,
+                args,
+                &mut gas_status,
+            )
+            .map_err(|e| e.into_vm_status())?
+            .return_values;
+        Ok(return_values
+            .into_iter()
+            .map(|(bytes, _layout)| bytes)
+            .collect())
+    }
+
     /// Generates a transaction output for a transaction that encountered errors during the
     /// execution process. This is public for now only for tests.
     pub fn failed_transaction_cleanup<S: MoveResolverExt>(
@@ -567,6 +626,7 @@ impl AptosVM {
         &self,
         storage: &S,
         block_metadata: BlockMetadata,
+        failed_proposers: Option<Vec<AccountAddress>>,
         log_context: &AdapterLogSchema,
     ) -> Result<(VMStatus, TransactionOutput), VMStatus> {
         fail_point!("move_adapter::process_block_prologue", |_| {
@@ -586,25 +646,35 @@ impl AptosVM {
             .new_session(storage, SessionId::block_meta(&block_metadata));
 
         let (round, timestamp, previous_vote, proposer) = block_metadata.into_inner();
-        let args = serialize_values(&vec![
+        let mut args = vec![
             MoveValue::Signer(txn_data.sender),
             MoveValue::U64(round),
             MoveValue::U64(timestamp),
             MoveValue::Vector(previous_vote.into_iter().map(MoveValue::Address).collect()),
             MoveValue::Address(proposer),
-        ]);
+        ];
+        let prologue = match failed_proposers {
+            Some(failed_proposers) => {
+                args.push(MoveValue::Vector(
+                    failed_proposers
+                        .into_iter()
+                        .map(MoveValue::Address)
+                        .collect(),
+                ));
+                BLOCK_PROLOGUE_V2
+            }
+            None => BLOCK_PROLOGUE,
+        };
         session
             .execute_function_bypass_visibility(
                 &BLOCK_MODULE,
-                BLOCK_PROLOGUE,
+                prologue,
                 vec![],
-                args,
+                serialize_values(&args),
                 &mut gas_status,
             )
             .map(|_return_vals| ())
-            .or_else(|e| {
-                expect_only_successful_execution(e, BLOCK_PROLOGUE.as_str(), log_context)
-            })?;
+            .or_else(|e| expect_only_successful_execution(e, prologue.as_str(), log_context))?;
         SYSTEM_TRANSACTIONS_EXECUTED.inc();
 
         let output = get_transaction_output(
@@ -893,8 +963,22 @@ impl VMAdapter for AptosVM {
     ) -> Result<(VMStatus, TransactionOutput, Option<String>), VMStatus> {
         Ok(match txn {
             PreprocessedTransaction::BlockMetadata(block_metadata) => {
-                let (vm_status, output) =
-                    self.process_block_prologue(data_cache, block_metadata.clone(), log_context)?;
+                let (vm_status, output) = self.process_block_prologue(
+                    data_cache,
+                    block_metadata.clone(),
+                    None,
+                    log_context,
+                )?;
+                (vm_status, output, Some("block_prologue".to_string()))
+            }
+            PreprocessedTransaction::BlockMetadataV2(block_metadata) => {
+                let (block_metadata, failed_proposers) = block_metadata.clone().into_inner();
+                let (vm_status, output) = self.process_block_prologue(
+                    data_cache,
+                    block_metadata,
+                    Some(failed_proposers),
+                    log_context,
+                )?;
                 (vm_status, output, Some("block_prologue".to_string()))
             }
             PreprocessedTransaction::WaypointWriteSet(write_set_payload) => {
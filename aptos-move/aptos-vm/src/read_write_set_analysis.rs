@@ -5,11 +5,15 @@ use crate::{
     adapter_common::PreprocessedTransaction,
     move_vm_ext::MoveResolverExt,
     script_to_script_function::remapping,
-    system_module_names::{BLOCK_MODULE, BLOCK_PROLOGUE, SCRIPT_PROLOGUE_NAME, USER_EPILOGUE_NAME},
+    system_module_names::{
+        BLOCK_MODULE, BLOCK_PROLOGUE, BLOCK_PROLOGUE_V2, SCRIPT_PROLOGUE_NAME, USER_EPILOGUE_NAME,
+    },
 };
 use anyhow::{anyhow, bail, Result};
 use aptos_types::{
+    account_address::AccountAddress,
     account_config,
+    block_metadata::BlockMetadata,
     transaction::{SignedTransaction, TransactionPayload},
 };
 use move_bytecode_utils::module_cache::SyncModuleCache;
@@ -35,6 +39,7 @@ const TRANSACTION_FEES_NAME: &IdentStr = ident_str!("TransactionFee");
 pub fn add_on_functions_list() -> Vec<(ModuleId, Identifier)> {
     vec![
         (BLOCK_MODULE.clone(), BLOCK_PROLOGUE.to_owned()),
+        (BLOCK_MODULE.clone(), BLOCK_PROLOGUE_V2.to_owned()),
         (
             account_config::constants::APTOS_ACCOUNT_MODULE.clone(),
             SCRIPT_PROLOGUE_NAME.to_owned(),
@@ -175,24 +180,11 @@ impl<'a, R: MoveResolverExt> ReadWriteSetAnalysis<'a, R> {
                 self.get_keys_user_transaction_impl(tx, concretize)
             }
             PreprocessedTransaction::BlockMetadata(block_metadata) => {
-                let (round, timestamp, previous_vote, proposer) =
-                    block_metadata.clone().into_inner();
-                let args = serialize_values(&vec![
-                    MoveValue::Signer(account_config::reserved_vm_address()),
-                    MoveValue::U64(round),
-                    MoveValue::U64(timestamp),
-                    MoveValue::Vector(previous_vote.into_iter().map(MoveValue::Address).collect()),
-                    MoveValue::Address(proposer),
-                ]);
-                let metadata_access = self.get_partially_concretized_summary(
-                    &BLOCK_MODULE,
-                    BLOCK_PROLOGUE,
-                    &[],
-                    &args,
-                    &[],
-                    &self.module_cache,
-                )?;
-                self.concretize_secondary_indexes(metadata_access, concretize)
+                self.get_keys_block_prologue(block_metadata.clone(), None, concretize)
+            }
+            PreprocessedTransaction::BlockMetadataV2(block_metadata) => {
+                let (block_metadata, failed_proposers) = block_metadata.clone().into_inner();
+                self.get_keys_block_prologue(block_metadata, Some(failed_proposers), concretize)
             }
             PreprocessedTransaction::InvalidSignature => Ok((vec![], vec![])),
             PreprocessedTransaction::WriteSet(_) | PreprocessedTransaction::WaypointWriteSet(_) => {
@@ -202,6 +194,43 @@ impl<'a, R: MoveResolverExt> ReadWriteSetAnalysis<'a, R> {
         }
     }
 
+    fn get_keys_block_prologue(
+        &self,
+        block_metadata: BlockMetadata,
+        failed_proposers: Option<Vec<AccountAddress>>,
+        concretize: bool,
+    ) -> Result<(Vec<ResourceKey>, Vec<ResourceKey>)> {
+        let (round, timestamp, previous_vote, proposer) = block_metadata.into_inner();
+        let mut args = vec![
+            MoveValue::Signer(account_config::reserved_vm_address()),
+            MoveValue::U64(round),
+            MoveValue::U64(timestamp),
+            MoveValue::Vector(previous_vote.into_iter().map(MoveValue::Address).collect()),
+            MoveValue::Address(proposer),
+        ];
+        let prologue = match failed_proposers {
+            Some(failed_proposers) => {
+                args.push(MoveValue::Vector(
+                    failed_proposers
+                        .into_iter()
+                        .map(MoveValue::Address)
+                        .collect(),
+                ));
+                BLOCK_PROLOGUE_V2
+            }
+            None => BLOCK_PROLOGUE,
+        };
+        let metadata_access = self.get_partially_concretized_summary(
+            &BLOCK_MODULE,
+            prologue,
+            &[],
+            &serialize_values(&args),
+            &[],
+            &self.module_cache,
+        )?;
+        self.concretize_secondary_indexes(metadata_access, concretize)
+    }
+
     fn get_concretized_keys_script_function(
         &self,
         tx: &SignedTransaction,
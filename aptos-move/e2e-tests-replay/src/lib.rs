@@ -27,7 +27,7 @@ use aptos_vm::{
     move_vm_ext::{MoveVmExt, SessionExt, SessionId, SessionOutput},
     script_to_script_function::remapping,
     system_module_names::{
-        BLOCK_MODULE, BLOCK_PROLOGUE, SCRIPT_PROLOGUE_NAME, USER_EPILOGUE_NAME,
+        BLOCK_MODULE, BLOCK_PROLOGUE, BLOCK_PROLOGUE_V2, SCRIPT_PROLOGUE_NAME, USER_EPILOGUE_NAME,
         WRITESET_EPILOGUE_NAME, WRITESET_PROLOGUE_NAME,
     },
     transaction_metadata::TransactionMetadata,
@@ -509,22 +509,36 @@ impl<'env> TraceReplayer<'env> {
     pub fn replay_txn_block_metadata(
         &self,
         block_metadata: BlockMetadata,
+        failed_proposers: Option<Vec<AccountAddress>>,
         expect_output: &TransactionOutput,
     ) {
         // args
         let signer = reserved_vm_address();
         let session_id = SessionId::block_meta(&block_metadata);
         let (round, timestamp, previous_votes, proposer) = block_metadata.into_inner();
-        let args: Vec<_> = vec![
+        let mut args = vec![
             MoveValue::Signer(signer),
             MoveValue::U64(round),
             MoveValue::U64(timestamp),
             MoveValue::Vector(previous_votes.into_iter().map(MoveValue::Address).collect()),
             MoveValue::Address(proposer),
-        ]
-        .into_iter()
-        .map(|v| v.simple_serialize().unwrap())
-        .collect();
+        ];
+        let prologue = match failed_proposers {
+            Some(failed_proposers) => {
+                args.push(MoveValue::Vector(
+                    failed_proposers
+                        .into_iter()
+                        .map(MoveValue::Address)
+                        .collect(),
+                ));
+                &*BLOCK_PROLOGUE_V2
+            }
+            None => &*BLOCK_PROLOGUE,
+        };
+        let args: Vec<_> = args
+            .into_iter()
+            .map(|v| v.simple_serialize().unwrap())
+            .collect();
 
         // execute
         let move_vm = MoveVmExt::new().unwrap();
@@ -543,7 +557,7 @@ impl<'env> TraceReplayer<'env> {
             &mut session,
             xrunner.as_mut(),
             &*BLOCK_MODULE,
-            &*BLOCK_PROLOGUE,
+            prologue,
             vec![],
             args,
         );
@@ -955,7 +969,7 @@ fn replay_trace<P: AsRef<Path>>(
                         return Ok(());
                     }
                 }
-                Transaction::BlockMetadata(block_metadata) => {
+                Transaction::BlockMetadata(_) | Transaction::BlockMetadataV2(_) => {
                     if !matches!(
                         res.status(),
                         TransactionStatus::Keep(KeptVMStatus::Executed)
@@ -968,7 +982,15 @@ fn replay_trace<P: AsRef<Path>>(
                         }
                         return Ok(());
                     }
-                    replayer.replay_txn_block_metadata(block_metadata, &res);
+                    let (block_metadata, failed_proposers) = match txn {
+                        Transaction::BlockMetadataV2(block_metadata) => {
+                            let (block_metadata, failed_proposers) = block_metadata.into_inner();
+                            (block_metadata, Some(failed_proposers))
+                        }
+                        Transaction::BlockMetadata(block_metadata) => (block_metadata, None),
+                        _ => unreachable!(),
+                    };
+                    replayer.replay_txn_block_metadata(block_metadata, failed_proposers, &res);
                     replayer.data_store.add_write_set(res.write_set());
                 }
                 Transaction::UserTransaction(signed_txn) => {
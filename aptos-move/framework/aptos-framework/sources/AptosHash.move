@@ -0,0 +1,24 @@
+/// Cryptographic hashes not provided by `Std::Hash`, which only has SHA2-256 and SHA3-256.
+module AptosFramework::AptosHash {
+    /// Return the SHA2-512 hash of `data`.
+    native public fun sha2_512(data: vector<u8>): vector<u8>;
+
+    /// Return the SHA3-512 hash of `data`.
+    native public fun sha3_512(data: vector<u8>): vector<u8>;
+
+    /// Return the Keccak-256 hash of `data`, as used by Ethereum. This differs from the
+    /// standardized SHA3-256 in its padding.
+    native public fun keccak256(data: vector<u8>): vector<u8>;
+
+    /// Return the 32 byte BLAKE2b hash of `data`.
+    native public fun blake2b_256(data: vector<u8>): vector<u8>;
+
+    #[test]
+    fun test_hashes() {
+        let data = b"abc";
+        assert!(sha2_512(copy data) == x"ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f", 0);
+        assert!(sha3_512(copy data) == x"b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0", 1);
+        assert!(keccak256(copy data) == x"4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45", 2);
+        assert!(blake2b_256(data) == x"bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319", 3);
+    }
+}
@@ -6,6 +6,8 @@ module AptosFramework::Block {
     use AptosFramework::SystemAddresses;
     use AptosFramework::Reconfiguration;
     use AptosFramework::Stake;
+    #[test_only]
+    use Std::Vector;
 
     struct BlockMetadata has key {
         /// Height of the current block
@@ -25,10 +27,24 @@ module AptosFramework::Block {
         time_microseconds: u64,
     }
 
+    /// Handle where the failed proposers of the new blocks are emitted, once the on-chain consensus
+    /// config enables recording them
+    struct FailedProposers has key {
+        failed_proposer_events: Event::EventHandle<Self::FailedProposersEvent>,
+    }
+
+    struct FailedProposersEvent has drop, store {
+        round: u64,
+        /// Proposers of the rounds that timed out since the previous block, in round order
+        failed_proposers: vector<address>,
+    }
+
     /// The `BlockMetadata` resource is in an invalid state
     const EBLOCK_METADATA: u64 = 0;
     /// An invalid signer was provided. Expected the signer to be the VM or a Validator.
     const EVM_OR_VALIDATOR: u64 = 1;
+    /// The `FailedProposers` resource is in an invalid state
+    const EFAILED_PROPOSERS: u64 = 2;
 
     /// This can only be invoked by the Association address, and only a single time.
     /// Currently, it is invoked in the genesis transaction
@@ -46,6 +62,23 @@ module AptosFramework::Block {
                 new_block_events: Event::new_event_handle<Self::NewBlockEvent>(account),
             }
         );
+        initialize_failed_proposers(account);
+    }
+
+    /// Publishes the handle where the failed proposers are emitted. Invoked in the genesis
+    /// transaction, and by the Association address on the chains created before the failed
+    /// proposers were recorded, only a single time.
+    public fun initialize_failed_proposers(account: &signer) {
+        // Operational constraint, only callable by the Association address
+        SystemAddresses::assert_core_resource(account);
+
+        assert!(!exists<FailedProposers>(@CoreResources), Errors::already_published(EFAILED_PROPOSERS));
+        move_to<FailedProposers>(
+            account,
+            FailedProposers {
+                failed_proposer_events: Event::new_event_handle<Self::FailedProposersEvent>(account),
+            }
+        );
     }
 
     /// Helper function to determine whether this module has been initialized.
@@ -90,9 +123,96 @@ module AptosFramework::Block {
         }
     }
 
+    /// Set the metadata for the current block, along with the proposers of the rounds that failed
+    /// since the previous block.
+    /// The runtime runs this instead of `block_prologue` once the on-chain consensus config enables
+    /// recording the failed proposers.
+    fun block_prologue_v2(
+        vm: signer,
+        round: u64,
+        timestamp: u64,
+        previous_block_votes: vector<address>,
+        proposer: address,
+        failed_proposers: vector<address>,
+    ) acquires BlockMetadata, FailedProposers {
+        block_prologue(vm, round, timestamp, previous_block_votes, proposer);
+
+        // The chains created before the failed proposers were recorded don't publish them, until
+        // the Association publishes their handle with `initialize_failed_proposers`
+        if (exists<FailedProposers>(@CoreResources)) {
+            Event::emit_event<FailedProposersEvent>(
+                &mut borrow_global_mut<FailedProposers>(@CoreResources).failed_proposer_events,
+                FailedProposersEvent {
+                    round,
+                    failed_proposers,
+                }
+            );
+        }
+    }
+
     /// Get the current block height
     public fun get_current_block_height(): u64 acquires BlockMetadata {
         assert!(is_initialized(), Errors::not_published(EBLOCK_METADATA));
         borrow_global<BlockMetadata>(@CoreResources).height
     }
+
+    #[test_only]
+    fun initialize_for_test(core_resources: &signer) {
+        // the event handles created before the reconfiguration's in genesis
+        let i = 0;
+        while (i < 4) {
+            Event::destroy_handle(Event::new_event_handle<u64>(core_resources));
+            i = i + 1;
+        };
+        Reconfiguration::initialize(core_resources);
+        initialize_block_metadata(core_resources, 1);
+        Timestamp::set_time_has_started_for_testing(core_resources);
+    }
+
+    /// Drops the failed proposers handle, as on the chains created before it was published
+    #[test_only]
+    fun remove_failed_proposers() acquires FailedProposers {
+        let FailedProposers { failed_proposer_events } = move_from<FailedProposers>(@CoreResources);
+        Event::destroy_handle(failed_proposer_events);
+    }
+
+    #[test(core_resources = @CoreResources, vm = @VMReserved)]
+    fun test_block_prologue_v2_without_failed_proposers(
+        core_resources: signer,
+        vm: signer,
+    ) acquires BlockMetadata, FailedProposers {
+        initialize_for_test(&core_resources);
+        remove_failed_proposers();
+
+        block_prologue_v2(vm, 1, 0, Vector::empty(), @VMReserved, Vector::singleton(@0x123));
+        assert!(get_current_block_height() == 1, 0);
+        assert!(!exists<FailedProposers>(@CoreResources), 1);
+    }
+
+    #[test(core_resources = @CoreResources, vm = @VMReserved)]
+    fun test_initialize_failed_proposers_after_genesis(
+        core_resources: signer,
+        vm: signer,
+    ) acquires BlockMetadata, FailedProposers {
+        initialize_for_test(&core_resources);
+        remove_failed_proposers();
+
+        initialize_failed_proposers(&core_resources);
+        assert!(exists<FailedProposers>(@CoreResources), 0);
+        block_prologue_v2(vm, 1, 0, Vector::empty(), @VMReserved, Vector::singleton(@0x123));
+        assert!(get_current_block_height() == 1, 1);
+    }
+
+    #[test(core_resources = @CoreResources)]
+    #[expected_failure(abort_code = 518)]
+    fun test_initialize_failed_proposers_twice(core_resources: signer) {
+        initialize_for_test(&core_resources);
+        initialize_failed_proposers(&core_resources);
+    }
+
+    #[test(account = @0x123)]
+    #[expected_failure(abort_code = 2)]
+    fun test_initialize_failed_proposers_not_core_resources(account: signer) {
+        initialize_failed_proposers(&account);
+    }
 }
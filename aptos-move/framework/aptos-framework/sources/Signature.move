@@ -1,4 +1,5 @@
-/// Contains functions for [ed25519](https://en.wikipedia.org/wiki/EdDSA) digital signatures.
+/// Contains functions for [ed25519](https://en.wikipedia.org/wiki/EdDSA), multi-ed25519 and
+/// [secp256k1](https://en.bitcoin.it/wiki/Secp256k1) ECDSA digital signatures.
 module AptosFramework::Signature {
 
     /// Return `true` if the bytes in `public_key` can be parsed as a valid Ed25519 public key.
@@ -21,4 +22,62 @@ module AptosFramework::Signature {
         public_key: vector<u8>,
         message: vector<u8>
     ): bool;
+
+    /// Return `true` if the bytes in `public_key` can be parsed as a valid multi-ed25519 public
+    /// key: a sequence of valid Ed25519 public keys followed by a one byte threshold that is
+    /// positive and at most the number of keys. See the Rust
+    /// `aptos_crypto::MultiEd25519PublicKey` type for more details.
+    /// Does not abort.
+    native public fun multi_ed25519_validate_pubkey(public_key: vector<u8>): bool;
+
+    /// Return true if the multi-ed25519 `signature` on `message` verifies against the
+    /// multi-ed25519 public key `public_key`, i.e. if at least threshold many of the signatures
+    /// selected by the bitmap at the end of `signature` verify.
+    /// Returns `false` if either `signature` or `public_key` is malformed.
+    /// Does not abort.
+    native public fun multi_ed25519_verify(
+        signature: vector<u8>,
+        public_key: vector<u8>,
+        message: vector<u8>
+    ): bool;
+
+    /// Recover the secp256k1 public key that produced the 64 byte `signature` (`r || s`) over the
+    /// 32 byte `message` digest, given the `recovery_id` (0 to 3, Ethereum's `v` minus 27).
+    /// Returns the 64 byte uncompressed public key without its 0x04 prefix and `true`, or an
+    /// empty vector and `false` if the inputs are malformed or no key can be recovered.
+    /// Hashing the returned key with `AptosHash::keccak256` and keeping the last 20 bytes gives
+    /// the Ethereum address of the signer.
+    /// Does not abort.
+    native public fun secp256k1_ecdsa_recover(
+        message: vector<u8>,
+        recovery_id: u8,
+        signature: vector<u8>
+    ): (vector<u8>, bool);
+
+    #[test]
+    fun test_secp256k1_ecdsa_recover() {
+        // Signature over sha2_256(b"aptos")
+        let (public_key, success) = secp256k1_ecdsa_recover(
+            x"83ca6d000a92893befe216e3bbc268d9df623fb3d822c6e72f5111e95e15f942",
+            1,
+            x"17f53289eac961e5adc858d3ca50dab056ddca7a1a906c0815a0369312d1aa495ae355cac7b14b6618a0f98c9738742faf765a89b221639ebeaca8992a9f4021",
+        );
+        assert!(success, 0);
+        assert!(public_key == x"285d8914a16d524631c88003be9bd0496fb3cf36e5757a730d337b82ec4882d3b76c6c6247418992521adc993f9193ff5f6e3cb41df38865ea4dd787afab8434", 1);
+
+        // Malformed signature
+        let (public_key, success) = secp256k1_ecdsa_recover(
+            x"83ca6d000a92893befe216e3bbc268d9df623fb3d822c6e72f5111e95e15f942",
+            1,
+            x"00",
+        );
+        assert!(!success, 2);
+        assert!(public_key == x"", 3);
+    }
+
+    #[test]
+    fun test_multi_ed25519_rejects_malformed_input() {
+        assert!(!multi_ed25519_validate_pubkey(x""), 0);
+        assert!(!multi_ed25519_verify(x"", x"", x"01"), 1);
+    }
 }
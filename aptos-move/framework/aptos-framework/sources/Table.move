@@ -1,9 +1,10 @@
 /// Type of large-scale storage tables.
 /// source: https://github.com/move-language/move/blob/1b6b7513dcc1a5c866f178ca5c1e74beb2ce181e/language/extensions/move-table-extension/sources/Table.move#L1
 ///
-/// This is a exact copy from the Move repo. It implements the Table type which supports individual table items to
-/// be represented by separate global state items. The number of items and a unique handle are tracked on the table
-/// struct itself, while the operations are implemented as native functions. No traversal is provided.
+/// This is a copy from the Move repo, with the length of a table exposed through the `length_box` native of the
+/// Aptos framework. It implements the Table type which supports individual table items to be represented by separate
+/// global state items. The number of items and a unique handle are tracked on the table struct itself, while the
+/// operations are implemented as native functions. No traversal is provided.
 module AptosFramework::Table {
     use Std::Errors;
 
@@ -56,7 +57,7 @@ module AptosFramework::Table {
 
     /// Returns the length of the table, i.e. the number of entries.
     public fun length<K, V>(table: &Table<K, V>): u64 {
-        table.length
+        length_box<K, V, Box<V>>(table)
     }
 
     /// Returns true if this table is empty.
@@ -110,4 +111,20 @@ module AptosFramework::Table {
     native fun remove_box<K, V, B>(table: &mut Table<K, V>, key: &K): Box<V>;
     native fun destroy_empty_box<K, V, B>(table: &Table<K, V>);
     native fun drop_unchecked_box<K, V, B>(table: Table<K, V>);
+    native fun length_box<K, V, B>(table: &Table<K, V>): u64;
+
+    #[test]
+    fun test_length() {
+        let table = new<u64, u64>();
+        assert!(length(&table) == 0, 0);
+        add(&mut table, &1, 10);
+        add(&mut table, &2, 20);
+        assert!(length(&table) == 2, 1);
+        *borrow_mut(&mut table, &1) = 11;
+        assert!(length(&table) == 2, 2);
+        assert!(remove(&mut table, &2) == 20, 3);
+        assert!(length(&table) == 1, 4);
+        assert!(!empty(&table), 5);
+        drop_unchecked(table)
+    }
 }
@@ -110,6 +110,15 @@ module AptosFramework::Stake {
         }
     }
 
+    public(script) fun delegate_stake_script(
+        account: signer,
+        to: address,
+        amount: u64,
+        locked_until_secs: u64
+    ) acquires StakePool, ValidatorSet {
+        delegate_stake(&account, to, amount, locked_until_secs);
+    }
+
     /// Withdraw from active delegation, it's moved to pending_inactive if locked_until_secs < current_time or
     /// directly deposit if it's not from an active validator.
     public fun withdraw_active(account: &signer, from: address) acquires StakePool, ValidatorSet {
@@ -160,6 +169,15 @@ module AptosFramework::Stake {
         });
     }
 
+    public(script) fun register_validator_candidate_script(
+        account: signer,
+        consensus_pubkey: vector<u8>,
+        network_address: vector<u8>,
+        fullnode_address: vector<u8>
+    ) {
+        register_validator_candidate(&account, consensus_pubkey, network_address, fullnode_address);
+    }
+
     /// Rotate the consensus key of the validator, it'll take effect in next epoch.
     public fun rotate_consensus_key(account: &signer, consensus_pubkey: vector<u8>) acquires ValidatorConfig {
         let addr = Signer::address_of(account);
@@ -168,6 +186,31 @@ module AptosFramework::Stake {
         validator_info.consensus_pubkey = consensus_pubkey;
     }
 
+    public(script) fun rotate_consensus_key_script(account: signer, consensus_pubkey: vector<u8>) acquires ValidatorConfig {
+        rotate_consensus_key(&account, consensus_pubkey);
+    }
+
+    /// Update the network addresses of the validator, they'll take effect in next epoch.
+    public fun update_network_addresses(
+        account: &signer,
+        network_address: vector<u8>,
+        fullnode_address: vector<u8>
+    ) acquires ValidatorConfig {
+        let addr = Signer::address_of(account);
+        assert!(exists<ValidatorConfig>(addr), Errors::not_published(EVALIDATOR_CONFIG));
+        let validator_info = borrow_global_mut<ValidatorConfig>(addr);
+        validator_info.network_address = network_address;
+        validator_info.fullnode_address = fullnode_address;
+    }
+
+    public(script) fun update_network_addresses_script(
+        account: signer,
+        network_address: vector<u8>,
+        fullnode_address: vector<u8>
+    ) acquires ValidatorConfig {
+        update_network_addresses(&account, network_address, fullnode_address);
+    }
+
     /// Initialize validator set to the core resource account.
     public fun initialize_validator_set(account: &signer, minimum_stake: u64, maximum_stake: u64) {
         SystemAddresses::assert_core_resource(account);
@@ -198,6 +241,10 @@ module AptosFramework::Stake {
         Vector::push_back(&mut validator_set.pending_active, generate_validator_info(addr));
     }
 
+    public(script) fun join_validator_set_script(account: signer) acquires StakePool, ValidatorConfig, ValidatorSet {
+        join_validator_set(&account);
+    }
+
     /// Initiate by the validator info owner.
     public fun leave_validator_set(account: &signer) acquires ValidatorSet {
         let addr = Signer::address_of(account);
@@ -211,6 +258,10 @@ module AptosFramework::Stake {
         Vector::push_back(&mut validator_set.pending_inactive, validator_info);
     }
 
+    public(script) fun leave_validator_set_script(account: signer) acquires ValidatorSet {
+        leave_validator_set(&account);
+    }
+
     /// Triggers at epoch boundary.
     /// 1. distribute rewards to stake pool of active and pending inactive validators
     /// 2. purge pending queues
@@ -413,15 +464,19 @@ module AptosFramework::Stake {
         leave_validator_set(&account_2);
         join_validator_set(&account_3);
         rotate_consensus_key(&account_1, x"1234");
+        update_network_addresses(&account_1, x"5678", x"9abc");
         assert!(is_current_validator(addr2), 0);
         assert!(Vector::borrow(&borrow_global<ValidatorSet>(@CoreResources).pending_inactive, 0).addr == addr2, 0);
         assert!(!is_current_validator(addr3), 0);
         assert!(Vector::borrow(&borrow_global<ValidatorSet>(@CoreResources).pending_active, 0).addr == addr3, 0);
         assert!(Vector::borrow(&borrow_global<ValidatorSet>(@CoreResources).active_validators, 0).config.consensus_pubkey == Vector::empty(), 0);
+        assert!(Vector::borrow(&borrow_global<ValidatorSet>(@CoreResources).active_validators, 0).config.network_address == Vector::empty(), 0);
         // changes applied after new epoch
         on_new_epoch();
         assert!(!is_current_validator(addr2), 0);
         assert!(is_current_validator(addr3), 0);
         assert!(Vector::borrow(&borrow_global<ValidatorSet>(@CoreResources).active_validators, 0).config.consensus_pubkey == x"1234", 0);
+        assert!(Vector::borrow(&borrow_global<ValidatorSet>(@CoreResources).active_validators, 0).config.network_address == x"5678", 0);
+        assert!(Vector::borrow(&borrow_global<ValidatorSet>(@CoreResources).active_validators, 0).config.fullnode_address == x"9abc", 0);
     }
 }
@@ -157,4 +157,22 @@ module AptosFramework::VMConfig {
 
         Reconfiguration::reconfigure();
     }
+
+    /// Replace the instruction and native gas schedules with the given BCS encoded tables.
+    public(script) fun set_gas_schedule(
+        account: signer,
+        instruction_schedule: vector<u8>,
+        native_schedule: vector<u8>,
+    ) acquires VMConfig {
+        Timestamp::assert_operating();
+        SystemAddresses::assert_core_resource(&account);
+
+        assert!(exists<VMConfig>(@CoreResources), Errors::not_published(ECONFIG));
+
+        let gas_schedule = &mut borrow_global_mut<VMConfig>(@CoreResources).gas_schedule;
+        gas_schedule.instruction_schedule = instruction_schedule;
+        gas_schedule.native_schedule = native_schedule;
+
+        Reconfiguration::reconfigure();
+    }
 }
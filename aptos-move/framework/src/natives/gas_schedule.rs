@@ -0,0 +1,101 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Gas costs of the natives defined by the Aptos framework.
+//!
+//! `NativeCostIndex` only covers the natives known to Move itself, so Aptos natives are charged
+//! from entries appended to the native table of the `CostTable`, starting at
+//! `APTOS_NATIVES_BASE_INDEX`. Entries can be tuned on chain through `VMConfig` like any other
+//! entry of the gas schedule. Tables that predate an Aptos native (or that are used outside of a
+//! chain, e.g. by Move unit tests) fall back to the default cost compiled in below.
+
+use move_core_types::gas_schedule::{
+    AbstractMemorySize, CostTable, GasAlgebra, GasCarrier, GasCost, InternalGasUnits,
+};
+use move_vm_types::gas_schedule::INITIAL_COST_SCHEDULE;
+use once_cell::sync::Lazy;
+
+/// Slots below this index are reserved for the natives of `NativeCostIndex`.
+pub const APTOS_NATIVES_BASE_INDEX: usize = 64;
+
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum AptosNativeCostIndex {
+    MULTI_ED25519_VALIDATE_KEY = 0,
+    MULTI_ED25519_VERIFY = 1,
+    SECP256K1_RECOVER = 2,
+    SHA2_512 = 3,
+    SHA3_512 = 4,
+    KECCAK_256 = 5,
+    BLAKE2B_256 = 6,
+    TABLE_LENGTH = 7,
+}
+
+impl AptosNativeCostIndex {
+    pub const ALL: &'static [AptosNativeCostIndex] = &[
+        AptosNativeCostIndex::MULTI_ED25519_VALIDATE_KEY,
+        AptosNativeCostIndex::MULTI_ED25519_VERIFY,
+        AptosNativeCostIndex::SECP256K1_RECOVER,
+        AptosNativeCostIndex::SHA2_512,
+        AptosNativeCostIndex::SHA3_512,
+        AptosNativeCostIndex::KECCAK_256,
+        AptosNativeCostIndex::BLAKE2B_256,
+        AptosNativeCostIndex::TABLE_LENGTH,
+    ];
+
+    /// Position of the cost of this native in `CostTable::native_table`.
+    pub fn table_index(self) -> usize {
+        APTOS_NATIVES_BASE_INDEX + self as usize
+    }
+
+    /// Cost used when the gas schedule has no entry for this native.
+    pub fn default_cost(self) -> GasCost {
+        use AptosNativeCostIndex::*;
+        match self {
+            // Charged per byte of the public key.
+            MULTI_ED25519_VALIDATE_KEY => GasCost::new(26, 1),
+            // Charged per byte of the message, on top of the per signature work.
+            MULTI_ED25519_VERIFY => GasCost::new(61, 1),
+            // Charged once, the input sizes are fixed.
+            SECP256K1_RECOVER => GasCost::new(1_800, 1),
+            // Hashes are charged per byte of input.
+            SHA2_512 => GasCost::new(24, 1),
+            SHA3_512 => GasCost::new(24, 1),
+            KECCAK_256 => GasCost::new(21, 1),
+            BLAKE2B_256 => GasCost::new(15, 1),
+            // Charged once, the length is kept on the table rather than counted.
+            TABLE_LENGTH => GasCost::new(30, 1),
+        }
+    }
+}
+
+/// Computes the gas for an Aptos native, scaled by `size` like `natives::function::native_gas`.
+pub fn native_gas(
+    table: &CostTable,
+    key: AptosNativeCostIndex,
+    size: usize,
+) -> InternalGasUnits<GasCarrier> {
+    let gas_amt = match table.native_table.get(key.table_index()) {
+        Some(cost) => cost.clone(),
+        None => key.default_cost(),
+    };
+    let memory_size = AbstractMemorySize::new(std::cmp::max(1, size) as GasCarrier);
+    gas_amt.total().mul(memory_size)
+}
+
+/// The gas schedule installed at genesis: the Move schedule followed by the default costs of the
+/// Aptos natives.
+pub static INITIAL_APTOS_COST_SCHEDULE: Lazy<CostTable> = Lazy::new(|| {
+    let mut cost_table = INITIAL_COST_SCHEDULE.clone();
+    assert!(cost_table.native_table.len() <= APTOS_NATIVES_BASE_INDEX);
+    cost_table
+        .native_table
+        .resize(APTOS_NATIVES_BASE_INDEX, GasCost::new(0, 0));
+    cost_table.native_table.extend(
+        AptosNativeCostIndex::ALL
+            .iter()
+            .map(|key| key.default_cost()),
+    );
+    cost_table
+});
@@ -0,0 +1,106 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::natives::gas_schedule::{self, AptosNativeCostIndex};
+use blake2::digest::{Update, VariableOutput};
+use move_binary_format::errors::PartialVMResult;
+use move_vm_runtime::native_functions::NativeContext;
+use move_vm_types::{
+    loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
+};
+use sha2::Digest;
+use smallvec::smallvec;
+use std::collections::VecDeque;
+use tiny_keccak::{Hasher, Keccak, Sha3};
+
+fn native_hash(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+    key: AptosNativeCostIndex,
+    hash: fn(&[u8]) -> Vec<u8>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let input = pop_arg!(arguments, Vec<u8>);
+    let cost = gas_schedule::native_gas(context.cost_table(), key, input.len());
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(hash(&input))],
+    ))
+}
+
+pub fn native_sha2_512(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    native_hash(
+        context,
+        ty_args,
+        arguments,
+        AptosNativeCostIndex::SHA2_512,
+        |input| sha2::Sha512::digest(input).to_vec(),
+    )
+}
+
+pub fn native_sha3_512(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    native_hash(
+        context,
+        ty_args,
+        arguments,
+        AptosNativeCostIndex::SHA3_512,
+        |input| {
+            let mut hasher = Sha3::v512();
+            hasher.update(input);
+            let mut output = [0u8; 64];
+            hasher.finalize(&mut output);
+            output.to_vec()
+        },
+    )
+}
+
+pub fn native_keccak256(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    native_hash(
+        context,
+        ty_args,
+        arguments,
+        AptosNativeCostIndex::KECCAK_256,
+        |input| {
+            let mut hasher = Keccak::v256();
+            hasher.update(input);
+            let mut output = [0u8; 32];
+            hasher.finalize(&mut output);
+            output.to_vec()
+        },
+    )
+}
+
+pub fn native_blake2b_256(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    native_hash(
+        context,
+        ty_args,
+        arguments,
+        AptosNativeCostIndex::BLAKE2B_256,
+        |input| {
+            blake2::VarBlake2b::new(32)
+                .expect("32 is a valid BLAKE2b output size")
+                .chain(input)
+                .finalize_boxed()
+                .to_vec()
+        },
+    )
+}
@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod account;
+pub mod gas_schedule;
+pub mod hash;
 pub mod signature;
+pub mod table;
 
 use move_core_types::{account_address::AccountAddress, identifier::Identifier};
 use move_vm_runtime::native_functions::{NativeFunction, NativeFunctionTable};
@@ -19,7 +22,27 @@ pub fn all_natives(framework_addr: AccountAddress) -> NativeFunctionTable {
             "ed25519_verify",
             signature::native_ed25519_signature_verification,
         ),
+        (
+            "Signature",
+            "multi_ed25519_validate_pubkey",
+            signature::native_multi_ed25519_publickey_validation,
+        ),
+        (
+            "Signature",
+            "multi_ed25519_verify",
+            signature::native_multi_ed25519_signature_verification,
+        ),
+        (
+            "Signature",
+            "secp256k1_ecdsa_recover",
+            signature::native_secp256k1_recover,
+        ),
+        ("AptosHash", "sha2_512", hash::native_sha2_512),
+        ("AptosHash", "sha3_512", hash::native_sha3_512),
+        ("AptosHash", "keccak256", hash::native_keccak256),
+        ("AptosHash", "blake2b_256", hash::native_blake2b_256),
         ("Account", "create_signer", account::native_create_signer),
+        ("Table", "length_box", table::native_length_box),
     ];
     NATIVES
         .iter()
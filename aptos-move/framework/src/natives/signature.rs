@@ -1,7 +1,8 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use aptos_crypto::{ed25519, traits::*};
+use crate::natives::gas_schedule::{self, AptosNativeCostIndex};
+use aptos_crypto::{ed25519, multi_ed25519, traits::*};
 use move_binary_format::errors::PartialVMResult;
 use move_vm_runtime::native_functions::NativeContext;
 use move_vm_types::{
@@ -72,3 +73,117 @@ pub fn native_ed25519_signature_verification(
         smallvec![Value::bool(verify_result)],
     ))
 }
+
+pub fn native_multi_ed25519_publickey_validation(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let key_bytes = pop_arg!(arguments, Vec<u8>);
+
+    let cost = gas_schedule::native_gas(
+        context.cost_table(),
+        AptosNativeCostIndex::MULTI_ED25519_VALIDATE_KEY,
+        key_bytes.len(),
+    );
+
+    // This deserialization checks the threshold and performs point-on-curve and small subgroup
+    // checks on every key
+    let valid = multi_ed25519::MultiEd25519PublicKey::try_from(&key_bytes[..]).is_ok();
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(valid)]))
+}
+
+pub fn native_multi_ed25519_signature_verification(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let msg = pop_arg!(arguments, Vec<u8>);
+    let pubkey = pop_arg!(arguments, Vec<u8>);
+    let signature = pop_arg!(arguments, Vec<u8>);
+
+    // Every signature in the bitmap is verified, so charge for the message once per signature
+    let num_signatures = signature.len() / ed25519::ED25519_SIGNATURE_LENGTH;
+    let cost = gas_schedule::native_gas(
+        context.cost_table(),
+        AptosNativeCostIndex::MULTI_ED25519_VERIFY,
+        std::cmp::max(1, num_signatures) * std::cmp::max(1, msg.len()),
+    );
+
+    let sig = match multi_ed25519::MultiEd25519Signature::try_from(signature.as_slice()) {
+        Ok(sig) => sig,
+        Err(_) => {
+            return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
+        }
+    };
+    let pk = match multi_ed25519::MultiEd25519PublicKey::try_from(pubkey.as_slice()) {
+        Ok(pk) => pk,
+        Err(_) => {
+            return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
+        }
+    };
+
+    let verify_result = sig.verify_arbitrary_msg(msg.as_slice(), &pk).is_ok();
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::bool(verify_result)],
+    ))
+}
+
+pub fn native_secp256k1_recover(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let signature = pop_arg!(arguments, Vec<u8>);
+    let recovery_id = pop_arg!(arguments, u8);
+    let msg = pop_arg!(arguments, Vec<u8>);
+
+    let cost = gas_schedule::native_gas(
+        context.cost_table(),
+        AptosNativeCostIndex::SECP256K1_RECOVER,
+        0,
+    );
+
+    let failure = || {
+        Ok(NativeResult::ok(
+            cost,
+            smallvec![Value::vector_u8(vec![]), Value::bool(false)],
+        ))
+    };
+
+    let msg = match libsecp256k1::Message::parse_slice(&msg) {
+        Ok(msg) => msg,
+        Err(_) => return failure(),
+    };
+    let recovery_id = match libsecp256k1::RecoveryId::parse(recovery_id) {
+        Ok(recovery_id) => recovery_id,
+        Err(_) => return failure(),
+    };
+    let sig = match libsecp256k1::Signature::parse_standard_slice(&signature) {
+        Ok(sig) => sig,
+        Err(_) => return failure(),
+    };
+
+    match libsecp256k1::recover(&msg, &sig, &recovery_id) {
+        // Drop the leading 0x04 tag of the uncompressed encoding, which is the form Ethereum
+        // hashes into addresses
+        Ok(pk) => Ok(NativeResult::ok(
+            cost,
+            smallvec![
+                Value::vector_u8(pk.serialize()[1..].to_vec()),
+                Value::bool(true)
+            ],
+        )),
+        Err(_) => failure(),
+    }
+}
@@ -0,0 +1,40 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Natives of `Table` on top of the item natives of the Move table extension.
+
+use crate::natives::gas_schedule::{self, AptosNativeCostIndex};
+use move_binary_format::errors::PartialVMResult;
+use move_vm_runtime::native_functions::NativeContext;
+use move_vm_types::{
+    loaded_data::runtime_types::Type,
+    natives::function::NativeResult,
+    pop_arg,
+    values::{Reference, StructRef, Value},
+};
+use smallvec::smallvec;
+use std::collections::VecDeque;
+
+/// Position of `length` in the fields of `Table`, after `handle`.
+const LENGTH_FIELD_INDEX: usize = 1;
+
+/// Returns the number of items of a table. Items are only added and removed through the natives
+/// of the table extension, whose Move wrappers keep the count on the table itself.
+pub fn native_length_box(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 3);
+    debug_assert!(arguments.len() == 1);
+
+    let table = pop_arg!(arguments, StructRef);
+    let length = table
+        .borrow_field(LENGTH_FIELD_INDEX)?
+        .value_as::<Reference>()?
+        .read_ref()?
+        .value_as::<u64>()?;
+    let cost =
+        gas_schedule::native_gas(context.cost_table(), AptosNativeCostIndex::TABLE_LENGTH, 0);
+    Ok(NativeResult::ok(cost, smallvec![Value::u64(length)]))
+}
@@ -0,0 +1,281 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! Tooling for changing the on-chain gas schedule stored in `VMConfig`: diffing two `CostTable`s,
+//! encoding the transaction payloads that install a new schedule, and estimating the effect of
+//! the new schedule by replaying historical transactions against it.
+
+use anyhow::{bail, ensure, format_err, Result};
+use aptos_state_view::{StateView, StateViewId};
+use aptos_transaction_replay::AptosDebugger;
+use aptos_types::{
+    on_chain_config::{access_path_for_config, OnChainConfig, VMConfig},
+    state_store::state_key::StateKey,
+    transaction::{TransactionPayload, TransactionStatus, Version},
+};
+use aptos_vm::{AptosVM, VMExecutor};
+use move_core_types::gas_schedule::CostTable;
+use rayon::prelude::*;
+use serde::Serialize;
+use serde_json::Value;
+use std::{collections::BTreeSet, fmt};
+
+/// A single entry of the gas schedule that differs between two `CostTable`s.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CostTableChange {
+    /// Location of the entry, e.g. `instruction_table[3].instruction_gas`.
+    pub path: String,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+impl fmt::Display for CostTableChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let render = |value: &Option<Value>| match value {
+            Some(value) => value.to_string(),
+            None => "<none>".to_owned(),
+        };
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.path,
+            render(&self.old),
+            render(&self.new)
+        )
+    }
+}
+
+/// Lists every entry of the gas schedule that changes from `old` to `new`.
+pub fn diff_cost_tables(old: &CostTable, new: &CostTable) -> Result<Vec<CostTableChange>> {
+    let mut changes = vec![];
+    diff_values(
+        "",
+        Some(&serde_json::to_value(old)?),
+        Some(&serde_json::to_value(new)?),
+        &mut changes,
+    );
+    Ok(changes)
+}
+
+fn diff_values(
+    path: &str,
+    old: Option<&Value>,
+    new: Option<&Value>,
+    changes: &mut Vec<CostTableChange>,
+) {
+    match (old, new) {
+        (Some(Value::Object(old_fields)), Some(Value::Object(new_fields))) => {
+            let names: BTreeSet<_> = old_fields.keys().chain(new_fields.keys()).collect();
+            for name in names {
+                let field_path = if path.is_empty() {
+                    name.to_owned()
+                } else {
+                    format!("{}.{}", path, name)
+                };
+                diff_values(
+                    &field_path,
+                    old_fields.get(name),
+                    new_fields.get(name),
+                    changes,
+                );
+            }
+        }
+        (Some(Value::Array(old_items)), Some(Value::Array(new_items))) => {
+            for idx in 0..std::cmp::max(old_items.len(), new_items.len()) {
+                diff_values(
+                    &format!("{}[{}]", path, idx),
+                    old_items.get(idx),
+                    new_items.get(idx),
+                    changes,
+                );
+            }
+        }
+        (old, new) if old != new => changes.push(CostTableChange {
+            path: path.to_owned(),
+            old: old.cloned(),
+            new: new.cloned(),
+        }),
+        _ => (),
+    }
+}
+
+/// Checks that `new` can replace `old` on chain. Slots in the instruction and native tables are
+/// permanently bound to an instruction or native function, so they may be appended but never
+/// removed.
+pub fn check_compatibility(old: &CostTable, new: &CostTable) -> Result<()> {
+    ensure!(
+        new.instruction_table.len() >= old.instruction_table.len(),
+        "The new instruction table has {} entries, but at least {} are required",
+        new.instruction_table.len(),
+        old.instruction_table.len()
+    );
+    ensure!(
+        new.native_table.len() >= old.native_table.len(),
+        "The new native table has {} entries, but at least {} are required",
+        new.native_table.len(),
+        old.native_table.len()
+    );
+    Ok(())
+}
+
+/// Encodes a call to `VMConfig::set_gas_schedule`, to be sent by the core resources account or
+/// through governance.
+pub fn encode_set_gas_schedule_payload(cost_table: &CostTable) -> Result<TransactionPayload> {
+    Ok(
+        aptos_transaction_builder::aptos_stdlib::encode_set_gas_schedule_script_function(
+            bcs::to_bytes(&cost_table.instruction_table)?,
+            bcs::to_bytes(&cost_table.native_table)?,
+        ),
+    )
+}
+
+/// Reads the gas schedule stored in `VMConfig` from `state_view`.
+pub fn fetch_cost_table<S: StateView>(state_view: &S) -> Result<CostTable> {
+    let bytes = state_view
+        .get_state_value(&vm_config_state_key())?
+        .ok_or_else(|| format_err!("VMConfig not found"))?;
+    Ok(VMConfig::deserialize_into_config(&bytes)?.gas_schedule)
+}
+
+fn vm_config_state_key() -> StateKey {
+    StateKey::AccessPath(access_path_for_config(VMConfig::CONFIG_ID))
+}
+
+/// A state view that serves a replacement `VMConfig` on top of `base`.
+pub struct CostTableOverride<'a, S> {
+    base: &'a S,
+    vm_config: Vec<u8>,
+}
+
+impl<'a, S: StateView> CostTableOverride<'a, S> {
+    pub fn new(base: &'a S, cost_table: &CostTable) -> Result<Self> {
+        let vm_config = VMConfig {
+            gas_schedule: cost_table.clone(),
+        }
+        .serialize_into_config()?;
+        Ok(Self { base, vm_config })
+    }
+}
+
+impl<'a, S: StateView> StateView for CostTableOverride<'a, S> {
+    fn id(&self) -> StateViewId {
+        self.base.id()
+    }
+
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<Vec<u8>>> {
+        if *state_key == vm_config_state_key() {
+            return Ok(Some(self.vm_config.clone()));
+        }
+        self.base.get_state_value(state_key)
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.base.is_genesis()
+    }
+}
+
+/// Gas used by a historical transaction under the schedule it was committed with and under the
+/// proposed one.
+#[derive(Clone, Debug, Serialize)]
+pub struct GasDelta {
+    pub version: Version,
+    pub old_gas_used: u64,
+    pub new_gas_used: u64,
+    /// Set when the transaction no longer succeeds the way it did on chain, e.g. because it now
+    /// runs out of gas.
+    pub status_change: Option<String>,
+}
+
+impl GasDelta {
+    pub fn delta(&self) -> i128 {
+        self.new_gas_used as i128 - self.old_gas_used as i128
+    }
+}
+
+/// Re-executes each of `versions` on top of the state it was originally executed against, with
+/// `cost_table` installed as the gas schedule, and reports how the gas used changes.
+pub fn replay_with_cost_table(
+    debugger: &AptosDebugger,
+    cost_table: &CostTable,
+    versions: &[Version],
+) -> Result<Vec<GasDelta>> {
+    versions
+        .par_iter()
+        .map(|version| {
+            let version = *version;
+            let txns = debugger.get_committed_transactions(version, 1)?;
+            let (_, txn_info) = debugger
+                .get_committed_transaction_outputs(version, 1)?
+                .pop()
+                .ok_or_else(|| format_err!("No transaction info at version {}", version))?;
+
+            let base_view = debugger.state_view_at_version(version.checked_sub(1));
+            let state_view = CostTableOverride::new(&base_view, cost_table)?;
+            let output = match AptosVM::execute_block(txns, &state_view) {
+                Ok(mut outputs) if outputs.len() == 1 => outputs.pop().unwrap(),
+                Ok(outputs) => bail!(
+                    "Expected one output at version {}, got {}",
+                    version,
+                    outputs.len()
+                ),
+                Err(err) => bail!("Unexpected VM Error at version {}: {:?}", version, err),
+            };
+
+            let status_change = match output.status() {
+                TransactionStatus::Keep(status) if status == txn_info.status() => None,
+                status => Some(format!("{:?} -> {:?}", txn_info.status(), status)),
+            };
+            Ok(GasDelta {
+                version,
+                old_gas_used: txn_info.gas_used(),
+                new_gas_used: output.gas_used(),
+                status_change,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::gas_schedule::{GasAlgebra, GasCost};
+    use move_vm_types::gas_schedule::INITIAL_COST_SCHEDULE;
+
+    #[test]
+    fn test_diff_cost_tables() {
+        let old = INITIAL_COST_SCHEDULE.clone();
+        assert!(diff_cost_tables(&old, &old).unwrap().is_empty());
+
+        let mut new = old.clone();
+        new.instruction_table[0] = GasCost::new(
+            old.instruction_table[0].instruction_gas.get() + 1,
+            old.instruction_table[0].memory_gas.get(),
+        );
+        new.native_table.push(GasCost::new(1, 1));
+        let changes = diff_cost_tables(&old, &new).unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].path, "instruction_table[0].instruction_gas");
+        assert_eq!(
+            changes[1].path,
+            format!("native_table[{}]", old.native_table.len())
+        );
+        assert!(changes[1].old.is_none());
+
+        check_compatibility(&old, &new).unwrap();
+        check_compatibility(&new, &old).unwrap_err();
+    }
+
+    #[test]
+    fn test_vm_config_round_trip() {
+        let vm_config = VMConfig {
+            gas_schedule: INITIAL_COST_SCHEDULE.clone(),
+        };
+        let bytes = vm_config.serialize_into_config().unwrap();
+        assert_eq!(
+            VMConfig::deserialize_into_config(&bytes).unwrap(),
+            vm_config
+        );
+    }
+}
@@ -0,0 +1,137 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, Result};
+use aptos_gas_schedule_tool::{
+    check_compatibility, diff_cost_tables, encode_set_gas_schedule_payload, fetch_cost_table,
+    replay_with_cost_table,
+};
+use aptos_transaction_replay::AptosDebugger;
+use aptos_types::transaction::{TransactionPayload, Version};
+use aptos_writeset_generator::encode_update_gas_schedule_payload;
+use move_core_types::gas_schedule::CostTable;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Print the gas schedule stored on chain at `version` (or the latest version) as JSON.
+    #[structopt(name = "dump")]
+    Dump {
+        /// Path to the local AptosDB file
+        #[structopt(long, parse(from_os_str))]
+        db: PathBuf,
+        version: Option<Version>,
+    },
+    /// Print every entry that differs between two JSON gas schedules.
+    #[structopt(name = "diff")]
+    Diff {
+        #[structopt(parse(from_os_str))]
+        old: PathBuf,
+        #[structopt(parse(from_os_str))]
+        new: PathBuf,
+    },
+    /// Write a BCS serialized `TransactionPayload` calling `VMConfig::set_gas_schedule`, to be
+    /// submitted through governance or by the core resources account.
+    #[structopt(name = "generate-script-function")]
+    GenerateScriptFunction {
+        #[structopt(parse(from_os_str))]
+        cost_table: PathBuf,
+        #[structopt(long, parse(from_os_str))]
+        output: PathBuf,
+    },
+    /// Write a BCS serialized `TransactionPayload::WriteSet` installing the gas schedule, for
+    /// chains that are upgraded with admin write sets.
+    #[structopt(name = "generate-writeset")]
+    GenerateWriteSet {
+        #[structopt(parse(from_os_str))]
+        cost_table: PathBuf,
+        #[structopt(long, parse(from_os_str))]
+        output: PathBuf,
+    },
+    /// Replay transactions from version `start` to `start + limit` under the new gas schedule
+    /// and report the change in gas used for each of them.
+    #[structopt(name = "validate")]
+    Validate {
+        /// Path to the local AptosDB file
+        #[structopt(long, parse(from_os_str))]
+        db: PathBuf,
+        #[structopt(parse(from_os_str))]
+        cost_table: PathBuf,
+        start: Version,
+        limit: u64,
+        /// Only replay every `sample_rate`th transaction of the range.
+        #[structopt(long, default_value = "1")]
+        sample_rate: u64,
+    },
+}
+
+fn read_cost_table(path: &Path) -> Result<CostTable> {
+    Ok(serde_json::from_slice(&fs::read(path)?)?)
+}
+
+fn main() -> Result<()> {
+    match Command::from_args() {
+        Command::Dump { db, version } => {
+            let debugger = AptosDebugger::db(db)?;
+            let version = match version {
+                Some(version) => version,
+                None => debugger.get_latest_version()?,
+            };
+            let cost_table = fetch_cost_table(&debugger.state_view_at_version(Some(version)))?;
+            println!("{}", serde_json::to_string_pretty(&cost_table)?);
+        }
+        Command::Diff { old, new } => {
+            let (old, new) = (read_cost_table(&old)?, read_cost_table(&new)?);
+            for change in diff_cost_tables(&old, &new)? {
+                println!("{}", change);
+            }
+            check_compatibility(&old, &new)?;
+        }
+        Command::GenerateScriptFunction { cost_table, output } => {
+            let payload = encode_set_gas_schedule_payload(&read_cost_table(&cost_table)?)?;
+            fs::write(output, bcs::to_bytes(&payload)?)?;
+        }
+        Command::GenerateWriteSet { cost_table, output } => {
+            let payload = TransactionPayload::WriteSet(encode_update_gas_schedule_payload(
+                &read_cost_table(&cost_table)?,
+            ));
+            fs::write(output, bcs::to_bytes(&payload)?)?;
+        }
+        Command::Validate {
+            db,
+            cost_table,
+            start,
+            limit,
+            sample_rate,
+        } => {
+            ensure!(sample_rate > 0, "Sample rate must be positive.");
+            let debugger = AptosDebugger::db(db)?;
+            let new = read_cost_table(&cost_table)?;
+            let old = fetch_cost_table(&debugger.state_view_at_version(start.checked_sub(1)))?;
+            check_compatibility(&old, &new)?;
+
+            let versions: Vec<_> = (start..start + limit)
+                .step_by(sample_rate as usize)
+                .collect();
+            let deltas = replay_with_cost_table(&debugger, &new, &versions)?;
+            println!("{}", serde_json::to_string_pretty(&deltas)?);
+
+            let total_delta: i128 = deltas.iter().map(|delta| delta.delta()).sum();
+            let num_status_changes = deltas
+                .iter()
+                .filter(|delta| delta.status_change.is_some())
+                .count();
+            eprintln!(
+                "Replayed {} transactions: total gas delta {}, {} status changes",
+                deltas.len(),
+                total_delta,
+                num_status_changes
+            );
+        }
+    }
+    Ok(())
+}
@@ -73,10 +73,24 @@ struct Options {
     /// (e.g. `AddressAccount` <- `path/to/AddressAccount.py`)
     #[structopt(long)]
     with_custom_aptos_code: Vec<PathBuf>,
+
+    /// Generate typed Rust bindings of the structs and script functions of the compiled Move
+    /// modules (`.mv` files) in the given directories, instead of builders from ABI files.
+    /// With `--target-source-dir`, the bindings are written to `<module_name>.rs`.
+    #[structopt(long)]
+    module_directories: Vec<PathBuf>,
+
+    /// Directories of compiled Move modules defining the structs used by `--module-directories`.
+    #[structopt(long)]
+    dependency_directories: Vec<PathBuf>,
 }
 
 fn main() {
     let options = Options::from_args();
+    if !options.module_directories.is_empty() {
+        output_bindings(options);
+        return;
+    }
     let abis =
         buildgen::read_abis(&options.abi_directories).expect("Failed to read ABI in directory");
 
@@ -225,3 +239,30 @@ fn main() {
             .unwrap();
     }
 }
+
+fn output_bindings(options: Options) {
+    if !matches!(options.language, Language::Rust) {
+        panic!("Bindings of Move modules can only be generated in Rust");
+    }
+    let modules = buildgen::read_modules(&options.module_directories)
+        .expect("Failed to read modules in directory");
+    let dependencies = buildgen::read_modules(&options.dependency_directories)
+        .expect("Failed to read dependencies in directory");
+
+    match options.target_source_dir {
+        None => {
+            let stdout = std::io::stdout();
+            let mut out = stdout.lock();
+            buildgen::rust_bindings::output(&mut out, &modules, &dependencies).unwrap();
+        }
+        Some(dir) => {
+            std::fs::create_dir_all(&dir).unwrap();
+            let name = options
+                .module_name
+                .unwrap_or_else(|| "move_bindings".to_string());
+            let mut file = std::fs::File::create(dir.join(format!("{}.rs", name)))
+                .expect("Failed to create bindings file");
+            buildgen::rust_bindings::output(&mut file, &modules, &dependencies).unwrap();
+        }
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_types::transaction::ScriptABI;
+use move_binary_format::CompiledModule;
 use serde_generate::CustomCode;
 use std::{ffi::OsStr, fs, io::Read, path::Path};
 
@@ -17,6 +18,8 @@ pub mod java;
 pub mod python3;
 /// Support for code-generation in Rust.
 pub mod rust;
+/// Support for typed Rust bindings of compiled Move modules.
+pub mod rust_bindings;
 /// Support for code-generation in Swift.
 pub mod swift;
 /// Support for code-generation in TypeScript.
@@ -58,6 +61,25 @@ pub fn read_abis(dir_paths: &[impl AsRef<Path>]) -> anyhow::Result<Vec<ScriptABI
     Ok(abis)
 }
 
+/// Read the compiled Move modules (`.mv` files) in the specified directories, without descending
+/// into subdirectories.
+pub fn read_modules(dir_paths: &[impl AsRef<Path>]) -> anyhow::Result<Vec<CompiledModule>> {
+    let mut modules = Vec::new();
+    for dir in dir_paths.iter() {
+        for entry in fs::read_dir(dir.as_ref())? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().and_then(OsStr::to_str) == Some("mv") {
+                let bytes = fs::read(&path)?;
+                modules.push(CompiledModule::deserialize(&bytes).map_err(|err| {
+                    anyhow::format_err!("Failed to deserialize {}: {:?}", path.display(), err)
+                })?);
+            }
+        }
+    }
+    modules.sort_by_key(|module| module.self_id());
+    Ok(modules)
+}
+
 /// How to copy ABI-generated source code for a given language.
 pub trait SourceInstaller {
     type Error;
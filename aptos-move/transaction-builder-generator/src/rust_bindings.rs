@@ -0,0 +1,527 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, Result};
+use heck::SnakeCase;
+use move_binary_format::{
+    file_format::{Ability, Visibility},
+    normalized::{Function, Module, Struct, Type},
+    CompiledModule,
+};
+use move_core_types::{
+    account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
+};
+use serde_generate::indent::{IndentConfig, IndentedWriter};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::Write,
+};
+
+/// Output typed Rust bindings for the given compiled Move modules.
+///
+/// Every struct of `modules` becomes a BCS-compatible Rust struct implementing `MoveStructType`,
+/// and `MoveResource` when the struct has `key`, so resources and events can be decoded without
+/// hand-written mirrors.  Every script function gets a builder of its `TransactionPayload`.
+///
+/// Structs of other modules referenced by `modules` are looked up in `dependencies`, and only
+/// the ones that are referenced are generated.
+pub fn output(
+    out: &mut dyn Write,
+    modules: &[CompiledModule],
+    dependencies: &[CompiledModule],
+) -> Result<()> {
+    let targets: BTreeMap<ModuleId, Module> = modules
+        .iter()
+        .map(|module| (module.self_id(), Module::new(module)))
+        .collect();
+    let mut available: BTreeMap<ModuleId, Module> = dependencies
+        .iter()
+        .map(|module| (module.self_id(), Module::new(module)))
+        .collect();
+    available.extend(targets.clone());
+
+    // Find the structs of the dependencies that are reachable from the target modules
+    let mut structs: BTreeMap<ModuleId, BTreeSet<Identifier>> = targets
+        .iter()
+        .map(|(id, module)| (id.clone(), module.structs.keys().cloned().collect()))
+        .collect();
+    let mut pending: Vec<(ModuleId, Identifier)> = vec![];
+    for module in targets.values() {
+        for struct_ in module.structs.values() {
+            for field in &struct_.fields {
+                collect_structs(&field.type_, &mut pending);
+            }
+        }
+        for function in script_functions(module).map(|(_, function)| function) {
+            for parameter in &function.parameters {
+                collect_structs(parameter, &mut pending);
+            }
+        }
+    }
+    while let Some((module_id, name)) = pending.pop() {
+        if structs
+            .get(&module_id)
+            .map(|names| names.contains(&name))
+            .unwrap_or(false)
+        {
+            continue;
+        }
+        let struct_ = match available
+            .get(&module_id)
+            .and_then(|module| module.structs.get(&name))
+        {
+            Some(struct_) => struct_,
+            None => bail!(
+                "Struct {}::{} is referenced but its module isn't among the dependencies",
+                module_id,
+                name
+            ),
+        };
+        for field in &struct_.fields {
+            collect_structs(&field.type_, &mut pending);
+        }
+        structs.entry(module_id).or_default().insert(name);
+    }
+
+    // Modules are generated as Rust modules named after them, regardless of their address
+    let mut rust_modules = BTreeMap::new();
+    for module_id in structs.keys() {
+        if let Some(other) = rust_modules.insert(module_name(module_id), module_id) {
+            bail!(
+                "Modules {} and {} would both be generated as Rust module `{}`",
+                other,
+                module_id,
+                module_name(module_id)
+            );
+        }
+    }
+
+    let mut emitter = BindingsEmitter {
+        out: IndentedWriter::new(out, IndentConfig::Space(4)),
+    };
+    emitter.output_preamble()?;
+    for (module_id, names) in &structs {
+        let module = &available[module_id];
+        emitter.output_module(
+            module_id,
+            names.iter().map(|name| (name, &module.structs[name])),
+            targets
+                .get(module_id)
+                .map(|module| script_functions(module).collect())
+                .unwrap_or_default(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Script functions are the entry points that can be called by transactions
+fn script_functions(module: &Module) -> impl Iterator<Item = (&Identifier, &Function)> {
+    module
+        .exposed_functions
+        .iter()
+        .filter(|(_, function)| function.visibility == Visibility::Script)
+}
+
+fn collect_structs(type_: &Type, structs: &mut Vec<(ModuleId, Identifier)>) {
+    match type_ {
+        Type::Struct {
+            address,
+            module,
+            name,
+            type_arguments,
+        } => {
+            structs.push((ModuleId::new(*address, module.clone()), name.clone()));
+            for type_argument in type_arguments {
+                collect_structs(type_argument, structs);
+            }
+        }
+        Type::Vector(type_) | Type::Reference(type_) | Type::MutableReference(type_) => {
+            collect_structs(type_, structs)
+        }
+        _ => (),
+    }
+}
+
+fn module_name(module_id: &ModuleId) -> String {
+    quote_identifier(&module_id.name().as_str().to_snake_case())
+}
+
+/// Rust keywords are escaped as raw identifiers, or suffixed when they can't be raw
+fn quote_identifier(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let",
+        "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+        "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+        "virtual", "where", "while", "yield",
+    ];
+    match name {
+        "crate" | "self" | "Self" | "super" => format!("{}_", name),
+        name if KEYWORDS.contains(&name) => format!("r#{}", name),
+        name => name.to_string(),
+    }
+}
+
+fn quote_address(address: &AccountAddress) -> String {
+    format!(
+        "::move_core_types::account_address::AccountAddress::new([{}])",
+        address
+            .to_vec()
+            .iter()
+            .map(|byte| byte.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+fn quote_type_parameters(count: usize, bound: &str) -> String {
+    if count == 0 {
+        String::new()
+    } else {
+        format!(
+            "<{}>",
+            (0..count)
+                .map(|index| format!("T{}{}", index, bound))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+/// The Rust type of a Move type, as seen from the Rust module of `current`
+fn quote_type(type_: &Type, current: &ModuleId) -> String {
+    match type_ {
+        Type::Bool => "bool".to_string(),
+        Type::U8 => "u8".to_string(),
+        Type::U64 => "u64".to_string(),
+        Type::U128 => "u128".to_string(),
+        Type::Address | Type::Signer => {
+            "::move_core_types::account_address::AccountAddress".to_string()
+        }
+        Type::Vector(type_) => format!("::std::vec::Vec<{}>", quote_type(type_, current)),
+        Type::Struct {
+            address,
+            module,
+            name,
+            type_arguments,
+        } => {
+            let module_id = ModuleId::new(*address, module.clone());
+            let path = if &module_id == current {
+                name.to_string()
+            } else {
+                format!("super::{}::{}", module_name(&module_id), name)
+            };
+            if type_arguments.is_empty() {
+                path
+            } else {
+                format!(
+                    "{}<{}>",
+                    path,
+                    type_arguments
+                        .iter()
+                        .map(|type_| quote_type(type_, current))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            }
+        }
+        Type::TypeParameter(index) => format!("T{}", index),
+        Type::Reference(type_) | Type::MutableReference(type_) => quote_type(type_, current),
+    }
+}
+
+fn uses_type_parameter(type_: &Type, index: u16) -> bool {
+    match type_ {
+        Type::TypeParameter(other) => *other == index,
+        Type::Vector(type_) | Type::Reference(type_) | Type::MutableReference(type_) => {
+            uses_type_parameter(type_, index)
+        }
+        Type::Struct { type_arguments, .. } => type_arguments
+            .iter()
+            .any(|type_| uses_type_parameter(type_, index)),
+        _ => false,
+    }
+}
+
+fn is_signer(type_: &Type) -> bool {
+    match type_ {
+        Type::Signer => true,
+        Type::Reference(type_) | Type::MutableReference(type_) => is_signer(type_),
+        _ => false,
+    }
+}
+
+/// Shared state for the bindings generator.
+struct BindingsEmitter<T> {
+    /// Writer.
+    out: IndentedWriter<T>,
+}
+
+impl<T> BindingsEmitter<T>
+where
+    T: Write,
+{
+    fn output_preamble(&mut self) -> std::io::Result<()> {
+        writeln!(
+            self.out,
+            r#"// Typed bindings of Move structs and script functions, BCS-compatible with their Move values.
+//
+// This code was generated from compiled Move modules with the tool `transaction-builder-generator`.
+
+#![allow(clippy::too_many_arguments, non_camel_case_types, non_snake_case)]
+
+/// A Rust type with a Move counterpart
+pub trait MoveType: ::serde::Serialize + ::serde::de::DeserializeOwned {{
+    fn type_tag() -> ::move_core_types::language_storage::TypeTag;
+}}
+
+impl MoveType for bool {{
+    fn type_tag() -> ::move_core_types::language_storage::TypeTag {{
+        ::move_core_types::language_storage::TypeTag::Bool
+    }}
+}}
+
+impl MoveType for u8 {{
+    fn type_tag() -> ::move_core_types::language_storage::TypeTag {{
+        ::move_core_types::language_storage::TypeTag::U8
+    }}
+}}
+
+impl MoveType for u64 {{
+    fn type_tag() -> ::move_core_types::language_storage::TypeTag {{
+        ::move_core_types::language_storage::TypeTag::U64
+    }}
+}}
+
+impl MoveType for u128 {{
+    fn type_tag() -> ::move_core_types::language_storage::TypeTag {{
+        ::move_core_types::language_storage::TypeTag::U128
+    }}
+}}
+
+impl MoveType for ::move_core_types::account_address::AccountAddress {{
+    fn type_tag() -> ::move_core_types::language_storage::TypeTag {{
+        ::move_core_types::language_storage::TypeTag::Address
+    }}
+}}
+
+impl<T: MoveType> MoveType for ::std::vec::Vec<T> {{
+    fn type_tag() -> ::move_core_types::language_storage::TypeTag {{
+        ::move_core_types::language_storage::TypeTag::Vector(Box::new(T::type_tag()))
+    }}
+}}"#
+        )
+    }
+
+    fn output_module<'a>(
+        &mut self,
+        module_id: &ModuleId,
+        structs: impl Iterator<Item = (&'a Identifier, &'a Struct)>,
+        functions: Vec<(&Identifier, &Function)>,
+    ) -> std::io::Result<()> {
+        writeln!(
+            self.out,
+            "\n/// Bindings of the Move module `{}`",
+            module_id
+        )?;
+        writeln!(self.out, "pub mod {} {{", module_name(module_id))?;
+        self.out.indent();
+        for (name, struct_) in structs {
+            self.output_struct(module_id, name, struct_)?;
+        }
+        for (name, function) in functions {
+            self.output_script_function(module_id, name, function)?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_struct(
+        &mut self,
+        module_id: &ModuleId,
+        name: &Identifier,
+        struct_: &Struct,
+    ) -> std::io::Result<()> {
+        let num_type_parameters = struct_.type_parameters.len();
+        let unused_type_parameters: Vec<_> = (0..num_type_parameters as u16)
+            .filter(|index| {
+                !struct_
+                    .fields
+                    .iter()
+                    .any(|field| uses_type_parameter(&field.type_, *index))
+            })
+            .map(|index| format!("T{}", index))
+            .collect();
+
+        writeln!(
+            self.out,
+            "\n/// Mirror of the Move struct `{}::{}`",
+            module_id, name
+        )?;
+        writeln!(
+            self.out,
+            "#[derive(Clone, Debug, PartialEq, ::serde::Serialize, ::serde::Deserialize)]"
+        )?;
+        writeln!(
+            self.out,
+            "pub struct {}{} {{",
+            name,
+            quote_type_parameters(num_type_parameters, "")
+        )?;
+        self.out.indent();
+        for field in &struct_.fields {
+            writeln!(
+                self.out,
+                "pub {}: {},",
+                quote_identifier(field.name.as_str()),
+                quote_type(&field.type_, module_id)
+            )?;
+        }
+        // Phantom type parameters don't change the layout, but Rust requires them to be used
+        if !unused_type_parameters.is_empty() {
+            writeln!(self.out, "#[serde(skip)]")?;
+            writeln!(
+                self.out,
+                "pub phantom_data: ::std::marker::PhantomData<({},)>,",
+                unused_type_parameters.join(", ")
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+
+        let impl_generics = quote_type_parameters(num_type_parameters, ": super::MoveType");
+        let type_ = format!("{}{}", name, quote_type_parameters(num_type_parameters, ""));
+        writeln!(
+            self.out,
+            "\nimpl{} ::move_core_types::move_resource::MoveStructType for {} {{",
+            impl_generics, type_
+        )?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            "const ADDRESS: ::move_core_types::account_address::AccountAddress = {};",
+            quote_address(module_id.address())
+        )?;
+        writeln!(
+            self.out,
+            "const MODULE_NAME: &'static ::move_core_types::identifier::IdentStr = ::move_core_types::ident_str!(\"{}\");",
+            module_id.name()
+        )?;
+        writeln!(
+            self.out,
+            "const STRUCT_NAME: &'static ::move_core_types::identifier::IdentStr = ::move_core_types::ident_str!(\"{}\");",
+            name
+        )?;
+        if num_type_parameters > 0 {
+            writeln!(
+                self.out,
+                "\nfn type_params() -> ::std::vec::Vec<::move_core_types::language_storage::TypeTag> {{"
+            )?;
+            self.out.indent();
+            writeln!(
+                self.out,
+                "vec![{}]",
+                (0..num_type_parameters)
+                    .map(|index| format!("T{}::type_tag()", index))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?;
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+
+        writeln!(
+            self.out,
+            "\nimpl{} super::MoveType for {} {{",
+            impl_generics, type_
+        )?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            "fn type_tag() -> ::move_core_types::language_storage::TypeTag {{"
+        )?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            "::move_core_types::language_storage::TypeTag::Struct(<Self as ::move_core_types::move_resource::MoveStructType>::struct_tag())"
+        )?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+
+        if struct_.abilities.has_ability(Ability::Key) {
+            writeln!(
+                self.out,
+                "\nimpl{} ::move_core_types::move_resource::MoveResource for {} {{}}",
+                impl_generics, type_
+            )?;
+        }
+        Ok(())
+    }
+
+    fn output_script_function(
+        &mut self,
+        module_id: &ModuleId,
+        name: &Identifier,
+        function: &Function,
+    ) -> std::io::Result<()> {
+        // Signers are provided by the transaction, not passed as arguments
+        let parameters: Vec<_> = function
+            .parameters
+            .iter()
+            .skip_while(|type_| is_signer(type_))
+            .collect();
+
+        writeln!(
+            self.out,
+            "\n/// Builds a payload calling the Move script function `{}::{}`",
+            module_id, name
+        )?;
+        writeln!(
+            self.out,
+            "pub fn {}({}) -> ::aptos_types::transaction::TransactionPayload {{",
+            quote_identifier(name.as_str()),
+            (0..function.type_parameters.len())
+                .map(|index| format!(
+                    "ty_arg{}: ::move_core_types::language_storage::TypeTag",
+                    index
+                ))
+                .chain(parameters.iter().enumerate().map(|(index, type_)| format!(
+                    "arg{}: {}",
+                    index,
+                    quote_type(type_, module_id)
+                )))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            r#"::aptos_types::transaction::TransactionPayload::ScriptFunction(::aptos_types::transaction::ScriptFunction::new(
+    ::move_core_types::language_storage::ModuleId::new(
+        {},
+        ::move_core_types::ident_str!("{}").to_owned(),
+    ),
+    ::move_core_types::ident_str!("{}").to_owned(),
+    vec![{}],
+    vec![{}],
+))"#,
+            quote_address(module_id.address()),
+            module_id.name(),
+            name,
+            (0..function.type_parameters.len())
+                .map(|index| format!("ty_arg{}", index))
+                .collect::<Vec<_>>()
+                .join(", "),
+            (0..parameters.len())
+                .map(|index| format!("::bcs::to_bytes(&arg{}).unwrap()", index))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+}
@@ -239,6 +239,95 @@ fn test_that_rust_script_fun_code_compiles() {
     );
 }
 
+#[test]
+fn test_rust_bindings_of_modules() {
+    let (test_coin, dependencies): (Vec<_>, Vec<_>) = cached_framework_packages::modules()
+        .iter()
+        .cloned()
+        .partition(|module| module.self_id().name().as_str() == "TestCoin");
+    let mut out = Vec::new();
+    buildgen::rust_bindings::output(&mut out, &test_coin, &dependencies).unwrap();
+    let bindings = String::from_utf8(out).unwrap();
+
+    assert!(bindings.contains("pub mod test_coin {"));
+    assert!(bindings.contains("pub struct Balance {"));
+    assert!(bindings.contains("pub coin: Coin,"));
+    assert!(bindings.contains("impl ::move_core_types::move_resource::MoveResource for Balance {}"));
+    // `Coin` doesn't have `key`, so it can't be read as a resource
+    assert!(!bindings.contains("MoveResource for Coin "));
+    assert!(bindings.contains(
+        "pub fn transfer(arg0: ::move_core_types::account_address::AccountAddress, arg1: u64)"
+    ));
+    // Only the structs used by `TestCoin` are generated for its dependencies
+    assert!(!bindings.contains("pub mod token {"));
+
+    // The struct tags of the bindings match the Move structs
+    assert!(bindings.contains("::move_core_types::ident_str!(\"TestCoin\")"));
+}
+
+#[test]
+fn test_that_rust_bindings_of_modules_compile() {
+    let (test_coin, dependencies): (Vec<_>, Vec<_>) = cached_framework_packages::modules()
+        .iter()
+        .cloned()
+        .partition(|module| module.self_id().name().as_str() == "TestCoin");
+    let dir = tempdir().unwrap();
+    let bindings_dir_path = dir.path().join("bindings");
+    std::fs::create_dir_all(bindings_dir_path.join("src")).unwrap();
+
+    let repository_path = std::env::current_dir().unwrap().join("../..");
+    let mut cargo = std::fs::File::create(&bindings_dir_path.join("Cargo.toml")).unwrap();
+    write!(
+        cargo,
+        r#"[package]
+name = "bindings"
+version = "0.1.0"
+edition = "2018"
+
+[dependencies]
+aptos-types = {{ path = "{}" }}
+bcs = "0.1.2"
+move-core-types = {{ git = "https://github.com/move-language/move", rev = "1b6b7513dcc1a5c866f178ca5c1e74beb2ce181e", features = ["address32"] }}
+serde = {{ version = "1.0.114", features = ["derive"] }}
+
+[workspace]
+"#,
+        repository_path.join("types").display()
+    )
+    .unwrap();
+    // Resolve the same versions as the repository when it has a lockfile, cargo resolves the
+    // dependencies of the temporary crate otherwise
+    let lockfile = repository_path.join("Cargo.lock");
+    if lockfile.exists() {
+        std::fs::copy(lockfile, bindings_dir_path.join("Cargo.lock")).unwrap();
+    }
+
+    let mut source = std::fs::File::create(&bindings_dir_path.join("src/lib.rs")).unwrap();
+    buildgen::rust_bindings::output(&mut source, &test_coin, &dependencies).unwrap();
+
+    // Use a stable `target` dir to avoid downloading and recompiling crates everytime.
+    let target_dir = repository_path.join("target");
+    let status = Command::new("cargo")
+        .current_dir(&bindings_dir_path)
+        .arg("build")
+        .arg("--target-dir")
+        .arg(target_dir)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn test_rust_bindings_require_dependencies() {
+    let test_coin: Vec<_> = cached_framework_packages::modules()
+        .iter()
+        .filter(|module| module.self_id().name().as_str() == "TestCoin")
+        .cloned()
+        .collect();
+    let mut out = Vec::new();
+    assert!(buildgen::rust_bindings::output(&mut out, &test_coin, &[]).is_err());
+}
+
 #[test]
 #[ignore]
 fn test_that_cpp_code_compiles_and_demo_runs() {
@@ -12,7 +12,9 @@ use aptos_types::{
     account_state::AccountState,
     contract_event::{ContractEvent, EventWithProof},
     event::EventKey,
-    transaction::{ChangeSet, Transaction, TransactionOutput, Version, WriteSetPayload},
+    transaction::{
+        ChangeSet, Transaction, TransactionInfo, TransactionOutput, Version, WriteSetPayload,
+    },
     write_set::WriteOp,
 };
 use aptos_validator_interface::{AptosValidatorInterface, DBDebuggerInterface, DebuggerStateView};
@@ -36,6 +38,9 @@ use std::{
 
 #[cfg(test)]
 mod unit_tests;
+mod verify;
+
+pub use verify::{compare_output, Divergence, DivergenceKind, VerificationReport};
 
 pub struct AptosDebugger {
     debugger: Box<dyn AptosValidatorInterface>,
@@ -58,6 +63,11 @@ impl AptosDebugger {
         )?)))
     }
 
+    /// Returns a view of the state right after `version` was committed.
+    pub fn state_view_at_version(&self, version: Option<Version>) -> DebuggerStateView {
+        DebuggerStateView::new(&*self.debugger, version)
+    }
+
     pub fn execute_transactions_at_version(
         &self,
         version: Version,
@@ -69,22 +79,34 @@ impl AptosDebugger {
     }
 
     pub fn execute_past_transactions(
+        &self,
+        begin: Version,
+        limit: u64,
+        save_write_sets: bool,
+    ) -> Result<Vec<TransactionOutput>> {
+        let txns = self.debugger.get_committed_transactions(begin, limit)?;
+        self.execute_transactions_from_version(begin, txns, save_write_sets)
+    }
+
+    /// Execute `txns` as the transactions starting at version `begin`, on top of the state
+    /// committed right before it, starting over from the committed state at every new epoch.
+    pub fn execute_transactions_from_version(
         &self,
         mut begin: Version,
-        mut limit: u64,
+        mut txns: Vec<Transaction>,
         save_write_sets: bool,
     ) -> Result<Vec<TransactionOutput>> {
-        let mut txns = self.debugger.get_committed_transactions(begin, limit)?;
         let mut ret = vec![];
-        while limit != 0 {
-            println!(
+        while !txns.is_empty() {
+            // stdout is kept for the results of the commands
+            eprintln!(
                 "Starting epoch execution at {:?}, {:?} transactions remaining",
-                begin, limit
+                begin,
+                txns.len()
             );
             let mut epoch_result =
                 self.execute_transactions_by_epoch(begin, txns.clone(), save_write_sets)?;
             begin += epoch_result.len() as u64;
-            limit -= epoch_result.len() as u64;
             txns = txns.split_off(epoch_result.len());
             ret.append(&mut epoch_result);
         }
@@ -308,6 +330,23 @@ impl AptosDebugger {
         self.debugger.get_latest_version()
     }
 
+    pub fn get_committed_transactions(
+        &self,
+        start: Version,
+        limit: u64,
+    ) -> Result<Vec<Transaction>> {
+        self.debugger.get_committed_transactions(start, limit)
+    }
+
+    pub fn get_committed_transaction_outputs(
+        &self,
+        start: Version,
+        limit: u64,
+    ) -> Result<Vec<(TransactionOutput, TransactionInfo)>> {
+        self.debugger
+            .get_committed_transaction_outputs(start, limit)
+    }
+
     pub fn get_version_by_account_sequence(
         &self,
         account: AccountAddress,
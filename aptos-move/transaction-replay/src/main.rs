@@ -30,6 +30,19 @@ enum Command {
     /// Replay transactions starting from version `start` to `start + limit`.
     #[structopt(name = "replay-transactions")]
     ReplayTransactions { start: Version, limit: u64 },
+    /// Re-execute transactions from version `start` to `start + limit` with the local VM and
+    /// report every output that diverges from the one committed in storage.
+    #[structopt(name = "verify-transactions")]
+    VerifyTransactions {
+        start: Version,
+        limit: u64,
+        /// Number of transactions executed per chunk. Chunks are executed in parallel.
+        #[structopt(long, default_value = "1000")]
+        chunk_size: u64,
+        /// If set, write the divergence report as JSON to this file instead of stdout.
+        #[structopt(long, parse(from_os_str))]
+        report_path: Option<PathBuf>,
+    },
     /// Replay the last `txns` committed transactions.
     #[structopt(name = "replay-recent-transactions")]
     ReplayRecentTransactions { txns: u64 },
@@ -93,7 +106,7 @@ fn main() -> Result<()> {
         panic!("No debugger attached")
     };
 
-    println!("Connection Succeeded");
+    eprintln!("Connection Succeeded");
 
     match opt.cmd {
         Command::ReplayTransactions { start, limit } => {
@@ -102,6 +115,26 @@ fn main() -> Result<()> {
                 debugger.execute_past_transactions(start, limit, opt.save_write_sets)
             );
         }
+        Command::VerifyTransactions {
+            start,
+            limit,
+            chunk_size,
+            report_path,
+        } => {
+            let report = debugger.verify_past_transactions(start, limit, chunk_size)?;
+            let report_json = serde_json::to_string_pretty(&report)?;
+            match report_path {
+                Some(path) => fs::write(path, report_json)?,
+                None => println!("{}", report_json),
+            }
+            if !report.is_ok() {
+                bail!(
+                    "{} out of {} transactions diverged",
+                    report.num_diverged_transactions,
+                    report.num_transactions
+                );
+            }
+        }
         Command::ReplayRecentTransactions { txns } => {
             let latest_version = debugger
                 .get_latest_version()
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod bisection_tests;
+mod verify_tests;
 
 use crate::AptosValidatorInterface;
 use anyhow::{bail, Result};
@@ -13,7 +14,7 @@ use aptos_types::{
     contract_event::EventWithProof,
     event::EventKey,
     state_store::{state_key::StateKey, state_value::StateValue},
-    transaction::{Transaction, Version, WriteSetPayload},
+    transaction::{Transaction, TransactionInfo, TransactionOutput, Version, WriteSetPayload},
     write_set::WriteOp,
 };
 use std::{collections::HashMap, convert::TryFrom};
@@ -110,6 +111,14 @@ impl AptosValidatorInterface for TestInterface {
         Ok(result)
     }
 
+    fn get_committed_transaction_outputs(
+        &self,
+        _start: Version,
+        _limit: u64,
+    ) -> Result<Vec<(TransactionOutput, TransactionInfo)>> {
+        unimplemented!()
+    }
+
     fn get_latest_version(&self) -> Result<Version> {
         Ok(self.latest_version)
     }
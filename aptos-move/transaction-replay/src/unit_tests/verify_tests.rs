@@ -0,0 +1,92 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{compare_output, verify::compare, DivergenceKind};
+use aptos_crypto::{
+    hash::{CryptoHash, EventAccumulatorHasher},
+    HashValue,
+};
+use aptos_types::{
+    contract_event::ContractEvent,
+    event::EventKey,
+    proof::accumulator::InMemoryAccumulator,
+    state_store::state_key::StateKey,
+    transaction::{TransactionInfo, TransactionOutput, TransactionStatus},
+    vm_status::KeptVMStatus,
+    write_set::{WriteOp, WriteSetMut},
+};
+use move_core_types::language_storage::TypeTag;
+
+fn output(value: u8, gas_used: u64, status: KeptVMStatus) -> TransactionOutput {
+    TransactionOutput::new(
+        WriteSetMut::new(vec![(
+            StateKey::Raw(b"key".to_vec()),
+            WriteOp::Value(vec![value]),
+        )])
+        .freeze()
+        .unwrap(),
+        vec![ContractEvent::new(
+            EventKey::new_from_address(&Default::default(), 0),
+            0,
+            TypeTag::U64,
+            vec![value],
+        )],
+        gas_used,
+        TransactionStatus::Keep(status),
+    )
+}
+
+fn txn_info(output: &TransactionOutput) -> TransactionInfo {
+    let event_hashes: Vec<_> = output.events().iter().map(CryptoHash::hash).collect();
+    TransactionInfo::new(
+        HashValue::zero(),
+        HashValue::zero(),
+        InMemoryAccumulator::<EventAccumulatorHasher>::from_leaves(&event_hashes).root_hash(),
+        output.gas_used(),
+        KeptVMStatus::Executed,
+    )
+}
+
+#[test]
+fn test_identical_output() {
+    let expected = output(1, 10, KeptVMStatus::Executed);
+    let info = txn_info(&expected);
+    assert!(compare_output(0, &expected, &expected, &info).is_empty());
+}
+
+#[test]
+fn test_divergent_output() {
+    let expected = output(1, 10, KeptVMStatus::Executed);
+    let info = txn_info(&expected);
+
+    let kinds = |actual: &TransactionOutput| {
+        compare_output(0, actual, &expected, &info)
+            .into_iter()
+            .map(|(kind, _)| kind)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        kinds(&output(1, 11, KeptVMStatus::Executed)),
+        vec![DivergenceKind::GasUsed]
+    );
+    assert_eq!(
+        kinds(&output(1, 10, KeptVMStatus::OutOfGas)),
+        vec![DivergenceKind::Status]
+    );
+    assert_eq!(
+        kinds(&output(2, 10, KeptVMStatus::Executed)),
+        vec![DivergenceKind::WriteSet, DivergenceKind::Events]
+    );
+}
+
+#[test]
+fn test_divergent_output_without_write_set() {
+    // the transactions of a backup come without their write sets
+    let info = txn_info(&output(1, 10, KeptVMStatus::Executed));
+    let kinds: Vec<_> = compare(0, &output(2, 10, KeptVMStatus::Executed), None, &info)
+        .into_iter()
+        .map(|(kind, _)| kind)
+        .collect();
+    assert_eq!(kinds, vec![DivergenceKind::Events]);
+}
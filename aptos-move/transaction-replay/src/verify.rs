@@ -0,0 +1,289 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Re-executes committed transactions with the locally built VM and compares the outputs against
+//! what is recorded in storage, so VM changes can be checked for unintended divergences before
+//! they are rolled out.
+
+use crate::AptosDebugger;
+use anyhow::{ensure, Result};
+use aptos_crypto::hash::{CryptoHash, EventAccumulatorHasher};
+use aptos_types::{
+    proof::accumulator::InMemoryAccumulator,
+    transaction::{Transaction, TransactionInfo, TransactionOutput, TransactionStatus, Version},
+};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The reason a re-executed transaction did not match the committed one.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DivergenceKind {
+    /// The VM failed to execute the chunk the transaction belongs to.
+    ExecutionError,
+    /// The transaction was discarded or retried instead of being kept.
+    Status,
+    /// The amount of gas charged differs.
+    GasUsed,
+    /// The set of state keys written or the values written differ.
+    WriteSet,
+    /// The emitted events differ.
+    Events,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Divergence {
+    pub version: Version,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// Summary of a verification run, with divergences grouped by cause.
+#[derive(Debug, Default, Serialize)]
+pub struct VerificationReport {
+    pub start_version: Version,
+    pub num_transactions: u64,
+    pub num_diverged_transactions: u64,
+    pub divergences: BTreeMap<DivergenceKind, Vec<Divergence>>,
+}
+
+impl VerificationReport {
+    pub fn new(start_version: Version) -> Self {
+        Self {
+            start_version,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.divergences.is_empty()
+    }
+
+    /// Add the results of a later range of transactions.
+    pub fn merge(&mut self, other: Self) {
+        self.num_transactions += other.num_transactions;
+        for (kind, mut divergences) in other.divergences {
+            self.divergences
+                .entry(kind)
+                .or_insert_with(Vec::new)
+                .append(&mut divergences);
+        }
+    }
+
+    fn add_execution_error(&mut self, begin: Version, num_txns: u64, err: anyhow::Error) {
+        self.add(
+            DivergenceKind::ExecutionError,
+            Divergence {
+                version: begin,
+                expected: format!("{} transactions executed", num_txns),
+                actual: format!("{:?}", err),
+            },
+        );
+    }
+
+    fn add(&mut self, kind: DivergenceKind, divergence: Divergence) {
+        self.divergences
+            .entry(kind)
+            .or_insert_with(Vec::new)
+            .push(divergence);
+    }
+
+    /// Sort the divergences and count the diverged transactions, once all ranges are merged.
+    pub fn finalize(&mut self) {
+        let mut diverged = BTreeSet::new();
+        for divergences in self.divergences.values_mut() {
+            divergences.sort_by_key(|d| d.version);
+            diverged.extend(divergences.iter().map(|d| d.version));
+        }
+        self.num_diverged_transactions = diverged.len() as u64;
+    }
+}
+
+impl AptosDebugger {
+    /// Re-execute the transactions in `[begin, begin + limit)` in chunks of `chunk_size`, running
+    /// the chunks in parallel, and compare every output against the committed one.
+    pub fn verify_past_transactions(
+        &self,
+        begin: Version,
+        limit: u64,
+        chunk_size: u64,
+    ) -> Result<VerificationReport> {
+        ensure!(chunk_size > 0, "Chunk size must be positive.");
+        let chunks: Vec<_> = (begin..begin + limit)
+            .step_by(chunk_size as usize)
+            .map(|chunk_begin| {
+                (
+                    chunk_begin,
+                    std::cmp::min(chunk_size, begin + limit - chunk_begin),
+                )
+            })
+            .collect();
+
+        let reports = chunks
+            .into_par_iter()
+            .map(|(chunk_begin, chunk_limit)| self.verify_chunk(chunk_begin, chunk_limit))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut report = VerificationReport::new(begin);
+        for chunk_report in reports {
+            report.merge(chunk_report);
+        }
+        report.finalize();
+        Ok(report)
+    }
+
+    /// Re-execute `txns` as the transactions starting at `begin`, e.g. the transactions of a
+    /// backup, and compare the outputs against their `TransactionInfo`s. Only the state up to
+    /// `begin - 1` needs to be in storage, so the write sets are not compared.
+    pub fn verify_transactions(
+        &self,
+        begin: Version,
+        txns: Vec<Transaction>,
+        txn_infos: &[TransactionInfo],
+    ) -> Result<VerificationReport> {
+        ensure!(
+            txns.len() == txn_infos.len(),
+            "Got {} transactions but {} transaction infos",
+            txns.len(),
+            txn_infos.len()
+        );
+        let mut report = VerificationReport {
+            start_version: begin,
+            num_transactions: txns.len() as u64,
+            ..Default::default()
+        };
+        let outputs = match self.execute_transactions_from_version(begin, txns, false) {
+            Ok(outputs) => outputs,
+            Err(err) => {
+                report.add_execution_error(begin, txn_infos.len() as u64, err);
+                return Ok(report);
+            }
+        };
+        for (idx, (output, txn_info)) in outputs.iter().zip(txn_infos).enumerate() {
+            for (kind, divergence) in compare(begin + idx as u64, output, None, txn_info) {
+                report.add(kind, divergence);
+            }
+        }
+        Ok(report)
+    }
+
+    fn verify_chunk(&self, begin: Version, limit: u64) -> Result<VerificationReport> {
+        let committed = self
+            .debugger
+            .get_committed_transaction_outputs(begin, limit)?;
+        let mut report = VerificationReport {
+            start_version: begin,
+            num_transactions: limit,
+            ..Default::default()
+        };
+
+        let outputs = match self.execute_past_transactions(begin, limit, false) {
+            Ok(outputs) => outputs,
+            Err(err) => {
+                report.add_execution_error(begin, limit, err);
+                return Ok(report);
+            }
+        };
+        ensure!(
+            outputs.len() == committed.len(),
+            "Expected {} outputs at version {}, got {}",
+            committed.len(),
+            begin,
+            outputs.len()
+        );
+
+        for (idx, (output, (expected_output, txn_info))) in
+            outputs.iter().zip(committed.iter()).enumerate()
+        {
+            for (kind, divergence) in
+                compare_output(begin + idx as u64, output, expected_output, txn_info)
+            {
+                report.add(kind, divergence);
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// Compare a locally produced output with the output and `TransactionInfo` stored for `version`.
+pub fn compare_output(
+    version: Version,
+    output: &TransactionOutput,
+    expected_output: &TransactionOutput,
+    txn_info: &TransactionInfo,
+) -> Vec<(DivergenceKind, Divergence)> {
+    compare(version, output, Some(expected_output), txn_info)
+}
+
+/// The write set is only compared when the expected output is known.
+pub(crate) fn compare(
+    version: Version,
+    output: &TransactionOutput,
+    expected_output: Option<&TransactionOutput>,
+    txn_info: &TransactionInfo,
+) -> Vec<(DivergenceKind, Divergence)> {
+    let mut result = vec![];
+    let mut diverge = |kind, expected: String, actual: String| {
+        result.push((
+            kind,
+            Divergence {
+                version,
+                expected,
+                actual,
+            },
+        ))
+    };
+
+    match output.status() {
+        TransactionStatus::Keep(status) if status == txn_info.status() => (),
+        status => diverge(
+            DivergenceKind::Status,
+            format!("{:?}", txn_info.status()),
+            format!("{:?}", status),
+        ),
+    }
+
+    if output.gas_used() != txn_info.gas_used() {
+        diverge(
+            DivergenceKind::GasUsed,
+            txn_info.gas_used().to_string(),
+            output.gas_used().to_string(),
+        );
+    }
+
+    if let Some(expected_output) =
+        expected_output.filter(|expected| expected.write_set() != output.write_set())
+    {
+        let expected: BTreeMap<_, _> = expected_output.write_set().iter().cloned().collect();
+        let actual: BTreeMap<_, _> = output.write_set().iter().cloned().collect();
+        let keys: BTreeSet<_> = expected.keys().chain(actual.keys()).collect();
+        for key in keys {
+            let (expected_op, actual_op) = (expected.get(key), actual.get(key));
+            if expected_op != actual_op {
+                diverge(
+                    DivergenceKind::WriteSet,
+                    format!("{:?} => {:?}", key, expected_op),
+                    format!("{:?} => {:?}", key, actual_op),
+                );
+            }
+        }
+    }
+
+    let event_hashes: Vec<_> = output.events().iter().map(CryptoHash::hash).collect();
+    let event_root_hash =
+        InMemoryAccumulator::<EventAccumulatorHasher>::from_leaves(&event_hashes).root_hash();
+    if event_root_hash != txn_info.event_root_hash() {
+        let expected = match expected_output {
+            Some(expected_output) => format!("{:?}", expected_output.events()),
+            None => format!("event root hash {}", txn_info.event_root_hash()),
+        };
+        diverge(
+            DivergenceKind::Events,
+            expected,
+            format!("{:?}", output.events()),
+        );
+    }
+
+    result
+}
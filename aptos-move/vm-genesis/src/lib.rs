@@ -25,6 +25,7 @@ use aptos_vm::{
     data_cache::{IntoMoveResolver, StateViewCache},
     move_vm_ext::{MoveVmExt, SessionExt, SessionId},
 };
+use framework::natives::gas_schedule::INITIAL_APTOS_COST_SCHEDULE;
 use move_binary_format::CompiledModule;
 use move_bytecode_utils::Modules;
 use move_core_types::{
@@ -34,7 +35,7 @@ use move_core_types::{
     resolver::MoveResolver,
     value::{serialize_values, MoveValue},
 };
-use move_vm_types::gas_schedule::{GasStatus, INITIAL_COST_SCHEDULE};
+use move_vm_types::gas_schedule::GasStatus;
 use once_cell::sync::Lazy;
 use rand::prelude::*;
 
@@ -203,7 +204,7 @@ fn create_and_initialize_main_accounts(
             .collect(),
     );
 
-    let genesis_gas_schedule = &INITIAL_COST_SCHEDULE;
+    let genesis_gas_schedule = &INITIAL_APTOS_COST_SCHEDULE;
     let instr_gas_costs = bcs::to_bytes(&genesis_gas_schedule.instruction_table)
         .expect("Failure serializing genesis instr gas costs");
     let native_gas_costs = bcs::to_bytes(&genesis_gas_schedule.native_table)
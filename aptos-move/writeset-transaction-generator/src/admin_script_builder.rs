@@ -10,7 +10,7 @@ use aptos_types::{
 };
 use handlebars::Handlebars;
 use move_compiler::{compiled_unit::AnnotatedCompiledUnit, Compiler, Flags};
-use move_core_types::transaction_argument::TransactionArgument;
+use move_core_types::{gas_schedule::CostTable, transaction_argument::TransactionArgument};
 use read_write_set::analyze;
 use serde::Serialize;
 use std::{collections::HashMap, io::Write, path::PathBuf};
@@ -158,3 +158,24 @@ pub fn encode_enable_parallel_execution_with_config() -> WriteSetPayload {
         execute_as: aptos_root_address(),
     }
 }
+
+pub fn encode_update_gas_schedule_payload(cost_table: &CostTable) -> WriteSetPayload {
+    let instruction_schedule = bcs::to_bytes(&cost_table.instruction_table)
+        .expect("Failed to serialize instruction table");
+    let native_schedule =
+        bcs::to_bytes(&cost_table.native_table).expect("Failed to serialize native table");
+
+    let mut script = template_path();
+    script.push("update_gas_schedule.move");
+    WriteSetPayload::Script {
+        script: Script::new(
+            compile_script(script.to_str().unwrap().to_owned()),
+            vec![],
+            vec![
+                TransactionArgument::U8Vector(instruction_schedule),
+                TransactionArgument::U8Vector(native_schedule),
+            ],
+        ),
+        execute_as: aptos_root_address(),
+    }
+}
@@ -9,6 +9,7 @@ pub use admin_script_builder::{
     encode_custom_script, encode_disable_parallel_execution,
     encode_enable_parallel_execution_with_config, encode_halt_network_payload,
     encode_initialize_parallel_execution, encode_remove_validators_payload,
+    encode_update_gas_schedule_payload,
 };
 
 pub use writeset_builder::{build_changeset, GenesisSession};
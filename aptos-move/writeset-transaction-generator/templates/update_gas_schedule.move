@@ -0,0 +1,6 @@
+script {
+    use AptosFramework::VMConfig;
+    fun main(core_resources: signer, instruction_schedule: vector<u8>, native_schedule: vector<u8>) {
+        VMConfig::set_gas_schedule(core_resources, instruction_schedule, native_schedule);
+    }
+}
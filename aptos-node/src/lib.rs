@@ -55,7 +55,7 @@ use std::{
     convert::TryFrom,
     io::Write,
     net::ToSocketAddrs,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
@@ -137,15 +137,57 @@ pub fn load_test_environment<R>(
     R: ::rand::RngCore + ::rand::CryptoRng,
 {
     let config_temp_path = aptos_temppath::TempPath::new();
+    let config_path = config_path.unwrap_or_else(|| config_temp_path.as_ref().to_path_buf());
+
+    let (config, config_path) = load_or_create_test_config(
+        &config_path,
+        random_ports,
+        lazy,
+        publishing_option,
+        genesis_modules,
+        rng,
+    );
+    let aptos_root_key_path = config_path.join("mint.key");
 
-    let (try_load, config_path) = if let Some(config_path) = config_path {
-        (
-            config_path.join("0").join("node.yaml").exists(),
-            config_path,
-        )
-    } else {
-        (false, config_temp_path.as_ref().to_path_buf())
-    };
+    // Prepare log file since we cannot automatically route logs to stderr
+    let log_file = config_path.join("validator.log");
+
+    println!("Completed generating configuration:");
+    println!("\tLog file: {:?}", log_file);
+    println!("\tConfig path: {:?}", config_path);
+    println!("\tAptos root key path: {:?}", aptos_root_key_path);
+    println!("\tWaypoint: {}", config.base.waypoint.genesis_waypoint());
+    println!("\tChainId: {}", ChainId::test());
+    println!("\tREST API endpoint: {}", &config.api.address);
+    println!(
+        "\tFullNode network: {}",
+        &config.full_node_networks[0].listen_address
+    );
+    if lazy {
+        println!("\tLazy mode is enabled");
+    }
+
+    println!("\nAptos is running, press ctrl-c to exit\n");
+
+    start(&config, Some(log_file))
+}
+
+/// Creates the config of a single validator testnet in `config_path`, along with the key of the
+/// Aptos root account in `mint.key`, or loads the config created there by a previous run.
+///
+/// Returns the config of the validator and the canonicalized `config_path`.
+pub fn load_or_create_test_config<R>(
+    config_path: &Path,
+    random_ports: bool,
+    lazy: bool,
+    publishing_option: Option<VMPublishingOption>,
+    genesis_modules: Vec<Vec<u8>>,
+    rng: R,
+) -> (NodeConfig, PathBuf)
+where
+    R: ::rand::RngCore + ::rand::CryptoRng,
+{
+    let try_load = config_path.join("0").join("node.yaml").exists();
 
     std::fs::DirBuilder::new()
         .recursive(true)
@@ -200,27 +242,7 @@ pub fn load_test_environment<R>(
         validators[0].config.clone()
     };
 
-    // Prepare log file since we cannot automatically route logs to stderr
-    let log_file = config_path.join("validator.log");
-
-    println!("Completed generating configuration:");
-    println!("\tLog file: {:?}", log_file);
-    println!("\tConfig path: {:?}", config_path);
-    println!("\tAptos root key path: {:?}", aptos_root_key_path);
-    println!("\tWaypoint: {}", config.base.waypoint.genesis_waypoint());
-    println!("\tChainId: {}", ChainId::test());
-    println!("\tREST API endpoint: {}", &config.api.address);
-    println!(
-        "\tFullNode network: {}",
-        &config.full_node_networks[0].listen_address
-    );
-    if lazy {
-        println!("\tLazy mode is enabled");
-    }
-
-    println!("\nAptos is running, press ctrl-c to exit\n");
-
-    start(&config, Some(log_file))
+    (config, config_path)
 }
 
 // Fetch chain ID from on-chain resource
@@ -18,11 +18,15 @@ pub struct ApiConfig {
     // optional for compatible with old configuration
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub content_length_limit: Option<u64>,
+    // max gas a view function call may use before it is aborted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub view_max_gas_amount: Option<u64>,
 }
 
 pub const DEFAULT_ADDRESS: &str = "127.0.0.1";
 pub const DEFAULT_PORT: u16 = 8080;
 pub const DEFAULT_REQUEST_CONTENT_LENGTH_LIMIT: u64 = 4 * 1024 * 1024; // 4mb
+pub const DEFAULT_VIEW_MAX_GAS_AMOUNT: u64 = 1_000_000;
 
 fn default_enabled() -> bool {
     true
@@ -38,6 +42,7 @@ impl Default for ApiConfig {
             tls_cert_path: None,
             tls_key_path: None,
             content_length_limit: None,
+            view_max_gas_amount: None,
         }
     }
 }
@@ -53,4 +58,9 @@ impl ApiConfig {
             None => DEFAULT_REQUEST_CONTENT_LENGTH_LIMIT,
         }
     }
+
+    pub fn view_max_gas_amount(&self) -> u64 {
+        self.view_max_gas_amount
+            .unwrap_or(DEFAULT_VIEW_MAX_GAS_AMOUNT)
+    }
 }
@@ -11,6 +11,9 @@ use std::{collections::HashMap, path::PathBuf};
 pub struct ConsensusConfig {
     pub contiguous_rounds: u32,
     pub max_block_size: u64,
+    // Max total size in bytes of the transactions of the blocks proposed by this node, and of the
+    // transactions accepted by mempool.  The on-chain limit applies to proposals if it's lower.
+    pub max_block_bytes: u64,
     pub max_pruned_blocks_in_mem: usize,
     // Timeout for consensus to get an ack from mempool for executed transactions (in milliseconds)
     pub mempool_executed_txn_timeout_ms: u64,
@@ -22,10 +25,16 @@ pub struct ConsensusConfig {
     // Only sync committed transactions but not vote for any pending blocks. This is useful when
     // validators coordinate on the latest version to apply a manual transaction.
     pub sync_only: bool,
+    // Broadcast the votes and the fast commit votes of the blocks certified by them, so that a
+    // block is committed as soon as a quorum of fast commit votes is received instead of waiting
+    // for the QC of its child (2-chain only). It takes O(n^2) messages per round.
+    pub enable_fast_commit: bool,
     // Decides how long the leader waits before proposing empty block if there's no txns in mempool
     // the period = (poll_count - 1) * 30ms
     pub mempool_poll_count: u64,
     pub channel_size: usize,
+    pub quorum_store: QuorumStoreConfig,
+    pub event_journal: ConsensusJournalConfig,
 }
 
 impl Default for ConsensusConfig {
@@ -33,18 +42,21 @@ impl Default for ConsensusConfig {
         ConsensusConfig {
             contiguous_rounds: 2,
             max_block_size: 3000,
+            max_block_bytes: 5 * 1024 * 1024, // 5MB
             max_pruned_blocks_in_mem: 100,
             mempool_txn_pull_timeout_ms: 1000,
             mempool_executed_txn_timeout_ms: 1000,
             round_initial_timeout_ms: 1000,
-            proposer_type: ConsensusProposerType::LeaderReputation(LeaderReputationConfig {
-                active_weights: 99,
-                inactive_weights: 1,
-            }),
+            proposer_type: ConsensusProposerType::LeaderReputation(
+                LeaderReputationConfig::default(),
+            ),
             safety_rules: SafetyRulesConfig::default(),
             sync_only: false,
+            enable_fast_commit: false,
             mempool_poll_count: 20,
             channel_size: 30, // hard-coded
+            quorum_store: QuorumStoreConfig::default(),
+            event_journal: ConsensusJournalConfig::default(),
         }
     }
 }
@@ -71,8 +83,93 @@ pub enum ConsensusProposerType {
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
-#[serde(deny_unknown_fields)]
+#[serde(default, deny_unknown_fields)]
 pub struct LeaderReputationConfig {
     pub active_weights: u64,
     pub inactive_weights: u64,
+    // Weight of the validators which failed more than failure_threshold_percent of their rounds
+    // as proposer within the proposer window
+    pub failed_weights: u64,
+    pub failure_threshold_percent: u32,
+    // Number of the most recent committed blocks in which the proposals and failed rounds are
+    // counted, as a multiple of the number of validators. 0 disables the failed proposer penalty,
+    // which relies on the failed proposers recorded once enabled by the on-chain consensus config
+    pub proposer_window_num_validators_multiplier: usize,
+    // Number of the most recent committed blocks in which the votes are counted, as a multiple
+    // of the number of validators
+    pub voter_window_num_validators_multiplier: usize,
+}
+
+impl Default for LeaderReputationConfig {
+    fn default() -> LeaderReputationConfig {
+        LeaderReputationConfig {
+            active_weights: 99,
+            inactive_weights: 1,
+            failed_weights: 1,
+            failure_threshold_percent: 10,
+            proposer_window_num_validators_multiplier: 0,
+            voter_window_num_validators_multiplier: 1,
+        }
+    }
+}
+
+/// Experimental dissemination of the transactions in batches ahead of the ordering, the proposals
+/// only carry the proofs of store of the batches. It is enabled by the on-chain consensus config,
+/// and requires decoupled execution.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct QuorumStoreConfig {
+    // Interval between the batches pulled from mempool (in milliseconds)
+    pub batch_generation_interval_ms: u64,
+    // Max number of transactions of a batch
+    pub max_batch_size: u64,
+    // Max total size in bytes of the transactions of a batch
+    pub max_batch_bytes: u64,
+    // How long a batch can be included in blocks after its creation (in milliseconds)
+    pub batch_expiry_ms: u64,
+    // Timeout for fetching a missing batch from a validator storing it (in milliseconds)
+    pub batch_request_timeout_ms: u64,
+    // Max number of the unexpired batches stored for each validator, the others are rejected
+    pub max_batches_per_author: usize,
+    // Max total size in bytes of the unexpired batches stored for each validator
+    pub max_batch_bytes_per_author: u64,
+}
+
+impl Default for QuorumStoreConfig {
+    fn default() -> QuorumStoreConfig {
+        QuorumStoreConfig {
+            batch_generation_interval_ms: 100,
+            max_batch_size: 500,
+            max_batch_bytes: 1024 * 1024, // 1MB
+            batch_expiry_ms: 30_000,
+            batch_request_timeout_ms: 1000,
+            max_batches_per_author: 500,
+            max_batch_bytes_per_author: 300 * 1024 * 1024, // 300MB
+        }
+    }
+}
+
+/// Journal of the rounds (proposals, votes, certificates and timeouts) persisted in the
+/// ConsensusDB, for the offline reconstruction of the round timelines.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConsensusJournalConfig {
+    pub enabled: bool,
+    // Entries older than this are pruned
+    pub max_age_secs: u64,
+    // Max number of the entries kept in the journal, the oldest ones are pruned first
+    pub max_entries: u64,
+    // Max number of the events waiting to be written, the events recorded beyond it are dropped
+    pub channel_size: usize,
+}
+
+impl Default for ConsensusJournalConfig {
+    fn default() -> ConsensusJournalConfig {
+        ConsensusJournalConfig {
+            enabled: false,
+            max_age_secs: 7 * 24 * 3600,
+            max_entries: 10_000_000,
+            channel_size: 10_000,
+        }
+    }
 }
@@ -5,7 +5,7 @@ use crate::{
     config::{LoggerConfig, SecureBackend},
     keys::ConfigKey,
 };
-use aptos_crypto::{ed25519::Ed25519PrivateKey, Uniform};
+use aptos_crypto::{ed25519::Ed25519PrivateKey, x25519, Uniform};
 use aptos_types::{network_address::NetworkAddress, waypoint::Waypoint, PeerId};
 use rand::rngs::StdRng;
 use serde::{Deserialize, Serialize};
@@ -26,6 +26,8 @@ pub struct SafetyRulesConfig {
     // Read/Write/Connect networking operation timeout in milliseconds.
     pub network_timeout_ms: u64,
     pub enable_cached_safety_data: bool,
+    // Signs with a consensus key held outside of the node and its secure storage backend
+    pub external_signer: Option<ExternalSignerConfig>,
 }
 
 impl Default for SafetyRulesConfig {
@@ -40,6 +42,7 @@ impl Default for SafetyRulesConfig {
             // Default value of 30 seconds for a timeout
             network_timeout_ms: 30_000,
             enable_cached_safety_data: true,
+            external_signer: None,
         }
     }
 }
@@ -67,13 +70,33 @@ pub enum SafetyRulesService {
     Thread,
 }
 
+/// Defines where the external signer of the consensus key runs
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum ExternalSignerConfig {
+    /// A separate signing service, e.g., in front of a hardware security module, reached over the
+    /// same kind of link as the SafetyRules process
+    Process(RemoteService),
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct RemoteService {
     pub server_address: NetworkAddress,
+    // Authenticates and encrypts the link between consensus and the service, which is otherwise
+    // in plaintext
+    #[serde(default)]
+    pub authentication: Option<RemoteServiceAuthentication>,
 }
 
 impl RemoteService {
+    pub fn new(server_address: NetworkAddress) -> Self {
+        Self {
+            server_address,
+            authentication: None,
+        }
+    }
+
     pub fn server_address(&self) -> SocketAddr {
         self.server_address
             .to_socket_addrs()
@@ -83,6 +106,25 @@ impl RemoteService {
     }
 }
 
+/// The keys of one end of an authenticated link, consensus and the service each have their own.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteServiceAuthentication {
+    // Static x25519 key of this end of the link
+    pub private_key: ConfigKey<x25519::PrivateKey>,
+    // Public key expected from the other end: the service for consensus, consensus for the service
+    pub peer_public_key: x25519::PublicKey,
+}
+
+impl RemoteServiceAuthentication {
+    pub fn new(private_key: x25519::PrivateKey, peer_public_key: x25519::PublicKey) -> Self {
+        Self {
+            private_key: ConfigKey::new(private_key),
+            peer_public_key,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct SafetyRulesTestConfig {
     pub author: PeerId,
@@ -4,6 +4,7 @@
 use crate::{
     block_data::{BlockData, BlockType},
     common::{Author, Payload, Round},
+    experimental::batch::ProofOfStore,
     quorum_cert::QuorumCert,
 };
 use anyhow::{bail, ensure, format_err};
@@ -12,16 +13,19 @@ use aptos_infallible::duration_since_epoch;
 use aptos_types::{
     account_address::AccountAddress,
     block_info::BlockInfo,
-    block_metadata::BlockMetadata,
+    block_metadata::{BlockMetadata, BlockMetadataV2},
     epoch_state::EpochState,
     ledger_info::LedgerInfo,
-    transaction::{Transaction, Version},
+    transaction::{SignedTransaction, Transaction, Version},
     validator_signer::ValidatorSigner,
     validator_verifier::ValidatorVerifier,
 };
 use mirai_annotations::debug_checked_verify_eq;
 use serde::{Deserialize, Deserializer, Serialize};
-use std::fmt::{self, Display, Formatter};
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Formatter},
+};
 
 #[path = "block_test_utils.rs"]
 #[cfg(any(test, feature = "fuzzing"))]
@@ -94,6 +98,24 @@ impl Block {
         self.block_data.payload()
     }
 
+    pub fn proofs(&self) -> Option<&Vec<ProofOfStore>> {
+        self.block_data.proofs()
+    }
+
+    pub fn failed_authors(&self) -> Option<&Vec<(Round, Author)>> {
+        self.block_data.failed_authors()
+    }
+
+    /// Total size of the transactions of the block in bytes
+    pub fn payload_size_bytes(&self) -> u64 {
+        if let Some(proofs) = self.proofs() {
+            return proofs.iter().map(|proof| proof.info().num_bytes()).sum();
+        }
+        self.payload().map_or(0, |txns| {
+            txns.iter().map(|txn| txn.txn_bytes_len() as u64).sum()
+        })
+    }
+
     pub fn quorum_cert(&self) -> &QuorumCert {
         self.block_data.quorum_cert()
     }
@@ -171,8 +193,12 @@ impl Block {
 
     /// The NIL blocks are special: they're not carrying any real payload and are generated
     /// independently by different validators just to fill in the round with some QC.
-    pub fn new_nil(round: Round, quorum_cert: QuorumCert) -> Self {
-        let block_data = BlockData::new_nil(round, quorum_cert);
+    pub fn new_nil(
+        round: Round,
+        quorum_cert: QuorumCert,
+        failed_authors: Option<Vec<(Round, Author)>>,
+    ) -> Self {
+        let block_data = BlockData::new_nil(round, quorum_cert, failed_authors);
 
         Block {
             id: block_data.hash(),
@@ -194,6 +220,26 @@ impl Block {
             round,
             timestamp_usecs,
             quorum_cert,
+            None,
+        );
+
+        Self::new_proposal_from_block_data(block_data, validator_signer)
+    }
+
+    pub fn new_quorum_store_proposal(
+        proofs: Vec<ProofOfStore>,
+        round: Round,
+        timestamp_usecs: u64,
+        quorum_cert: QuorumCert,
+        validator_signer: &ValidatorSigner,
+    ) -> Self {
+        let block_data = BlockData::new_quorum_store_proposal(
+            proofs,
+            validator_signer.author(),
+            round,
+            timestamp_usecs,
+            quorum_cert,
+            None,
         );
 
         Self::new_proposal_from_block_data(block_data, validator_signer)
@@ -223,13 +269,26 @@ impl Block {
     pub fn validate_signature(&self, validator: &ValidatorVerifier) -> anyhow::Result<()> {
         match self.block_data.block_type() {
             BlockType::Genesis => bail!("We should not accept genesis from others"),
-            BlockType::NilBlock => self.quorum_cert().verify(validator),
-            BlockType::Proposal { author, .. } => {
+            BlockType::NilBlock | BlockType::NilBlockV2 { .. } => {
+                self.quorum_cert().verify(validator)
+            }
+            BlockType::Proposal { author, .. } | BlockType::ProposalV2 { author, .. } => {
+                let signature = self
+                    .signature
+                    .as_ref()
+                    .ok_or_else(|| format_err!("Missing signature in Proposal"))?;
+                validator.verify(*author, &self.block_data, signature)?;
+                self.quorum_cert().verify(validator)
+            }
+            BlockType::QuorumStoreProposal { proofs, author, .. } => {
                 let signature = self
                     .signature
                     .as_ref()
                     .ok_or_else(|| format_err!("Missing signature in Proposal"))?;
                 validator.verify(*author, &self.block_data, signature)?;
+                for proof in proofs {
+                    proof.verify(validator)?;
+                }
                 self.quorum_cert().verify(validator)
             }
         }
@@ -251,12 +310,51 @@ impl Block {
             parent.epoch() == self.epoch(),
             "block's parent should be in the same epoch"
         );
+        if let Some(failed_authors) = self.block_data.failed_authors() {
+            // The NIL block of a round is generated when the round itself failed.
+            let last_failed_round = if self.is_nil_block() {
+                self.round()
+            } else {
+                self.round() - 1
+            };
+            let mut previous_round = parent.round();
+            for (round, _) in failed_authors {
+                ensure!(
+                    previous_round < *round && *round <= last_failed_round,
+                    "Failed author rounds must be increasing and between the parent's round {} and the block's round {}",
+                    parent.round(),
+                    self.round()
+                );
+                previous_round = *round;
+            }
+        }
         if parent.has_reconfiguration() {
             ensure!(
-                self.payload().map_or(true, |p| p.is_empty()),
+                self.payload().map_or(true, |p| p.is_empty())
+                    && self.proofs().map_or(true, |p| p.is_empty()),
                 "Reconfiguration suffix should not carry payload"
             );
         }
+        if let Some(proofs) = self.proofs() {
+            let mut digests = HashSet::new();
+            for proof in proofs {
+                ensure!(
+                    proof.epoch() == self.epoch(),
+                    "Proof of store {} is from a different epoch than the block",
+                    proof
+                );
+                ensure!(
+                    proof.expiration() > self.timestamp_usecs(),
+                    "Proof of store {} expired before the block timestamp",
+                    proof
+                );
+                ensure!(
+                    digests.insert(proof.digest()),
+                    "Proof of store {} is included twice",
+                    proof
+                );
+            }
+        }
         if self.is_nil_block() || parent.has_reconfiguration() {
             ensure!(
                 self.timestamp_usecs() == parent.timestamp_usecs(),
@@ -290,16 +388,31 @@ impl Block {
     }
 
     pub fn transactions_to_execute(&self) -> Vec<Transaction> {
-        std::iter::once(Transaction::BlockMetadata(self.into()))
-            .chain(
-                self.payload()
-                    .unwrap_or(&Vec::new())
-                    .iter()
-                    .cloned()
-                    .map(Transaction::UserTransaction),
-            )
+        self.transactions_to_execute_with_payload(self.payload().map_or(&[], |p| p.as_slice()))
+    }
+
+    /// The transactions to execute given the payload of the block, for the blocks which carry
+    /// proofs of batches instead of their payload.
+    pub fn transactions_to_execute_with_payload(
+        &self,
+        payload: &[SignedTransaction],
+    ) -> Vec<Transaction> {
+        std::iter::once(self.block_metadata_transaction())
+            .chain(payload.iter().cloned().map(Transaction::UserTransaction))
             .collect()
     }
+
+    /// The block prologue, which also records the failed authors if the block does.
+    fn block_metadata_transaction(&self) -> Transaction {
+        let block_metadata = BlockMetadata::from(self);
+        match self.failed_authors() {
+            Some(failed_authors) => Transaction::BlockMetadataV2(BlockMetadataV2::new(
+                block_metadata,
+                failed_authors.iter().map(|(_, author)| *author).collect(),
+            )),
+            None => Transaction::BlockMetadata(block_metadata),
+        }
+    }
 }
 
 impl<'de> Deserialize<'de> for Block {
@@ -3,6 +3,7 @@
 
 use crate::{
     common::{Author, Payload, Round},
+    experimental::batch::ProofOfStore,
     quorum_cert::QuorumCert,
     vote_data::VoteData,
 };
@@ -32,6 +33,35 @@ pub enum BlockType {
     /// from the previous epoch.  The genesis block is used as the the first root block of the
     /// BlockTree for all epochs.
     Genesis,
+    /// A proposal whose transactions were disseminated in batches ahead of time, it only carries
+    /// the proofs of availability of the batches (experimental quorum store).
+    QuorumStoreProposal {
+        /// Proofs of the batches holding the transactions of the block, in execution order
+        proofs: Vec<ProofOfStore>,
+        /// Author of the block that can be validated by the author's public key and the signature
+        author: Author,
+        /// Failed authors from the parent's block to this block, as for a ProposalV2, if the
+        /// on-chain consensus config enables recording them
+        failed_authors: Option<Vec<(Round, Author)>>,
+    },
+    /// A Proposal recording the failed authors, once enabled by the on-chain consensus config.
+    ProposalV2 {
+        /// T of the block (e.g. one or more transaction(s)
+        payload: Payload,
+        /// Author of the block that can be validated by the author's public key and the signature
+        author: Author,
+        /// Failed authors from the parent's block to this block.
+        /// I.e. the list of consecutive proposers from the
+        /// immediately preceeding rounds that didn't produce a successful block.
+        failed_authors: Vec<(Round, Author)>,
+    },
+    /// A NilBlock recording the failed authors, once enabled by the on-chain consensus config.
+    NilBlockV2 {
+        /// Failed authors from the parent's block to this block (including this block)
+        /// I.e. the list of consecutive proposers from the
+        /// immediately preceeding rounds that didn't produce a successful block.
+        failed_authors: Vec<(Round, Author)>,
+    },
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, CryptoHasher, BCSCryptoHash)]
@@ -69,10 +99,11 @@ pub struct BlockData {
 
 impl BlockData {
     pub fn author(&self) -> Option<Author> {
-        if let BlockType::Proposal { author, .. } = self.block_type {
-            Some(author)
-        } else {
-            None
+        match self.block_type {
+            BlockType::Proposal { author, .. }
+            | BlockType::ProposalV2 { author, .. }
+            | BlockType::QuorumStoreProposal { author, .. } => Some(author),
+            BlockType::NilBlock | BlockType::NilBlockV2 { .. } | BlockType::Genesis => None,
         }
     }
 
@@ -89,13 +120,34 @@ impl BlockData {
     }
 
     pub fn payload(&self) -> Option<&Payload> {
-        if let BlockType::Proposal { payload, .. } = &self.block_type {
-            Some(payload)
+        match &self.block_type {
+            BlockType::Proposal { payload, .. } | BlockType::ProposalV2 { payload, .. } => {
+                Some(payload)
+            }
+            _ => None,
+        }
+    }
+
+    /// The proofs of the batches of a quorum store proposal, whose payload is not inlined
+    pub fn proofs(&self) -> Option<&Vec<ProofOfStore>> {
+        if let BlockType::QuorumStoreProposal { proofs, .. } = &self.block_type {
+            Some(proofs)
         } else {
             None
         }
     }
 
+    /// The proposers of the rounds between the parent and this block that didn't produce a
+    /// successful block, in round order, if the block records them
+    pub fn failed_authors(&self) -> Option<&Vec<(Round, Author)>> {
+        match &self.block_type {
+            BlockType::ProposalV2 { failed_authors, .. }
+            | BlockType::NilBlockV2 { failed_authors, .. } => Some(failed_authors),
+            BlockType::QuorumStoreProposal { failed_authors, .. } => failed_authors.as_ref(),
+            BlockType::Proposal { .. } | BlockType::NilBlock | BlockType::Genesis => None,
+        }
+    }
+
     pub fn round(&self) -> Round {
         self.round
     }
@@ -113,7 +165,10 @@ impl BlockData {
     }
 
     pub fn is_nil_block(&self) -> bool {
-        matches!(self.block_type, BlockType::NilBlock)
+        matches!(
+            self.block_type,
+            BlockType::NilBlock | BlockType::NilBlockV2 { .. }
+        )
     }
 
     pub fn new_genesis_from_ledger_info(ledger_info: &LedgerInfo) -> Self {
@@ -170,7 +225,12 @@ impl BlockData {
         }
     }
 
-    pub fn new_nil(round: Round, quorum_cert: QuorumCert) -> Self {
+    /// The failed authors are only recorded by a NilBlockV2, if given.
+    pub fn new_nil(
+        round: Round,
+        quorum_cert: QuorumCert,
+        failed_authors: Option<Vec<(Round, Author)>>,
+    ) -> Self {
         // We want all the NIL blocks to agree on the timestamps even though they're generated
         // independently by different validators, hence we're using the timestamp of a parent + 1.
         assume!(quorum_cert.certified_block().timestamp_usecs() < u64::max_value()); // unlikely to be false in this universe
@@ -181,23 +241,56 @@ impl BlockData {
             round,
             timestamp_usecs,
             quorum_cert,
-            block_type: BlockType::NilBlock,
+            block_type: match failed_authors {
+                Some(failed_authors) => BlockType::NilBlockV2 { failed_authors },
+                None => BlockType::NilBlock,
+            },
         }
     }
 
+    /// The failed authors are only recorded by a ProposalV2, if given.
     pub fn new_proposal(
         payload: Payload,
         author: Author,
         round: Round,
         timestamp_usecs: u64,
         quorum_cert: QuorumCert,
+        failed_authors: Option<Vec<(Round, Author)>>,
+    ) -> Self {
+        Self {
+            epoch: quorum_cert.certified_block().epoch(),
+            round,
+            timestamp_usecs,
+            quorum_cert,
+            block_type: match failed_authors {
+                Some(failed_authors) => BlockType::ProposalV2 {
+                    payload,
+                    author,
+                    failed_authors,
+                },
+                None => BlockType::Proposal { payload, author },
+            },
+        }
+    }
+
+    pub fn new_quorum_store_proposal(
+        proofs: Vec<ProofOfStore>,
+        author: Author,
+        round: Round,
+        timestamp_usecs: u64,
+        quorum_cert: QuorumCert,
+        failed_authors: Option<Vec<(Round, Author)>>,
     ) -> Self {
         Self {
             epoch: quorum_cert.certified_block().epoch(),
             round,
             timestamp_usecs,
             quorum_cert,
-            block_type: BlockType::Proposal { payload, author },
+            block_type: BlockType::QuorumStoreProposal {
+                proofs,
+                author,
+                failed_authors,
+            },
         }
     }
 
@@ -233,6 +326,6 @@ fn test_reconfiguration_suffix() {
         ),
     );
     let reconfig_suffix_block =
-        BlockData::new_proposal(vec![], AccountAddress::random(), 2, 2, quorum_cert);
+        BlockData::new_proposal(vec![], AccountAddress::random(), 2, 2, quorum_cert, None);
     assert!(reconfig_suffix_block.is_reconfiguration_suffix());
 }
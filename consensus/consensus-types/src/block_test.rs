@@ -6,6 +6,7 @@ use crate::{
         block_test_utils::{certificate_for_genesis, *},
         Block,
     },
+    block_data::{BlockData, BlockType},
     quorum_cert::QuorumCert,
 };
 use aptos_crypto::hash::HashValue;
@@ -26,7 +27,7 @@ fn test_nil_block() {
     let genesis_block = Block::make_genesis_block();
     let quorum_cert = certificate_for_genesis();
 
-    let nil_block = Block::new_nil(1, quorum_cert);
+    let nil_block = Block::new_nil(1, quorum_cert, None);
     assert_eq!(
         nil_block.quorum_cert().certified_block().id(),
         genesis_block.id()
@@ -134,3 +135,62 @@ fn test_same_qc_different_authors() {
     assert!(block_round_1.id() != block_round_1_altered.id());
     assert_eq!(block_round_1.id(), block_round_1_same.id());
 }
+
+#[test]
+fn test_failed_authors_well_formed() {
+    let signer = ValidatorSigner::random(None);
+    let other = ValidatorSigner::random(None);
+    let genesis_qc = certificate_for_genesis();
+    let timestamp = aptos_infallible::duration_since_epoch().as_micros() as u64;
+    let proposal = |failed_authors| {
+        Block::new_proposal_from_block_data(
+            BlockData::new_proposal(
+                vec![],
+                signer.author(),
+                4,
+                timestamp,
+                genesis_qc.clone(),
+                Some(failed_authors),
+            ),
+            &signer,
+        )
+    };
+
+    // the legacy format doesn't record them
+    let legacy_proposal = Block::new_proposal(vec![], 4, timestamp, genesis_qc.clone(), &signer);
+    assert!(legacy_proposal.failed_authors().is_none());
+    assert!(legacy_proposal.verify_well_formed().is_ok());
+    assert!(matches!(
+        proposal(vec![]).block_data().block_type(),
+        BlockType::ProposalV2 { .. }
+    ));
+
+    assert!(proposal(vec![]).verify_well_formed().is_ok());
+    assert!(proposal(vec![(1, other.author()), (3, signer.author())])
+        .verify_well_formed()
+        .is_ok());
+    // not increasing
+    assert!(proposal(vec![(2, other.author()), (2, signer.author())])
+        .verify_well_formed()
+        .is_err());
+    // the round of the parent
+    assert!(proposal(vec![(0, other.author())])
+        .verify_well_formed()
+        .is_err());
+    // the round of the proposal
+    assert!(proposal(vec![(4, other.author())])
+        .verify_well_formed()
+        .is_err());
+
+    // the round of a NIL block failed itself
+    assert!(
+        Block::new_nil(2, genesis_qc.clone(), Some(vec![(2, other.author())]))
+            .verify_well_formed()
+            .is_ok()
+    );
+    assert!(
+        Block::new_nil(2, genesis_qc, Some(vec![(3, other.author())]))
+            .verify_well_formed()
+            .is_err()
+    );
+}
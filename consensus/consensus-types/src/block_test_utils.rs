@@ -90,6 +90,7 @@ prop_compose! {
                     block.round(),
                     aptos_infallible::duration_since_epoch().as_micros() as u64,
                     block.quorum_cert().clone(),
+                    block.failed_authors().cloned(),
                 ),
                 signature: Some(block.signature().unwrap().clone()),
             }
@@ -11,7 +11,7 @@ use aptos_crypto::hash::HashValue;
 use aptos_types::{
     block_info::BlockInfo,
     contract_event::ContractEvent,
-    transaction::{Transaction, TransactionStatus},
+    transaction::{SignedTransaction, Transaction, TransactionStatus},
 };
 use executor_types::StateComputeResult;
 use std::fmt::{Debug, Display, Formatter};
@@ -106,12 +106,21 @@ impl ExecutedBlock {
     }
 
     pub fn transactions_to_commit(&self) -> Vec<Transaction> {
+        self.transactions_to_commit_with_payload(self.block.payload().map_or(&[], |p| p.as_slice()))
+    }
+
+    /// The transactions to commit given the payload of the block, for the blocks which carry
+    /// proofs of batches instead of their payload.
+    pub fn transactions_to_commit_with_payload(
+        &self,
+        payload: &[SignedTransaction],
+    ) -> Vec<Transaction> {
         // reconfiguration suffix don't execute
         if self.is_reconfiguration_suffix() {
             return vec![];
         }
         itertools::zip_eq(
-            self.block.transactions_to_execute(),
+            self.block.transactions_to_execute_with_payload(payload),
             self.state_compute_result.compute_status(),
         )
         .filter_map(|(txn, status)| match status {
@@ -0,0 +1,322 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::{Author, Payload};
+use anyhow::{ensure, Context};
+use aptos_crypto::{ed25519::Ed25519Signature, HashValue};
+use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
+use aptos_types::validator_verifier::ValidatorVerifier;
+use serde::{Deserialize, Serialize};
+use short_hex_str::AsShortHexStr;
+use std::{
+    collections::BTreeMap,
+    fmt::{Debug, Display, Formatter},
+};
+
+/// The metadata of a batch of transactions disseminated by a validator, this is what the
+/// validators storing the batch sign.
+#[derive(
+    Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Hash, CryptoHasher, BCSCryptoHash,
+)]
+pub struct BatchInfo {
+    epoch: u64,
+    author: Author,
+    /// Sequence number of the batch among the batches of its author in the epoch
+    batch_id: u64,
+    /// Time in microseconds after which the batch can't be included in a block anymore
+    expiration: u64,
+    /// Hash of the transactions of the batch
+    digest: HashValue,
+    num_txns: u64,
+    num_bytes: u64,
+}
+
+impl Display for BatchInfo {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "[author: {}, epoch: {}, batch_id: {}, digest: {}, txns: {}, bytes: {}]",
+            self.author.short_str(),
+            self.epoch,
+            self.batch_id,
+            self.digest,
+            self.num_txns,
+            self.num_bytes,
+        )
+    }
+}
+
+impl BatchInfo {
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    pub fn author(&self) -> Author {
+        self.author
+    }
+
+    pub fn batch_id(&self) -> u64 {
+        self.batch_id
+    }
+
+    pub fn expiration(&self) -> u64 {
+        self.expiration
+    }
+
+    pub fn digest(&self) -> HashValue {
+        self.digest
+    }
+
+    pub fn num_txns(&self) -> u64 {
+        self.num_txns
+    }
+
+    pub fn num_bytes(&self) -> u64 {
+        self.num_bytes
+    }
+}
+
+/// A batch of transactions broadcast by its author to the other validators, which store it and
+/// sign its `BatchInfo` in return.
+#[derive(Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct Batch {
+    info: BatchInfo,
+    payload: Payload,
+}
+
+// this is required by structured log
+impl Debug for Batch {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for Batch {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "Batch: {}", self.info)
+    }
+}
+
+impl Batch {
+    pub fn new(
+        epoch: u64,
+        author: Author,
+        batch_id: u64,
+        expiration: u64,
+        payload: Payload,
+    ) -> Self {
+        let info = BatchInfo {
+            epoch,
+            author,
+            batch_id,
+            expiration,
+            digest: Self::compute_digest(&payload),
+            num_txns: payload.len() as u64,
+            num_bytes: Self::compute_num_bytes(&payload),
+        };
+        Self { info, payload }
+    }
+
+    fn compute_digest(payload: &Payload) -> HashValue {
+        HashValue::sha3_256_of(&bcs::to_bytes(payload).expect("Unable to serialize payload"))
+    }
+
+    fn compute_num_bytes(payload: &Payload) -> u64 {
+        payload.iter().map(|txn| txn.txn_bytes_len() as u64).sum()
+    }
+
+    pub fn info(&self) -> &BatchInfo {
+        &self.info
+    }
+
+    pub fn payload(&self) -> &Payload {
+        &self.payload
+    }
+
+    pub fn into_payload(self) -> Payload {
+        self.payload
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.info.epoch
+    }
+
+    pub fn author(&self) -> Author {
+        self.info.author
+    }
+
+    pub fn digest(&self) -> HashValue {
+        self.info.digest
+    }
+
+    /// Verifies that the author is a validator and that the metadata matches the transactions.
+    pub fn verify(&self, validator: &ValidatorVerifier) -> anyhow::Result<()> {
+        ensure!(
+            validator.get_voting_power(&self.info.author).is_some(),
+            "Batch author {} is not a validator",
+            self.info.author
+        );
+        ensure!(
+            Self::compute_digest(&self.payload) == self.info.digest,
+            "Batch digest doesn't match its transactions"
+        );
+        ensure!(
+            self.payload.len() as u64 == self.info.num_txns
+                && Self::compute_num_bytes(&self.payload) == self.info.num_bytes,
+            "Batch size doesn't match its transactions"
+        );
+        Ok(())
+    }
+}
+
+/// The signature of a validator on the `BatchInfo` of a batch it stores, sent back to the
+/// author of the batch.
+#[derive(Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct SignedBatchInfo {
+    info: BatchInfo,
+    signer: Author,
+    signature: Ed25519Signature,
+}
+
+// this is required by structured log
+impl Debug for SignedBatchInfo {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for SignedBatchInfo {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "SignedBatchInfo: [signer: {}, {}]",
+            self.signer.short_str(),
+            self.info
+        )
+    }
+}
+
+impl SignedBatchInfo {
+    pub fn new(info: BatchInfo, signer: Author, signature: Ed25519Signature) -> Self {
+        Self {
+            info,
+            signer,
+            signature,
+        }
+    }
+
+    pub fn info(&self) -> &BatchInfo {
+        &self.info
+    }
+
+    pub fn signer(&self) -> Author {
+        self.signer
+    }
+
+    pub fn signature(&self) -> &Ed25519Signature {
+        &self.signature
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.info.epoch
+    }
+
+    pub fn verify(&self, validator: &ValidatorVerifier) -> anyhow::Result<()> {
+        validator
+            .verify(self.signer, &self.info, &self.signature)
+            .context("Failed to verify SignedBatchInfo")
+    }
+}
+
+/// Proof of availability of a batch: the signatures of a quorum of validators on its
+/// `BatchInfo`, guaranteeing that honest validators store the batch. Proposals carry these
+/// instead of the transactions.
+#[derive(Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct ProofOfStore {
+    info: BatchInfo,
+    signatures: BTreeMap<Author, Ed25519Signature>,
+}
+
+// this is required by structured log
+impl Debug for ProofOfStore {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for ProofOfStore {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "ProofOfStore: [{}, signers: {}]",
+            self.info,
+            self.signatures.len()
+        )
+    }
+}
+
+impl ProofOfStore {
+    pub fn new(info: BatchInfo, signatures: BTreeMap<Author, Ed25519Signature>) -> Self {
+        Self { info, signatures }
+    }
+
+    pub fn info(&self) -> &BatchInfo {
+        &self.info
+    }
+
+    pub fn digest(&self) -> HashValue {
+        self.info.digest
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.info.epoch
+    }
+
+    pub fn expiration(&self) -> u64 {
+        self.info.expiration
+    }
+
+    /// The validators that signed the batch info, hence store the batch
+    pub fn signers(&self) -> impl Iterator<Item = &Author> {
+        self.signatures.keys()
+    }
+
+    /// Verifies that a quorum of validators signed the batch info.
+    pub fn verify(&self, validator: &ValidatorVerifier) -> anyhow::Result<()> {
+        validator
+            .batch_verify_aggregated_signatures(&self.info, &self.signatures)
+            .context("Failed to verify ProofOfStore")
+    }
+}
+
+/// RPC to get a batch that isn't stored locally from one of the validators that signed its
+/// proof.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct BatchRequest {
+    epoch: u64,
+    digest: HashValue,
+}
+
+impl Display for BatchRequest {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "[BatchRequest epoch: {}, digest: {}]",
+            self.epoch, self.digest
+        )
+    }
+}
+
+impl BatchRequest {
+    pub fn new(epoch: u64, digest: HashValue) -> Self {
+        Self { epoch, digest }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    pub fn digest(&self) -> HashValue {
+        self.digest
+    }
+}
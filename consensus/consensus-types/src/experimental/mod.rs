@@ -1,5 +1,6 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod batch;
 pub mod commit_decision;
 pub mod commit_vote;
@@ -0,0 +1,257 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! The optimistic fast path commits a certified block without waiting for the certification of
+//! its child: the votes of a round are broadcast to all the validators, and once a validator
+//! aggregates them into the QC of a block, it signs a FastCommitVote whose LedgerInfo commits the
+//! certified block itself. A quorum of these votes forms a FastCommitCertificate.
+//!
+//! With a proposal broadcast at time 0 and a message delay d, the 2-chain rule commits a block
+//! when the QC of its child is formed: at 4d on the leader of the round after the child, and at
+//! 5d on the other validators, which receive that QC with the next proposal. The fast path
+//! commits it at 3d on every validator: the votes reach all of them at 2d, and the fast commit
+//! votes at 3d. This saves 2d on most validators (1d on one of them) for 2n^2 messages per round
+//! instead of 2n, which only pays off when the validators are close to each other.
+//!
+//! Committing a block as soon as its own votes are unanimous is not safe by itself: the leader
+//! aggregating them may crash and the 2-chain timeouts of the other validators would only carry
+//! the QC of the parent, so that a later leader can extend the parent with a conflicting block.
+//! The fast commit votes are an additional phase that is guarded by SafetyRules instead: a
+//! validator never fast votes a round it timed out, and never times out afterwards with a QC
+//! lower than the fast voted one. Any timeout certificate of a later round then carries a QC
+//! at least as high as the fast committed block.
+
+use crate::{
+    common::{Author, Round},
+    quorum_cert::QuorumCert,
+    vote_data::VoteData,
+};
+use anyhow::{ensure, Context};
+use aptos_crypto::{ed25519::Ed25519Signature, hash::CryptoHash};
+use aptos_types::{
+    block_info::BlockInfo,
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    validator_verifier::ValidatorVerifier,
+};
+use serde::{Deserialize, Serialize};
+use short_hex_str::AsShortHexStr;
+use std::fmt::{Debug, Display, Formatter};
+
+/// A vote to commit a certified block without waiting for the QC of its child, broadcast to all
+/// the validators.
+#[derive(Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct FastCommitVote {
+    /// The vote data of the QC of the committed block
+    vote_data: VoteData,
+    author: Author,
+    /// LedgerInfo committing the certified block of vote_data
+    ledger_info: LedgerInfo,
+    signature: Ed25519Signature,
+}
+
+// this is required by structured log
+impl Debug for FastCommitVote {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for FastCommitVote {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "FastCommitVote: [author: {}, {}]",
+            self.author.short_str(),
+            self.ledger_info
+        )
+    }
+}
+
+impl FastCommitVote {
+    /// The LedgerInfo signed by a fast commit vote for the block certified by the QC
+    pub fn ledger_info_for(quorum_cert: &QuorumCert) -> LedgerInfo {
+        LedgerInfo::new(
+            quorum_cert.certified_block().clone(),
+            quorum_cert.vote_data().hash(),
+        )
+    }
+
+    /// Generates a new FastCommitVote using a signature over the specified ledger_info
+    pub fn new_with_signature(
+        vote_data: VoteData,
+        author: Author,
+        ledger_info: LedgerInfo,
+        signature: Ed25519Signature,
+    ) -> Self {
+        Self {
+            vote_data,
+            author,
+            ledger_info,
+            signature,
+        }
+    }
+
+    pub fn vote_data(&self) -> &VoteData {
+        &self.vote_data
+    }
+
+    pub fn author(&self) -> Author {
+        self.author
+    }
+
+    pub fn ledger_info(&self) -> &LedgerInfo {
+        &self.ledger_info
+    }
+
+    pub fn signature(&self) -> &Ed25519Signature {
+        &self.signature
+    }
+
+    pub fn commit_info(&self) -> &BlockInfo {
+        self.ledger_info.commit_info()
+    }
+
+    pub fn round(&self) -> Round {
+        self.ledger_info.round()
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.ledger_info.epoch()
+    }
+
+    /// Verifies that the LedgerInfo commits the block of the vote data, and then verifies the
+    /// signature.
+    pub fn verify(&self, validator: &ValidatorVerifier) -> anyhow::Result<()> {
+        ensure!(
+            self.ledger_info.consensus_data_hash() == self.vote_data.hash(),
+            "Fast commit vote's hash mismatch LedgerInfo"
+        );
+        ensure!(
+            self.commit_info() == self.vote_data.proposed(),
+            "Fast commit vote doesn't commit its certified block"
+        );
+        validator
+            .verify(self.author, &self.ledger_info, &self.signature)
+            .context("Failed to verify FastCommitVote")
+    }
+}
+
+/// A quorum of fast commit votes, it is a QuorumCert that commits its own certified block so
+/// that it can be used wherever an ordered cert is expected (e.g., in SyncInfo).
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct FastCommitCertificate {
+    quorum_cert: QuorumCert,
+}
+
+impl Display for FastCommitCertificate {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "FastCommitCertificate: [{}]", self.quorum_cert)
+    }
+}
+
+impl FastCommitCertificate {
+    pub fn new(vote_data: VoteData, signed_ledger_info: LedgerInfoWithSignatures) -> Self {
+        Self {
+            quorum_cert: QuorumCert::new(vote_data, signed_ledger_info),
+        }
+    }
+
+    pub fn quorum_cert(&self) -> &QuorumCert {
+        &self.quorum_cert
+    }
+
+    pub fn commit_info(&self) -> &BlockInfo {
+        self.quorum_cert.commit_info()
+    }
+
+    pub fn round(&self) -> Round {
+        self.commit_info().round()
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.commit_info().epoch()
+    }
+
+    /// Verifies that the certificate commits its certified block and that it carries the
+    /// signatures of a quorum.
+    pub fn verify(&self, validator: &ValidatorVerifier) -> anyhow::Result<()> {
+        ensure!(
+            self.commit_info() == self.quorum_cert.certified_block(),
+            "Fast commit certificate doesn't commit its certified block"
+        );
+        ensure!(
+            self.round() > 0,
+            "Fast commit certificate can't commit a genesis block"
+        );
+        self.quorum_cert
+            .verify(validator)
+            .context("Failed to verify FastCommitCertificate")
+    }
+}
+
+#[test]
+fn test_fast_commit_certificate() {
+    use aptos_types::validator_verifier::random_validator_verifier;
+    use std::collections::BTreeMap;
+
+    let (signers, validators) = random_validator_verifier(4, None, false);
+    let quorum_size = validators.quorum_voting_power() as usize;
+    let vote_data = VoteData::new(BlockInfo::random(2), BlockInfo::random(1));
+    let regular_qc = QuorumCert::new(
+        vote_data.clone(),
+        LedgerInfoWithSignatures::new(
+            LedgerInfo::new(BlockInfo::empty(), vote_data.hash()),
+            BTreeMap::new(),
+        ),
+    );
+    let ledger_info = FastCommitVote::ledger_info_for(&regular_qc);
+    let votes: Vec<_> = signers
+        .iter()
+        .map(|signer| {
+            FastCommitVote::new_with_signature(
+                vote_data.clone(),
+                signer.author(),
+                ledger_info.clone(),
+                signer.sign(&ledger_info),
+            )
+        })
+        .collect();
+    for vote in &votes {
+        vote.verify(&validators).unwrap();
+    }
+
+    let certificate = |votes: &[FastCommitVote], ledger_info: &LedgerInfo| {
+        let mut signed_ledger_info =
+            LedgerInfoWithSignatures::new(ledger_info.clone(), BTreeMap::new());
+        for vote in votes {
+            signed_ledger_info.add_signature(vote.author(), vote.signature().clone());
+        }
+        FastCommitCertificate::new(vote_data.clone(), signed_ledger_info)
+    };
+    let valid_certificate = certificate(&votes[..quorum_size], &ledger_info);
+    valid_certificate.verify(&validators).unwrap();
+    assert_eq!(valid_certificate.round(), 2);
+
+    // not enough signatures
+    certificate(&votes[..quorum_size - 1], &ledger_info)
+        .verify(&validators)
+        .unwrap_err();
+
+    // the votes of a regular QC don't commit the certified block
+    let regular_ledger_info = LedgerInfo::new(BlockInfo::empty(), vote_data.hash());
+    let regular_votes: Vec<_> = signers
+        .iter()
+        .map(|signer| {
+            FastCommitVote::new_with_signature(
+                vote_data.clone(),
+                signer.author(),
+                regular_ledger_info.clone(),
+                signer.sign(&regular_ledger_info),
+            )
+        })
+        .collect();
+    regular_votes[0].verify(&validators).unwrap_err();
+    certificate(&regular_votes, &regular_ledger_info)
+        .verify(&validators)
+        .unwrap_err();
+}
@@ -16,6 +16,9 @@ pub struct SafetyData {
     #[serde(default)]
     pub one_chain_round: u64,
     pub last_vote: Option<Vote>,
+    // highest round of a signed 2-chain timeout, used for the fast commit votes
+    #[serde(default)]
+    pub highest_timeout_round: u64,
 }
 
 impl SafetyData {
@@ -32,6 +35,7 @@ impl SafetyData {
             preferred_round,
             one_chain_round,
             last_vote,
+            highest_timeout_round: 0,
         }
     }
 }
@@ -40,8 +44,12 @@ impl fmt::Display for SafetyData {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
             f,
-            "SafetyData: [epoch: {}, last_voted_round: {}, preferred_round: {}, one_chain_round: {}]",
-            self.epoch, self.last_voted_round, self.preferred_round, self.one_chain_round
+            "SafetyData: [epoch: {}, last_voted_round: {}, preferred_round: {}, one_chain_round: {}, highest_timeout_round: {}]",
+            self.epoch,
+            self.last_voted_round,
+            self.preferred_round,
+            self.one_chain_round,
+            self.highest_timeout_round
         )
     }
 }
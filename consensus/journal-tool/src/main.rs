@@ -0,0 +1,124 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+use anyhow::Result;
+use aptos_types::block_info::Round;
+use consensus::event_journal::{JournalEntry, JournalReader, RoundTimeline};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use structopt::StructOpt;
+
+/// Reads the consensus journal of stopped validators (`consensus.event_journal.enabled`) and
+/// reconstructs the timelines of their rounds.
+/// The times of different validators are compared on their own wall clocks.
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// Storage directory of a validator, the one containing `consensusdb`. Repeat it to show the
+    /// rounds as seen by several validators side by side.
+    #[structopt(long, parse(from_os_str), required = true)]
+    db: Vec<PathBuf>,
+
+    /// Only the rounds of this epoch
+    #[structopt(long)]
+    epoch: Option<u64>,
+
+    /// Only the rounds from this one on
+    #[structopt(long, default_value = "0")]
+    from_round: Round,
+
+    /// Only the rounds up to this one
+    #[structopt(long)]
+    to_round: Option<Round>,
+
+    #[structopt(subcommand)]
+    cmd: Command,
+}
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Print the journal entries as recorded
+    #[structopt(name = "list-events")]
+    ListEvents,
+    /// Print the timeline of each round
+    #[structopt(name = "timelines")]
+    Timelines {
+        /// Only the rounds where a validator stayed at least this long
+        #[structopt(long)]
+        slower_than_ms: Option<u64>,
+    },
+}
+
+impl Opt {
+    fn read_entries(&self, db: &Path) -> Result<Vec<JournalEntry>> {
+        let reader = JournalReader::open(db)?;
+        let entries = reader.entries(self.epoch.unwrap_or(0), self.from_round)?;
+        Ok(entries
+            .into_iter()
+            .filter(|entry| {
+                self.epoch.map_or(true, |epoch| entry.epoch == epoch)
+                    && entry.round >= self.from_round
+                    && self.to_round.map_or(true, |round| entry.round <= round)
+            })
+            .collect())
+    }
+}
+
+fn list_events(opt: &Opt) -> Result<()> {
+    for db in &opt.db {
+        println!("== {}", db.display());
+        for entry in opt.read_entries(db)? {
+            println!(
+                "epoch {} round {} at {}: {:?}",
+                entry.epoch, entry.round, entry.event.timestamp_usecs, entry.event.kind
+            );
+        }
+    }
+    Ok(())
+}
+
+fn print_timelines(opt: &Opt, slower_than_ms: Option<u64>) -> Result<()> {
+    // timelines of each round, by validator
+    let mut rounds: BTreeMap<(u64, Round), Vec<(&PathBuf, RoundTimeline)>> = BTreeMap::new();
+    for db in &opt.db {
+        for timeline in RoundTimeline::from_entries(opt.read_entries(db)?) {
+            rounds
+                .entry((timeline.epoch, timeline.round))
+                .or_default()
+                .push((db, timeline));
+        }
+    }
+
+    let threshold = slower_than_ms.map(Duration::from_millis);
+    for timelines in rounds.values() {
+        let slow = threshold.map_or(true, |threshold| {
+            timelines.iter().any(|(_, timeline)| {
+                timeline
+                    .duration()
+                    .map_or(false, |duration| duration >= threshold)
+            })
+        });
+        if !slow {
+            continue;
+        }
+        for (db, timeline) in timelines {
+            if opt.db.len() > 1 {
+                println!("== {}", db.display());
+            }
+            print!("{}", timeline);
+        }
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let opt = Opt::from_args();
+    match opt.cmd {
+        Command::ListEvents => list_events(&opt),
+        Command::Timelines { slower_than_ms } => print_timelines(&opt, slower_than_ms),
+    }
+}
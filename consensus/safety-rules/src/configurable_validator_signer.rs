@@ -1,22 +1,25 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{Error, PersistentSafetyStorage};
+use crate::{external_signer::ExternalSigner, Error, PersistentSafetyStorage};
 use aptos_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
     hash::CryptoHash,
+    traits::signing_message,
 };
 use aptos_global_constants::CONSENSUS_KEY;
 use aptos_types::{account_address::AccountAddress, validator_signer::ValidatorSigner};
 use serde::Serialize;
+use std::sync::Arc;
 
 /// A ConfigurableValidatorSigner is a ValidatorSigner wrapper that offers either
-/// a ValidatorSigner instance or a ValidatorHandle instance, depending on the
-/// configuration chosen. This abstracts away the complexities of handling either
-/// instance, while offering the same API as a ValidatorSigner.
+/// a ValidatorSigner instance, a ValidatorHandle instance or an ExternalValidatorSigner
+/// instance, depending on the configuration chosen. This abstracts away the complexities of
+/// handling either instance, while offering the same API as a ValidatorSigner.
 pub enum ConfigurableValidatorSigner {
     Signer(ValidatorSigner),
     Handle(ValidatorHandle),
+    External(ExternalValidatorSigner),
 }
 
 impl ConfigurableValidatorSigner {
@@ -32,11 +35,22 @@ impl ConfigurableValidatorSigner {
         ConfigurableValidatorSigner::Handle(handle)
     }
 
+    /// Returns a new ExternalValidatorSigner instance
+    pub fn new_external(
+        author: AccountAddress,
+        public_key: Ed25519PublicKey,
+        signer: Arc<dyn ExternalSigner>,
+    ) -> Self {
+        let external = ExternalValidatorSigner::new(author, public_key, signer);
+        ConfigurableValidatorSigner::External(external)
+    }
+
     /// Returns the author associated with the signer configuration.
     pub fn author(&self) -> AccountAddress {
         match self {
             ConfigurableValidatorSigner::Signer(signer) => signer.author(),
             ConfigurableValidatorSigner::Handle(handle) => handle.author(),
+            ConfigurableValidatorSigner::External(external) => external.author(),
         }
     }
 
@@ -45,6 +59,7 @@ impl ConfigurableValidatorSigner {
         match self {
             ConfigurableValidatorSigner::Signer(signer) => signer.public_key(),
             ConfigurableValidatorSigner::Handle(handle) => handle.key_version(),
+            ConfigurableValidatorSigner::External(external) => external.public_key(),
         }
     }
 
@@ -57,6 +72,7 @@ impl ConfigurableValidatorSigner {
         match self {
             ConfigurableValidatorSigner::Signer(signer) => Ok(signer.sign(message)),
             ConfigurableValidatorSigner::Handle(handle) => handle.sign(message, storage),
+            ConfigurableValidatorSigner::External(external) => external.sign(message),
         }
     }
 }
@@ -97,3 +113,42 @@ impl ValidatorHandle {
         storage.sign(CONSENSUS_KEY.into(), self.key_version(), message)
     }
 }
+
+/// An ExternalValidatorSigner associates a validator with a consensus key held by an
+/// ExternalSigner. Neither the node process nor its secure storage ever see the private key,
+/// the signing messages are sent to the external signer instead.
+pub struct ExternalValidatorSigner {
+    author: AccountAddress,
+    public_key: Ed25519PublicKey,
+    signer: Arc<dyn ExternalSigner>,
+}
+
+impl ExternalValidatorSigner {
+    pub fn new(
+        author: AccountAddress,
+        public_key: Ed25519PublicKey,
+        signer: Arc<dyn ExternalSigner>,
+    ) -> Self {
+        ExternalValidatorSigner {
+            author,
+            public_key,
+            signer,
+        }
+    }
+
+    /// Returns the author associated with this signer.
+    pub fn author(&self) -> AccountAddress {
+        self.author
+    }
+
+    /// Returns the public key of the consensus key held by the external signer.
+    pub fn public_key(&self) -> Ed25519PublicKey {
+        self.public_key.clone()
+    }
+
+    /// Signs a given message using the external signer.
+    pub fn sign<T: Serialize + CryptoHash>(&self, message: &T) -> Result<Ed25519Signature, Error> {
+        self.signer
+            .sign_message(&self.public_key, &signing_message(message))
+    }
+}
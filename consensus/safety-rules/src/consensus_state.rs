@@ -66,6 +66,11 @@ impl ConsensusState {
         self.safety_data.one_chain_round
     }
 
+    /// The round of the highest signed 2-chain timeout.
+    pub fn highest_timeout_round(&self) -> Round {
+        self.safety_data.highest_timeout_round
+    }
+
     /// Last known checkpoint this should map to a LedgerInfo that contains a new ValidatorSet
     pub fn waypoint(&self) -> Waypoint {
         self.waypoint
@@ -45,6 +45,8 @@ pub enum Error {
     NotSafeToVote(u64, u64, u64, u64),
     #[error("Does not satisfy 2-chain timeout rule. Round {0}, Quorum round {1}, TC round {2}, one-chain round {3}")]
     NotSafeToTimeout(u64, u64, u64, u64),
+    #[error("Does not satisfy the fast commit rule. Round {0}, highest timeout round {1}")]
+    NotSafeToFastCommit(u64, u64),
     #[error("Invalid TC: {0}")]
     InvalidTimeoutCertificate(String),
     #[error("Inconsistent Execution Result: Ordered BlockInfo doesn't match executed BlockInfo. Ordered: {0}, Executed: {1}")]
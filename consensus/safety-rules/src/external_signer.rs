@@ -0,0 +1,172 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! An interface to sign with a consensus key that lives outside of the node process and its
+//! secure storage, e.g., in a hardware security module.
+
+use crate::Error;
+use aptos_config::config::RemoteServiceAuthentication;
+use aptos_crypto::ed25519::{Ed25519PublicKey, Ed25519Signature};
+#[cfg(any(test, feature = "testing"))]
+use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, SigningKey};
+use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
+use aptos_infallible::Mutex;
+use aptos_logger::warn;
+use aptos_secure_net::{NetworkClient, NetworkServer};
+use aptos_types::account_address::AccountAddress;
+use serde::{Deserialize, Serialize};
+#[cfg(any(test, feature = "testing"))]
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::{net::SocketAddr, sync::Arc};
+
+/// A signer holding consensus private keys, each one being identified by its public key.
+pub trait ExternalSigner: Send + Sync {
+    /// Signs the message with the private key of the given public key. The message is the
+    /// signing message of a consensus structure (see `aptos_crypto::traits::signing_message`),
+    /// it is signed as is.
+    fn sign_message(
+        &self,
+        public_key: &Ed25519PublicKey,
+        message: &[u8],
+    ) -> Result<Ed25519Signature, Error>;
+}
+
+/// Signed by an external signer to show that it holds the consensus key of a validator. It isn't
+/// part of the consensus protocol: its signing message is salted with its own type, so that the
+/// signature can't stand for any consensus message.
+#[derive(BCSCryptoHash, CryptoHasher, Deserialize, Serialize)]
+pub struct ExternalSignerProbe {
+    author: AccountAddress,
+}
+
+impl ExternalSignerProbe {
+    pub fn new(author: AccountAddress) -> Self {
+        Self { author }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+struct SignMessageRequest {
+    public_key: Ed25519PublicKey,
+    message: Vec<u8>,
+}
+
+/// An ExternalSigner reached over the network, see `execute` for the service.
+pub struct RemoteExternalSigner {
+    network_client: Mutex<NetworkClient>,
+}
+
+impl RemoteExternalSigner {
+    pub fn new(
+        server_addr: SocketAddr,
+        network_timeout_ms: u64,
+        authentication: Option<RemoteServiceAuthentication>,
+    ) -> Self {
+        let network_client = match authentication {
+            Some(authentication) => NetworkClient::new_authenticated(
+                "external-signer",
+                server_addr,
+                network_timeout_ms,
+                authentication.private_key.private_key(),
+                authentication.peer_public_key,
+            ),
+            None => NetworkClient::new("external-signer", server_addr, network_timeout_ms),
+        };
+        Self {
+            network_client: Mutex::new(network_client),
+        }
+    }
+}
+
+impl ExternalSigner for RemoteExternalSigner {
+    fn sign_message(
+        &self,
+        public_key: &Ed25519PublicKey,
+        message: &[u8],
+    ) -> Result<Ed25519Signature, Error> {
+        let request = serde_json::to_vec(&SignMessageRequest {
+            public_key: public_key.clone(),
+            message: message.to_vec(),
+        })?;
+        let mut network_client = self.network_client.lock();
+        network_client.write(&request)?;
+        let response = network_client.read()?;
+        serde_json::from_slice::<Result<Ed25519Signature, Error>>(&response)?
+    }
+}
+
+/// Serves the signatures of the external signer to the `RemoteExternalSigner`s connecting to the
+/// listening address, e.g., from a binary built with the client of a hardware security module.
+pub fn execute(
+    external_signer: Arc<dyn ExternalSigner>,
+    listen_addr: SocketAddr,
+    network_timeout_ms: u64,
+    authentication: Option<RemoteServiceAuthentication>,
+) {
+    let mut network_server = match authentication {
+        Some(authentication) => NetworkServer::new_authenticated(
+            "external-signer",
+            listen_addr,
+            network_timeout_ms,
+            authentication.private_key.private_key(),
+            authentication.peer_public_key,
+        ),
+        None => NetworkServer::new("external-signer", listen_addr, network_timeout_ms),
+    };
+
+    loop {
+        if let Err(e) = process_one_message(&mut network_server, external_signer.as_ref()) {
+            warn!("Failed to process message: {}", e);
+        }
+    }
+}
+
+fn process_one_message(
+    network_server: &mut NetworkServer,
+    external_signer: &dyn ExternalSigner,
+) -> Result<(), Error> {
+    let request = network_server.read()?;
+    let request: SignMessageRequest = serde_json::from_slice(&request)?;
+    let response = external_signer.sign_message(&request.public_key, &request.message);
+    network_server.write(&serde_json::to_vec(&response)?)?;
+    Ok(())
+}
+
+/// A local ExternalSigner holding its keys in memory, for testing.
+#[cfg(any(test, feature = "testing"))]
+pub struct MockExternalSigner {
+    keys: Vec<Ed25519PrivateKey>,
+    num_signatures: AtomicU64,
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl MockExternalSigner {
+    pub fn new(keys: Vec<Ed25519PrivateKey>) -> Self {
+        Self {
+            keys,
+            num_signatures: AtomicU64::new(0),
+        }
+    }
+
+    /// Number of the messages signed so far
+    pub fn num_signatures(&self) -> u64 {
+        self.num_signatures.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl ExternalSigner for MockExternalSigner {
+    fn sign_message(
+        &self,
+        public_key: &Ed25519PublicKey,
+        message: &[u8],
+    ) -> Result<Ed25519Signature, Error> {
+        let key = self
+            .keys
+            .iter()
+            .find(|key| &key.public_key() == public_key)
+            .ok_or_else(|| Error::ValidatorKeyNotFound(public_key.to_string()))?;
+        self.num_signatures.fetch_add(1, Ordering::Relaxed);
+        Ok(SigningKey::sign_arbitrary_message(key, message))
+    }
+}
@@ -7,6 +7,7 @@ mod configurable_validator_signer;
 mod consensus_state;
 mod counters;
 mod error;
+mod external_signer;
 mod local_client;
 mod logging;
 mod persistent_safety_storage;
@@ -20,7 +21,7 @@ mod t_safety_rules;
 mod thread;
 
 pub use crate::{
-    consensus_state::ConsensusState, error::Error,
+    consensus_state::ConsensusState, error::Error, external_signer::ExternalSigner,
     persistent_safety_storage::PersistentSafetyStorage, process::Process,
     safety_rules::SafetyRules, safety_rules_manager::SafetyRulesManager,
     t_safety_rules::TSafetyRules,
@@ -35,5 +36,8 @@ pub use crate::fuzzing_utils::fuzzing;
 #[cfg(any(test, feature = "testing"))]
 pub mod test_utils;
 
+#[cfg(any(test, feature = "testing"))]
+pub use crate::external_signer::MockExternalSigner;
+
 #[cfg(test)]
 mod tests;
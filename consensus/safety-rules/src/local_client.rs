@@ -10,6 +10,9 @@ use aptos_types::{
 };
 use consensus_types::{
     block_data::BlockData,
+    experimental::batch::BatchInfo,
+    fast_commit::FastCommitVote,
+    quorum_cert::QuorumCert,
     timeout::Timeout,
     timeout_2chain::{TwoChainTimeout, TwoChainTimeoutCertificate},
     vote::Vote,
@@ -83,4 +86,12 @@ impl TSafetyRules for LocalClient {
             .write()
             .sign_commit_vote(ledger_info, new_ledger_info)
     }
+
+    fn sign_batch_info(&mut self, batch_info: &BatchInfo) -> Result<Ed25519Signature, Error> {
+        self.internal.write().sign_batch_info(batch_info)
+    }
+
+    fn sign_fast_commit_vote(&mut self, quorum_cert: &QuorumCert) -> Result<FastCommitVote, Error> {
+        self.internal.write().sign_fast_commit_vote(quorum_cert)
+    }
 }
@@ -55,6 +55,8 @@ pub enum LogEntry {
     State,
     Waypoint,
     SignCommitVote,
+    SignBatchInfo,
+    SignFastCommitVote,
 }
 
 impl LogEntry {
@@ -75,6 +77,8 @@ impl LogEntry {
             LogEntry::State => "state",
             LogEntry::Waypoint => "waypoint",
             LogEntry::SignCommitVote => "sign_commit_vote",
+            LogEntry::SignBatchInfo => "sign_batch_info",
+            LogEntry::SignFastCommitVote => "sign_fast_commit_vote",
         }
     }
 }
@@ -2,13 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    external_signer::ExternalSigner,
     persistent_safety_storage::PersistentSafetyStorage,
     remote_service::{self, RemoteService},
     safety_rules_manager,
 };
-use aptos_config::config::{SafetyRulesConfig, SafetyRulesService};
+use aptos_config::config::{RemoteServiceAuthentication, SafetyRulesConfig, SafetyRulesService};
 
-use std::net::SocketAddr;
+use std::{net::SocketAddr, sync::Arc};
 
 pub struct Process {
     data: Option<ProcessData>,
@@ -25,6 +26,8 @@ impl Process {
             _ => panic!("Unexpected SafetyRules service: {:?}", config.service),
         };
         let server_addr = service.server_address();
+        let authentication = service.authentication.clone();
+        let external_signer = safety_rules_manager::external_signer(&config);
 
         Self {
             data: Some(ProcessData {
@@ -33,10 +36,26 @@ impl Process {
                 verify_vote_proposal_signature,
                 export_consensus_key,
                 network_timeout: config.network_timeout_ms,
+                authentication,
+                external_signer,
             }),
         }
     }
 
+    /// A service signing with the consensus keys held by the external signer, instead of the one
+    /// selected by the config if any, e.g., for a safety rules binary built with the client of a
+    /// hardware security module.
+    pub fn new_with_external_signer(
+        config: SafetyRulesConfig,
+        external_signer: Arc<dyn ExternalSigner>,
+    ) -> Self {
+        let mut process = Self::new(config);
+        if let Some(data) = process.data.as_mut() {
+            data.external_signer = Some(external_signer);
+        }
+        process
+    }
+
     pub fn start(&mut self) {
         let data = self.data.take().expect("Unable to retrieve ProcessData");
         remote_service::execute(
@@ -45,6 +64,8 @@ impl Process {
             data.verify_vote_proposal_signature,
             data.export_consensus_key,
             data.network_timeout,
+            data.authentication,
+            data.external_signer,
         );
     }
 }
@@ -56,18 +77,26 @@ struct ProcessData {
     export_consensus_key: bool,
     // Timeout in Seconds for network operations
     network_timeout: u64,
+    authentication: Option<RemoteServiceAuthentication>,
+    external_signer: Option<Arc<dyn ExternalSigner>>,
 }
 
 pub struct ProcessService {
     server_addr: SocketAddr,
     network_timeout_ms: u64,
+    authentication: Option<RemoteServiceAuthentication>,
 }
 
 impl ProcessService {
-    pub fn new(server_addr: SocketAddr, network_timeout: u64) -> Self {
+    pub fn new(
+        server_addr: SocketAddr,
+        network_timeout: u64,
+        authentication: Option<RemoteServiceAuthentication>,
+    ) -> Self {
         Self {
             server_addr,
             network_timeout_ms: network_timeout,
+            authentication,
         }
     }
 }
@@ -80,4 +109,8 @@ impl RemoteService for ProcessService {
     fn network_timeout_ms(&self) -> u64 {
         self.network_timeout_ms
     }
+
+    fn authentication(&self) -> Option<&RemoteServiceAuthentication> {
+        self.authentication.as_ref()
+    }
 }
@@ -2,21 +2,32 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    external_signer::ExternalSigner,
     persistent_safety_storage::PersistentSafetyStorage,
     serializer::{SafetyRulesInput, SerializerClient, SerializerService, TSerializerClient},
     Error, SafetyRules, TSafetyRules,
 };
+use aptos_config::config::RemoteServiceAuthentication;
 use aptos_logger::warn;
 use aptos_secure_net::{NetworkClient, NetworkServer};
-use std::net::SocketAddr;
+use std::{net::SocketAddr, sync::Arc};
 
 pub trait RemoteService {
     fn client(&self) -> SerializerClient {
-        let network_client = NetworkClient::new(
-            "safety-rules",
-            self.server_address(),
-            self.network_timeout_ms(),
-        );
+        let network_client = match self.authentication() {
+            Some(authentication) => NetworkClient::new_authenticated(
+                "safety-rules",
+                self.server_address(),
+                self.network_timeout_ms(),
+                authentication.private_key.private_key(),
+                authentication.peer_public_key,
+            ),
+            None => NetworkClient::new(
+                "safety-rules",
+                self.server_address(),
+                self.network_timeout_ms(),
+            ),
+        };
         let service = Box::new(RemoteClient::new(network_client));
         SerializerClient::new_client(service)
     }
@@ -25,6 +36,11 @@ pub trait RemoteService {
 
     /// Network Timeout in milliseconds.
     fn network_timeout_ms(&self) -> u64;
+
+    /// The keys of the client if the link to the service is authenticated.
+    fn authentication(&self) -> Option<&RemoteServiceAuthentication> {
+        None
+    }
 }
 
 pub fn execute(
@@ -33,18 +49,32 @@ pub fn execute(
     verify_vote_proposal_signature: bool,
     export_consensus_key: bool,
     network_timeout_ms: u64,
+    authentication: Option<RemoteServiceAuthentication>,
+    external_signer: Option<Arc<dyn ExternalSigner>>,
 ) {
     let mut safety_rules = SafetyRules::new(
         storage,
         verify_vote_proposal_signature,
         export_consensus_key,
     );
+    if let Some(external_signer) = external_signer {
+        safety_rules.set_external_signer(external_signer);
+    }
     if let Err(e) = safety_rules.consensus_state() {
         warn!("Unable to print consensus state: {}", e);
     }
 
     let mut serializer_service = SerializerService::new(safety_rules);
-    let mut network_server = NetworkServer::new("safety-rules", listen_addr, network_timeout_ms);
+    let mut network_server = match authentication {
+        Some(authentication) => NetworkServer::new_authenticated(
+            "safety-rules",
+            listen_addr,
+            network_timeout_ms,
+            authentication.private_key.private_key(),
+            authentication.peer_public_key,
+        ),
+        None => NetworkServer::new("safety-rules", listen_addr, network_timeout_ms),
+    };
 
     loop {
         if let Err(e) = process_one_message(&mut network_server, &mut serializer_service) {
@@ -6,6 +6,7 @@ use crate::{
     consensus_state::ConsensusState,
     counters,
     error::Error,
+    external_signer::{ExternalSigner, ExternalSignerProbe},
     logging::{LogEntry, LogEvent, SafetyLogSchema},
     persistent_safety_storage::PersistentSafetyStorage,
     t_safety_rules::TSafetyRules,
@@ -27,6 +28,8 @@ use consensus_types::{
     block::Block,
     block_data::BlockData,
     common::{Author, Round},
+    experimental::batch::BatchInfo,
+    fast_commit::FastCommitVote,
     quorum_cert::QuorumCert,
     safety_data::SafetyData,
     timeout::Timeout,
@@ -36,7 +39,7 @@ use consensus_types::{
     vote_proposal::MaybeSignedVoteProposal,
 };
 use serde::Serialize;
-use std::cmp::Ordering;
+use std::{cmp::Ordering, sync::Arc};
 
 pub(crate) fn next_round(round: Round) -> Result<Round, Error> {
     u64::checked_add(round, 1).ok_or(Error::IncorrectRound(round))
@@ -49,6 +52,7 @@ pub struct SafetyRules {
     pub(crate) export_consensus_key: bool,
     pub(crate) validator_signer: Option<ConfigurableValidatorSigner>,
     pub(crate) epoch_state: Option<EpochState>,
+    pub(crate) external_signer: Option<Arc<dyn ExternalSigner>>,
 }
 
 impl SafetyRules {
@@ -74,9 +78,16 @@ impl SafetyRules {
             export_consensus_key,
             validator_signer: None,
             epoch_state: None,
+            external_signer: None,
         }
     }
 
+    /// Signs with the consensus keys held by the external signer instead of the ones in the
+    /// persistent storage, once the consensus key is reconciled by the next initialization.
+    pub fn set_external_signer(&mut self, external_signer: Arc<dyn ExternalSigner>) {
+        self.external_signer = Some(external_signer);
+    }
+
     /// Validity checks
     pub(crate) fn verify_proposal(
         &mut self,
@@ -330,6 +341,30 @@ impl SafetyRules {
                         "in set",
                     );
                     Ok(())
+                } else if let Some(external_signer) = &self.external_signer {
+                    // Ensure the external signer actually holds the expected key, by verifying
+                    // its signature over a probe, which isn't a consensus message.
+                    let signer = ConfigurableValidatorSigner::new_external(
+                        author,
+                        expected_key.clone(),
+                        external_signer.clone(),
+                    );
+                    let probe = ExternalSignerProbe::new(author);
+                    let result =
+                        signer
+                            .sign(&probe, &self.persistent_storage)
+                            .and_then(|signature| {
+                                signature
+                                    .verify(&probe, &expected_key)
+                                    .map_err(|error| Error::InternalError(error.to_string()))
+                            });
+                    match result {
+                        Ok(()) => {
+                            self.validator_signer = Some(signer);
+                            Ok(())
+                        }
+                        Err(error) => Err(Error::ValidatorKeyNotFound(error.to_string())),
+                    }
                 } else if self.export_consensus_key {
                     // Try to export the consensus key directly from storage.
                     match self
@@ -493,6 +528,19 @@ impl SafetyRules {
 
         Ok(signature)
     }
+
+    fn guarded_sign_batch_info(
+        &mut self,
+        batch_info: &BatchInfo,
+    ) -> Result<Ed25519Signature, Error> {
+        self.signer()?;
+
+        let safety_data = self.persistent_storage.safety_data()?;
+        self.verify_epoch(batch_info.epoch(), &safety_data)?;
+
+        let signature = self.sign(batch_info)?;
+        Ok(signature)
+    }
 }
 
 impl TSafetyRules for SafetyRules {
@@ -563,6 +611,17 @@ impl TSafetyRules for SafetyRules {
         let cb = || self.guarded_sign_commit_vote(ledger_info, new_ledger_info);
         run_and_log(cb, |log| log, LogEntry::SignCommitVote)
     }
+
+    fn sign_batch_info(&mut self, batch_info: &BatchInfo) -> Result<Ed25519Signature, Error> {
+        let cb = || self.guarded_sign_batch_info(batch_info);
+        run_and_log(cb, |log| log, LogEntry::SignBatchInfo)
+    }
+
+    fn sign_fast_commit_vote(&mut self, quorum_cert: &QuorumCert) -> Result<FastCommitVote, Error> {
+        let round = quorum_cert.certified_block().round();
+        let cb = || self.guarded_sign_fast_commit_vote(quorum_cert);
+        run_and_log(cb, |log| log.round(round), LogEntry::SignFastCommitVote)
+    }
 }
 
 fn run_and_log<F, L, R>(callback: F, log_cb: L, log_entry: LogEntry) -> Result<R, Error>
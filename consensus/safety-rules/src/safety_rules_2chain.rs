@@ -6,6 +6,8 @@ use aptos_crypto::{ed25519::Ed25519Signature, hash::CryptoHash, HashValue};
 use aptos_types::{block_info::BlockInfo, ledger_info::LedgerInfo};
 use consensus_types::{
     block::Block,
+    fast_commit::FastCommitVote,
+    quorum_cert::QuorumCert,
     safety_data::SafetyData,
     timeout_2chain::{TwoChainTimeout, TwoChainTimeoutCertificate},
     vote::Vote,
@@ -38,6 +40,10 @@ impl SafetyRules {
         }
         if timeout.round() > safety_data.last_voted_round {
             self.verify_and_update_last_vote_round(timeout.round(), &mut safety_data)?;
+        }
+        // the highest timeout round is at most the last voted round
+        if timeout.round() > safety_data.highest_timeout_round {
+            safety_data.highest_timeout_round = timeout.round();
             self.persistent_storage.set_safety_data(safety_data)?;
         }
 
@@ -89,6 +95,34 @@ impl SafetyRules {
         Ok(vote)
     }
 
+    pub(crate) fn guarded_sign_fast_commit_vote(
+        &mut self,
+        quorum_cert: &QuorumCert,
+    ) -> Result<FastCommitVote, Error> {
+        // Exit early if we cannot sign
+        self.signer()?;
+
+        let mut safety_data = self.persistent_storage.safety_data()?;
+        self.verify_epoch(quorum_cert.certified_block().epoch(), &safety_data)?;
+        self.verify_qc(quorum_cert)?;
+
+        self.safe_to_fast_commit(quorum_cert, &safety_data)?;
+        // Any later timeout carries a QC at least as high as the committed block
+        if self.observe_qc(quorum_cert, &mut safety_data) {
+            self.persistent_storage.set_safety_data(safety_data)?;
+        }
+
+        let author = self.signer()?.author();
+        let ledger_info = FastCommitVote::ledger_info_for(quorum_cert);
+        let signature = self.sign(&ledger_info)?;
+        Ok(FastCommitVote::new_with_signature(
+            quorum_cert.vote_data().clone(),
+            author,
+            ledger_info,
+            signature,
+        ))
+    }
+
     /// Core safety timeout rule for 2-chain protocol. Return success if 1 and 2 are true
     /// 1. round == timeout.qc.round + 1 || round == tc.round + 1
     /// 2. timeout.qc.round >= one_chain_round
@@ -136,6 +170,28 @@ impl SafetyRules {
         }
     }
 
+    /// Fast commit rule for 2-chain protocol. Return success if 1 and 2 are true
+    /// 1. qc.round > 0
+    /// 2. qc.round > highest_timeout_round
+    /// Along with the one_chain_round updated to the QC, this ensures that a quorum of fast
+    /// commit votes and a timeout certificate of a later round have an honest signer in common
+    /// whose timeout carries a QC at least as high as the committed block.
+    fn safe_to_fast_commit(
+        &self,
+        quorum_cert: &QuorumCert,
+        safety_data: &SafetyData,
+    ) -> Result<(), Error> {
+        let round = quorum_cert.certified_block().round();
+        if round > 0 && round > safety_data.highest_timeout_round {
+            Ok(())
+        } else {
+            Err(Error::NotSafeToFastCommit(
+                round,
+                safety_data.highest_timeout_round,
+            ))
+        }
+    }
+
     fn verify_tc(&self, tc: &TwoChainTimeoutCertificate) -> Result<(), Error> {
         let epoch_state = self.epoch_state()?;
 
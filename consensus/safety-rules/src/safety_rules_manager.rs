@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    external_signer::{ExternalSigner, RemoteExternalSigner},
     local_client::LocalClient,
     persistent_safety_storage::PersistentSafetyStorage,
     process::ProcessService,
@@ -10,7 +11,9 @@ use crate::{
     thread::ThreadService,
     SafetyRules, TSafetyRules,
 };
-use aptos_config::config::{SafetyRulesConfig, SafetyRulesService};
+use aptos_config::config::{
+    ExternalSignerConfig, RemoteServiceAuthentication, SafetyRulesConfig, SafetyRulesService,
+};
 use aptos_infallible::RwLock;
 use aptos_secure_storage::{KVStorage, Storage};
 use std::{convert::TryInto, net::SocketAddr, sync::Arc};
@@ -49,6 +52,37 @@ pub fn storage(config: &SafetyRulesConfig) -> PersistentSafetyStorage {
     }
 }
 
+/// The external signer of the consensus key selected by the config, if any.
+pub fn external_signer(config: &SafetyRulesConfig) -> Option<Arc<dyn ExternalSigner>> {
+    config
+        .external_signer
+        .as_ref()
+        .map(|external_signer| match external_signer {
+            ExternalSignerConfig::Process(service) => Arc::new(RemoteExternalSigner::new(
+                service.server_address(),
+                config.network_timeout_ms,
+                service.authentication.clone(),
+            )) as Arc<dyn ExternalSigner>,
+        })
+}
+
+fn safety_rules(
+    storage: PersistentSafetyStorage,
+    verify_vote_proposal_signature: bool,
+    export_consensus_key: bool,
+    external_signer: Option<Arc<dyn ExternalSigner>>,
+) -> SafetyRules {
+    let mut safety_rules = SafetyRules::new(
+        storage,
+        verify_vote_proposal_signature,
+        export_consensus_key,
+    );
+    if let Some(external_signer) = external_signer {
+        safety_rules.set_external_signer(external_signer);
+    }
+    safety_rules
+}
+
 enum SafetyRulesWrapper {
     Local(Arc<RwLock<SafetyRules>>),
     Process(ProcessService),
@@ -63,29 +97,37 @@ pub struct SafetyRulesManager {
 impl SafetyRulesManager {
     pub fn new(config: &SafetyRulesConfig) -> Self {
         if let SafetyRulesService::Process(conf) = &config.service {
-            return Self::new_process(conf.server_address(), config.network_timeout_ms);
+            return Self::new_process(
+                conf.server_address(),
+                config.network_timeout_ms,
+                conf.authentication.clone(),
+            );
         }
 
         let storage = storage(config);
         let verify_vote_proposal_signature = config.verify_vote_proposal_signature;
         let export_consensus_key = config.export_consensus_key;
+        let external_signer = external_signer(config);
         match config.service {
-            SafetyRulesService::Local => Self::new_local(
+            SafetyRulesService::Local => Self::local(safety_rules(
                 storage,
                 verify_vote_proposal_signature,
                 export_consensus_key,
-            ),
-            SafetyRulesService::Serializer => Self::new_serializer(
+                external_signer,
+            )),
+            SafetyRulesService::Serializer => Self::serializer(safety_rules(
                 storage,
                 verify_vote_proposal_signature,
                 export_consensus_key,
-            ),
-            SafetyRulesService::Thread => Self::new_thread(
+                external_signer,
+            )),
+            SafetyRulesService::Thread => Self::thread(ThreadService::new(
                 storage,
                 verify_vote_proposal_signature,
                 export_consensus_key,
                 config.network_timeout_ms,
-            ),
+                external_signer,
+            )),
             _ => panic!("Unimplemented SafetyRulesService: {:?}", config.service),
         }
     }
@@ -95,18 +137,25 @@ impl SafetyRulesManager {
         verify_vote_proposal_signature: bool,
         export_consensus_key: bool,
     ) -> Self {
-        let safety_rules = SafetyRules::new(
+        Self::local(SafetyRules::new(
             storage,
             verify_vote_proposal_signature,
             export_consensus_key,
-        );
+        ))
+    }
+
+    fn local(safety_rules: SafetyRules) -> Self {
         Self {
             internal_safety_rules: SafetyRulesWrapper::Local(Arc::new(RwLock::new(safety_rules))),
         }
     }
 
-    pub fn new_process(server_addr: SocketAddr, timeout_ms: u64) -> Self {
-        let process_service = ProcessService::new(server_addr, timeout_ms);
+    pub fn new_process(
+        server_addr: SocketAddr,
+        timeout_ms: u64,
+        authentication: Option<RemoteServiceAuthentication>,
+    ) -> Self {
+        let process_service = ProcessService::new(server_addr, timeout_ms, authentication);
         Self {
             internal_safety_rules: SafetyRulesWrapper::Process(process_service),
         }
@@ -117,11 +166,14 @@ impl SafetyRulesManager {
         verify_vote_proposal_signature: bool,
         export_consensus_key: bool,
     ) -> Self {
-        let safety_rules = SafetyRules::new(
+        Self::serializer(SafetyRules::new(
             storage,
             verify_vote_proposal_signature,
             export_consensus_key,
-        );
+        ))
+    }
+
+    fn serializer(safety_rules: SafetyRules) -> Self {
         let serializer_service = SerializerService::new(safety_rules);
         Self {
             internal_safety_rules: SafetyRulesWrapper::Serializer(Arc::new(RwLock::new(
@@ -136,12 +188,16 @@ impl SafetyRulesManager {
         export_consensus_key: bool,
         timeout_ms: u64,
     ) -> Self {
-        let thread = ThreadService::new(
+        Self::thread(ThreadService::new(
             storage,
             verify_vote_proposal_signature,
             export_consensus_key,
             timeout_ms,
-        );
+            None,
+        ))
+    }
+
+    fn thread(thread: ThreadService) -> Self {
         Self {
             internal_safety_rules: SafetyRulesWrapper::Thread(thread),
         }
@@ -10,6 +10,9 @@ use aptos_types::{
 };
 use consensus_types::{
     block_data::BlockData,
+    experimental::batch::BatchInfo,
+    fast_commit::FastCommitVote,
+    quorum_cert::QuorumCert,
     timeout::Timeout,
     timeout_2chain::{TwoChainTimeout, TwoChainTimeoutCertificate},
     vote::Vote,
@@ -34,6 +37,8 @@ pub enum SafetyRulesInput {
         Box<Option<TwoChainTimeoutCertificate>>,
     ),
     SignCommitVote(Box<LedgerInfoWithSignatures>, Box<LedgerInfo>),
+    SignBatchInfo(Box<BatchInfo>),
+    SignFastCommitVote(Box<QuorumCert>),
 }
 
 pub struct SerializerService {
@@ -80,6 +85,12 @@ impl SerializerService {
                     .internal
                     .sign_commit_vote(*ledger_info, *new_ledger_info),
             ),
+            SafetyRulesInput::SignBatchInfo(batch_info) => {
+                serde_json::to_vec(&self.internal.sign_batch_info(&batch_info))
+            }
+            SafetyRulesInput::SignFastCommitVote(quorum_cert) => {
+                serde_json::to_vec(&self.internal.sign_fast_commit_vote(&quorum_cert))
+            }
         };
 
         Ok(output?)
@@ -181,6 +192,22 @@ impl TSafetyRules for SerializerClient {
         ))?;
         serde_json::from_slice(&response)?
     }
+
+    fn sign_batch_info(&mut self, batch_info: &BatchInfo) -> Result<Ed25519Signature, Error> {
+        let _timer = counters::start_timer("external", LogEntry::SignBatchInfo.as_str());
+        let response = self.request(SafetyRulesInput::SignBatchInfo(Box::new(
+            batch_info.clone(),
+        )))?;
+        serde_json::from_slice(&response)?
+    }
+
+    fn sign_fast_commit_vote(&mut self, quorum_cert: &QuorumCert) -> Result<FastCommitVote, Error> {
+        let _timer = counters::start_timer("external", LogEntry::SignFastCommitVote.as_str());
+        let response = self.request(SafetyRulesInput::SignFastCommitVote(Box::new(
+            quorum_cert.clone(),
+        )))?;
+        serde_json::from_slice(&response)?
+    }
 }
 
 pub trait TSerializerClient: Send + Sync {
@@ -9,6 +9,9 @@ use aptos_types::{
 };
 use consensus_types::{
     block_data::BlockData,
+    experimental::batch::BatchInfo,
+    fast_commit::FastCommitVote,
+    quorum_cert::QuorumCert,
     timeout::Timeout,
     timeout_2chain::{TwoChainTimeout, TwoChainTimeoutCertificate},
     vote::Vote,
@@ -62,4 +65,12 @@ pub trait TSafetyRules {
         ledger_info: LedgerInfoWithSignatures,
         new_ledger_info: LedgerInfo,
     ) -> Result<Ed25519Signature, Error>;
+
+    /// As the holder of the private key, SafetyRules also signs the info of the batches it
+    /// stores for the quorum store, attesting their availability.
+    fn sign_batch_info(&mut self, batch_info: &BatchInfo) -> Result<Ed25519Signature, Error>;
+
+    /// Sign a vote to commit the block certified by the QC without waiting for the QC of its
+    /// child, following the fast commit rule of the 2-chain protocol.
+    fn sign_fast_commit_vote(&mut self, quorum_cert: &QuorumCert) -> Result<FastCommitVote, Error>;
 }
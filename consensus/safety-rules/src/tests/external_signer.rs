@@ -0,0 +1,131 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    external_signer, test_utils, tests::suite, Error, MockExternalSigner, SafetyRules,
+    SafetyRulesManager, TSafetyRules,
+};
+use aptos_config::{
+    config::{ExternalSignerConfig, NodeConfig, RemoteService, SafetyRulesService},
+    utils,
+};
+use aptos_crypto::{ed25519::Ed25519PrivateKey, Signature, Uniform};
+use aptos_types::validator_signer::ValidatorSigner;
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::Arc,
+    thread,
+};
+
+#[test]
+fn test() {
+    let boolean_values = [false, true];
+    for verify_vote_proposal_signature in &boolean_values {
+        for export_consensus_key in &boolean_values {
+            suite::run_test_suite(&safety_rules(
+                *verify_vote_proposal_signature,
+                *export_consensus_key,
+            ));
+        }
+    }
+}
+
+fn safety_rules(
+    verify_vote_proposal_signature: bool,
+    export_consensus_key: bool,
+) -> suite::Callback {
+    Box::new(move || {
+        let signer = ValidatorSigner::from_int(0);
+        let storage = test_utils::test_storage(&signer);
+        let mut safety_rules = SafetyRules::new(
+            storage,
+            verify_vote_proposal_signature,
+            export_consensus_key,
+        );
+        let external_signer = MockExternalSigner::new(vec![signer.private_key().clone()]);
+        safety_rules.set_external_signer(Arc::new(external_signer));
+        (
+            Box::new(safety_rules),
+            signer,
+            if verify_vote_proposal_signature {
+                Some(Ed25519PrivateKey::generate_for_testing())
+            } else {
+                None
+            },
+        )
+    })
+}
+
+#[test]
+fn test_signs_with_external_signer() {
+    let signer = ValidatorSigner::from_int(0);
+    let (proof, genesis_qc) = test_utils::make_genesis(&signer);
+    let round = genesis_qc.certified_block().round();
+
+    let external_signer = Arc::new(MockExternalSigner::new(vec![signer.private_key().clone()]));
+    let mut safety_rules = SafetyRules::new(test_utils::test_storage(&signer), false, true);
+    safety_rules.set_external_signer(external_signer.clone());
+    safety_rules.initialize(&proof).unwrap();
+    // the key is checked with a test signature
+    assert_eq!(external_signer.num_signatures(), 1);
+
+    let a1 = test_utils::make_proposal_with_qc(round + 1, genesis_qc, &signer, None);
+    let vote = safety_rules.construct_and_sign_vote(&a1).unwrap();
+    assert_eq!(external_signer.num_signatures(), 2);
+    vote.signature()
+        .verify(vote.ledger_info(), &signer.public_key())
+        .unwrap();
+}
+
+#[test]
+fn test_key_not_in_external_signer() {
+    let signer = ValidatorSigner::from_int(0);
+    let (proof, _genesis_qc) = test_utils::make_genesis(&signer);
+
+    // The external signer holds another key than the one of the validator set
+    let other_signer = ValidatorSigner::from_int(1);
+    let external_signer = MockExternalSigner::new(vec![other_signer.private_key().clone()]);
+    let mut safety_rules = SafetyRules::new(test_utils::test_storage(&signer), false, true);
+    safety_rules.set_external_signer(Arc::new(external_signer));
+    assert!(matches!(
+        safety_rules.initialize(&proof),
+        Err(Error::ValidatorKeyNotFound(_))
+    ));
+    assert!(!safety_rules.consensus_state().unwrap().in_validator_set());
+}
+
+#[test]
+fn test_signs_with_configured_external_signer() {
+    let mut config = NodeConfig::random().consensus.safety_rules;
+    let test_config = config.test.as_mut().unwrap();
+    let private_key = test_config.consensus_key.as_ref().unwrap().private_key();
+    let signer = ValidatorSigner::new(test_config.author, private_key);
+    test_config.waypoint = Some(test_utils::validator_signers_to_waypoint(&[&signer]));
+    let (proof, genesis_qc) = test_utils::make_genesis(&signer);
+    let round = genesis_qc.certified_block().round();
+
+    // the external signer is served by a separate service
+    let server_port = utils::get_available_port();
+    let server_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), server_port);
+    let external_signer = Arc::new(MockExternalSigner::new(vec![signer.private_key().clone()]));
+    let service_signer = external_signer.clone();
+    let network_timeout = config.network_timeout_ms;
+    thread::spawn(move || {
+        external_signer::execute(service_signer, server_addr, network_timeout, None)
+    });
+    config.service = SafetyRulesService::Local;
+    config.external_signer = Some(ExternalSignerConfig::Process(RemoteService::new(
+        server_addr.into(),
+    )));
+
+    let mut safety_rules = SafetyRulesManager::new(&config).client();
+    safety_rules.initialize(&proof).unwrap();
+    assert_eq!(external_signer.num_signatures(), 1);
+
+    let a1 = test_utils::make_proposal_with_qc(round + 1, genesis_qc, &signer, None);
+    let vote = safety_rules.construct_and_sign_vote(&a1).unwrap();
+    assert_eq!(external_signer.num_signatures(), 2);
+    vote.signature()
+        .verify(vote.ledger_info(), &signer.public_key())
+        .unwrap();
+}
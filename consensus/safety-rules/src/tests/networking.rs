@@ -1,8 +1,14 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{test_utils, SafetyRulesManager};
+use crate::{remote_service, test_utils, SafetyRulesManager};
+use aptos_config::{config::RemoteServiceAuthentication, utils};
+use aptos_crypto::x25519;
 use aptos_types::validator_signer::ValidatorSigner;
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    thread,
+};
 
 #[test]
 fn test_reconnect() {
@@ -18,3 +24,40 @@ fn test_reconnect() {
     let state1 = safety_rules_manager.client().consensus_state().unwrap();
     assert_eq!(state0, state1);
 }
+
+#[test]
+fn test_authenticated() {
+    let signer = ValidatorSigner::from_int(0);
+    let storage = test_utils::test_storage(&signer);
+    // test value for network timeout, in milliseconds.
+    let network_timeout = 5_000;
+    let server_port = utils::get_available_port();
+    let server_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), server_port);
+
+    let server_key = x25519::PrivateKey::from([1; x25519::PRIVATE_KEY_SIZE]);
+    let client_key = x25519::PrivateKey::from([2; x25519::PRIVATE_KEY_SIZE]);
+    let server_authentication =
+        RemoteServiceAuthentication::new(server_key, client_key.public_key());
+    let client_authentication = RemoteServiceAuthentication::new(
+        client_key,
+        server_authentication.private_key.public_key(),
+    );
+    thread::spawn(move || {
+        remote_service::execute(
+            storage,
+            server_addr,
+            false,
+            false,
+            network_timeout,
+            Some(server_authentication),
+            None,
+        )
+    });
+
+    // Verify that each new client runs the handshake and resumes operations
+    let safety_rules_manager =
+        SafetyRulesManager::new_process(server_addr, network_timeout, Some(client_authentication));
+    let state0 = safety_rules_manager.client().consensus_state().unwrap();
+    let state1 = safety_rules_manager.client().consensus_state().unwrap();
+    assert_eq!(state0, state1);
+}
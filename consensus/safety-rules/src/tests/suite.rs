@@ -5,6 +5,7 @@ use crate::{test_utils, test_utils::make_timeout_cert, Error, SafetyRules, TSafe
 use aptos_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519Signature},
     hash::{CryptoHash, HashValue, ACCUMULATOR_PLACEHOLDER_HASH},
+    Signature,
 };
 use aptos_global_constants::CONSENSUS_KEY;
 use aptos_secure_storage::CryptoStorage;
@@ -19,6 +20,7 @@ use aptos_types::{
 use consensus_types::{
     block::block_test_utils::random_payload,
     common::Round,
+    experimental::batch::Batch,
     quorum_cert::QuorumCert,
     timeout::Timeout,
     timeout_2chain::{TwoChainTimeout, TwoChainTimeoutCertificate},
@@ -38,6 +40,28 @@ fn make_proposal_with_qc_and_proof(
     test_utils::make_proposal_with_qc_and_proof(vec![], round, proof, qc, signer, exec_key)
 }
 
+/// Test that we sign the info of the batches of any validator, as long as they are in our epoch
+fn test_sign_batch_info(constructor: &Callback) {
+    let (mut safety_rules, signer, _key) = constructor();
+    let (proof, _genesis_qc) = test_utils::make_genesis(&signer);
+    let author = ValidatorSigner::from_int(1).author();
+    let batch = Batch::new(1, author, 0, 100, random_payload(10));
+
+    assert!(safety_rules.sign_batch_info(batch.info()).is_err());
+    safety_rules.initialize(&proof).unwrap();
+
+    let signature = safety_rules.sign_batch_info(batch.info()).unwrap();
+    signature
+        .verify(batch.info(), &signer.public_key())
+        .unwrap();
+
+    let future_batch = Batch::new(2, author, 0, 100, random_payload(10));
+    assert_eq!(
+        safety_rules.sign_batch_info(future_batch.info()),
+        Err(Error::IncorrectEpoch(2, 1))
+    );
+}
+
 fn make_proposal_with_parent(
     round: Round,
     parent: &MaybeSignedVoteProposal,
@@ -75,7 +99,9 @@ pub fn run_test_suite(safety_rules: &Callback) {
     test_key_not_in_store(safety_rules);
     test_2chain_rules(safety_rules);
     test_2chain_timeout(safety_rules);
+    test_2chain_fast_commit(safety_rules);
     test_sign_commit_vote(safety_rules);
+    test_sign_batch_info(safety_rules);
     test_bad_execution_output(safety_rules);
 }
 
@@ -972,6 +998,64 @@ fn test_2chain_timeout(constructor: &Callback) {
     ));
 }
 
+/// Test that fast commit votes and timeouts exclude each other: a round that was timed out is
+/// never fast voted, and a later timeout carries a QC at least as high as the fast voted one.
+fn test_2chain_fast_commit(constructor: &Callback) {
+    let (mut safety_rules, signer, key) = constructor();
+    let (proof, genesis_qc) = test_utils::make_genesis(&signer);
+    let round = genesis_qc.certified_block().round();
+    safety_rules.initialize(&proof).unwrap();
+    let a1 =
+        test_utils::make_proposal_with_qc(round + 1, genesis_qc.clone(), &signer, key.as_ref());
+    let a2 = make_proposal_with_parent(round + 2, &a1, None, &signer, key.as_ref());
+    let a3 = make_proposal_with_parent(round + 3, &a2, None, &signer, key.as_ref());
+    let a4 = make_proposal_with_parent(round + 4, &a3, None, &signer, key.as_ref());
+    let qc1 = a2.vote_proposal.block().quorum_cert();
+    let qc2 = a3.vote_proposal.block().quorum_cert();
+    let qc3 = a4.vote_proposal.block().quorum_cert();
+
+    assert_eq!(
+        safety_rules.sign_fast_commit_vote(&genesis_qc).unwrap_err(),
+        Error::NotSafeToFastCommit(0, 0)
+    );
+    let vote = safety_rules.sign_fast_commit_vote(qc1).unwrap();
+    assert_eq!(vote.commit_info(), qc1.certified_block());
+    assert_eq!(vote.vote_data(), qc1.vote_data());
+    vote.signature()
+        .verify(vote.ledger_info(), &signer.public_key())
+        .unwrap();
+    assert_eq!(safety_rules.consensus_state().unwrap().one_chain_round(), 1);
+
+    // a timeout can't carry a QC lower than the fast voted one
+    assert_eq!(
+        safety_rules
+            .sign_timeout_with_qc(
+                &TwoChainTimeout::new(1, 2, genesis_qc.clone()),
+                Some(make_timeout_cert(1, &genesis_qc, &signer)).as_ref()
+            )
+            .unwrap_err(),
+        Error::NotSafeToTimeout(2, 0, 1, 1)
+    );
+    safety_rules
+        .sign_timeout_with_qc(&TwoChainTimeout::new(1, 2, qc1.clone()), None)
+        .unwrap();
+    assert_eq!(
+        safety_rules
+            .consensus_state()
+            .unwrap()
+            .highest_timeout_round(),
+        2
+    );
+
+    // a timed out round can't be fast voted
+    assert_eq!(
+        safety_rules.sign_fast_commit_vote(qc2).unwrap_err(),
+        Error::NotSafeToFastCommit(2, 2)
+    );
+    safety_rules.sign_fast_commit_vote(qc3).unwrap();
+    assert_eq!(safety_rules.consensus_state().unwrap().one_chain_round(), 3);
+}
+
 /// Test that we can succesfully sign a valid commit vote
 fn test_sign_commit_vote(constructor: &Callback) {
     // we construct a chain of proposals
@@ -8,12 +8,14 @@
 //! in testing correctness of the communication layer between Consensus and SafetyRules.
 
 use crate::{
+    external_signer::ExternalSigner,
     persistent_safety_storage::PersistentSafetyStorage,
     remote_service::{self, RemoteService},
 };
 use aptos_config::utils;
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::Arc,
     thread::{self, JoinHandle},
 };
 
@@ -31,6 +33,7 @@ impl ThreadService {
         verify_vote_proposal_signature: bool,
         export_consensus_key: bool,
         timeout: u64,
+        external_signer: Option<Arc<dyn ExternalSigner>>,
     ) -> Self {
         let listen_port = utils::get_available_port();
         let listen_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), listen_port);
@@ -43,6 +46,8 @@ impl ThreadService {
                 verify_vote_proposal_signature,
                 export_consensus_key,
                 timeout,
+                None,
+                external_signer,
             )
         });
 
@@ -2,9 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_config::{
-    config::{NodeConfig, PersistableConfig, RemoteService, SafetyRulesService},
+    config::{
+        NodeConfig, PersistableConfig, RemoteService, RemoteServiceAuthentication,
+        SafetyRulesConfig, SafetyRulesService,
+    },
     utils,
 };
+use aptos_crypto::x25519;
 use aptos_types::validator_signer::ValidatorSigner;
 use safety_rules::{test_utils, SafetyRulesManager};
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
@@ -13,6 +17,35 @@ const BINARY: &str = env!("CARGO_BIN_EXE_safety-rules");
 
 #[test]
 fn test_consensus_state() {
+    let config = test_config();
+    run_consensus_state(&config, &config);
+}
+
+#[test]
+fn test_authenticated_consensus_state() {
+    let mut service_config = test_config();
+    let mut client_config = service_config.clone();
+
+    let service_key = x25519::PrivateKey::from([1; x25519::PRIVATE_KEY_SIZE]);
+    let client_key = x25519::PrivateKey::from([2; x25519::PRIVATE_KEY_SIZE]);
+    let service_public_key = service_key.public_key();
+    let client_public_key = client_key.public_key();
+    if let SafetyRulesService::Process(service) = &mut service_config.service {
+        service.authentication = Some(RemoteServiceAuthentication::new(
+            service_key,
+            client_public_key,
+        ));
+    }
+    if let SafetyRulesService::Process(service) = &mut client_config.service {
+        service.authentication = Some(RemoteServiceAuthentication::new(
+            client_key,
+            service_public_key,
+        ));
+    }
+    run_consensus_state(&service_config, &client_config);
+}
+
+fn test_config() -> SafetyRulesConfig {
     let mut config = NodeConfig::random().consensus.safety_rules;
     let test_config = config.test.as_mut().unwrap();
     let private_key = test_config.consensus_key.as_ref().unwrap().private_key();
@@ -22,11 +55,15 @@ fn test_consensus_state() {
 
     let server_port = utils::get_available_port();
     let server_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), server_port).into();
-    config.service = SafetyRulesService::Process(RemoteService { server_address });
+    config.service = SafetyRulesService::Process(RemoteService::new(server_address));
+    config
+}
 
+/// Runs the binary with the service config and queries it with the client config.
+fn run_consensus_state(service_config: &SafetyRulesConfig, client_config: &SafetyRulesConfig) {
     let config_path = aptos_temppath::TempPath::new();
     config_path.create_as_file().unwrap();
-    config.save_config(config_path.path()).unwrap();
+    service_config.save_config(config_path.path()).unwrap();
 
     let mut command = std::process::Command::new(BINARY);
     command
@@ -36,7 +73,7 @@ fn test_consensus_state() {
         .stderr(std::process::Stdio::inherit());
     let mut child = command.spawn().unwrap();
 
-    let safety_rules_manager = SafetyRulesManager::new(&config);
+    let safety_rules_manager = SafetyRulesManager::new(client_config);
     let mut safety_rules = safety_rules_manager.client();
     let consensus_state = safety_rules.consensus_state();
 
@@ -367,7 +367,7 @@ impl BlockStore {
 
         // ensure local time past the block time
         let block_time = Duration::from_micros(executed_block.timestamp_usecs());
-        self.time_service.wait_until(block_time);
+        self.time_service.wait_until(block_time).await;
         self.storage
             .save_tree(vec![executed_block.block().clone()], vec![])
             .context("Insert block failed when saving block")?;
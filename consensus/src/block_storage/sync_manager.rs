@@ -9,7 +9,7 @@ use crate::{
     persistent_liveness_storage::{PersistentLivenessStorage, RecoveryData},
     state_replication::StateComputer,
 };
-use anyhow::bail;
+use anyhow::{bail, ensure};
 
 use aptos_crypto::HashValue;
 use aptos_logger::prelude::*;
@@ -21,6 +21,7 @@ use consensus_types::{
     block::Block,
     block_retrieval::{BlockRetrievalRequest, BlockRetrievalStatus, MAX_BLOCKS_PER_REQUEST},
     common::Author,
+    fast_commit::FastCommitCertificate,
     quorum_cert::QuorumCert,
     sync_info::SyncInfo,
 };
@@ -122,6 +123,29 @@ impl BlockStore {
         Ok(())
     }
 
+    /// Commits the block of a fast commit certificate, retrieving it and its missing ancestors
+    /// from the given peer if needed. The QC of the certificate is persisted even if the block is
+    /// already certified, as it is the ordered cert of the block once committed.
+    pub async fn insert_fast_commit_cert(
+        &self,
+        cert: &FastCommitCertificate,
+        retriever: &mut BlockRetriever,
+    ) -> anyhow::Result<()> {
+        let qc = cert.quorum_cert();
+        ensure!(
+            qc.commit_info() == qc.certified_block(),
+            "{} doesn't commit its certified block",
+            cert
+        );
+        if qc.commit_info().round() <= self.ordered_root().round() {
+            return Ok(());
+        }
+        if self.block_exists(qc.certified_block().id()) {
+            self.insert_single_quorum_cert(qc.clone())?;
+        }
+        self.insert_quorum_cert(qc, retriever).await
+    }
+
     /// Insert the quorum certificate separately from the block, used to split the processing of
     /// updating the consensus state(with qc) and deciding whether to vote(with block)
     /// The missing ancestors are going to be retrieved from the given peer. If a given peer
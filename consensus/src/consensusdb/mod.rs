@@ -5,41 +5,48 @@
 mod consensusdb_test;
 mod schema;
 
+pub(crate) use schema::round_event::RoundEventKey;
+
 use crate::{
     consensusdb::schema::{
         block::BlockSchema,
         quorum_certificate::QCSchema,
+        round_event::RoundEventSchema,
         single_entry::{SingleEntryKey, SingleEntrySchema},
     },
     error::DbError,
+    event_journal::RoundEvent,
 };
 use anyhow::Result;
 use aptos_crypto::HashValue;
 use aptos_logger::prelude::*;
 use consensus_types::{block::Block, quorum_cert::QuorumCert};
-use schema::{BLOCK_CF_NAME, QC_CF_NAME, SINGLE_ENTRY_CF_NAME};
-use schemadb::{Options, ReadOptions, SchemaBatch, DB, DEFAULT_CF_NAME};
+use schema::{BLOCK_CF_NAME, QC_CF_NAME, ROUND_EVENT_CF_NAME, SINGLE_ENTRY_CF_NAME};
+use schemadb::{Options, ReadOptions, SchemaBatch, SchemaIterator, DB, DEFAULT_CF_NAME};
 use std::{collections::HashMap, iter::Iterator, path::Path, time::Instant};
 
 pub struct ConsensusDB {
     db: DB,
 }
 
+fn column_families() -> Vec<&'static str> {
+    vec![
+        /* UNUSED CF = */ DEFAULT_CF_NAME,
+        BLOCK_CF_NAME,
+        QC_CF_NAME,
+        SINGLE_ENTRY_CF_NAME,
+        ROUND_EVENT_CF_NAME,
+    ]
+}
+
 impl ConsensusDB {
     pub fn new<P: AsRef<Path> + Clone>(db_root_path: P) -> Self {
-        let column_families = vec![
-            /* UNUSED CF = */ DEFAULT_CF_NAME,
-            BLOCK_CF_NAME,
-            QC_CF_NAME,
-            SINGLE_ENTRY_CF_NAME,
-        ];
-
         let path = db_root_path.as_ref().join("consensusdb");
         let instant = Instant::now();
         let mut opts = Options::default();
         opts.create_if_missing(true);
         opts.create_missing_column_families(true);
-        let db = DB::open(path.clone(), "consensus", column_families, &opts)
+        let db = DB::open(path.clone(), "consensus", column_families(), &opts)
             .expect("ConsensusDB open failed; unable to continue");
 
         info!(
@@ -51,6 +58,18 @@ impl ConsensusDB {
         Self { db }
     }
 
+    /// Opens the ConsensusDB of a stopped node for inspection.
+    pub fn open_readonly<P: AsRef<Path>>(db_root_path: P) -> Result<Self> {
+        let path = db_root_path.as_ref().join("consensusdb");
+        let db = DB::open_readonly(
+            path,
+            "consensus_readonly",
+            column_families(),
+            &Options::default(),
+        )?;
+        Ok(Self { db })
+    }
+
     pub fn get_data(
         &self,
     ) -> Result<(
@@ -141,6 +160,51 @@ impl ConsensusDB {
         self.commit(batch)
     }
 
+    /// Save the journal entries.
+    pub fn save_round_events(&self, events: &[(RoundEventKey, RoundEvent)]) -> Result<(), DbError> {
+        let mut batch = SchemaBatch::new();
+        for (key, event) in events {
+            batch.put::<RoundEventSchema>(key, event)?;
+        }
+        // the journal is not needed for safety, it isn't worth a sync of the disk
+        self.db.write_schemas_relaxed(batch)?;
+        Ok(())
+    }
+
+    /// Delete the journal entries before the given key.
+    pub fn prune_round_events(&self, end: &RoundEventKey) -> Result<(), DbError> {
+        let mut batch = SchemaBatch::new();
+        batch.delete_range::<RoundEventSchema>(&RoundEventKey::new(0, 0, 0), end)?;
+        self.db.write_schemas_relaxed(batch)?;
+        Ok(())
+    }
+
+    /// Iterate over the journal entries in the order of the keys.
+    pub fn round_events_iter(&self) -> Result<SchemaIterator<'_, RoundEventSchema>, DbError> {
+        let mut iter = self.db.iter::<RoundEventSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        Ok(iter)
+    }
+
+    /// Get the journal entries from the given key on, in the order of the keys.
+    pub fn get_round_events(
+        &self,
+        start: &RoundEventKey,
+    ) -> Result<Vec<(RoundEventKey, RoundEvent)>, DbError> {
+        let mut iter = self.db.iter::<RoundEventSchema>(ReadOptions::default())?;
+        iter.seek(start)?;
+        Ok(iter.collect::<Result<Vec<_>>>()?)
+    }
+
+    /// Get the key of the latest journal entry (if available)
+    pub fn get_last_round_event_key(&self) -> Result<Option<RoundEventKey>, DbError> {
+        let mut iter = self
+            .db
+            .rev_iter::<RoundEventSchema>(ReadOptions::default())?;
+        iter.seek_to_last();
+        Ok(iter.next().transpose()?.map(|(key, _)| key))
+    }
+
     /// Write the whole schema batch including all data necessary to mutate the ledger
     /// state of some transaction by leveraging rocksdb atomicity support.
     fn commit(&self, batch: SchemaBatch) -> Result<(), DbError> {
@@ -3,6 +3,7 @@
 
 pub(crate) mod block;
 pub(crate) mod quorum_certificate;
+pub(crate) mod round_event;
 pub(crate) mod single_entry;
 
 use anyhow::{ensure, Result};
@@ -10,6 +11,7 @@ use schemadb::ColumnFamilyName;
 
 pub(super) const BLOCK_CF_NAME: ColumnFamilyName = "block";
 pub(super) const QC_CF_NAME: ColumnFamilyName = "quorum_certificate";
+pub(super) const ROUND_EVENT_CF_NAME: ColumnFamilyName = "round_event";
 pub(super) const SINGLE_ENTRY_CF_NAME: ColumnFamilyName = "single_entry";
 
 fn ensure_slice_len_eq(data: &[u8], len: usize) -> Result<()> {
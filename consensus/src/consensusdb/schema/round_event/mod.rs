@@ -0,0 +1,74 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for the consensus event journal.
+//!
+//! Serialized round events identified by the epoch and round they belong to, and a sequence
+//! number giving the order they were recorded in.
+//! ```text
+//! |<----------key---------->|<---value--->|
+//! |  epoch | round |  seq   | round event |
+//! ```
+
+use super::{ensure_slice_len_eq, ROUND_EVENT_CF_NAME};
+use crate::event_journal::RoundEvent;
+use anyhow::Result;
+use aptos_types::block_info::Round;
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use std::mem::size_of;
+
+define_schema!(
+    RoundEventSchema,
+    RoundEventKey,
+    RoundEvent,
+    ROUND_EVENT_CF_NAME
+);
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct RoundEventKey {
+    pub epoch: u64,
+    pub round: Round,
+    pub seq: u64,
+}
+
+impl RoundEventKey {
+    pub fn new(epoch: u64, round: Round, seq: u64) -> Self {
+        Self { epoch, round, seq }
+    }
+}
+
+impl KeyCodec<RoundEventSchema> for RoundEventKey {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        // big endian so that the keys are sorted by epoch, round and seq
+        let mut encoded = Vec::with_capacity(3 * size_of::<u64>());
+        encoded.write_u64::<BigEndian>(self.epoch)?;
+        encoded.write_u64::<BigEndian>(self.round)?;
+        encoded.write_u64::<BigEndian>(self.seq)?;
+        Ok(encoded)
+    }
+
+    fn decode_key(mut data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, 3 * size_of::<u64>())?;
+        let epoch = data.read_u64::<BigEndian>()?;
+        let round = data.read_u64::<BigEndian>()?;
+        let seq = data.read_u64::<BigEndian>()?;
+        Ok(Self { epoch, round, seq })
+    }
+}
+
+impl ValueCodec<RoundEventSchema> for RoundEvent {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(self)?)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok(bcs::from_bytes(data)?)
+    }
+}
+
+#[cfg(test)]
+mod test;
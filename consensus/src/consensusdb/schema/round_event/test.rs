@@ -0,0 +1,39 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::event_journal::RoundEventKind;
+use aptos_crypto::HashValue;
+use aptos_types::account_address::AccountAddress;
+use schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+
+#[test]
+fn test_encode_decode() {
+    let event = RoundEvent::new(
+        1_000,
+        RoundEventKind::VoteReceived {
+            author: AccountAddress::random(),
+            block_id: HashValue::random(),
+            timeout: false,
+        },
+    );
+    assert_encode_decode::<RoundEventSchema>(&RoundEventKey::new(2, 10, 7), &event);
+}
+
+#[test]
+fn test_key_order() {
+    let keys = [
+        RoundEventKey::new(1, 300, 5),
+        RoundEventKey::new(2, 1, 0),
+        RoundEventKey::new(2, 1, 1),
+        RoundEventKey::new(2, 256, 0),
+    ];
+    for pair in keys.windows(2) {
+        assert!(
+            <RoundEventKey as KeyCodec<RoundEventSchema>>::encode_key(&pair[0]).unwrap()
+                < <RoundEventKey as KeyCodec<RoundEventSchema>>::encode_key(&pair[1]).unwrap()
+        );
+    }
+}
+
+test_no_panic_decoding!(RoundEventSchema);
@@ -71,6 +71,24 @@ pub static PROPOSALS_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!("aptos_consensus_proposals_count", "Count of the block proposals sent by this validator since last restart (both primary and secondary)").unwrap()
 });
 
+/// Count of the received proposals rejected for exceeding the max block size in bytes
+pub static REJECTED_OVERSIZED_PROPOSALS_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_consensus_rejected_oversized_proposals_count",
+        "Count of the received proposals rejected for exceeding the max block size in bytes since last restart."
+    )
+    .unwrap()
+});
+
+/// Count of the blocks committed by a fast commit certificate
+pub static FAST_COMMIT_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_consensus_fast_commit_count",
+        "Count of the fast commit certificates aggregated for uncommitted blocks since last restart."
+    )
+    .unwrap()
+});
+
 /// Count the number of times a validator voted for a nil block since last restart.
 pub static VOTE_NIL_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
@@ -98,6 +116,15 @@ pub static COMMITTED_VOTES_IN_WINDOW: Lazy<IntGauge> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Failed proposals from this validator when using LeaderReputation as the ProposerElection
+pub static FAILED_PROPOSALS_IN_WINDOW: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_failed_proposals_in_window",
+        "Total number of this validator's failed proposals in the current reputation window"
+    )
+    .unwrap()
+});
+
 //////////////////////
 // RoundState COUNTERS
 //////////////////////
@@ -300,3 +327,68 @@ pub static BLOCK_RETRIEVAL_CHANNEL_MSGS: Lazy<IntCounterVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+/// Counters(queued,dequeued,dropped) related to quorum store channel
+pub static QUORUM_STORE_CHANNEL_MSGS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_quorum_store_channel_msgs_count",
+        "Counters(queued,dequeued,dropped) related to quorum store channel",
+        &["state"]
+    )
+    .unwrap()
+});
+
+/// Counters(queued,dequeued,dropped) related to batch retrieval channel
+pub static BATCH_RETRIEVAL_CHANNEL_MSGS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_batch_retrieval_channel_msgs_count",
+        "Counters(queued,dequeued,dropped) related to batch retrieval channel",
+        &["state"]
+    )
+    .unwrap()
+});
+
+/// Count of the batches created by this validator for the quorum store
+pub static QUORUM_STORE_CREATED_BATCHES_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_consensus_quorum_store_created_batches_count",
+        "Count of the batches created by this validator for the quorum store since last restart."
+    )
+    .unwrap()
+});
+
+/// Count of the proofs of store formed for the batches of this validator
+pub static QUORUM_STORE_CREATED_PROOFS_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_consensus_quorum_store_created_proofs_count",
+        "Count of the proofs of store formed for the batches of this validator since last restart."
+    )
+    .unwrap()
+});
+
+/// Count of the round events dropped because the consensus journal writer fell behind
+pub static CONSENSUS_JOURNAL_DROPPED_EVENTS_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_consensus_journal_dropped_events_count",
+        "Count of the round events dropped because the consensus journal writer fell behind since last restart."
+    )
+    .unwrap()
+});
+
+/// Count of the batches rejected because their author exceeded the limits of the stored batches
+pub static QUORUM_STORE_REJECTED_BATCHES_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_consensus_quorum_store_rejected_batches_count",
+        "Count of the batches rejected because their author exceeded the limits of the stored batches since last restart."
+    )
+    .unwrap()
+});
+
+/// Count of the batches fetched from other validators because they were missing locally
+pub static QUORUM_STORE_FETCHED_BATCHES_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_consensus_quorum_store_fetched_batches_count",
+        "Count of the batches fetched from other validators because they were missing locally since last restart."
+    )
+    .unwrap()
+});
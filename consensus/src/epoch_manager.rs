@@ -6,12 +6,18 @@ use crate::{
     counters,
     error::{error_kind, DbError},
     experimental::{
+        batch_store::BatchStore,
         buffer_manager::{OrderedBlocks, ResetRequest},
         decoupled_execution_utils::prepare_phases_and_buffer_manager,
         ordering_state_computer::OrderingStateComputer,
+        proof_queue::ProofQueue,
+        quorum_store::QuorumStore,
     },
     liveness::{
-        leader_reputation::{ActiveInactiveHeuristic, AptosDBBackend, LeaderReputation},
+        leader_reputation::{
+            ActiveInactiveHeuristic, AptosDBBackend, LeaderReputation, ProposerAndVoterHeuristic,
+            ReputationHeuristic,
+        },
         proposal_generator::ProposalGenerator,
         proposer_election::ProposerElection,
         rotating_proposer_election::{choose_leader, RotatingProposer},
@@ -20,7 +26,10 @@ use crate::{
     },
     logging::{LogEvent, LogSchema},
     metrics_safety_rules::MetricsSafetyRules,
-    network::{IncomingBlockRetrievalRequest, NetworkReceivers, NetworkSender},
+    network::{
+        IncomingBatchRetrievalRequest, IncomingBlockRetrievalRequest, NetworkReceivers,
+        NetworkSender,
+    },
     network_interface::{ConsensusMsg, ConsensusNetworkSender},
     persistent_liveness_storage::{LedgerRecoveryData, PersistentLivenessStorage, RecoveryData},
     round_manager::{RoundManager, UnverifiedEvent, VerifiedEvent},
@@ -97,6 +106,9 @@ pub struct EpochManager {
     round_manager_tx: Option<
         aptos_channel::Sender<(Author, Discriminant<VerifiedEvent>), (Author, VerifiedEvent)>,
     >,
+    // channels to quorum store
+    quorum_store_tx: Option<aptos_channel::Sender<AccountAddress, (Author, VerifiedEvent)>>,
+    quorum_store_shutdown_tx: Option<oneshot::Sender<oneshot::Sender<()>>>,
     epoch_state: Option<EpochState>,
 }
 
@@ -131,6 +143,8 @@ impl EpochManager {
             buffer_manager_msg_tx: None,
             buffer_manager_reset_tx: None,
             round_manager_tx: None,
+            quorum_store_tx: None,
+            quorum_store_shutdown_tx: None,
             epoch_state: None,
         }
     }
@@ -184,15 +198,31 @@ impl EpochManager {
                 ))
             }
             ConsensusProposerType::LeaderReputation(heuristic_config) => {
+                let voter_window_size =
+                    proposers.len() * heuristic_config.voter_window_num_validators_multiplier;
+                let proposer_window_size =
+                    proposers.len() * heuristic_config.proposer_window_num_validators_multiplier;
                 let backend = Box::new(AptosDBBackend::new(
-                    proposers.len(),
+                    std::cmp::max(voter_window_size, proposer_window_size),
                     self.storage.aptos_db(),
                 ));
-                let heuristic = Box::new(ActiveInactiveHeuristic::new(
-                    self.author,
-                    heuristic_config.active_weights,
-                    heuristic_config.inactive_weights,
-                ));
+                let heuristic: Box<dyn ReputationHeuristic> = if proposer_window_size == 0 {
+                    Box::new(ActiveInactiveHeuristic::new(
+                        self.author,
+                        heuristic_config.active_weights,
+                        heuristic_config.inactive_weights,
+                    ))
+                } else {
+                    Box::new(ProposerAndVoterHeuristic::new(
+                        self.author,
+                        heuristic_config.active_weights,
+                        heuristic_config.inactive_weights,
+                        heuristic_config.failed_weights,
+                        heuristic_config.failure_threshold_percent,
+                        voter_window_size,
+                        proposer_window_size,
+                    ))
+                };
                 Box::new(LeaderReputation::new(
                     proposers,
                     backend,
@@ -345,6 +375,58 @@ impl EpochManager {
         OrderingStateComputer::new(block_tx, self.commit_state_computer.clone(), reset_tx)
     }
 
+    /// this function spawns the quorum store of the epoch, it sets `self.quorum_store_tx` and
+    /// `self.quorum_store_shutdown_tx`, and
+    /// returns the queue of the proofs of store for the proposer
+    fn spawn_quorum_store(
+        &mut self,
+        safety_rules_container: Arc<Mutex<MetricsSafetyRules>>,
+        epoch_state: &EpochState,
+    ) -> Arc<ProofQueue> {
+        let config = self.config.quorum_store.clone();
+        let network_sender = NetworkSender::new(
+            self.author,
+            self.network_sender.clone(),
+            self.self_sender.clone(),
+            epoch_state.verifier.clone(),
+        );
+        let proof_queue = Arc::new(ProofQueue::new());
+        let batch_store = Arc::new(BatchStore::new(
+            epoch_state.epoch,
+            self.author,
+            proof_queue.clone(),
+            network_sender.clone(),
+            self.time_service.clone(),
+            &config,
+        ));
+        self.commit_state_computer
+            .set_batch_reader(Some(batch_store.clone()));
+
+        let (quorum_store_tx, quorum_store_rx) = aptos_channel::new(
+            QueueStyle::FIFO,
+            self.config.channel_size,
+            Some(&counters::QUORUM_STORE_CHANNEL_MSGS),
+        );
+        self.quorum_store_tx = Some(quorum_store_tx);
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        self.quorum_store_shutdown_tx = Some(shutdown_tx);
+
+        let quorum_store = QuorumStore::new(
+            epoch_state.epoch,
+            self.author,
+            config,
+            batch_store,
+            proof_queue.clone(),
+            self.txn_manager.clone(),
+            safety_rules_container,
+            network_sender,
+            epoch_state.verifier.clone(),
+            self.time_service.clone(),
+        );
+        tokio::spawn(quorum_store.start(quorum_store_rx, shutdown_rx));
+        proof_queue
+    }
+
     async fn shutdown_current_processor(&mut self) {
         if self.round_manager_tx.is_some() {
             // Release the previous RoundManager, especially the SafetyRule client
@@ -357,6 +439,17 @@ impl EpochManager {
         }
         self.round_manager_tx = None;
 
+        // Shutdown the previous quorum store, to release the SafetyRule client
+        self.quorum_store_tx = None;
+        if let Some(tx) = self.quorum_store_shutdown_tx.take() {
+            let (ack_tx, ack_rx) = oneshot::channel();
+            tx.send(ack_tx)
+                .expect("[EpochManager] Fail to drop quorum store");
+            ack_rx
+                .await
+                .expect("[EpochManager] Fail to drop quorum store");
+        }
+
         // Shutdown the previous buffer manager, to release the SafetyRule client
         self.buffer_manager_msg_tx = None;
         if let Some(mut tx) = self.buffer_manager_reset_tx.take() {
@@ -371,6 +464,7 @@ impl EpochManager {
                 .await
                 .expect("[EpochManager] Fail to drop buffer manager");
         }
+        self.commit_state_computer.set_batch_reader(None);
     }
 
     async fn start_round_manager(
@@ -426,6 +520,13 @@ impl EpochManager {
             self.commit_state_computer.clone()
         };
 
+        let proof_queue = if onchain_config.quorum_store_enabled() {
+            info!(epoch = epoch, "Create QuorumStore");
+            Some(self.spawn_quorum_store(safety_rules_container.clone(), &epoch_state))
+        } else {
+            None
+        };
+
         info!(epoch = epoch, "Create BlockStore");
         let block_store = Arc::new(BlockStore::new(
             Arc::clone(&self.storage),
@@ -445,6 +546,12 @@ impl EpochManager {
             self.txn_manager.clone(),
             self.time_service.clone(),
             self.config.max_block_size,
+            // Proposals above the on-chain limit are rejected by the other validators
+            self.config
+                .max_block_bytes
+                .min(onchain_config.max_block_bytes()),
+            onchain_config.max_failed_authors_to_store(),
+            proof_queue,
         );
 
         let mut round_manager = RoundManager::new(
@@ -458,6 +565,7 @@ impl EpochManager {
             self.storage.clone(),
             self.config.sync_only,
             onchain_config,
+            self.config.enable_fast_commit,
         );
 
         round_manager.init(last_vote).await;
@@ -531,7 +639,11 @@ impl EpochManager {
             | ConsensusMsg::SyncInfo(_)
             | ConsensusMsg::VoteMsg(_)
             | ConsensusMsg::CommitVoteMsg(_)
-            | ConsensusMsg::CommitDecisionMsg(_) => {
+            | ConsensusMsg::CommitDecisionMsg(_)
+            | ConsensusMsg::BatchMsg(_)
+            | ConsensusMsg::SignedBatchInfoMsg(_)
+            | ConsensusMsg::ProofOfStoreMsg(_)
+            | ConsensusMsg::FastCommitVoteMsg(_) => {
                 let event: UnverifiedEvent = msg.into();
                 if event.epoch() == self.epoch() {
                     return Ok(Some(event));
@@ -594,6 +706,15 @@ impl EpochManager {
                     bail!("Commit Phase not started but received Commit Message (CommitVote/CommitDecision)");
                 }
             }
+            quorum_store_event @ (VerifiedEvent::Batch(_)
+            | VerifiedEvent::SignedBatchInfo(_)
+            | VerifiedEvent::ProofOfStore(_)) => {
+                if let Some(sender) = &mut self.quorum_store_tx {
+                    sender.push(peer_id, (peer_id, quorum_store_event))?;
+                } else {
+                    bail!("Quorum Store not started but received Quorum Store Message (Batch/SignedBatchInfo/ProofOfStore)");
+                }
+            }
             round_manager_event => {
                 self.forward_to_round_manager(peer_id, round_manager_event);
             }
@@ -618,6 +739,24 @@ impl EpochManager {
         );
     }
 
+    fn process_batch_retrieval(
+        &mut self,
+        peer_id: AccountAddress,
+        request: IncomingBatchRetrievalRequest,
+    ) -> anyhow::Result<()> {
+        // the request is dropped (and the rpc fails) when the quorum store is not running
+        if let Some(sender) = &mut self.quorum_store_tx {
+            sender.push(
+                peer_id,
+                (
+                    peer_id,
+                    VerifiedEvent::BatchRetrievalRequest(Box::new(request)),
+                ),
+            )?;
+        }
+        Ok(())
+    }
+
     fn process_local_timeout(&mut self, round: u64) {
         self.forward_to_round_manager(self.author, VerifiedEvent::LocalTimeout(round));
     }
@@ -649,6 +788,16 @@ impl EpochManager {
                 Some(request) = network_receivers.block_retrieval.next() => {
                     self.process_block_retrieval(request);
                 }
+                Some((peer, msg)) = network_receivers.quorum_store_messages.next() => {
+                    if let Err(e) = self.process_message(peer, msg).await {
+                        error!(epoch = self.epoch(), error = ?e, kind = error_kind(&e));
+                    }
+                }
+                Some((peer, request)) = network_receivers.batch_retrieval.next() => {
+                    if let Err(e) = self.process_batch_retrieval(peer, request) {
+                        error!(epoch = self.epoch(), error = ?e, kind = error_kind(&e));
+                    }
+                }
                 Some(round) = round_timeout_sender_rx.next() => {
                     self.process_local_timeout(round);
                 }
@@ -0,0 +1,412 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    consensusdb::{ConsensusDB, RoundEventKey},
+    counters,
+};
+use anyhow::Result;
+use aptos_config::config::ConsensusJournalConfig;
+use aptos_crypto::HashValue;
+use aptos_infallible::duration_since_epoch;
+use aptos_logger::prelude::*;
+use aptos_types::block_info::Round;
+use consensus_types::common::Author;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Display, Formatter},
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{sync_channel, Receiver, SyncSender, TrySendError},
+        Arc,
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+#[cfg(test)]
+#[path = "event_journal_test.rs"]
+mod event_journal_test;
+
+/// The journal is pruned at least once every this many written events.
+const PRUNE_INTERVAL_EVENTS: u64 = 10_000;
+/// Max number of the events written to the ConsensusDB at once.
+const WRITE_BATCH_SIZE: usize = 100;
+
+/// What happened in a round, as observed by this validator.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum RoundEventKind {
+    /// The validator entered the round, after a QC or a TC of the previous round.
+    RoundStarted {
+        /// QCReady or TCReady
+        reason: String,
+    },
+    /// A proposal of the round was received, before it is verified against the proposer election.
+    ProposalReceived {
+        /// Author of the proposal
+        proposer: Author,
+        /// Id of the proposed block
+        block_id: HashValue,
+    },
+    /// A vote on a block of the round was received.
+    VoteReceived {
+        /// Author of the vote
+        author: Author,
+        /// Id of the block voted for
+        block_id: HashValue,
+        /// Whether the vote carries a timeout signature
+        timeout: bool,
+    },
+    /// The votes received formed a QC on a block of the round.
+    QuorumCertFormed {
+        /// Id of the certified block
+        block_id: HashValue,
+    },
+    /// The timeout votes received formed a TC of the round.
+    TimeoutCertFormed {
+        /// Authors of the timeout votes in the TC
+        signers: Vec<Author>,
+    },
+    /// The round timed out locally.
+    LocalTimeout,
+}
+
+/// An entry of the journal, the time is the wall clock of the validator in microseconds.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct RoundEvent {
+    /// When the event was recorded
+    pub timestamp_usecs: u64,
+    /// What happened
+    pub kind: RoundEventKind,
+}
+
+impl RoundEvent {
+    /// Creates an event at the given time.
+    pub fn new(timestamp_usecs: u64, kind: RoundEventKind) -> Self {
+        Self {
+            timestamp_usecs,
+            kind,
+        }
+    }
+}
+
+/// Appends the round events to the ConsensusDB from a background thread, so that consensus never
+/// waits for the disk.  The events recorded while the writer is `channel_size` events behind are
+/// dropped.
+pub(crate) struct EventJournal {
+    sender: Option<SyncSender<(RoundEventKey, RoundEvent)>>,
+    writer: Option<JoinHandle<()>>,
+    next_seq: AtomicU64,
+}
+
+impl EventJournal {
+    pub fn new(db: Arc<ConsensusDB>, config: &ConsensusJournalConfig) -> Result<Self> {
+        let next_seq = db.get_last_round_event_key()?.map_or(0, |key| key.seq + 1);
+        let num_entries = db.round_events_iter()?.count() as u64;
+        let (sender, receiver) = sync_channel(config.channel_size);
+        let writer = JournalWriter {
+            db,
+            max_age: Duration::from_secs(config.max_age_secs),
+            max_entries: config.max_entries,
+            num_entries,
+        };
+        let writer = std::thread::Builder::new()
+            .name("consensus-journal".to_string())
+            .spawn(move || writer.run(receiver))?;
+        Ok(Self {
+            sender: Some(sender),
+            writer: Some(writer),
+            next_seq: AtomicU64::new(next_seq),
+        })
+    }
+
+    /// Journaling is best effort, failures are logged and don't affect consensus.
+    pub fn record(&self, epoch: u64, round: Round, kind: RoundEventKind) {
+        let seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+        let event = RoundEvent::new(duration_since_epoch().as_micros() as u64, kind);
+        let sender = self.sender.as_ref().expect("journal sender must exist");
+        if let Err(TrySendError::Full(_)) =
+            sender.try_send((RoundEventKey::new(epoch, round, seq), event))
+        {
+            counters::CONSENSUS_JOURNAL_DROPPED_EVENTS_COUNT.inc();
+        }
+    }
+}
+
+impl Drop for EventJournal {
+    /// Waits for the writer to write the events recorded so far.
+    fn drop(&mut self) {
+        self.sender = None;
+        if let Some(writer) = self.writer.take() {
+            if writer.join().is_err() {
+                warn!("The consensus journal writer panicked");
+            }
+        }
+    }
+}
+
+/// Writes the events recorded by the `EventJournal`, and prunes the entries older than
+/// `max_age` or beyond the `max_entries` most recent ones.
+struct JournalWriter {
+    db: Arc<ConsensusDB>,
+    max_age: Duration,
+    max_entries: u64,
+    num_entries: u64,
+}
+
+impl JournalWriter {
+    fn run(mut self, receiver: Receiver<(RoundEventKey, RoundEvent)>) {
+        // the entries of the previous runs may have expired since
+        self.prune();
+        let mut written_since_prune = 0;
+        while let Ok(event) = receiver.recv() {
+            let events: Vec<_> = std::iter::once(event)
+                .chain(receiver.try_iter().take(WRITE_BATCH_SIZE - 1))
+                .collect();
+            if let Err(e) = self.db.save_round_events(&events) {
+                warn!(error = ?e, "Failed to journal {} events", events.len());
+                continue;
+            }
+            self.num_entries += events.len() as u64;
+            written_since_prune += events.len() as u64;
+            if written_since_prune >= PRUNE_INTERVAL_EVENTS || self.num_entries > self.max_entries {
+                self.prune();
+                written_since_prune = 0;
+            }
+        }
+    }
+
+    fn prune(&mut self) {
+        if let Err(e) = self.try_prune() {
+            warn!(error = ?e, "Failed to prune the consensus journal");
+        }
+    }
+
+    /// Deletes the oldest entries until the remaining ones are recent and few enough.
+    fn try_prune(&mut self) -> Result<()> {
+        let min_timestamp_usecs = duration_since_epoch()
+            .saturating_sub(self.max_age)
+            .as_micros() as u64;
+        let excess_entries = self.num_entries.saturating_sub(self.max_entries);
+        let mut num_pruned = 0;
+        // past the last possible key if all the entries are pruned
+        let mut end = RoundEventKey::new(u64::MAX, Round::MAX, u64::MAX);
+        for entry in self.db.round_events_iter()? {
+            let (key, event) = entry?;
+            if num_pruned >= excess_entries && event.timestamp_usecs >= min_timestamp_usecs {
+                end = key;
+                break;
+            }
+            num_pruned += 1;
+        }
+        if num_pruned > 0 {
+            self.db.prune_round_events(&end)?;
+            self.num_entries = self.num_entries.saturating_sub(num_pruned);
+        }
+        Ok(())
+    }
+}
+
+/// An event of the journal with the epoch and round it belongs to.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JournalEntry {
+    /// Epoch of the round
+    pub epoch: u64,
+    /// Round the event belongs to
+    pub round: Round,
+    /// The journaled event
+    pub event: RoundEvent,
+}
+
+/// Reads the journal from the ConsensusDB of a stopped validator.
+pub struct JournalReader {
+    db: ConsensusDB,
+}
+
+impl JournalReader {
+    /// Opens the ConsensusDB under the storage directory of the validator.
+    pub fn open<P: AsRef<Path>>(db_root_path: P) -> Result<Self> {
+        Ok(Self {
+            db: ConsensusDB::open_readonly(db_root_path)?,
+        })
+    }
+
+    /// Returns the entries from the given epoch and round on, in the order they were recorded
+    /// within each round.
+    pub fn entries(&self, from_epoch: u64, from_round: Round) -> Result<Vec<JournalEntry>> {
+        Ok(self
+            .db
+            .get_round_events(&RoundEventKey::new(from_epoch, from_round, 0))?
+            .into_iter()
+            .map(|(key, event)| JournalEntry {
+                epoch: key.epoch,
+                round: key.round,
+                event,
+            })
+            .collect())
+    }
+}
+
+/// The reconstructed course of a round, all the times are in microseconds.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RoundTimeline {
+    /// Epoch of the round
+    pub epoch: u64,
+    /// The round
+    pub round: Round,
+    /// When the validator entered the round
+    pub start_usecs: Option<u64>,
+    /// Why the validator entered the round (QCReady or TCReady)
+    pub start_reason: Option<String>,
+    /// Author of the first proposal received
+    pub proposer: Option<Author>,
+    /// When the first proposal was received
+    pub proposal_usecs: Option<u64>,
+    /// Authors of the votes received and when
+    pub votes: Vec<(Author, u64)>,
+    /// Authors of the timeout votes received and when
+    pub timeout_votes: Vec<(Author, u64)>,
+    /// When the votes received formed a QC
+    pub qc_usecs: Option<u64>,
+    /// Authors of the timeout votes in the TC formed
+    pub tc_signers: Vec<Author>,
+    /// When the timeout votes received formed a TC
+    pub tc_usecs: Option<u64>,
+    /// When the round timed out locally
+    pub local_timeout_usecs: Option<u64>,
+    /// Start of the next round, if the validator entered it within the same epoch
+    pub end_usecs: Option<u64>,
+}
+
+impl RoundTimeline {
+    /// Groups the entries, sorted by epoch and round, into the timelines of their rounds.
+    pub fn from_entries(entries: impl IntoIterator<Item = JournalEntry>) -> Vec<Self> {
+        let mut timelines: Vec<Self> = vec![];
+        for entry in entries {
+            let new_round = timelines.last().map_or(true, |last| {
+                (last.epoch, last.round) != (entry.epoch, entry.round)
+            });
+            if new_round {
+                timelines.push(Self {
+                    epoch: entry.epoch,
+                    round: entry.round,
+                    ..Self::default()
+                });
+            }
+            timelines
+                .last_mut()
+                .expect("timeline of the round must exist")
+                .add_event(entry.event);
+        }
+        for i in 1..timelines.len() {
+            if timelines[i - 1].epoch == timelines[i].epoch {
+                timelines[i - 1].end_usecs = timelines[i].start_usecs;
+            }
+        }
+        timelines
+    }
+
+    fn add_event(&mut self, event: RoundEvent) {
+        let time = event.timestamp_usecs;
+        match event.kind {
+            RoundEventKind::RoundStarted { reason } => {
+                self.start_usecs = Some(time);
+                self.start_reason = Some(reason);
+            }
+            RoundEventKind::ProposalReceived { proposer, .. } => {
+                self.proposer.get_or_insert(proposer);
+                self.proposal_usecs.get_or_insert(time);
+            }
+            RoundEventKind::VoteReceived {
+                author, timeout, ..
+            } => {
+                if timeout {
+                    self.timeout_votes.push((author, time));
+                } else {
+                    self.votes.push((author, time));
+                }
+            }
+            RoundEventKind::QuorumCertFormed { .. } => {
+                self.qc_usecs.get_or_insert(time);
+            }
+            RoundEventKind::TimeoutCertFormed { signers } => {
+                self.tc_signers = signers;
+                self.tc_usecs.get_or_insert(time);
+            }
+            RoundEventKind::LocalTimeout => {
+                self.local_timeout_usecs.get_or_insert(time);
+            }
+        }
+    }
+
+    /// How long the validator stayed in the round.
+    pub fn duration(&self) -> Option<Duration> {
+        match (self.start_usecs, self.end_usecs) {
+            (Some(start), Some(end)) => Some(Duration::from_micros(end.saturating_sub(start))),
+            _ => None,
+        }
+    }
+
+    /// The times are shown relative to the start of the round, or to the first event if the
+    /// start wasn't journaled.
+    fn origin_usecs(&self) -> u64 {
+        self.start_usecs
+            .into_iter()
+            .chain(self.proposal_usecs)
+            .chain(self.votes.iter().map(|(_, time)| *time))
+            .chain(self.timeout_votes.iter().map(|(_, time)| *time))
+            .chain(self.qc_usecs)
+            .chain(self.tc_usecs)
+            .chain(self.local_timeout_usecs)
+            .min()
+            .unwrap_or(0)
+    }
+}
+
+impl Display for RoundTimeline {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let origin = self.origin_usecs();
+        let offset = |time: u64| format!("+{}ms", time.saturating_sub(origin) / 1000);
+
+        write!(f, "[epoch {} round {}]", self.epoch, self.round)?;
+        match &self.start_reason {
+            Some(reason) => write!(f, " started ({})", reason)?,
+            None => write!(f, " start not journaled")?,
+        }
+        if let Some(duration) = self.duration() {
+            write!(f, ", lasted {}ms", duration.as_millis())?;
+        }
+        writeln!(f)?;
+        if let (Some(proposer), Some(time)) = (self.proposer, self.proposal_usecs) {
+            writeln!(f, "  proposal from {} at {}", proposer, offset(time))?;
+        }
+        for (name, votes) in [
+            ("votes", &self.votes),
+            ("timeout votes", &self.timeout_votes),
+        ] {
+            if !votes.is_empty() {
+                let votes: Vec<_> = votes
+                    .iter()
+                    .map(|(author, time)| format!("{} {}", author, offset(*time)))
+                    .collect();
+                writeln!(f, "  {} {}: {}", votes.len(), name, votes.join(", "))?;
+            }
+        }
+        if let Some(time) = self.qc_usecs {
+            writeln!(f, "  QC formed at {}", offset(time))?;
+        }
+        if let Some(time) = self.tc_usecs {
+            writeln!(
+                f,
+                "  TC formed at {} with {} signers",
+                offset(time),
+                self.tc_signers.len()
+            )?;
+        }
+        if let Some(time) = self.local_timeout_usecs {
+            writeln!(f, "  local timeout at {}", offset(time))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_temppath::TempPath;
+use aptos_types::account_address::AccountAddress;
+
+fn round_started() -> RoundEventKind {
+    RoundEventKind::RoundStarted {
+        reason: "QCReady".to_string(),
+    }
+}
+
+fn journal_config(max_age_secs: u64, max_entries: u64) -> ConsensusJournalConfig {
+    ConsensusJournalConfig {
+        enabled: true,
+        max_age_secs,
+        max_entries,
+        channel_size: 100,
+    }
+}
+
+fn journaled_keys(db: &ConsensusDB) -> Vec<RoundEventKey> {
+    db.get_round_events(&RoundEventKey::new(0, 0, 0))
+        .unwrap()
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect()
+}
+
+#[test]
+fn test_record_and_prune() {
+    let tmp_dir = TempPath::new();
+    let db = Arc::new(ConsensusDB::new(&tmp_dir));
+    let journal = EventJournal::new(db.clone(), &journal_config(3600, 3)).unwrap();
+    for round in 1..=3 {
+        journal.record(1, round, RoundEventKind::LocalTimeout);
+    }
+    // dropping the journal waits for the writer
+    drop(journal);
+    assert_eq!(
+        journaled_keys(&db),
+        vec![
+            RoundEventKey::new(1, 1, 0),
+            RoundEventKey::new(1, 2, 1),
+            RoundEventKey::new(1, 3, 2),
+        ]
+    );
+
+    // a restarted validator continues the sequence, beyond 3 entries the oldest ones are pruned
+    let journal = EventJournal::new(db.clone(), &journal_config(3600, 3)).unwrap();
+    journal.record(1, 3, RoundEventKind::LocalTimeout);
+    journal.record(1, 5, round_started());
+    drop(journal);
+    assert_eq!(
+        journaled_keys(&db),
+        vec![
+            RoundEventKey::new(1, 3, 2),
+            RoundEventKey::new(1, 3, 3),
+            RoundEventKey::new(1, 5, 4),
+        ]
+    );
+
+    // the expired entries are pruned when the journal is opened
+    let journal = EventJournal::new(db.clone(), &journal_config(0, 3)).unwrap();
+    drop(journal);
+    assert_eq!(journaled_keys(&db), vec![]);
+    assert_eq!(db.get_last_round_event_key().unwrap(), None);
+}
+
+#[test]
+fn test_round_timelines() {
+    let proposer = AccountAddress::random();
+    let voter = AccountAddress::random();
+    let block_id = HashValue::random();
+    let entry = |round, timestamp_usecs, kind| JournalEntry {
+        epoch: 1,
+        round,
+        event: RoundEvent::new(timestamp_usecs, kind),
+    };
+    let entries = vec![
+        entry(1, 1_000, round_started()),
+        entry(
+            1,
+            3_000,
+            RoundEventKind::ProposalReceived { proposer, block_id },
+        ),
+        entry(
+            1,
+            5_000,
+            RoundEventKind::VoteReceived {
+                author: voter,
+                block_id,
+                timeout: false,
+            },
+        ),
+        entry(1, 6_000, RoundEventKind::QuorumCertFormed { block_id }),
+        entry(2, 7_000, round_started()),
+        entry(2, 1_007_000, RoundEventKind::LocalTimeout),
+    ];
+
+    let timelines = RoundTimeline::from_entries(entries);
+    assert_eq!(timelines.len(), 2);
+    assert_eq!(timelines[0].round, 1);
+    assert_eq!(timelines[0].proposer, Some(proposer));
+    assert_eq!(timelines[0].votes, vec![(voter, 5_000)]);
+    assert_eq!(timelines[0].qc_usecs, Some(6_000));
+    assert_eq!(timelines[0].duration(), Some(Duration::from_millis(6)));
+    assert_eq!(timelines[1].local_timeout_usecs, Some(1_007_000));
+    // the validator didn't enter round 3
+    assert_eq!(timelines[1].duration(), None);
+
+    let display = timelines[0].to_string();
+    assert!(display.contains("lasted 6ms"));
+    assert!(display.contains("QC formed at +5ms"));
+}
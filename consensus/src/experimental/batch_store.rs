@@ -0,0 +1,221 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    counters, experimental::proof_queue::ProofQueue, logging::LogEvent, network::NetworkSender,
+    util::time_service::TimeService,
+};
+use anyhow::{bail, ensure};
+use aptos_config::config::QuorumStoreConfig;
+use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use consensus_types::{
+    common::{Author, Payload},
+    experimental::batch::{Batch, BatchRequest, ProofOfStore},
+};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+/// How long the expired batches are kept around, for the validators that are behind to fetch
+/// the batches of the blocks they still have to execute.
+const EXPIRED_BATCH_RETENTION: Duration = Duration::from_secs(60);
+
+/// Gives the execution access to the transactions of the batches of a block.
+#[async_trait::async_trait]
+pub trait BatchReader: Send + Sync {
+    /// Returns the transactions of the batch of the proof, fetching the batch from the
+    /// validators that signed the proof if it isn't stored locally.
+    async fn get_batch(&self, proof: &ProofOfStore) -> anyhow::Result<Payload>;
+
+    /// Notifies that the batches of the proofs were committed.
+    fn notify_commit(&self, proofs: &[ProofOfStore]);
+}
+
+struct StoredBatch {
+    batch: Batch,
+    committed: bool,
+}
+
+/// Stores the batches of the epoch, both the ones of this validator and the ones it signed for
+/// the others, and serves them to the execution and to the other validators.
+///
+/// The unexpired batches stored for each author are bounded in number and size, so that a
+/// validator can't exhaust the memory of the others.
+pub struct BatchStore {
+    epoch: u64,
+    author: Author,
+    batches: Mutex<HashMap<HashValue, StoredBatch>>,
+    proof_queue: Arc<ProofQueue>,
+    network: NetworkSender,
+    time_service: Arc<dyn TimeService>,
+    request_timeout: Duration,
+    max_batches_per_author: usize,
+    max_bytes_per_author: u64,
+}
+
+impl BatchStore {
+    pub fn new(
+        epoch: u64,
+        author: Author,
+        proof_queue: Arc<ProofQueue>,
+        network: NetworkSender,
+        time_service: Arc<dyn TimeService>,
+        config: &QuorumStoreConfig,
+    ) -> Self {
+        Self {
+            epoch,
+            author,
+            batches: Mutex::new(HashMap::new()),
+            proof_queue,
+            network,
+            time_service,
+            request_timeout: Duration::from_millis(config.batch_request_timeout_ms),
+            max_batches_per_author: config.max_batches_per_author,
+            max_bytes_per_author: config.max_batch_bytes_per_author,
+        }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Stores a verified batch, unless it is from another epoch or its author exceeds the
+    /// limits of the stored batches.
+    pub fn insert(&self, batch: Batch) -> anyhow::Result<()> {
+        ensure!(
+            batch.epoch() == self.epoch,
+            "Batch {} from a different epoch",
+            batch.info()
+        );
+        self.prune_expired();
+        let now = self.now_usecs();
+        let mut batches = self.batches.lock();
+        if batches.contains_key(&batch.digest()) {
+            return Ok(());
+        }
+        let (num_batches, num_bytes) = batches
+            .values()
+            .filter(|stored| {
+                stored.batch.author() == batch.author() && stored.batch.info().expiration() > now
+            })
+            .fold((0, 0), |(num_batches, num_bytes), stored| {
+                (num_batches + 1, num_bytes + stored.batch.info().num_bytes())
+            });
+        if num_batches >= self.max_batches_per_author
+            || num_bytes + batch.info().num_bytes() > self.max_bytes_per_author
+        {
+            counters::QUORUM_STORE_REJECTED_BATCHES_COUNT.inc();
+            bail!(
+                "Batch {} exceeds the limits of the stored batches of its author: {} batches, {} bytes",
+                batch.info(),
+                num_batches,
+                num_bytes
+            );
+        }
+        batches.insert(
+            batch.digest(),
+            StoredBatch {
+                batch,
+                committed: false,
+            },
+        );
+        Ok(())
+    }
+
+    /// Stores a batch fetched for the execution, which is needed regardless of the limits.
+    fn insert_fetched(&self, batch: Batch) {
+        self.batches
+            .lock()
+            .entry(batch.digest())
+            .or_insert(StoredBatch {
+                batch,
+                committed: false,
+            });
+    }
+
+    pub fn get(&self, digest: &HashValue) -> Option<Batch> {
+        self.batches
+            .lock()
+            .get(digest)
+            .map(|stored| stored.batch.clone())
+    }
+
+    pub fn contains(&self, digest: &HashValue) -> bool {
+        self.batches.lock().contains_key(digest)
+    }
+
+    /// The transactions of the stored batches, of any author, that can still be committed. New
+    /// batches must not include them again.
+    pub fn pending_payloads(&self) -> Vec<Payload> {
+        let now = self.now_usecs();
+        self.batches
+            .lock()
+            .values()
+            .filter(|stored| !stored.committed && stored.batch.info().expiration() > now)
+            .map(|stored| stored.batch.payload().clone())
+            .collect()
+    }
+
+    /// Drops the batches expired for longer than the retention.
+    pub fn prune_expired(&self) {
+        let retention = EXPIRED_BATCH_RETENTION.as_micros() as u64;
+        let now = self.now_usecs();
+        self.batches
+            .lock()
+            .retain(|_, stored| stored.batch.info().expiration() + retention > now);
+    }
+
+    fn now_usecs(&self) -> u64 {
+        self.time_service.get_current_timestamp().as_micros() as u64
+    }
+}
+
+#[async_trait::async_trait]
+impl BatchReader for BatchStore {
+    async fn get_batch(&self, proof: &ProofOfStore) -> anyhow::Result<Payload> {
+        if let Some(batch) = self.get(&proof.digest()) {
+            return Ok(batch.into_payload());
+        }
+
+        let request = BatchRequest::new(self.epoch, proof.digest());
+        for signer in proof.signers().filter(|signer| **signer != self.author) {
+            debug!(
+                remote_peer = *signer,
+                event = LogEvent::RetrieveBatch,
+                "{}",
+                request
+            );
+            match self
+                .network
+                .request_batch(request.clone(), *signer, self.request_timeout)
+                .await
+            {
+                Ok(batch) => {
+                    counters::QUORUM_STORE_FETCHED_BATCHES_COUNT.inc();
+                    let payload = batch.payload().clone();
+                    self.insert_fetched(batch);
+                    return Ok(payload);
+                }
+                Err(e) => {
+                    warn!(
+                        remote_peer = *signer,
+                        error = ?e, "Failed to fetch batch {}", proof.info(),
+                    );
+                }
+            }
+        }
+        bail!("Unable to fetch batch {} from its signers", proof.info())
+    }
+
+    fn notify_commit(&self, proofs: &[ProofOfStore]) {
+        {
+            let mut batches = self.batches.lock();
+            for proof in proofs {
+                if let Some(stored) = batches.get_mut(&proof.digest()) {
+                    stored.committed = true;
+                }
+            }
+        }
+        self.proof_queue.mark_committed(proofs);
+    }
+}
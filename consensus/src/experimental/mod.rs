@@ -19,8 +19,24 @@
  *  │ State    │   Sync Req  │
  *  │ Computer ├─────────────┘
  *  └──────────┘
+ *
+ * Quorum store (optional): the transactions are disseminated in batches ahead of the ordering,
+ * the proposals carry the proofs of store of the batches and the execution phase reads the
+ * batches from the Batch Store.
+ *
+ *  ┌───────────┐  Batch / Signed Batch Info / Proof of Store  ┌─────────┐
+ *  │  Quorum   ├──────────────────────────────────────────────►         │
+ *  │  Store    ◄──────────────────────────────────────────────┤ Network │
+ *  └──┬─────┬──┘                                              └─────────┘
+ *     │     │ Batches      ┌─────────────┐    Batches    ┌────────────────────┐
+ *     │     └──────────────► Batch Store ├───────────────► 1. Execution Phase │
+ *     │ Proofs             └─────────────┘               └────────────────────┘
+ *  ┌──▼──────────┐          ┌────────────────────┐
+ *  │ Proof Queue ├──────────► Proposal Generator │
+ *  └─────────────┘          └────────────────────┘
  */
 
+pub mod batch_store;
 pub mod buffer;
 pub mod buffer_item;
 pub mod buffer_manager;
@@ -31,6 +47,8 @@ pub mod hashable;
 pub mod ordering_state_computer;
 pub mod persisting_phase;
 pub mod pipeline_phase;
+pub mod proof_queue;
+pub mod quorum_store;
 pub mod signing_phase;
 
 #[cfg(test)]
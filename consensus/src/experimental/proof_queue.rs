@@ -0,0 +1,107 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
+use consensus_types::experimental::batch::ProofOfStore;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// The proofs of store available to the proposer, in the order they were received.
+/// A proof leaves the queue once its batch is committed or it expires.
+#[derive(Default)]
+pub struct ProofQueue {
+    inner: Mutex<ProofQueueInner>,
+}
+
+#[derive(Default)]
+struct ProofQueueInner {
+    proofs: VecDeque<ProofOfStore>,
+    // Digests of the batches in the queue
+    queued: HashSet<HashValue>,
+    // Expiration of the committed batches, so that proofs received late are ignored
+    committed: HashMap<HashValue, u64>,
+}
+
+impl ProofQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a verified proof to the queue, unless it's already there or its batch was committed.
+    pub fn push(&self, proof: ProofOfStore) {
+        let mut inner = self.inner.lock();
+        let digest = proof.digest();
+        if inner.committed.contains_key(&digest) || !inner.queued.insert(digest) {
+            return;
+        }
+        inner.proofs.push_back(proof);
+    }
+
+    /// Returns the oldest proofs that can be included in a block with the given timestamp,
+    /// within the limits on the number of transactions and bytes of the block.
+    /// The proofs in `excluded` are skipped, they are already in the pending blocks.
+    pub fn pull_proofs(
+        &self,
+        max_txns: u64,
+        max_bytes: u64,
+        excluded: &HashSet<HashValue>,
+        timestamp_usecs: u64,
+    ) -> Vec<ProofOfStore> {
+        let mut inner = self.inner.lock();
+        inner.prune_expired(timestamp_usecs);
+
+        let mut proofs = vec![];
+        let (mut txns, mut bytes) = (0, 0);
+        for proof in inner.proofs.iter() {
+            if excluded.contains(&proof.digest()) {
+                continue;
+            }
+            txns += proof.info().num_txns();
+            bytes += proof.info().num_bytes();
+            if txns > max_txns || bytes > max_bytes {
+                break;
+            }
+            proofs.push(proof.clone());
+        }
+        proofs
+    }
+
+    /// Removes the proofs of the committed batches from the queue.
+    pub fn mark_committed(&self, proofs: &[ProofOfStore]) {
+        let mut inner = self.inner.lock();
+        for proof in proofs {
+            inner.queued.remove(&proof.digest());
+            inner.committed.insert(proof.digest(), proof.expiration());
+        }
+        let inner = &mut *inner;
+        let committed = &inner.committed;
+        inner
+            .proofs
+            .retain(|proof| !committed.contains_key(&proof.digest()));
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.lock().proofs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl ProofQueueInner {
+    /// Expired proofs can't be included in blocks anymore, and late proofs of expired committed
+    /// batches are rejected by the expiration check already.
+    fn prune_expired(&mut self, timestamp_usecs: u64) {
+        let queued = &mut self.queued;
+        self.proofs.retain(|proof| {
+            let expired = proof.expiration() <= timestamp_usecs;
+            if expired {
+                queued.remove(&proof.digest());
+            }
+            !expired
+        });
+        self.committed
+            .retain(|_, expiration| *expiration > timestamp_usecs);
+    }
+}
@@ -0,0 +1,317 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    counters,
+    experimental::{batch_store::BatchStore, proof_queue::ProofQueue},
+    logging::{LogEvent, LogSchema},
+    metrics_safety_rules::MetricsSafetyRules,
+    network::{IncomingBatchRetrievalRequest, NetworkSender},
+    network_interface::ConsensusMsg,
+    round_manager::VerifiedEvent,
+    state_replication::TxnManager,
+    util::time_service::TimeService,
+};
+use anyhow::{ensure, Context};
+use aptos_config::config::QuorumStoreConfig;
+use aptos_crypto::{ed25519::Ed25519Signature, HashValue};
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use aptos_types::{account_address::AccountAddress, validator_verifier::ValidatorVerifier};
+use channel::aptos_channel;
+use consensus_types::{
+    common::Author,
+    experimental::batch::{Batch, BatchInfo, ProofOfStore, SignedBatchInfo},
+};
+use futures::{channel::oneshot, future::FutureExt, StreamExt};
+use network::protocols::rpc::error::RpcError;
+use safety_rules::TSafetyRules;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::Duration,
+};
+
+/// Signatures collected on the info of a batch of this validator.
+struct PendingProof {
+    info: BatchInfo,
+    signatures: BTreeMap<Author, Ed25519Signature>,
+}
+
+/// QuorumStore disseminates the transactions ahead of the ordering:
+/// 1. It periodically pulls a batch of transactions from mempool and broadcasts it.
+/// 2. The validators store the batches they receive and send back their signature on the
+/// batch info.
+/// 3. Once a quorum of signatures is collected, the proof of store is broadcast and the
+/// proposers can include the batch in blocks by its proof.
+pub struct QuorumStore {
+    epoch: u64,
+    author: Author,
+    config: QuorumStoreConfig,
+    batch_store: Arc<BatchStore>,
+    proof_queue: Arc<ProofQueue>,
+    txn_manager: Arc<dyn TxnManager>,
+    safety_rules: Arc<Mutex<MetricsSafetyRules>>,
+    network: NetworkSender,
+    verifier: ValidatorVerifier,
+    time_service: Arc<dyn TimeService>,
+    next_batch_id: u64,
+    pending_proofs: HashMap<HashValue, PendingProof>,
+}
+
+impl QuorumStore {
+    pub fn new(
+        epoch: u64,
+        author: Author,
+        config: QuorumStoreConfig,
+        batch_store: Arc<BatchStore>,
+        proof_queue: Arc<ProofQueue>,
+        txn_manager: Arc<dyn TxnManager>,
+        safety_rules: Arc<Mutex<MetricsSafetyRules>>,
+        network: NetworkSender,
+        verifier: ValidatorVerifier,
+        time_service: Arc<dyn TimeService>,
+    ) -> Self {
+        Self {
+            epoch,
+            author,
+            config,
+            batch_store,
+            proof_queue,
+            txn_manager,
+            safety_rules,
+            network,
+            verifier,
+            time_service,
+            next_batch_id: 0,
+            pending_proofs: HashMap::new(),
+        }
+    }
+
+    fn now_usecs(&self) -> u64 {
+        self.time_service.get_current_timestamp().as_micros() as u64
+    }
+
+    fn batch_expiry_usecs(&self) -> u64 {
+        Duration::from_millis(self.config.batch_expiry_ms).as_micros() as u64
+    }
+
+    /// Pulls the transactions that are not in the pending batches stored by this validator from
+    /// mempool, and broadcasts them as a new batch.
+    pub(crate) async fn generate_batch(&mut self) -> anyhow::Result<()> {
+        let pending_payloads = self.batch_store.pending_payloads();
+        let payload = self
+            .txn_manager
+            .pull_txns(
+                self.config.max_batch_size,
+                self.config.max_batch_bytes,
+                pending_payloads.iter().collect(),
+                futures::future::ready(()).boxed(),
+                true,
+            )
+            .await
+            .context("[QuorumStore] Fail to retrieve txn")?;
+        if payload.is_empty() {
+            return Ok(());
+        }
+
+        let batch = Batch::new(
+            self.epoch,
+            self.author,
+            self.next_batch_id,
+            self.now_usecs() + self.batch_expiry_usecs(),
+            payload,
+        );
+        self.next_batch_id += 1;
+        counters::QUORUM_STORE_CREATED_BATCHES_COUNT.inc();
+        debug!("[QuorumStore] Broadcast {}", batch);
+
+        self.pending_proofs.insert(
+            batch.digest(),
+            PendingProof {
+                info: batch.info().clone(),
+                signatures: BTreeMap::new(),
+            },
+        );
+        self.network
+            .broadcast(ConsensusMsg::BatchMsg(Box::new(batch)))
+            .await;
+        Ok(())
+    }
+
+    /// Stores the batch of a validator and sends back the signature of its info.
+    pub(crate) async fn process_batch(&mut self, peer: Author, batch: Batch) -> anyhow::Result<()> {
+        debug!(
+            LogSchema::new(LogEvent::ReceiveBatch).remote_peer(peer),
+            "{}", batch
+        );
+        ensure!(
+            batch.author() == peer,
+            "[QuorumStore] Batch {} not sent by its author {}",
+            batch,
+            peer
+        );
+        let now = self.now_usecs();
+        let expiration = batch.info().expiration();
+        ensure!(
+            expiration > now && expiration <= now + self.batch_expiry_usecs(),
+            "[QuorumStore] Batch {} expires at {}, out of the accepted window from {}",
+            batch,
+            expiration,
+            now
+        );
+
+        let info = batch.info().clone();
+        self.batch_store
+            .insert(batch)
+            .context("[QuorumStore] Fail to store batch")?;
+        let signature = self.safety_rules.lock().sign_batch_info(&info)?;
+        let signed_info = SignedBatchInfo::new(info, self.author, signature);
+        self.network
+            .send(
+                ConsensusMsg::SignedBatchInfoMsg(Box::new(signed_info)),
+                vec![peer],
+            )
+            .await;
+        Ok(())
+    }
+
+    /// Aggregates the signatures on the info of a batch of this validator, the proof of store is
+    /// broadcast once they reach a quorum.
+    pub(crate) async fn process_signed_batch_info(
+        &mut self,
+        peer: Author,
+        signed_info: SignedBatchInfo,
+    ) -> anyhow::Result<()> {
+        debug!(
+            LogSchema::new(LogEvent::ReceiveSignedBatchInfo).remote_peer(peer),
+            "{}", signed_info
+        );
+        ensure!(
+            signed_info.signer() == peer,
+            "[QuorumStore] {} not sent by its signer {}",
+            signed_info,
+            peer
+        );
+        let digest = signed_info.info().digest();
+        let pending = match self.pending_proofs.get_mut(&digest) {
+            Some(pending) => pending,
+            // the proof was already formed or the batch isn't from this validator
+            None => return Ok(()),
+        };
+        ensure!(
+            &pending.info == signed_info.info(),
+            "[QuorumStore] {} doesn't match the batch info {}",
+            signed_info,
+            pending.info
+        );
+        pending
+            .signatures
+            .insert(signed_info.signer(), signed_info.signature().clone());
+        if self
+            .verifier
+            .check_voting_power(pending.signatures.keys())
+            .is_err()
+        {
+            return Ok(());
+        }
+
+        let pending = self
+            .pending_proofs
+            .remove(&digest)
+            .expect("Pending proof must exist");
+        let proof = ProofOfStore::new(pending.info, pending.signatures);
+        counters::QUORUM_STORE_CREATED_PROOFS_COUNT.inc();
+        debug!("[QuorumStore] Broadcast {}", proof);
+        self.network
+            .broadcast(ConsensusMsg::ProofOfStoreMsg(Box::new(proof)))
+            .await;
+        Ok(())
+    }
+
+    /// Makes the batch of the proof available to the proposer.
+    pub(crate) fn process_proof_of_store(&mut self, peer: Author, proof: ProofOfStore) {
+        debug!(
+            LogSchema::new(LogEvent::ReceiveProofOfStore).remote_peer(peer),
+            "{}", proof
+        );
+        self.proof_queue.push(proof);
+    }
+
+    /// Replies to the request of a validator missing a batch.
+    pub(crate) fn process_batch_retrieval(
+        &self,
+        request: IncomingBatchRetrievalRequest,
+    ) -> anyhow::Result<()> {
+        let response = match self.batch_store.get(&request.req.digest()) {
+            Some(batch) => Ok(request
+                .protocol
+                .to_bytes(&ConsensusMsg::BatchResponse(Box::new(batch)))?
+                .into()),
+            None => Err(RpcError::Error(anyhow::anyhow!(
+                "Batch {} not found",
+                request.req.digest()
+            ))),
+        };
+        request
+            .response_sender
+            .send(response)
+            .map_err(|e| anyhow::anyhow!("{:?}", e))
+            .context("[QuorumStore] Failed to process batch retrieval")
+    }
+
+    /// Drops the signatures collected for the batches that expired without a proof.
+    fn prune_pending_proofs(&mut self) {
+        let now = self.now_usecs();
+        self.pending_proofs
+            .retain(|_, pending| pending.info.expiration() > now);
+    }
+
+    pub async fn start(
+        mut self,
+        mut event_rx: aptos_channel::Receiver<AccountAddress, (Author, VerifiedEvent)>,
+        mut shutdown_rx: oneshot::Receiver<oneshot::Sender<()>>,
+    ) {
+        info!(epoch = self.epoch, "QuorumStore starts.");
+        let mut interval = tokio::time::interval(Duration::from_millis(
+            self.config.batch_generation_interval_ms,
+        ));
+        loop {
+            let result = tokio::select! {
+                ack_sender = &mut shutdown_rx => {
+                    if let Ok(ack_sender) = ack_sender {
+                        ack_sender
+                            .send(())
+                            .expect("[QuorumStore] Fail to ack shutdown");
+                    }
+                    break;
+                }
+                _ = interval.tick() => {
+                    self.prune_pending_proofs();
+                    self.batch_store.prune_expired();
+                    self.generate_batch().await
+                }
+                Some((peer, event)) = event_rx.next() => {
+                    match event {
+                        VerifiedEvent::Batch(batch) => self.process_batch(peer, *batch).await,
+                        VerifiedEvent::SignedBatchInfo(signed_info) => {
+                            self.process_signed_batch_info(peer, *signed_info).await
+                        }
+                        VerifiedEvent::ProofOfStore(proof) => {
+                            self.process_proof_of_store(peer, *proof);
+                            Ok(())
+                        }
+                        VerifiedEvent::BatchRetrievalRequest(request) => {
+                            self.process_batch_retrieval(*request)
+                        }
+                        unexpected_event => unreachable!("Unexpected event: {:?}", unexpected_event),
+                    }
+                }
+            };
+            if let Err(e) = result {
+                warn!(epoch = self.epoch, error = ?e, "[QuorumStore] Error");
+            }
+        }
+        info!(epoch = self.epoch, "QuorumStore stops.");
+    }
+}
@@ -6,5 +6,6 @@ mod execution_phase_tests;
 mod integration_tests;
 mod ordering_state_computer_tests;
 mod phase_tester;
+mod quorum_store_tests;
 mod signing_phase_tests;
 mod test_utils;
@@ -0,0 +1,454 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    experimental::{
+        batch_store::{BatchReader, BatchStore},
+        proof_queue::ProofQueue,
+        quorum_store::QuorumStore,
+    },
+    metrics_safety_rules::MetricsSafetyRules,
+    network::{NetworkReceivers, NetworkSender, NetworkTask},
+    network_interface::{ConsensusNetworkEvents, ConsensusNetworkSender},
+    network_tests::{NetworkPlayground, TwinId},
+    round_manager::{UnverifiedEvent, VerifiedEvent},
+    test_utils::{consensus_runtime, timed_block_on, MockStorage, MockTransactionManager},
+    twins::twins_node::SMRNode,
+    util::time_service::ClockTimeService,
+};
+use aptos_config::{config::QuorumStoreConfig, network_id::NetworkId};
+use aptos_crypto::{ed25519::Ed25519PrivateKey, HashValue, Uniform};
+use aptos_infallible::{duration_since_epoch, Mutex};
+use aptos_secure_storage::Storage;
+use aptos_types::{
+    ledger_info::LedgerInfo,
+    transaction::Transaction,
+    validator_signer::ValidatorSigner,
+    validator_verifier::{random_validator_verifier, ValidatorVerifier},
+    waypoint::Waypoint,
+};
+use channel::{self, aptos_channel, message_queues::QueueStyle};
+use consensus_types::{
+    block::block_test_utils::random_payload,
+    common::Author,
+    experimental::batch::{Batch, ProofOfStore, SignedBatchInfo},
+};
+use futures::StreamExt;
+use network::{
+    peer_manager::{conn_notifs_channel, ConnectionRequestSender, PeerManagerRequestSender},
+    protocols::{network::NewNetworkSender, wire::handshake::v1::ProtocolIdSet},
+    transport::ConnectionMetadata,
+    ProtocolId,
+};
+use safety_rules::{PersistentSafetyStorage, SafetyRulesManager};
+use std::{
+    collections::{BTreeMap, HashSet},
+    iter::FromIterator,
+    sync::Arc,
+};
+use tokio::runtime::Handle;
+
+/// A validator running a quorum store, whose messages are processed one by one by the test.
+struct QuorumStoreNode {
+    author: Author,
+    quorum_store: QuorumStore,
+    batch_store: Arc<BatchStore>,
+    proof_queue: Arc<ProofQueue>,
+    receivers: NetworkReceivers,
+    verifier: ValidatorVerifier,
+    _safety_rules_manager: SafetyRulesManager,
+}
+
+impl QuorumStoreNode {
+    fn create_nodes(
+        playground: &mut NetworkPlayground,
+        executor: Handle,
+        num_nodes: usize,
+        config: QuorumStoreConfig,
+    ) -> Vec<Self> {
+        let (signers, validators) = random_validator_verifier(num_nodes, None, false);
+        let validator_set = (&validators).into();
+        let waypoint =
+            Waypoint::new_epoch_boundary(&LedgerInfo::mock_genesis(Some(validator_set))).unwrap();
+
+        let peer_metadata_storage = playground.peer_protocols();
+        for signer in signers.iter() {
+            let mut conn_meta = ConnectionMetadata::mock(signer.author());
+            conn_meta.application_protocols = ProtocolIdSet::from_iter([
+                ProtocolId::ConsensusDirectSendJson,
+                ProtocolId::ConsensusDirectSendBcs,
+                ProtocolId::ConsensusRpcBcs,
+            ]);
+            peer_metadata_storage.insert_connection(NetworkId::Validator, conn_meta);
+        }
+        signers
+            .iter()
+            .enumerate()
+            .map(|(id, signer)| {
+                Self::new(
+                    playground,
+                    executor.clone(),
+                    signer,
+                    &validators,
+                    waypoint,
+                    id,
+                    config.clone(),
+                )
+            })
+            .collect()
+    }
+
+    fn new(
+        playground: &mut NetworkPlayground,
+        executor: Handle,
+        signer: &ValidatorSigner,
+        validators: &ValidatorVerifier,
+        waypoint: Waypoint,
+        id: usize,
+        config: QuorumStoreConfig,
+    ) -> Self {
+        let author = signer.author();
+        let (network_reqs_tx, network_reqs_rx) = aptos_channel::new(QueueStyle::FIFO, 8, None);
+        let (connection_reqs_tx, _) = aptos_channel::new(QueueStyle::FIFO, 8, None);
+        let (consensus_tx, consensus_rx) = aptos_channel::new(QueueStyle::FIFO, 8, None);
+        let (_conn_mgr_reqs_tx, conn_mgr_reqs_rx) = channel::new_test(8);
+        let (_, conn_status_rx) = conn_notifs_channel::new();
+        let mut network_sender = ConsensusNetworkSender::new(
+            PeerManagerRequestSender::new(network_reqs_tx),
+            ConnectionRequestSender::new(connection_reqs_tx),
+        );
+        network_sender.initialize(playground.peer_protocols());
+        let network_events = ConsensusNetworkEvents::new(consensus_rx, conn_status_rx);
+        playground.add_node(
+            TwinId { id, author },
+            consensus_tx,
+            network_reqs_rx,
+            conn_mgr_reqs_rx,
+        );
+
+        let (self_sender, self_receiver) = channel::new_test(1000);
+        let network = NetworkSender::new(author, network_sender, self_sender, validators.clone());
+        let (task, receivers) = NetworkTask::new(network_events, self_receiver);
+        executor.spawn(task.start());
+
+        let (_, storage) = MockStorage::start_for_testing(validators.into());
+        let safety_storage = PersistentSafetyStorage::initialize(
+            Storage::from(aptos_secure_storage::InMemoryStorage::new()),
+            author,
+            signer.private_key().clone(),
+            Ed25519PrivateKey::generate_for_testing(),
+            waypoint,
+            true,
+        );
+        let safety_rules_manager = SafetyRulesManager::new_local(safety_storage, false, false);
+        let mut safety_rules = MetricsSafetyRules::new(safety_rules_manager.client(), storage);
+        safety_rules.perform_initialize().unwrap();
+
+        let time_service = Arc::new(ClockTimeService::new(executor));
+        let proof_queue = Arc::new(ProofQueue::new());
+        let batch_store = Arc::new(BatchStore::new(
+            1,
+            author,
+            proof_queue.clone(),
+            network.clone(),
+            time_service.clone(),
+            &config,
+        ));
+        let quorum_store = QuorumStore::new(
+            1,
+            author,
+            config,
+            batch_store.clone(),
+            proof_queue.clone(),
+            Arc::new(MockTransactionManager::new(None)),
+            Arc::new(Mutex::new(safety_rules)),
+            network,
+            validators.clone(),
+            time_service,
+        );
+        Self {
+            author,
+            quorum_store,
+            batch_store,
+            proof_queue,
+            receivers,
+            verifier: validators.clone(),
+            _safety_rules_manager: safety_rules_manager,
+        }
+    }
+
+    /// Verifies and processes the next quorum store message received by the node.
+    async fn process_next_message(&mut self) {
+        let (peer, msg) = self.receivers.quorum_store_messages.next().await.unwrap();
+        let event = UnverifiedEvent::from(msg).verify(&self.verifier).unwrap();
+        match event {
+            VerifiedEvent::Batch(batch) => {
+                self.quorum_store.process_batch(peer, *batch).await.unwrap()
+            }
+            VerifiedEvent::SignedBatchInfo(signed_info) => self
+                .quorum_store
+                .process_signed_batch_info(peer, *signed_info)
+                .await
+                .unwrap(),
+            VerifiedEvent::ProofOfStore(proof) => {
+                self.quorum_store.process_proof_of_store(peer, *proof)
+            }
+            event => panic!("Unexpected event {:?}", event),
+        }
+    }
+
+    /// Replies to the next batch retrieval request received by the node.
+    async fn serve_batch_retrieval(&mut self) {
+        let (_, request) = self.receivers.batch_retrieval.next().await.unwrap();
+        self.quorum_store.process_batch_retrieval(request).unwrap();
+    }
+
+    fn pull_all_proofs(&self) -> Vec<ProofOfStore> {
+        self.proof_queue
+            .pull_proofs(u64::MAX, u64::MAX, &HashSet::new(), 0)
+    }
+}
+
+#[test]
+fn test_batch_dissemination_and_retrieval() {
+    let mut runtime = consensus_runtime();
+    let mut playground = NetworkPlayground::new(runtime.handle().clone());
+    let mut nodes = QuorumStoreNode::create_nodes(
+        &mut playground,
+        runtime.handle().clone(),
+        4,
+        QuorumStoreConfig::default(),
+    );
+
+    timed_block_on(&mut runtime, async {
+        // node 0 broadcasts a batch, delivered to itself without the network
+        nodes[0].quorum_store.generate_batch().await.unwrap();
+        playground
+            .wait_for_messages(3, NetworkPlayground::take_all)
+            .await;
+
+        // node 3 never stores it, the others sign it
+        for node in nodes.iter_mut().take(3) {
+            node.process_next_message().await;
+        }
+        let _ = nodes[3].receivers.quorum_store_messages.next().await;
+        playground
+            .wait_for_messages(2, NetworkPlayground::take_all)
+            .await;
+
+        // the 3 signatures form a quorum of the 4 validators, the proof is broadcast
+        for _ in 0..3 {
+            nodes[0].process_next_message().await;
+        }
+        playground
+            .wait_for_messages(3, NetworkPlayground::take_all)
+            .await;
+        for node in nodes.iter_mut() {
+            node.process_next_message().await;
+        }
+        let proofs = nodes[0].pull_all_proofs();
+        assert_eq!(proofs.len(), 1);
+        let proof = proofs[0].clone();
+        for node in &nodes {
+            assert_eq!(node.pull_all_proofs(), proofs);
+        }
+        assert!(proof.verify(&nodes[3].verifier).is_ok());
+        assert!(!proof.signers().any(|signer| *signer == nodes[3].author));
+
+        // node 3 fetches the missing batch from the first signer of the proof
+        let batch = nodes[0].batch_store.get(&proof.digest()).unwrap();
+        let signer = *proof.signers().next().unwrap();
+        let batch_store = nodes[3].batch_store.clone();
+        let server = nodes.iter_mut().find(|node| node.author == signer).unwrap();
+        let (payload, _) = futures::join!(
+            batch_store.get_batch(&proof),
+            server.serve_batch_retrieval()
+        );
+        assert_eq!(&payload.unwrap(), batch.payload());
+        assert!(batch_store.contains(&proof.digest()));
+
+        // committing the batch removes the proof from the queue
+        nodes[1].batch_store.notify_commit(&proofs);
+        assert!(nodes[1].proof_queue.is_empty());
+        nodes[1].proof_queue.push(proof);
+        assert!(nodes[1].proof_queue.is_empty());
+    });
+}
+
+#[test]
+fn test_batch_store_limits() {
+    let runtime = consensus_runtime();
+    let mut playground = NetworkPlayground::new(runtime.handle().clone());
+    let config = QuorumStoreConfig {
+        max_batches_per_author: 2,
+        ..QuorumStoreConfig::default()
+    };
+    let nodes = QuorumStoreNode::create_nodes(&mut playground, runtime.handle().clone(), 2, config);
+    let (author, other) = (nodes[0].author, nodes[1].author);
+    let batch_store = &nodes[0].batch_store;
+    let expiration = duration_since_epoch().as_micros() as u64 + 60_000_000;
+    let batch = |epoch, author, batch_id| {
+        Batch::new(epoch, author, batch_id, expiration, random_payload(10))
+    };
+
+    assert!(batch_store.insert(batch(1, other, 0)).is_ok());
+    assert!(batch_store.insert(batch(1, other, 1)).is_ok());
+    // the author already has the max number of unexpired batches stored
+    assert!(batch_store.insert(batch(1, other, 2)).is_err());
+    // the limit is per author
+    assert!(batch_store.insert(batch(1, author, 0)).is_ok());
+    // the batches of other epochs are rejected
+    assert!(batch_store.insert(batch(2, author, 1)).is_err());
+}
+
+#[test]
+fn test_batch_verify() {
+    let (signers, verifier) = random_validator_verifier(1, None, false);
+    let batch = Batch::new(1, signers[0].author(), 0, 100, random_payload(10));
+    assert!(batch.verify(&verifier).is_ok());
+    assert_eq!(batch.info().num_txns(), 10);
+
+    // the batch of a non validator
+    let unknown_author = ValidatorSigner::random(None).author();
+    let unknown_batch = Batch::new(1, unknown_author, 0, 100, random_payload(10));
+    assert!(unknown_batch.verify(&verifier).is_err());
+
+    // the transactions don't match the digest
+    let other = Batch::new(1, signers[0].author(), 0, 100, random_payload(10));
+    let mut bytes = bcs::to_bytes(batch.info()).unwrap();
+    bytes.extend(bcs::to_bytes(other.payload()).unwrap());
+    let tampered = bcs::from_bytes::<Batch>(&bytes).unwrap();
+    assert!(tampered.verify(&verifier).is_err());
+}
+
+#[test]
+fn test_proof_of_store_verify() {
+    let (signers, verifier) = random_validator_verifier(4, None, false);
+    let batch = Batch::new(1, signers[0].author(), 0, 100, random_payload(10));
+    let signed_infos: Vec<_> = signers
+        .iter()
+        .map(|signer| {
+            SignedBatchInfo::new(
+                batch.info().clone(),
+                signer.author(),
+                signer.sign(batch.info()),
+            )
+        })
+        .collect();
+    for signed_info in &signed_infos {
+        assert!(signed_info.verify(&verifier).is_ok());
+    }
+
+    let signatures: BTreeMap<_, _> = signed_infos
+        .iter()
+        .map(|signed_info| (signed_info.signer(), signed_info.signature().clone()))
+        .collect();
+    let proof = ProofOfStore::new(batch.info().clone(), signatures.clone());
+    assert!(proof.verify(&verifier).is_ok());
+
+    // 2 of the 4 validators aren't a quorum
+    let proof = ProofOfStore::new(
+        batch.info().clone(),
+        signatures.into_iter().take(2).collect(),
+    );
+    assert!(proof.verify(&verifier).is_err());
+}
+
+#[test]
+fn test_proof_queue() {
+    let author = ValidatorSigner::random(None).author();
+    let proofs: Vec<_> = (0..4)
+        .map(|batch_id| {
+            let batch = Batch::new(
+                1,
+                author,
+                batch_id,
+                100 * (batch_id + 1),
+                random_payload(10),
+            );
+            ProofOfStore::new(batch.info().clone(), BTreeMap::new())
+        })
+        .collect();
+    let queue = ProofQueue::new();
+    for proof in &proofs {
+        queue.push(proof.clone());
+    }
+    // duplicates are ignored
+    queue.push(proofs[0].clone());
+    assert_eq!(queue.len(), 4);
+
+    // the number of transactions is bounded
+    assert_eq!(
+        queue.pull_proofs(25, u64::MAX, &HashSet::new(), 0),
+        proofs[..2].to_vec()
+    );
+    // the number of bytes is bounded
+    let bytes = proofs[0].info().num_bytes();
+    assert_eq!(
+        queue.pull_proofs(u64::MAX, bytes, &HashSet::new(), 0),
+        proofs[..1].to_vec()
+    );
+    // the proofs of the pending blocks are excluded
+    let excluded: HashSet<HashValue> = HashSet::from_iter([proofs[0].digest()]);
+    assert_eq!(
+        queue.pull_proofs(25, u64::MAX, &excluded, 0),
+        proofs[1..3].to_vec()
+    );
+    // the expired proofs are dropped
+    assert_eq!(
+        queue.pull_proofs(u64::MAX, u64::MAX, &HashSet::new(), 200),
+        proofs[2..].to_vec()
+    );
+    assert_eq!(queue.len(), 2);
+
+    // the committed proofs are dropped and not added back
+    queue.mark_committed(&proofs[2..3]);
+    queue.push(proofs[2].clone());
+    assert_eq!(
+        queue.pull_proofs(u64::MAX, u64::MAX, &HashSet::new(), 200),
+        proofs[3..].to_vec()
+    );
+}
+
+#[test]
+fn test_quorum_store_proposals_commit() {
+    let mut runtime = consensus_runtime();
+    let mut playground = NetworkPlayground::new(runtime.handle().clone());
+    let mut nodes = SMRNode::start_num_nodes_with_quorum_store(4, &mut playground);
+    runtime.spawn(playground.start());
+
+    timed_block_on(&mut runtime, async {
+        // the blocks are executed with the transactions of the batches of their proofs
+        let node = &mut nodes[0];
+        let user_txns = loop {
+            let txns = node.committed_txns_receiver.next().await.unwrap();
+            let user_txns: Vec<_> = txns
+                .into_iter()
+                .filter_map(|txn| match txn {
+                    Transaction::UserTransaction(txn) => Some(txn),
+                    _ => None,
+                })
+                .collect();
+            if !user_txns.is_empty() {
+                break user_txns;
+            }
+        };
+        let unique: HashSet<_> = user_txns
+            .iter()
+            .map(|txn| (txn.sender(), txn.sequence_number()))
+            .collect();
+        assert_eq!(unique.len(), user_txns.len());
+
+        // the proposals only carry the proofs of store
+        let blocks = node.storage.shared_storage.block.lock();
+        let proposals: Vec<_> = blocks
+            .values()
+            .filter(|block| !block.is_genesis_block() && !block.is_nil_block())
+            .collect();
+        assert!(!proposals.is_empty());
+        for block in proposals {
+            assert!(block.proofs().is_some());
+            assert!(block.payload().is_none());
+        }
+    });
+}
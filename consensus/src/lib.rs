@@ -24,9 +24,12 @@ mod metrics_safety_rules;
 mod network;
 #[cfg(test)]
 mod network_tests;
+mod pending_fast_commit_votes;
 mod pending_votes;
 mod persistent_liveness_storage;
 mod round_manager;
+#[cfg(test)]
+mod simulator;
 mod state_computer;
 mod state_replication;
 #[cfg(any(test, feature = "fuzzing"))]
@@ -38,6 +41,8 @@ mod util;
 
 /// AptosBFT implementation
 pub mod consensus_provider;
+/// Journal of the consensus rounds, for the offline inspection of the round timelines.
+pub mod event_journal;
 /// AptosNet interface.
 pub mod network_interface;
 
@@ -2,13 +2,21 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    counters::{COMMITTED_PROPOSALS_IN_WINDOW, COMMITTED_VOTES_IN_WINDOW},
+    counters::{
+        COMMITTED_PROPOSALS_IN_WINDOW, COMMITTED_VOTES_IN_WINDOW, FAILED_PROPOSALS_IN_WINDOW,
+    },
     liveness::proposer_election::{next, ProposerElection},
 };
 use aptos_crypto::HashValue;
 use aptos_infallible::Mutex;
 use aptos_logger::prelude::*;
-use aptos_types::block_metadata::{new_block_event_key, NewBlockEvent};
+use aptos_types::{
+    account_config::aptos_root_address,
+    account_state::AccountState,
+    block_metadata::{new_block_event_key, FailedProposersEvent, NewBlockEvent},
+    event::EventKey,
+    state_store::state_key::StateKey,
+};
 use consensus_types::{
     block::Block,
     common::{Author, Round},
@@ -16,6 +24,7 @@ use consensus_types::{
 use std::{
     cmp::Ordering,
     collections::{HashMap, HashSet},
+    convert::TryFrom,
     sync::Arc,
 };
 use storage_interface::{DbReader, Order};
@@ -24,6 +33,7 @@ use storage_interface::{DbReader, Order};
 pub trait MetadataBackend: Send + Sync {
     /// Return a contiguous BlockMetadata window in which last one is at target_round or
     /// latest committed, return all previous one if not enough.
+    /// The window is ordered from the most recent block.
     fn get_block_metadata(&self, target_round: Round) -> Vec<NewBlockEvent>;
 }
 
@@ -31,6 +41,7 @@ pub struct AptosDBBackend {
     window_size: usize,
     aptos_db: Arc<dyn DbReader>,
     window: Mutex<Vec<(u64, NewBlockEvent)>>,
+    failed_proposers_event_key: Mutex<Option<EventKey>>,
 }
 
 impl AptosDBBackend {
@@ -39,9 +50,27 @@ impl AptosDBBackend {
             window_size,
             aptos_db,
             window: Mutex::new(vec![]),
+            failed_proposers_event_key: Mutex::new(None),
         }
     }
 
+    /// The key of the failed proposers events, read from their handle under the aptos root
+    /// account. None until the handle is published, after genesis on the older chains.
+    fn failed_proposers_event_key(&self) -> anyhow::Result<Option<EventKey>> {
+        let mut key = self.failed_proposers_event_key.lock();
+        if key.is_none() {
+            if let Some(state_value) = self
+                .aptos_db
+                .get_latest_state_value(StateKey::AccountAddressKey(aptos_root_address()))?
+            {
+                *key = AccountState::try_from(&state_value)?
+                    .get_failed_proposers_resource()?
+                    .map(|resource| *resource.failed_proposer_events().key());
+            }
+        }
+        Ok(*key)
+    }
+
     fn refresh_window(&self, target_round: Round) -> anyhow::Result<()> {
         // assumes target round is not too far from latest commit
         let buffer = 10;
@@ -51,9 +80,25 @@ impl AptosDBBackend {
             Order::Descending,
             self.window_size as u64 + buffer,
         )?;
+        // emitted along with the new block events of the blocks recording them, if any
+        let mut failed_proposers = HashMap::new();
+        if let Some(failed_proposers_event_key) = self.failed_proposers_event_key()? {
+            for (v, e) in self.aptos_db.get_events(
+                &failed_proposers_event_key,
+                u64::max_value(),
+                Order::Descending,
+                self.window_size as u64 + buffer,
+            )? {
+                let e = bcs::from_bytes::<FailedProposersEvent>(e.event_data())?;
+                failed_proposers.insert(v, e.failed_proposers().clone());
+            }
+        }
         let mut result = vec![];
         for (v, e) in events {
-            let e = bcs::from_bytes::<NewBlockEvent>(e.event_data())?;
+            let mut e = bcs::from_bytes::<NewBlockEvent>(e.event_data())?;
+            if let Some(failed_proposers) = failed_proposers.remove(&v) {
+                e = e.with_failed_proposers(failed_proposers);
+            }
             if e.round() <= target_round && result.len() < self.window_size {
                 result.push((v, e));
             }
@@ -153,6 +198,100 @@ impl ReputationHeuristic for ActiveInactiveHeuristic {
     }
 }
 
+/// Weighs the candidates by their proposals and votes in the history, ordered from the most
+/// recent block:
+/// - a candidate that failed more than failure_threshold_percent of its rounds as proposer within
+/// the proposer window is assigned failed_weight,
+/// - otherwise a candidate that proposed or voted within the voter window is assigned
+/// active_weight,
+/// - otherwise inactive_weight.
+pub struct ProposerAndVoterHeuristic {
+    author: Author,
+    active_weight: u64,
+    inactive_weight: u64,
+    failed_weight: u64,
+    failure_threshold_percent: u32,
+    voter_window_size: usize,
+    proposer_window_size: usize,
+}
+
+impl ProposerAndVoterHeuristic {
+    pub fn new(
+        author: Author,
+        active_weight: u64,
+        inactive_weight: u64,
+        failed_weight: u64,
+        failure_threshold_percent: u32,
+        voter_window_size: usize,
+        proposer_window_size: usize,
+    ) -> Self {
+        Self {
+            author,
+            active_weight,
+            inactive_weight,
+            failed_weight,
+            failure_threshold_percent,
+            voter_window_size,
+            proposer_window_size,
+        }
+    }
+
+    // Number of the successful and failed rounds of each proposer within the proposer window
+    fn count_proposals(&self, history: &[NewBlockEvent]) -> HashMap<Author, (u64, u64)> {
+        let mut proposals: HashMap<Author, (u64, u64)> = HashMap::new();
+        for meta in history.iter().take(self.proposer_window_size) {
+            proposals.entry(meta.proposer()).or_default().0 += 1;
+            for failed_proposer in meta.failed_proposers() {
+                proposals.entry(*failed_proposer).or_default().1 += 1;
+            }
+        }
+        proposals
+    }
+
+    fn count_votes(&self, history: &[NewBlockEvent]) -> HashMap<Author, u64> {
+        let mut votes: HashMap<Author, u64> = HashMap::new();
+        for meta in history.iter().take(self.voter_window_size) {
+            for vote in meta.votes() {
+                *votes.entry(vote).or_default() += 1;
+            }
+        }
+        votes
+    }
+}
+
+impl ReputationHeuristic for ProposerAndVoterHeuristic {
+    fn get_weights(&self, candidates: &[Author], history: &[NewBlockEvent]) -> Vec<u64> {
+        let proposals = self.count_proposals(history);
+        let votes = self.count_votes(history);
+
+        let (committed_proposals, failed_proposals) =
+            proposals.get(&self.author).cloned().unwrap_or_default();
+        COMMITTED_PROPOSALS_IN_WINDOW.set(committed_proposals as i64);
+        FAILED_PROPOSALS_IN_WINDOW.set(failed_proposals as i64);
+        COMMITTED_VOTES_IN_WINDOW.set(votes.get(&self.author).cloned().unwrap_or_default() as i64);
+
+        let proposed_in_voter_window: HashSet<_> = history
+            .iter()
+            .take(self.voter_window_size)
+            .map(|meta| meta.proposer())
+            .collect();
+
+        candidates
+            .iter()
+            .map(|author| {
+                let (committed, failed) = proposals.get(author).cloned().unwrap_or_default();
+                if failed * 100 > (committed + failed) * self.failure_threshold_percent as u64 {
+                    self.failed_weight
+                } else if votes.contains_key(author) || proposed_in_voter_window.contains(author) {
+                    self.active_weight
+                } else {
+                    self.inactive_weight
+                }
+            })
+            .collect()
+    }
+}
+
 /// Committed history based proposer election implementation that could help bias towards
 /// successful leaders to help improve performance.
 pub struct LeaderReputation {
@@ -3,7 +3,8 @@
 
 use crate::liveness::{
     leader_reputation::{
-        ActiveInactiveHeuristic, LeaderReputation, MetadataBackend, ReputationHeuristic,
+        ActiveInactiveHeuristic, LeaderReputation, MetadataBackend, ProposerAndVoterHeuristic,
+        ReputationHeuristic,
     },
     proposer_election::{next, ProposerElection},
 };
@@ -36,7 +37,16 @@ impl MetadataBackend for MockHistory {
 }
 
 fn create_block(proposer: Author, voters: Vec<&ValidatorSigner>) -> NewBlockEvent {
+    create_block_with_failures(proposer, voters, vec![])
+}
+
+fn create_block_with_failures(
+    proposer: Author,
+    voters: Vec<&ValidatorSigner>,
+    failed_proposers: Vec<Author>,
+) -> NewBlockEvent {
     NewBlockEvent::new(0, proposer, voters.iter().map(|v| v.author()).collect(), 0)
+        .with_failed_proposers(failed_proposers)
 }
 
 #[test]
@@ -76,6 +86,77 @@ fn test_simple_heuristic() {
     }
 }
 
+#[test]
+fn test_proposer_and_voter_heuristic() {
+    let active_weight = 100;
+    let inactive_weight = 10;
+    let failed_weight = 1;
+    let mut proposers = vec![];
+    let mut signers = vec![];
+    for i in 0..8 {
+        let signer = ValidatorSigner::random([i; 32]);
+        proposers.push(signer.author());
+        signers.push(signer);
+    }
+    // failure threshold of 20%, voter window of 2 blocks and proposer window of 4 blocks
+    let heuristic = ProposerAndVoterHeuristic::new(
+        proposers[0],
+        active_weight,
+        inactive_weight,
+        failed_weight,
+        20,
+        2,
+        4,
+    );
+    // 1. Empty history
+    let weights = heuristic.get_weights(&proposers, &[]);
+    assert_eq!(weights, vec![inactive_weight; proposers.len()]);
+
+    // 2. History from the most recent block:
+    // [proposer 0, voters 1, 2, failed 3],
+    // [proposer 4, voters 5],
+    // [proposer 0, voters 6, failed 4],
+    // [proposer 1, voters 7, failed 3, 5], outside of the voter window
+    // [proposer 7, failed 6], outside of both windows
+    let history = vec![
+        create_block_with_failures(
+            proposers[0],
+            vec![&signers[1], &signers[2]],
+            vec![proposers[3]],
+        ),
+        create_block(proposers[4], vec![&signers[5]]),
+        create_block_with_failures(proposers[0], vec![&signers[6]], vec![proposers[4]]),
+        create_block_with_failures(
+            proposers[1],
+            vec![&signers[7]],
+            vec![proposers[3], proposers[5]],
+        ),
+        create_block_with_failures(proposers[7], vec![], vec![proposers[6]]),
+    ];
+    let weights = heuristic.get_weights(&proposers, &history);
+    assert_eq!(
+        weights,
+        vec![
+            // proposed twice
+            active_weight,
+            // voted, and proposed once without failure
+            active_weight,
+            // voted
+            active_weight,
+            // failed twice
+            failed_weight,
+            // proposed once and failed once
+            failed_weight,
+            // failed once without any successful proposal, even if it voted
+            failed_weight,
+            // only voted outside of the voter window
+            inactive_weight,
+            // only voted and proposed outside of the voter window
+            inactive_weight,
+        ]
+    );
+}
+
 #[test]
 fn test_api() {
     let active_weight = 9;
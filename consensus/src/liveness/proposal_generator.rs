@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    block_storage::BlockReader, state_replication::TxnManager, util::time_service::TimeService,
+    block_storage::BlockReader, experimental::proof_queue::ProofQueue,
+    liveness::proposer_election::ProposerElection, state_replication::TxnManager,
+    util::time_service::TimeService,
 };
 use anyhow::{bail, ensure, format_err, Context};
 use consensus_types::{
@@ -14,7 +16,7 @@ use consensus_types::{
 
 use aptos_infallible::Mutex;
 use futures::future::BoxFuture;
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 #[cfg(test)]
 #[path = "proposal_generator_test.rs"]
@@ -29,6 +31,9 @@ mod proposal_generator_test;
 ///
 /// TxnManager should be aware of the pending transactions in the branch that it is extending,
 /// such that it will filter them out to avoid transaction duplication.
+///
+/// When the quorum store is enabled the transactions are disseminated ahead of time and the
+/// proposed block only carries the proofs of store of their batches, pulled from the ProofQueue.
 pub struct ProposalGenerator {
     // The account address of this validator
     author: Author,
@@ -41,6 +46,13 @@ pub struct ProposalGenerator {
     time_service: Arc<dyn TimeService>,
     // Max number of transactions to be added to a proposed block.
     max_block_size: u64,
+    // Max total size in bytes of the transactions added to a proposed block.
+    max_block_bytes: u64,
+    // Max number of failed authors of the previous rounds to be added to a proposed block, set by
+    // the on-chain consensus config. The blocks don't record the failed authors if 0.
+    max_failed_authors_to_store: usize,
+    // Proofs of the batches disseminated by the quorum store, if it is enabled.
+    proof_queue: Option<Arc<ProofQueue>>,
     // Last round that a proposal was generated
     last_round_generated: Mutex<Round>,
 }
@@ -52,6 +64,9 @@ impl ProposalGenerator {
         txn_manager: Arc<dyn TxnManager>,
         time_service: Arc<dyn TimeService>,
         max_block_size: u64,
+        max_block_bytes: u64,
+        max_failed_authors_to_store: usize,
+        proof_queue: Option<Arc<ProofQueue>>,
     ) -> Self {
         Self {
             author,
@@ -59,6 +74,9 @@ impl ProposalGenerator {
             txn_manager,
             time_service,
             max_block_size,
+            max_block_bytes,
+            max_failed_authors_to_store,
+            proof_queue,
             last_round_generated: Mutex::new(0),
         }
     }
@@ -68,9 +86,20 @@ impl ProposalGenerator {
     }
 
     /// Creates a NIL block proposal extending the highest certified block from the block store.
-    pub fn generate_nil_block(&self, round: Round) -> anyhow::Result<Block> {
+    pub fn generate_nil_block(
+        &self,
+        round: Round,
+        proposer_election: &dyn ProposerElection,
+    ) -> anyhow::Result<Block> {
         let hqc = self.ensure_highest_quorum_cert(round)?;
-        Ok(Block::new_nil(round, hqc.as_ref().clone()))
+        let quorum_cert = hqc.as_ref().clone();
+        let failed_authors = self.compute_failed_authors(
+            round, // to include current round, as that is part of failed proposal
+            quorum_cert.certified_block().round(),
+            true,
+            proposer_election,
+        );
+        Ok(Block::new_nil(round, quorum_cert, failed_authors))
     }
 
     /// The function generates a new proposal block: the returned future is fulfilled when the
@@ -86,6 +115,7 @@ impl ProposalGenerator {
     pub async fn generate_proposal(
         &mut self,
         round: Round,
+        proposer_election: &(dyn ProposerElection + Send + Sync),
         wait_callback: BoxFuture<'static, ()>,
     ) -> anyhow::Result<BlockData> {
         {
@@ -98,11 +128,21 @@ impl ProposalGenerator {
         }
 
         let hqc = self.ensure_highest_quorum_cert(round)?;
+        let failed_authors = self.compute_failed_authors(
+            round,
+            hqc.certified_block().round(),
+            false,
+            proposer_election,
+        );
 
-        let (payload, timestamp) = if hqc.certified_block().has_reconfiguration() {
+        let (payload, proofs, timestamp) = if hqc.certified_block().has_reconfiguration() {
             // Reconfiguration rule - we propose empty blocks with parents' timestamp
             // after reconfiguration until it's committed
-            (vec![], hqc.certified_block().timestamp_usecs())
+            (
+                vec![],
+                self.proof_queue.as_ref().map(|_| vec![]),
+                hqc.certified_block().timestamp_usecs(),
+            )
         } else {
             // One needs to hold the blocks with the references to the payloads while get_block is
             // being executed: pending blocks vector keeps all the pending ancestors of the extended branch.
@@ -114,47 +154,106 @@ impl ProposalGenerator {
             // deliver the commit proof to others without delay.
             pending_blocks.push(self.block_store.commit_root());
 
-            // Exclude all the pending transactions: these are all the ancestors of
-            // parent (including) up to the root (including).
-            let exclude_payload: Vec<&Vec<_>> = pending_blocks
-                .iter()
-                .flat_map(|block| block.payload())
-                .collect();
-
-            let pending_ordering = self
-                .block_store
-                .path_from_ordered_root(hqc.certified_block().id())
-                .ok_or_else(|| format_err!("HQC {} already pruned", hqc.certified_block().id()))?
-                .iter()
-                .any(|block| !block.payload().map_or(true, |txns| txns.is_empty()));
-
-            // All proposed blocks in a branch are guaranteed to have increasing timestamps
-            // since their predecessor block will not be added to the BlockStore until
-            // the local time exceeds it.
-            let timestamp = self.time_service.get_current_timestamp();
-
-            let payload = self
-                .txn_manager
-                .pull_txns(
+            if let Some(proof_queue) = &self.proof_queue {
+                // Exclude the batches of the pending blocks, their transactions are already
+                // being ordered.
+                let exclude_digests: HashSet<_> = pending_blocks
+                    .iter()
+                    .flat_map(|block| block.proofs())
+                    .flatten()
+                    .map(|proof| proof.digest())
+                    .collect();
+                let timestamp = self.time_service.get_current_timestamp().as_micros() as u64;
+                let proofs = proof_queue.pull_proofs(
                     self.max_block_size,
-                    exclude_payload,
-                    wait_callback,
-                    pending_ordering,
-                )
-                .await
-                .context("Fail to retrieve txn")?;
-
-            (payload, timestamp.as_micros() as u64)
+                    self.max_block_bytes,
+                    &exclude_digests,
+                    timestamp,
+                );
+                (vec![], Some(proofs), timestamp)
+            } else {
+                // Exclude all the pending transactions: these are all the ancestors of
+                // parent (including) up to the root (including).
+                let exclude_payload: Vec<&Vec<_>> = pending_blocks
+                    .iter()
+                    .flat_map(|block| block.payload())
+                    .collect();
+
+                let pending_ordering = self
+                    .block_store
+                    .path_from_ordered_root(hqc.certified_block().id())
+                    .ok_or_else(|| {
+                        format_err!("HQC {} already pruned", hqc.certified_block().id())
+                    })?
+                    .iter()
+                    .any(|block| !block.payload().map_or(true, |txns| txns.is_empty()));
+
+                // All proposed blocks in a branch are guaranteed to have increasing timestamps
+                // since their predecessor block will not be added to the BlockStore until
+                // the local time exceeds it.
+                let timestamp = self.time_service.get_current_timestamp();
+
+                let payload = self
+                    .txn_manager
+                    .pull_txns(
+                        self.max_block_size,
+                        self.max_block_bytes,
+                        exclude_payload,
+                        wait_callback,
+                        pending_ordering,
+                    )
+                    .await
+                    .context("Fail to retrieve txn")?;
+
+                (payload, None, timestamp.as_micros() as u64)
+            }
         };
 
         // create block proposal
-        Ok(BlockData::new_proposal(
-            payload,
-            self.author,
-            round,
-            timestamp,
-            hqc.as_ref().clone(),
-        ))
+        Ok(match proofs {
+            Some(proofs) => BlockData::new_quorum_store_proposal(
+                proofs,
+                self.author,
+                round,
+                timestamp,
+                hqc.as_ref().clone(),
+                failed_authors,
+            ),
+            None => BlockData::new_proposal(
+                payload,
+                self.author,
+                round,
+                timestamp,
+                hqc.as_ref().clone(),
+                failed_authors,
+            ),
+        })
+    }
+
+    /// The proposers of the rounds since the previous round (excluded) which didn't produce a
+    /// block, i.e. of all the rounds up to the given one (included if include_cur_round), limited
+    /// to the most recent max_failed_authors_to_store rounds. None if the blocks don't record
+    /// them.
+    pub fn compute_failed_authors(
+        &self,
+        round: Round,
+        previous_round: Round,
+        include_cur_round: bool,
+        proposer_election: &dyn ProposerElection,
+    ) -> Option<Vec<(Round, Author)>> {
+        if self.max_failed_authors_to_store == 0 {
+            return None;
+        }
+        let end_round = round + u64::from(include_cur_round);
+        let start_round = std::cmp::max(
+            previous_round + 1,
+            end_round.saturating_sub(self.max_failed_authors_to_store as u64),
+        );
+        Some(
+            (start_round..end_round)
+                .map(|i| (i, proposer_election.get_valid_proposer(i)))
+                .collect(),
+        )
     }
 
     fn ensure_highest_quorum_cert(&self, round: Round) -> anyhow::Result<Arc<QuorumCert>> {
@@ -3,14 +3,24 @@
 
 use crate::{
     block_storage::BlockReader,
-    liveness::proposal_generator::ProposalGenerator,
+    experimental::proof_queue::ProofQueue,
+    liveness::{
+        proposal_generator::ProposalGenerator, rotating_proposer_election::RotatingProposer,
+    },
     test_utils::{build_empty_tree, MockTransactionManager, TreeInserter},
     util::mock_time_service::SimulatedTimeService,
 };
 use aptos_types::validator_signer::ValidatorSigner;
-use consensus_types::block::{block_test_utils::certificate_for_genesis, Block};
+use consensus_types::{
+    block::{
+        block_test_utils::{certificate_for_genesis, random_payload},
+        Block,
+    },
+    block_data::BlockType,
+    experimental::batch::{Batch, ProofOfStore},
+};
 use futures::{future::BoxFuture, FutureExt};
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 fn empty_callback() -> BoxFuture<'static, ()> {
     async move {}.boxed()
@@ -26,12 +36,16 @@ async fn test_proposal_generation_empty_tree() {
         Arc::new(MockTransactionManager::new(None)),
         Arc::new(SimulatedTimeService::new()),
         1,
+        u64::MAX,
+        10,
+        None,
     );
+    let proposer_election = RotatingProposer::new(vec![signer.author()], 1);
     let genesis = block_store.ordered_root();
 
     // Generate proposals for an empty tree.
     let proposal_data = proposal_generator
-        .generate_proposal(1, empty_callback())
+        .generate_proposal(1, &proposer_election, empty_callback())
         .await
         .unwrap();
     let proposal = Block::new_proposal_from_block_data(proposal_data, &signer);
@@ -41,12 +55,46 @@ async fn test_proposal_generation_empty_tree() {
 
     // Duplicate proposals on the same round are not allowed
     let proposal_err = proposal_generator
-        .generate_proposal(1, empty_callback())
+        .generate_proposal(1, &proposer_election, empty_callback())
         .await
         .err();
     assert!(proposal_err.is_some());
 }
 
+#[tokio::test]
+async fn test_proposal_generation_without_failed_authors() {
+    let signer = ValidatorSigner::random(None);
+    let block_store = build_empty_tree();
+    // the on-chain consensus config doesn't enable recording the failed authors
+    let mut proposal_generator = ProposalGenerator::new(
+        signer.author(),
+        block_store,
+        Arc::new(MockTransactionManager::new(None)),
+        Arc::new(SimulatedTimeService::new()),
+        1,
+        u64::MAX,
+        0,
+        None,
+    );
+    let proposer_election = RotatingProposer::new(vec![signer.author()], 1);
+
+    let proposal_data = proposal_generator
+        .generate_proposal(5, &proposer_election, empty_callback())
+        .await
+        .unwrap();
+    assert!(matches!(
+        proposal_data.block_type(),
+        BlockType::Proposal { .. }
+    ));
+    let nil_block = proposal_generator
+        .generate_nil_block(6, &proposer_election)
+        .unwrap();
+    assert!(matches!(
+        nil_block.block_data().block_type(),
+        BlockType::NilBlock
+    ));
+}
+
 #[tokio::test]
 async fn test_proposal_generation_parent() {
     let mut inserter = TreeInserter::default();
@@ -57,7 +105,11 @@ async fn test_proposal_generation_parent() {
         Arc::new(MockTransactionManager::new(None)),
         Arc::new(SimulatedTimeService::new()),
         1,
+        u64::MAX,
+        5,
+        None,
     );
+    let proposer_election = RotatingProposer::new(vec![inserter.signer().author()], 1);
     let genesis = block_store.ordered_root();
     let a1 = inserter
         .insert_block_with_qc(certificate_for_genesis(), &genesis, 1)
@@ -68,34 +120,45 @@ async fn test_proposal_generation_parent() {
 
     // With no certifications the parent is genesis
     // generate proposals for an empty tree.
+    let genesis_child_res = proposal_generator
+        .generate_proposal(10, &proposer_election, empty_callback())
+        .await
+        .unwrap();
+    assert_eq!(genesis_child_res.parent_id(), genesis.id());
+    // The rounds since genesis failed, only the most recent ones are stored
+    let author = inserter.signer().author();
     assert_eq!(
-        proposal_generator
-            .generate_proposal(10, empty_callback())
-            .await
-            .unwrap()
-            .parent_id(),
-        genesis.id()
+        genesis_child_res.failed_authors(),
+        Some(&(5..10).map(|round| (round, author)).collect())
     );
 
     // Once a1 is certified, it should be the one to choose from
     inserter.insert_qc_for_block(a1.as_ref(), None);
     let a1_child_res = proposal_generator
-        .generate_proposal(11, empty_callback())
+        .generate_proposal(11, &proposer_election, empty_callback())
         .await
         .unwrap();
     assert_eq!(a1_child_res.parent_id(), a1.id());
     assert_eq!(a1_child_res.round(), 11);
     assert_eq!(a1_child_res.quorum_cert().certified_block().id(), a1.id());
+    assert_eq!(
+        a1_child_res.failed_authors(),
+        Some(&(6..11).map(|round| (round, author)).collect())
+    );
 
     // Once b1 is certified, it should be the one to choose from
     inserter.insert_qc_for_block(b1.as_ref(), None);
     let b1_child_res = proposal_generator
-        .generate_proposal(12, empty_callback())
+        .generate_proposal(12, &proposer_election, empty_callback())
         .await
         .unwrap();
     assert_eq!(b1_child_res.parent_id(), b1.id());
     assert_eq!(b1_child_res.round(), 12);
     assert_eq!(b1_child_res.quorum_cert().certified_block().id(), b1.id());
+    assert_eq!(
+        b1_child_res.failed_authors(),
+        Some(&(7..12).map(|round| (round, author)).collect())
+    );
 }
 
 #[tokio::test]
@@ -108,7 +171,11 @@ async fn test_old_proposal_generation() {
         Arc::new(MockTransactionManager::new(None)),
         Arc::new(SimulatedTimeService::new()),
         1,
+        u64::MAX,
+        10,
+        None,
     );
+    let proposer_election = RotatingProposer::new(vec![inserter.signer().author()], 1);
     let genesis = block_store.ordered_root();
     let a1 = inserter
         .insert_block_with_qc(certificate_for_genesis(), &genesis, 1)
@@ -116,8 +183,45 @@ async fn test_old_proposal_generation() {
     inserter.insert_qc_for_block(a1.as_ref(), None);
 
     let proposal_err = proposal_generator
-        .generate_proposal(1, empty_callback())
+        .generate_proposal(1, &proposer_election, empty_callback())
         .await
         .err();
     assert!(proposal_err.is_some());
 }
+
+#[tokio::test]
+async fn test_proposal_generation_quorum_store() {
+    let signer = ValidatorSigner::random(None);
+    let block_store = build_empty_tree();
+    let proof_queue = Arc::new(ProofQueue::new());
+    let mut proposal_generator = ProposalGenerator::new(
+        signer.author(),
+        block_store.clone(),
+        Arc::new(MockTransactionManager::new(None)),
+        Arc::new(SimulatedTimeService::new()),
+        15,
+        u64::MAX,
+        10,
+        Some(proof_queue.clone()),
+    );
+    let proposer_election = RotatingProposer::new(vec![signer.author()], 1);
+    let proofs: Vec<_> = (0..2)
+        .map(|batch_id| {
+            let batch = Batch::new(1, signer.author(), batch_id, u64::MAX, random_payload(10));
+            ProofOfStore::new(batch.info().clone(), BTreeMap::new())
+        })
+        .collect();
+    for proof in &proofs {
+        proof_queue.push(proof.clone());
+    }
+
+    // The proposal carries the proofs of store within the block size instead of transactions
+    let proposal_data = proposal_generator
+        .generate_proposal(1, &proposer_election, empty_callback())
+        .await
+        .unwrap();
+    let proposal = Block::new_proposal_from_block_data(proposal_data, &signer);
+    assert!(proposal.payload().is_none());
+    assert_eq!(proposal.proofs(), Some(&proofs[..1].to_vec()));
+    assert_eq!(proposal.payload_size_bytes(), proofs[0].info().num_bytes());
+}
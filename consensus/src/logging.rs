@@ -18,17 +18,24 @@ pub struct LogSchema {
 pub enum LogEvent {
     CommitViaBlock,
     CommitViaSync,
+    FastCommit,
+    FastCommitVote,
     HelpPeerSync,
     NewEpoch,
     NewRound,
     Propose,
+    ReceiveBatch,
+    ReceiveBatchRetrieval,
     ReceiveBlockRetrieval,
     ReceiveEpochChangeProof,
     ReceiveEpochRetrieval,
     ReceiveMessageFromDifferentEpoch,
+    ReceiveProofOfStore,
     ReceiveProposal,
+    ReceiveSignedBatchInfo,
     ReceiveSyncInfo,
     ReceiveVote,
+    RetrieveBatch,
     RetrieveBlock,
     StateSync,
     SyncToPeer,
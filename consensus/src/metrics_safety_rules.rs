@@ -11,6 +11,9 @@ use aptos_types::{
 };
 use consensus_types::{
     block_data::BlockData,
+    experimental::batch::BatchInfo,
+    fast_commit::FastCommitVote,
+    quorum_cert::QuorumCert,
     timeout::Timeout,
     timeout_2chain::{TwoChainTimeout, TwoChainTimeoutCertificate},
     vote::Vote,
@@ -143,6 +146,13 @@ impl TSafetyRules for MetricsSafetyRules {
             )
         })
     }
+    fn sign_batch_info(&mut self, batch_info: &BatchInfo) -> Result<Ed25519Signature, Error> {
+        self.retry(|inner| monitor!("safety_rules", inner.sign_batch_info(batch_info)))
+    }
+
+    fn sign_fast_commit_vote(&mut self, quorum_cert: &QuorumCert) -> Result<FastCommitVote, Error> {
+        self.retry(|inner| monitor!("safety_rules", inner.sign_fast_commit_vote(quorum_cert)))
+    }
 }
 
 #[cfg(test)]
@@ -156,6 +166,9 @@ mod tests {
     use claim::{assert_matches, assert_ok};
     use consensus_types::{
         block_data::BlockData,
+        experimental::batch::BatchInfo,
+        fast_commit::FastCommitVote,
+        quorum_cert::QuorumCert,
         timeout::Timeout,
         timeout_2chain::{TwoChainTimeout, TwoChainTimeoutCertificate},
         vote::Vote,
@@ -241,6 +254,13 @@ mod tests {
         ) -> Result<Ed25519Signature, Error> {
             unimplemented!()
         }
+        fn sign_batch_info(&mut self, _: &BatchInfo) -> Result<Ed25519Signature, Error> {
+            unimplemented!()
+        }
+
+        fn sign_fast_commit_vote(&mut self, _: &QuorumCert) -> Result<FastCommitVote, Error> {
+            unimplemented!()
+        }
     }
 
     #[test]
@@ -18,7 +18,10 @@ use channel::{self, aptos_channel, message_queues::QueueStyle};
 use consensus_types::{
     block_retrieval::{BlockRetrievalRequest, BlockRetrievalResponse, MAX_BLOCKS_PER_REQUEST},
     common::Author,
-    experimental::commit_decision::CommitDecision,
+    experimental::{
+        batch::{Batch, BatchRequest},
+        commit_decision::CommitDecision,
+    },
     sync_info::SyncInfo,
     vote_msg::VoteMsg,
 };
@@ -44,6 +47,15 @@ pub struct IncomingBlockRetrievalRequest {
     pub response_sender: oneshot::Sender<Result<Bytes, RpcError>>,
 }
 
+/// The batch retrieval request is used internally for implementing RPC: the callback is executed
+/// for carrying the response
+#[derive(Debug)]
+pub struct IncomingBatchRetrievalRequest {
+    pub req: BatchRequest,
+    pub protocol: ProtocolId,
+    pub response_sender: oneshot::Sender<Result<Bytes, RpcError>>,
+}
+
 /// Just a convenience struct to keep all the network proxy receiving queues in one place.
 /// Will be returned by the NetworkTask upon startup.
 pub struct NetworkReceivers {
@@ -53,6 +65,13 @@ pub struct NetworkReceivers {
         (AccountAddress, ConsensusMsg),
     >,
     pub block_retrieval: aptos_channel::Receiver<AccountAddress, IncomingBlockRetrievalRequest>,
+    /// Provide a FIFO buffer per author for the quorum store messages, so that they are processed
+    /// in order instead of only the latest one.  Once the buffer of an author is full, its newest
+    /// messages are dropped.
+    pub quorum_store_messages:
+        aptos_channel::Receiver<AccountAddress, (AccountAddress, ConsensusMsg)>,
+    pub batch_retrieval:
+        aptos_channel::Receiver<AccountAddress, (AccountAddress, IncomingBatchRetrievalRequest)>,
 }
 
 /// Implements the actual networking support for all consensus messaging.
@@ -114,6 +133,34 @@ impl NetworkSender {
         Ok(response)
     }
 
+    /// Tries to retrieve the batch with the given digest from the given peer, which signed its
+    /// proof of store.
+    pub async fn request_batch(
+        &self,
+        request: BatchRequest,
+        from: Author,
+        timeout: Duration,
+    ) -> anyhow::Result<Batch> {
+        ensure!(from != self.author, "Retrieve batch from self");
+        let msg = ConsensusMsg::BatchRequest(Box::new(request.clone()));
+        let response_msg = monitor!(
+            "batch_retrieval",
+            self.network_sender.send_rpc(from, msg, timeout).await?
+        );
+        let batch = match response_msg {
+            ConsensusMsg::BatchResponse(batch) => *batch,
+            _ => return Err(anyhow!("Invalid response to request")),
+        };
+        ensure!(
+            batch.digest() == request.digest(),
+            "Retrieved batch {} doesn't match the requested digest {}",
+            batch,
+            request.digest()
+        );
+        batch.verify(&self.validators)?;
+        Ok(batch)
+    }
+
     /// Tries to send the given msg to all the participants.
     ///
     /// The future is fulfilled as soon as the message put into the mpsc channel to network
@@ -196,12 +243,19 @@ impl NetworkSender {
     }
 }
 
+/// Number of quorum store messages buffered per author, batches are broadcast periodically so
+/// a few of them may be in flight at once
+const QUORUM_STORE_CHANNEL_SIZE: usize = 50;
+
 pub struct NetworkTask {
     consensus_messages_tx: aptos_channel::Sender<
         (AccountAddress, Discriminant<ConsensusMsg>),
         (AccountAddress, ConsensusMsg),
     >,
     block_retrieval_tx: aptos_channel::Sender<AccountAddress, IncomingBlockRetrievalRequest>,
+    quorum_store_messages_tx: aptos_channel::Sender<AccountAddress, (AccountAddress, ConsensusMsg)>,
+    batch_retrieval_tx:
+        aptos_channel::Sender<AccountAddress, (AccountAddress, IncomingBatchRetrievalRequest)>,
     all_events: Box<dyn Stream<Item = Event<ConsensusMsg>> + Send + Unpin>,
 }
 
@@ -218,16 +272,30 @@ impl NetworkTask {
             1,
             Some(&counters::BLOCK_RETRIEVAL_CHANNEL_MSGS),
         );
+        let (quorum_store_messages_tx, quorum_store_messages) = aptos_channel::new(
+            QueueStyle::FIFO,
+            QUORUM_STORE_CHANNEL_SIZE,
+            Some(&counters::QUORUM_STORE_CHANNEL_MSGS),
+        );
+        let (batch_retrieval_tx, batch_retrieval) = aptos_channel::new(
+            QueueStyle::LIFO,
+            QUORUM_STORE_CHANNEL_SIZE,
+            Some(&counters::BATCH_RETRIEVAL_CHANNEL_MSGS),
+        );
         let all_events = Box::new(select(network_events, self_receiver));
         (
             NetworkTask {
                 consensus_messages_tx,
                 block_retrieval_tx,
+                quorum_store_messages_tx,
+                batch_retrieval_tx,
                 all_events,
             },
             NetworkReceivers {
                 consensus_messages,
                 block_retrieval,
+                quorum_store_messages,
+                batch_retrieval,
             },
         )
     }
@@ -236,10 +304,17 @@ impl NetworkTask {
         while let Some(message) = self.all_events.next().await {
             match message {
                 Event::Message(peer_id, msg) => {
-                    if let Err(e) = self
-                        .consensus_messages_tx
-                        .push((peer_id, discriminant(&msg)), (peer_id, msg))
-                    {
+                    let result = match msg {
+                        ConsensusMsg::BatchMsg(_)
+                        | ConsensusMsg::SignedBatchInfoMsg(_)
+                        | ConsensusMsg::ProofOfStoreMsg(_) => {
+                            self.quorum_store_messages_tx.push(peer_id, (peer_id, msg))
+                        }
+                        _ => self
+                            .consensus_messages_tx
+                            .push((peer_id, discriminant(&msg)), (peer_id, msg)),
+                    };
+                    if let Err(e) = result {
                         warn!(
                             remote_peer = peer_id,
                             error = ?e, "Error pushing consensus msg",
@@ -271,6 +346,25 @@ impl NetworkTask {
                             warn!(error = ?e, "aptos channel closed");
                         }
                     }
+                    ConsensusMsg::BatchRequest(request) => {
+                        debug!(
+                            remote_peer = peer_id,
+                            event = LogEvent::ReceiveBatchRetrieval,
+                            "{}",
+                            request
+                        );
+                        let req_with_callback = IncomingBatchRetrievalRequest {
+                            req: *request,
+                            protocol,
+                            response_sender: callback,
+                        };
+                        if let Err(e) = self
+                            .batch_retrieval_tx
+                            .push(peer_id, (peer_id, req_with_callback))
+                        {
+                            warn!(error = ?e, "aptos channel closed");
+                        }
+                    }
                     _ => {
                         warn!(remote_peer = peer_id, "Unexpected msg: {:?}", msg);
                         continue;
@@ -13,7 +13,12 @@ use channel::{aptos_channel, message_queues::QueueStyle};
 use consensus_types::{
     block_retrieval::{BlockRetrievalRequest, BlockRetrievalResponse},
     epoch_retrieval::EpochRetrievalRequest,
-    experimental::{commit_decision::CommitDecision, commit_vote::CommitVote},
+    experimental::{
+        batch::{Batch, BatchRequest, ProofOfStore, SignedBatchInfo},
+        commit_decision::CommitDecision,
+        commit_vote::CommitVote,
+    },
+    fast_commit::FastCommitVote,
     proposal_msg::ProposalMsg,
     sync_info::SyncInfo,
     vote_msg::VoteMsg,
@@ -62,6 +67,21 @@ pub enum ConsensusMsg {
     /// than 2f + 1 signatures on the commit proposal. This part is not on the critical path, but
     /// it can save slow machines to quickly confirm the execution result.
     CommitDecisionMsg(Box<CommitDecision>),
+    /// Batch of transactions broadcast by its author for the quorum store, the validators
+    /// storing it reply with a SignedBatchInfoMsg.
+    BatchMsg(Box<Batch>),
+    /// Signature of a validator on the info of a batch it stores, sent to the batch author.
+    SignedBatchInfoMsg(Box<SignedBatchInfo>),
+    /// Proof that a quorum of validators stores a batch, broadcast by the batch author so that
+    /// the batch can be included in proposals.
+    ProofOfStoreMsg(Box<ProofOfStore>),
+    /// RPC to get a batch that is missing locally from one of the validators storing it.
+    BatchRequest(Box<BatchRequest>),
+    /// Carries the requested batch.
+    BatchResponse(Box<Batch>),
+    /// Vote to commit a certified block without waiting for the QC of its child, broadcast to
+    /// all the validators when the fast commit is enabled.
+    FastCommitVoteMsg(Box<FastCommitVote>),
 }
 
 /// The interface from Network to Consensus layer.
@@ -87,9 +107,16 @@ pub struct ConsensusNetworkSender {
 }
 
 /// Supported protocols in preferred order (from highest priority to lowest).
-pub const RPC: &[ProtocolId] = &[ProtocolId::ConsensusRpcJson, ProtocolId::ConsensusRpcBcs];
+/// The compressed protocols are preferred as the proposals and the retrieved blocks carry the
+/// transactions, the peers that don't support them fall back to the uncompressed ones.
+pub const RPC: &[ProtocolId] = &[
+    ProtocolId::ConsensusRpcCompressed,
+    ProtocolId::ConsensusRpcJson,
+    ProtocolId::ConsensusRpcBcs,
+];
 /// Supported protocols in preferred order (from highest priority to lowest).
 pub const DIRECT_SEND: &[ProtocolId] = &[
+    ProtocolId::ConsensusDirectSendCompressed,
     ProtocolId::ConsensusDirectSendJson,
     ProtocolId::ConsensusDirectSendBcs,
 ];
@@ -8,7 +8,9 @@ use crate::{
 };
 use aptos_config::network_id::NetworkId;
 use aptos_infallible::{Mutex, RwLock};
+use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::{block_info::BlockInfo, PeerId};
+use bytes::Bytes;
 use channel::{self, aptos_channel, message_queues::QueueStyle};
 use consensus_types::{
     block::{block_test_utils::certificate_for_genesis, Block},
@@ -19,7 +21,10 @@ use consensus_types::{
     vote_data::VoteData,
     vote_msg::VoteMsg,
 };
-use futures::{channel::mpsc, SinkExt, StreamExt};
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt, StreamExt,
+};
 use network::{
     application::storage::PeerMetadataStorage,
     peer_manager::{
@@ -28,7 +33,7 @@ use network::{
     },
     protocols::{
         network::{NewNetworkEvents, NewNetworkSender, SerializedRequest},
-        rpc::InboundRpcRequest,
+        rpc::{error::RpcError, InboundRpcRequest},
         wire::handshake::v1::ProtocolIdSet,
     },
     ProtocolId,
@@ -51,6 +56,85 @@ pub struct TwinId {
     pub author: Author,
 }
 
+/// Models the links between the nodes, e.g. to simulate a wide area network.
+pub trait LinkModel: Send + Sync {
+    /// The delay of a message of the given size from `src` to `dst`, or `None` if the message
+    /// is dropped.
+    fn delay(&self, src: &TwinId, dst: &TwinId, num_bytes: usize) -> Option<Duration>;
+}
+
+/// A `LinkModel` delaying the messages on a time service.
+#[derive(Clone)]
+struct SimulatedLinks {
+    model: Arc<dyn LinkModel>,
+    time_service: TimeService,
+    executor: Handle,
+}
+
+impl SimulatedLinks {
+    /// Pushes the notification to the destination once the delay of the link elapsed, unless
+    /// the link drops it.
+    fn deliver(
+        &self,
+        src_twin_id: &TwinId,
+        dst_twin_id: &TwinId,
+        num_bytes: usize,
+        node_consensus_tx: aptos_channel::Sender<(PeerId, ProtocolId), PeerManagerNotification>,
+        protocol_id: ProtocolId,
+        msg_notif: PeerManagerNotification,
+    ) {
+        if let Some(delay) = self.model.delay(src_twin_id, dst_twin_id, num_bytes) {
+            let sleep = self.time_service.sleep(delay);
+            let key = (src_twin_id.author, protocol_id);
+            self.executor.spawn(async move {
+                sleep.await;
+                let _ = node_consensus_tx.push(key, msg_notif);
+            });
+        }
+    }
+
+    /// Delivers the rpc request like a message, and its response back on the reverse link once
+    /// the destination sends it, so that the responses (e.g., the blocks of a block retrieval)
+    /// are delayed or dropped too.
+    fn deliver_rpc(
+        &self,
+        src_twin_id: &TwinId,
+        dst_twin_id: &TwinId,
+        node_consensus_tx: aptos_channel::Sender<(PeerId, ProtocolId), PeerManagerNotification>,
+        protocol_id: ProtocolId,
+        data: Bytes,
+        res_tx: oneshot::Sender<Result<Bytes, RpcError>>,
+    ) {
+        let (dst_res_tx, dst_res_rx) = oneshot::channel();
+        let num_bytes = data.len();
+        let inbound_req = InboundRpcRequest {
+            protocol_id,
+            data,
+            res_tx: dst_res_tx,
+        };
+        let links = self.clone();
+        let (src, dst) = (*src_twin_id, *dst_twin_id);
+        self.executor.spawn(async move {
+            // the requester times out if the response is dropped
+            if let Ok(response) = dst_res_rx.await {
+                let num_bytes = response.as_ref().map_or(0, |bytes| bytes.len());
+                if let Some(delay) = links.model.delay(&dst, &src, num_bytes) {
+                    links.time_service.sleep(delay).await;
+                    let _ = res_tx.send(response);
+                }
+            }
+        });
+        self.deliver(
+            src_twin_id,
+            dst_twin_id,
+            num_bytes,
+            node_consensus_tx,
+            ProtocolId::ConsensusRpcBcs,
+            PeerManagerNotification::RecvRpc(src_twin_id.author, inbound_req),
+        );
+    }
+}
+
 /// `NetworkPlayground` mocks the network implementation and provides convenience
 /// methods for testing. Test clients can use `wait_for_messages` or
 /// `deliver_messages` to inspect the direct-send messages sent between peers.
@@ -59,6 +143,9 @@ pub struct TwinId {
 /// Currently, RPC messages are delivered immediately and are not controlled by
 /// `wait_for_messages` or `deliver_messages` for delivery. They are also not
 /// currently dropped according to the `NetworkPlayground`'s drop config.
+///
+/// With a `LinkModel`, the messages (including the RPC requests and their responses) are
+/// instead delayed or dropped by their link as they're sent, without being inspected.
 pub struct NetworkPlayground {
     /// Maps each Author to a Sender of their inbound network notifications.
     /// These events will usually be handled by the event loop spawned in
@@ -85,6 +172,8 @@ pub struct NetworkPlayground {
     author_to_twin_ids: Arc<RwLock<AuthorToTwinIds>>,
     /// Information about connections
     peer_metadata_storage: Arc<PeerMetadataStorage>,
+    /// Delays and drops the messages instead of the drop configs
+    links: Option<SimulatedLinks>,
 }
 
 impl NetworkPlayground {
@@ -100,9 +189,26 @@ impl NetworkPlayground {
             executor,
             author_to_twin_ids: Arc::new(RwLock::new(AuthorToTwinIds::default())),
             peer_metadata_storage: PeerMetadataStorage::new(&[NetworkId::Validator]),
+            links: None,
         }
     }
 
+    /// Creates a NetworkPlayground delivering the messages after the delays of the link model
+    /// on the given time service.
+    pub fn with_link_model(
+        executor: Handle,
+        model: Arc<dyn LinkModel>,
+        time_service: TimeService,
+    ) -> Self {
+        let mut playground = Self::new(executor.clone());
+        playground.links = Some(SimulatedLinks {
+            model,
+            time_service,
+            executor,
+        });
+        playground
+    }
+
     /// HashMap of supported protocols to initialize ConsensusNetworkSender.
     pub fn peer_protocols(&self) -> Arc<PeerMetadataStorage> {
         self.peer_metadata_storage.clone()
@@ -130,6 +236,7 @@ impl NetworkPlayground {
             >,
         >,
         author_to_twin_ids: Arc<RwLock<AuthorToTwinIds>>,
+        links: Option<SimulatedLinks>,
     ) {
         while let Some(net_req) = network_reqs_rx.next().await {
             match net_req {
@@ -158,17 +265,27 @@ impl NetworkPlayground {
                     let node_consensus_tx =
                         node_consensus_txs.lock().get(dst_twin_id).unwrap().clone();
 
+                    if let Some(links) = &links {
+                        links.deliver_rpc(
+                            &src_twin_id,
+                            dst_twin_id,
+                            node_consensus_tx,
+                            outbound_req.protocol_id,
+                            outbound_req.data,
+                            outbound_req.res_tx,
+                        );
+                        continue;
+                    }
+
                     let inbound_req = InboundRpcRequest {
                         protocol_id: outbound_req.protocol_id,
                         data: outbound_req.data,
                         res_tx: outbound_req.res_tx,
                     };
-
+                    let msg_notif =
+                        PeerManagerNotification::RecvRpc(src_twin_id.author, inbound_req);
                     node_consensus_tx
-                        .push(
-                            (src_twin_id.author, ProtocolId::ConsensusRpcBcs),
-                            PeerManagerNotification::RecvRpc(src_twin_id.author, inbound_req),
-                        )
+                        .push((src_twin_id.author, ProtocolId::ConsensusRpcBcs), msg_notif)
                         .unwrap();
                 }
                 // Other PeerManagerRequest get buffered for `deliver_messages` to
@@ -205,6 +322,7 @@ impl NetworkPlayground {
             self.outbound_msgs_tx.clone(),
             self.node_consensus_txs.clone(),
             self.author_to_twin_ids.clone(),
+            self.links.clone(),
         );
         let fut2 = conn_mgr_reqs_rx.map(Ok).forward(::futures::sink::drain());
         self.executor.spawn(futures::future::join(fut1, fut2));
@@ -302,6 +420,8 @@ impl NetworkPlayground {
         match msg {
             ConsensusMsg::ProposalMsg(proposal_msg) => Some(proposal_msg.proposal().round()),
             ConsensusMsg::VoteMsg(vote_msg) => Some(vote_msg.vote().vote_data().proposed().round()),
+            // the fast commit votes of a block are sent once the votes of its round form its QC
+            ConsensusMsg::FastCommitVoteMsg(vote) => Some(vote.round()),
             _ => None,
         }
     }
@@ -392,6 +512,23 @@ impl NetworkPlayground {
             for dst_twin_id in dst_twin_ids.iter() {
                 let msg_notif =
                     PeerManagerNotification::RecvMessage(src_twin_id.author, msg.clone());
+                if let Some(links) = &self.links {
+                    let node_consensus_tx = self
+                        .node_consensus_txs
+                        .lock()
+                        .get(dst_twin_id)
+                        .unwrap()
+                        .clone();
+                    links.deliver(
+                        &src_twin_id,
+                        dst_twin_id,
+                        msg.mdata.len(),
+                        node_consensus_tx,
+                        ProtocolId::ConsensusDirectSendBcs,
+                        msg_notif,
+                    );
+                    continue;
+                }
                 let consensus_msg = msg.to_message().unwrap();
 
                 // Deliver and copy message it if it's not dropped
@@ -571,6 +708,7 @@ mod tests {
                 &peer_metadata_storage,
                 peer,
                 &[
+                    ProtocolId::ConsensusDirectSendCompressed,
                     ProtocolId::ConsensusDirectSendJson,
                     ProtocolId::ConsensusDirectSendBcs,
                     ProtocolId::ConsensusRpcBcs,
@@ -0,0 +1,145 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! PendingFastCommitVotes store the fast commit votes received for the uncommitted rounds and
+//! create a FastCommitCertificate once a quorum of them is observed for a block.
+
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_logger::prelude::*;
+use aptos_types::{ledger_info::LedgerInfoWithSignatures, validator_verifier::ValidatorVerifier};
+use consensus_types::{
+    common::{Author, Round},
+    fast_commit::{FastCommitCertificate, FastCommitVote},
+    vote_data::VoteData,
+};
+use std::collections::{BTreeMap, HashMap};
+
+pub struct PendingFastCommitVotes {
+    /// Maps LedgerInfo digest to the vote data and the signatures collected so far.
+    li_digest_to_votes:
+        HashMap<HashValue /* LedgerInfo digest */, (VoteData, LedgerInfoWithSignatures)>,
+    /// The LedgerInfo digest voted by each author in each round, to discard multiple votes.
+    author_to_votes: HashMap<(Author, Round), HashValue>,
+}
+
+impl PendingFastCommitVotes {
+    pub fn new() -> Self {
+        Self {
+            li_digest_to_votes: HashMap::new(),
+            author_to_votes: HashMap::new(),
+        }
+    }
+
+    /// Insert a verified vote and return the FastCommitCertificate if the vote completes a quorum.
+    pub fn insert_vote(
+        &mut self,
+        vote: &FastCommitVote,
+        validator_verifier: &ValidatorVerifier,
+    ) -> Option<FastCommitCertificate> {
+        let li_digest = vote.ledger_info().hash();
+        if let Some(previous_digest) = self.author_to_votes.get(&(vote.author(), vote.round())) {
+            if *previous_digest != li_digest {
+                error!(
+                    SecurityEvent::ConsensusEquivocatingVote,
+                    remote_peer = vote.author(),
+                    vote = vote,
+                );
+            }
+            return None;
+        }
+        self.author_to_votes
+            .insert((vote.author(), vote.round()), li_digest);
+
+        let (_, li_with_sig) = self.li_digest_to_votes.entry(li_digest).or_insert_with(|| {
+            (
+                vote.vote_data().clone(),
+                LedgerInfoWithSignatures::new(vote.ledger_info().clone(), BTreeMap::new()),
+            )
+        });
+        li_with_sig.add_signature(vote.author(), vote.signature().clone());
+        validator_verifier
+            .check_voting_power(li_with_sig.signatures().keys())
+            .ok()?;
+
+        // the certificate is created once, later votes for the block are discarded when the
+        // block is committed
+        let (vote_data, li_with_sig) = self.li_digest_to_votes.remove(&li_digest)?;
+        Some(FastCommitCertificate::new(vote_data, li_with_sig))
+    }
+
+    /// Drop the votes of the rounds up to the given (committed) round.
+    pub fn prune(&mut self, round: Round) {
+        self.li_digest_to_votes
+            .retain(|_, (vote_data, _)| vote_data.proposed().round() > round);
+        self.author_to_votes.retain(|(_, r), _| *r > round);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PendingFastCommitVotes;
+    use aptos_crypto::{hash::CryptoHash, HashValue};
+    use aptos_types::{
+        block_info::BlockInfo,
+        ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+        validator_signer::ValidatorSigner,
+        validator_verifier::random_validator_verifier,
+    };
+    use consensus_types::{
+        fast_commit::FastCommitVote, quorum_cert::QuorumCert, vote_data::VoteData,
+    };
+    use std::collections::BTreeMap;
+
+    fn fast_commit_vote(signer: &ValidatorSigner, round: u64, id: HashValue) -> FastCommitVote {
+        let block_info = BlockInfo::new(1, round, id, HashValue::zero(), 0, 0, None);
+        let vote_data = VoteData::new(block_info, BlockInfo::random(0));
+        let qc = QuorumCert::new(
+            vote_data.clone(),
+            LedgerInfoWithSignatures::new(
+                LedgerInfo::new(BlockInfo::empty(), vote_data.hash()),
+                BTreeMap::new(),
+            ),
+        );
+        let ledger_info = FastCommitVote::ledger_info_for(&qc);
+        let signature = signer.sign(&ledger_info);
+        FastCommitVote::new_with_signature(vote_data, signer.author(), ledger_info, signature)
+    }
+
+    #[test]
+    fn test_fast_commit_votes() {
+        let (signers, validators) = random_validator_verifier(4, None, false);
+        let mut pending_votes = PendingFastCommitVotes::new();
+        let (b1, b2) = (HashValue::random(), HashValue::random());
+
+        assert!(pending_votes
+            .insert_vote(&fast_commit_vote(&signers[0], 1, b1), &validators)
+            .is_none());
+        // duplicate and equivocating votes are discarded
+        assert!(pending_votes
+            .insert_vote(&fast_commit_vote(&signers[0], 1, b1), &validators)
+            .is_none());
+        assert!(pending_votes
+            .insert_vote(&fast_commit_vote(&signers[0], 1, b2), &validators)
+            .is_none());
+        assert!(pending_votes
+            .insert_vote(&fast_commit_vote(&signers[1], 1, b2), &validators)
+            .is_none());
+        assert!(pending_votes
+            .insert_vote(&fast_commit_vote(&signers[2], 1, b1), &validators)
+            .is_none());
+        let certificate = pending_votes
+            .insert_vote(&fast_commit_vote(&signers[3], 1, b1), &validators)
+            .unwrap();
+        certificate.verify(&validators).unwrap();
+        assert_eq!(certificate.commit_info().id(), b1);
+
+        // the votes of the committed rounds are dropped
+        pending_votes.insert_vote(&fast_commit_vote(&signers[0], 2, b2), &validators);
+        pending_votes.prune(1);
+        assert_eq!(pending_votes.li_digest_to_votes.len(), 1);
+        assert_eq!(pending_votes.author_to_votes.len(), 1);
+        pending_votes.prune(2);
+        assert!(pending_votes.li_digest_to_votes.is_empty());
+        assert!(pending_votes.author_to_votes.is_empty());
+    }
+}
@@ -1,12 +1,18 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{consensusdb::ConsensusDB, epoch_manager::LivenessStorageData, error::DbError};
+use crate::{
+    consensusdb::ConsensusDB,
+    epoch_manager::LivenessStorageData,
+    error::DbError,
+    event_journal::{EventJournal, RoundEventKind},
+};
 use anyhow::{format_err, Context, Result};
 use aptos_config::config::NodeConfig;
 use aptos_crypto::{ed25519::Ed25519Signature, HashValue};
 use aptos_logger::prelude::*;
 use aptos_types::{
+    block_info::Round,
     epoch_change::EpochChangeProof,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
     transaction::Version,
@@ -59,6 +65,9 @@ pub trait PersistentLivenessStorage: Send + Sync {
 
     /// Returns a handle of the aptosdb.
     fn aptos_db(&self) -> Arc<dyn DbReader>;
+
+    /// Append an event of the round to the consensus journal, if it is enabled.
+    fn record_round_event(&self, _epoch: u64, _round: Round, _kind: RoundEventKind) {}
 }
 
 #[derive(Clone)]
@@ -308,12 +317,26 @@ impl RecoveryData {
 pub struct StorageWriteProxy {
     db: Arc<ConsensusDB>,
     aptos_db: Arc<dyn DbReader>,
+    journal: Option<EventJournal>,
 }
 
 impl StorageWriteProxy {
     pub fn new(config: &NodeConfig, aptos_db: Arc<dyn DbReader>) -> Self {
         let db = Arc::new(ConsensusDB::new(config.storage.dir()));
-        StorageWriteProxy { db, aptos_db }
+        let journal_config = &config.consensus.event_journal;
+        let journal = if journal_config.enabled {
+            Some(
+                EventJournal::new(db.clone(), journal_config)
+                    .expect("unable to open the consensus journal"),
+            )
+        } else {
+            None
+        };
+        StorageWriteProxy {
+            db,
+            aptos_db,
+            journal,
+        }
     }
 }
 
@@ -494,4 +517,10 @@ impl PersistentLivenessStorage for StorageWriteProxy {
     fn aptos_db(&self) -> Arc<dyn DbReader> {
         self.aptos_db.clone()
     }
+
+    fn record_round_event(&self, epoch: u64, round: Round, kind: RoundEventKind) {
+        if let Some(journal) = &self.journal {
+            journal.record(epoch, round, kind);
+        }
+    }
 }
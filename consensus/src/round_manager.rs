@@ -8,6 +8,7 @@ use crate::{
     },
     counters,
     error::{error_kind, VerifyError},
+    event_journal::RoundEventKind,
     liveness::{
         proposal_generator::ProposalGenerator,
         proposer_election::ProposerElection,
@@ -15,8 +16,9 @@ use crate::{
     },
     logging::{LogEvent, LogSchema},
     metrics_safety_rules::MetricsSafetyRules,
-    network::{IncomingBlockRetrievalRequest, NetworkSender},
+    network::{IncomingBatchRetrievalRequest, IncomingBlockRetrievalRequest, NetworkSender},
     network_interface::ConsensusMsg,
+    pending_fast_commit_votes::PendingFastCommitVotes,
     pending_votes::VoteReceptionResult,
     persistent_liveness_storage::PersistentLivenessStorage,
 };
@@ -33,7 +35,12 @@ use consensus_types::{
     block::Block,
     block_retrieval::{BlockRetrievalResponse, BlockRetrievalStatus},
     common::{Author, Round},
-    experimental::{commit_decision::CommitDecision, commit_vote::CommitVote},
+    experimental::{
+        batch::{Batch, ProofOfStore, SignedBatchInfo},
+        commit_decision::CommitDecision,
+        commit_vote::CommitVote,
+    },
+    fast_commit::{FastCommitCertificate, FastCommitVote},
     proposal_msg::ProposalMsg,
     quorum_cert::QuorumCert,
     sync_info::SyncInfo,
@@ -58,6 +65,10 @@ pub enum UnverifiedEvent {
     SyncInfo(Box<SyncInfo>),
     CommitVote(Box<CommitVote>),
     CommitDecision(Box<CommitDecision>),
+    Batch(Box<Batch>),
+    SignedBatchInfo(Box<SignedBatchInfo>),
+    ProofOfStore(Box<ProofOfStore>),
+    FastCommitVote(Box<FastCommitVote>),
 }
 
 impl UnverifiedEvent {
@@ -81,6 +92,22 @@ impl UnverifiedEvent {
                 cd.verify(validator)?;
                 VerifiedEvent::CommitDecision(cd)
             }
+            UnverifiedEvent::Batch(b) => {
+                b.verify(validator)?;
+                VerifiedEvent::Batch(b)
+            }
+            UnverifiedEvent::SignedBatchInfo(sbi) => {
+                sbi.verify(validator)?;
+                VerifiedEvent::SignedBatchInfo(sbi)
+            }
+            UnverifiedEvent::ProofOfStore(p) => {
+                p.verify(validator)?;
+                VerifiedEvent::ProofOfStore(p)
+            }
+            UnverifiedEvent::FastCommitVote(v) => {
+                v.verify(validator)?;
+                VerifiedEvent::FastCommitVote(v)
+            }
         })
     }
 
@@ -91,6 +118,10 @@ impl UnverifiedEvent {
             UnverifiedEvent::SyncInfo(s) => s.epoch(),
             UnverifiedEvent::CommitVote(cv) => cv.epoch(),
             UnverifiedEvent::CommitDecision(cd) => cd.epoch(),
+            UnverifiedEvent::Batch(b) => b.epoch(),
+            UnverifiedEvent::SignedBatchInfo(sbi) => sbi.epoch(),
+            UnverifiedEvent::ProofOfStore(p) => p.epoch(),
+            UnverifiedEvent::FastCommitVote(v) => v.epoch(),
         }
     }
 }
@@ -103,6 +134,10 @@ impl From<ConsensusMsg> for UnverifiedEvent {
             ConsensusMsg::SyncInfo(m) => UnverifiedEvent::SyncInfo(m),
             ConsensusMsg::CommitVoteMsg(m) => UnverifiedEvent::CommitVote(m),
             ConsensusMsg::CommitDecisionMsg(m) => UnverifiedEvent::CommitDecision(m),
+            ConsensusMsg::BatchMsg(m) => UnverifiedEvent::Batch(m),
+            ConsensusMsg::SignedBatchInfoMsg(m) => UnverifiedEvent::SignedBatchInfo(m),
+            ConsensusMsg::ProofOfStoreMsg(m) => UnverifiedEvent::ProofOfStore(m),
+            ConsensusMsg::FastCommitVoteMsg(m) => UnverifiedEvent::FastCommitVote(m),
             _ => unreachable!("Unexpected conversion"),
         }
     }
@@ -117,6 +152,11 @@ pub enum VerifiedEvent {
     CommitVote(Box<CommitVote>),
     CommitDecision(Box<CommitDecision>),
     BlockRetrievalRequest(Box<IncomingBlockRetrievalRequest>),
+    Batch(Box<Batch>),
+    SignedBatchInfo(Box<SignedBatchInfo>),
+    ProofOfStore(Box<ProofOfStore>),
+    BatchRetrievalRequest(Box<IncomingBatchRetrievalRequest>),
+    FastCommitVote(Box<FastCommitVote>),
     // local messages
     LocalTimeout(Round),
     Shutdown(oneshot::Sender<()>),
@@ -146,6 +186,8 @@ pub struct RoundManager {
     storage: Arc<dyn PersistentLivenessStorage>,
     sync_only: bool,
     onchain_config: OnChainConsensusConfig,
+    enable_fast_commit: bool,
+    pending_fast_commit_votes: PendingFastCommitVotes,
 }
 
 impl RoundManager {
@@ -160,6 +202,7 @@ impl RoundManager {
         storage: Arc<dyn PersistentLivenessStorage>,
         sync_only: bool,
         onchain_config: OnChainConsensusConfig,
+        enable_fast_commit: bool,
     ) -> Self {
         // when decoupled execution is false,
         // the counter is still static.
@@ -183,6 +226,8 @@ impl RoundManager {
             storage,
             sync_only,
             onchain_config,
+            enable_fast_commit,
+            pending_fast_commit_votes: PendingFastCommitVotes::new(),
         }
     }
 
@@ -194,10 +239,20 @@ impl RoundManager {
         self.onchain_config.decoupled_execution()
     }
 
+    /// The fast commit relies on the 2-chain timeout rule of SafetyRules
+    fn fast_commit(&self) -> bool {
+        self.enable_fast_commit && self.two_chain()
+    }
+
     fn back_pressure_limit(&self) -> u64 {
         self.onchain_config.back_pressure_limit()
     }
 
+    fn record_round_event(&self, round: Round, kind: RoundEventKind) {
+        self.storage
+            .record_round_event(self.epoch_state.epoch, round, kind);
+    }
+
     fn create_block_retriever(&self, author: Author) -> BlockRetriever {
         BlockRetriever::new(self.network.clone(), author)
     }
@@ -228,6 +283,12 @@ impl RoundManager {
                 counters::TIMEOUT_ROUNDS_COUNT.inc();
             }
         };
+        self.record_round_event(
+            new_round_event.round,
+            RoundEventKind::RoundStarted {
+                reason: new_round_event.reason.to_string(),
+            },
+        );
         debug!(
             self.new_log(LogEvent::NewRound),
             reason = new_round_event.reason
@@ -266,7 +327,11 @@ impl RoundManager {
         .boxed();
         let proposal = self
             .proposal_generator
-            .generate_proposal(new_round_event.round, callback)
+            .generate_proposal(
+                new_round_event.round,
+                self.proposer_election.as_ref(),
+                callback,
+            )
             .await?;
         let signature = self.safety_rules.lock().sign_proposal(&proposal)?;
         let signed_proposal =
@@ -291,6 +356,13 @@ impl RoundManager {
             proposal_msg.proposal().timestamp_usecs(),
             BlockStage::RECEIVED,
         );
+        self.record_round_event(
+            proposal_msg.proposal().round(),
+            RoundEventKind::ProposalReceived {
+                proposer: proposal_msg.proposer(),
+                block_id: proposal_msg.proposal().id(),
+            },
+        );
         if self
             .ensure_round_and_sync_up(
                 proposal_msg.proposal().round(),
@@ -444,6 +516,7 @@ impl RoundManager {
         if !self.round_state.process_local_timeout(round) {
             return Ok(());
         }
+        self.record_round_event(round, RoundEventKind::LocalTimeout);
 
         if self.sync_only() {
             self.network
@@ -458,7 +531,9 @@ impl RoundManager {
             Some(vote) if vote.vote_data().proposed().round() == round => (true, vote),
             _ => {
                 // Didn't vote in this round yet, generate a backup vote
-                let nil_block = self.proposal_generator.generate_nil_block(round)?;
+                let nil_block = self
+                    .proposal_generator
+                    .generate_nil_block(round, self.proposer_election.as_ref())?;
                 debug!(
                     self.new_log(LogEvent::VoteNIL),
                     "Planning to vote for a NIL block {}", nil_block
@@ -542,6 +617,41 @@ impl RoundManager {
             proposal,
         );
 
+        let expected_failed_authors = self.proposal_generator.compute_failed_authors(
+            proposal.round(),
+            proposal.quorum_cert().certified_block().round(),
+            false,
+            self.proposer_election.as_ref(),
+        );
+        ensure!(
+            proposal.failed_authors() == expected_failed_authors.as_ref(),
+            "[RoundManager] Proposal {} from {} has unexpected failed authors {:?}, expected {:?}",
+            proposal,
+            author,
+            proposal.failed_authors(),
+            expected_failed_authors,
+        );
+
+        let payload_size_bytes = proposal.payload_size_bytes();
+        let max_block_bytes = self.onchain_config.max_block_bytes();
+        if payload_size_bytes > max_block_bytes {
+            counters::REJECTED_OVERSIZED_PROPOSALS_COUNT.inc();
+            bail!(
+                "[RoundManager] Proposal {} from {} has a payload of {} bytes, exceeding the max block size of {} bytes",
+                proposal,
+                author,
+                payload_size_bytes,
+                max_block_bytes,
+            );
+        }
+
+        ensure!(
+            proposal.proofs().is_none() || self.onchain_config.quorum_store_enabled(),
+            "[RoundManager] Proposal {} from {} carries proofs of store but the quorum store is disabled",
+            proposal,
+            author,
+        );
+
         let block_time_since_epoch = Duration::from_micros(proposal.timestamp_usecs());
 
         ensure!(
@@ -567,10 +677,46 @@ impl RoundManager {
 
         self.round_state.record_vote(vote.clone());
         let vote_msg = VoteMsg::new(vote, self.block_store.sync_info());
-        self.network.send_vote(vote_msg, vec![recipients]).await;
+        if self.fast_commit() {
+            // every validator aggregates the QC of the round to fast vote it
+            self.network
+                .broadcast(ConsensusMsg::VoteMsg(Box::new(vote_msg)))
+                .await;
+        } else {
+            self.network.send_vote(vote_msg, vec![recipients]).await;
+        }
         Ok(())
     }
 
+    /// Broadcasts a fast commit vote for the block certified by a QC aggregated from the votes of
+    /// its round, unless the block is already committed or SafetyRules refuses to sign it (e.g.,
+    /// after a timeout of its round). The fast commit is optimistic, the block is committed by the
+    /// 2-chain rule otherwise.
+    async fn broadcast_fast_commit_vote(&mut self, qc: &QuorumCert) {
+        if qc.certified_block().round() <= self.block_store.ordered_root().round()
+            || self.sync_only()
+        {
+            return;
+        }
+        let result = self.safety_rules.lock().sign_fast_commit_vote(qc);
+        match result {
+            Ok(vote) => {
+                debug!(self.new_log(LogEvent::FastCommitVote), "{}", vote);
+                self.network
+                    .broadcast(ConsensusMsg::FastCommitVoteMsg(Box::new(vote)))
+                    .await;
+            }
+            Err(e) => {
+                debug!(
+                    self.new_log(LogEvent::FastCommitVote),
+                    "SafetyRules rejected the fast commit of {}: {}",
+                    qc.certified_block(),
+                    e
+                );
+            }
+        }
+    }
+
     /// The function generates a VoteMsg for a given proposed_block:
     /// * first execute the block and add it to the block store
     /// * then verify the voting rules
@@ -666,9 +812,18 @@ impl RoundManager {
             vote_id = vote.vote_data().proposed().id(),
             vote_state = vote.vote_data().proposed().executed_state_id(),
         );
+        self.record_round_event(
+            round,
+            RoundEventKind::VoteReceived {
+                author: vote.author(),
+                block_id: vote.vote_data().proposed().id(),
+                timeout: vote.is_timeout(),
+            },
+        );
 
-        if !vote.is_timeout() {
-            // Unlike timeout votes regular votes are sent to the leaders of the next round only.
+        if !vote.is_timeout() && !self.fast_commit() {
+            // Unlike timeout votes regular votes are sent to the leaders of the next round only,
+            // unless they are broadcast for the fast commit.
             let next_round = round + 1;
             ensure!(
                 self.proposer_election
@@ -693,16 +848,81 @@ impl RoundManager {
             .insert_vote(vote, &self.epoch_state.verifier)
         {
             VoteReceptionResult::NewQuorumCertificate(qc) => {
+                self.record_round_event(
+                    round,
+                    RoundEventKind::QuorumCertFormed {
+                        block_id: qc.certified_block().id(),
+                    },
+                );
                 self.new_qc_aggregated(qc, vote.author()).await
             }
-            VoteReceptionResult::NewTimeoutCertificate(tc) => self.new_tc_aggregated(tc).await,
+            VoteReceptionResult::NewTimeoutCertificate(tc) => {
+                self.record_round_event(
+                    round,
+                    RoundEventKind::TimeoutCertFormed {
+                        signers: tc.signatures().keys().cloned().collect(),
+                    },
+                );
+                self.new_tc_aggregated(tc).await
+            }
             VoteReceptionResult::New2ChainTimeoutCertificate(tc) => {
+                self.record_round_event(
+                    round,
+                    RoundEventKind::TimeoutCertFormed {
+                        signers: tc.signers().cloned().collect(),
+                    },
+                );
                 self.new_2chain_tc_aggregated(tc).await
             }
             _ => Ok(()),
         }
     }
 
+    /// Add a fast commit vote of an uncommitted round to the pending votes, the block is
+    /// committed once they complete a FastCommitCertificate.
+    /// As the votes of any uncommitted round are accepted, the votes beyond the current round are
+    /// dropped to bound the memory of the pending votes.
+    pub async fn process_fast_commit_vote(
+        &mut self,
+        vote: FastCommitVote,
+        peer: Author,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            self.fast_commit(),
+            "[RoundManager] Fast commit is disabled, ignore {}",
+            vote
+        );
+        if vote.round() <= self.block_store.ordered_root().round()
+            || vote.round() > self.round_state.current_round()
+        {
+            return Ok(());
+        }
+        match self
+            .pending_fast_commit_votes
+            .insert_vote(&vote, &self.epoch_state.verifier)
+        {
+            Some(cert) => self.new_fast_commit_cert_aggregated(cert, peer).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn new_fast_commit_cert_aggregated(
+        &mut self,
+        cert: FastCommitCertificate,
+        preferred_peer: Author,
+    ) -> anyhow::Result<()> {
+        info!(self.new_log(LogEvent::FastCommit), "{}", cert);
+        counters::FAST_COMMIT_COUNT.inc();
+        let result = self
+            .block_store
+            .insert_fast_commit_cert(&cert, &mut self.create_block_retriever(preferred_peer))
+            .await
+            .context("[RoundManager] Failed to process a newly aggregated fast commit certificate");
+        self.pending_fast_commit_votes
+            .prune(self.block_store.ordered_root().round());
+        result
+    }
+
     async fn new_qc_aggregated(
         &mut self,
         qc: Arc<QuorumCert>,
@@ -717,6 +937,9 @@ impl RoundManager {
             .insert_quorum_cert(&qc, &mut self.create_block_retriever(preferred_peer))
             .await
             .context("[RoundManager] Failed to process a newly aggregated QC");
+        if result.is_ok() && self.fast_commit() {
+            self.broadcast_fast_commit_vote(&qc).await;
+        }
         self.process_certificates().await?;
         result
     }
@@ -858,6 +1081,12 @@ impl RoundManager {
                         self.process_block_retrieval(*block_retrival).await
                     )
                 }
+                VerifiedEvent::FastCommitVote(vote) => {
+                    monitor!(
+                        "process_fast_commit_vote",
+                        self.process_fast_commit_vote(*vote, peer_id).await
+                    )
+                }
                 VerifiedEvent::LocalTimeout(round) => monitor!(
                     "process_local_timeout",
                     self.process_local_timeout(round).await
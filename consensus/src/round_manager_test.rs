@@ -28,7 +28,7 @@ use aptos_secure_storage::Storage;
 use aptos_types::{
     epoch_state::EpochState,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
-    on_chain_config::OnChainConsensusConfig,
+    on_chain_config::{ConsensusConfigV3, OnChainConsensusConfig},
     validator_signer::ValidatorSigner,
     validator_verifier::random_validator_verifier,
     waypoint::Waypoint,
@@ -36,7 +36,7 @@ use aptos_types::{
 use channel::{self, aptos_channel, message_queues::QueueStyle};
 use consensus_types::{
     block::{
-        block_test_utils::{certificate_for_genesis, gen_test_certificate},
+        block_test_utils::{certificate_for_genesis, gen_test_certificate, random_payload},
         Block,
     },
     block_retrieval::{BlockRetrievalRequest, BlockRetrievalStatus},
@@ -198,12 +198,16 @@ impl NodeSetup {
             10,
         ));
 
+        let onchain_config = OnChainConsensusConfig::default();
         let proposal_generator = ProposalGenerator::new(
             author,
             block_store.clone(),
             Arc::new(MockTransactionManager::new(None)),
             time_service.clone(),
             1,
+            u64::MAX,
+            onchain_config.max_failed_authors_to_store(),
+            None,
         );
 
         let round_state = Self::create_round_state(time_service);
@@ -222,7 +226,8 @@ impl NodeSetup {
             network,
             storage.clone(),
             false,
-            OnChainConsensusConfig::default(),
+            onchain_config,
+            false,
         );
         block_on(round_manager.init(last_vote_sent));
         Self {
@@ -510,6 +515,45 @@ fn no_vote_on_invalid_proposer() {
     });
 }
 
+#[test]
+/// We don't vote for proposals whose payload exceeds the max block size in bytes
+fn no_vote_on_oversized_proposal() {
+    let mut runtime = consensus_runtime();
+    let mut playground = NetworkPlayground::new(runtime.handle().clone());
+    let mut nodes = NodeSetup::create_nodes(&mut playground, runtime.handle().clone(), 1);
+    let node = &mut nodes[0];
+    let genesis_qc = certificate_for_genesis();
+    let oversized_block =
+        Block::new_proposal(random_payload(10), 1, 1, genesis_qc.clone(), &node.signer);
+    node.round_manager.onchain_config = OnChainConsensusConfig::V3(ConsensusConfigV3 {
+        two_chain: false,
+        decoupled_execution: false,
+        back_pressure_limit: 10,
+        exclude_round: 4,
+        max_block_bytes: oversized_block.payload_size_bytes() - 1,
+        quorum_store_enabled: false,
+        max_failed_authors_to_store: 0,
+    });
+    let block = Block::new_proposal(vec![], 1, 1, genesis_qc, &node.signer);
+    let block_id = block.id();
+    timed_block_on(&mut runtime, async {
+        // clear the message queue
+        node.next_proposal().await;
+
+        let err = node
+            .round_manager
+            .process_proposal(oversized_block)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("exceeding the max block size"));
+        assert_eq!(node.round_manager.consensus_state().last_voted_round(), 0);
+
+        node.round_manager.process_proposal(block).await.unwrap();
+        let vote_msg = node.next_vote().await;
+        assert_eq!(vote_msg.vote().vote_data().proposed().id(), block_id);
+    });
+}
+
 #[test]
 /// We allow to 'skip' round if proposal carries timeout certificate for next round
 fn new_round_on_timeout_certificate() {
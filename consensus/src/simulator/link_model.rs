@@ -0,0 +1,100 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::network_tests::{LinkModel, TwinId};
+use aptos_infallible::Mutex;
+use aptos_time_service::{MockTimeService, TimeServiceTrait};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{collections::HashMap, time::Duration};
+
+/// The characteristics of a one-way link between two validators
+#[derive(Clone, Copy, Debug)]
+pub struct Link {
+    pub latency: Duration,
+    /// Bytes per second
+    pub bandwidth: u64,
+    /// Probability of a message to be dropped
+    pub drop_rate: f64,
+}
+
+impl Link {
+    pub fn new(latency_ms: u64, bandwidth: u64, drop_rate: f64) -> Self {
+        assert!(bandwidth > 0);
+        assert!((0.0..=1.0).contains(&drop_rate));
+        Self {
+            latency: Duration::from_millis(latency_ms),
+            bandwidth,
+            drop_rate,
+        }
+    }
+
+    fn transmission_time(&self, num_bytes: usize) -> Duration {
+        Duration::from_nanos((num_bytes as u128 * 1_000_000_000 / self.bandwidth as u128) as u64)
+    }
+}
+
+/// The state of a link, the drops of each link are drawn from its own generator so that they
+/// don't depend on the messages of the other links.
+struct LinkState {
+    rng: StdRng,
+    // The messages are transmitted one after the other
+    busy_until: Duration,
+}
+
+/// Validators spread over regions, the validator i being in the region i % number of regions.
+/// The messages are queued on their link, transmitted at its bandwidth and then delivered after
+/// its latency.
+pub struct WanModel {
+    // The link from each region to each region
+    links: Vec<Vec<Link>>,
+    seed: [u8; 32],
+    time_service: MockTimeService,
+    link_states: Mutex<HashMap<(usize, usize), LinkState>>,
+}
+
+impl WanModel {
+    pub fn new(links: Vec<Vec<Link>>, seed: [u8; 32], time_service: MockTimeService) -> Self {
+        assert!(!links.is_empty());
+        assert!(links.iter().all(|to| to.len() == links.len()));
+        Self {
+            links,
+            seed,
+            time_service,
+            link_states: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn num_regions(&self) -> usize {
+        self.links.len()
+    }
+
+    fn link_seed(&self, src: usize, dst: usize) -> [u8; 32] {
+        let mut seed = self.seed;
+        for (byte, link_byte) in seed
+            .iter_mut()
+            .zip(src.to_le_bytes().iter().chain(dst.to_le_bytes().iter()))
+        {
+            *byte ^= link_byte;
+        }
+        seed
+    }
+}
+
+impl LinkModel for WanModel {
+    fn delay(&self, src: &TwinId, dst: &TwinId, num_bytes: usize) -> Option<Duration> {
+        let link = self.links[src.id % self.num_regions()][dst.id % self.num_regions()];
+        let now = self.time_service.now_unix_time();
+        let mut link_states = self.link_states.lock();
+        let state = link_states
+            .entry((src.id, dst.id))
+            .or_insert_with(|| LinkState {
+                rng: StdRng::from_seed(self.link_seed(src.id, dst.id)),
+                busy_until: now,
+            });
+        if state.rng.gen::<f64>() < link.drop_rate {
+            return None;
+        }
+        state.busy_until = state.busy_until.max(now) + link.transmission_time(num_bytes);
+        Some(state.busy_until - now + link.latency)
+    }
+}
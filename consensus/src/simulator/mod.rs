@@ -0,0 +1,12 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A simulator running hundreds of validators in one process on a simulated clock, with the
+//! messages delayed and dropped by a model of the links between them, to measure the throughput,
+//! commit latency and timeouts of consensus changes (e.g., to the proposer election or the
+//! pipeline) without a multi-region cluster.
+
+mod link_model;
+mod simulated_node;
+mod simulation;
+mod simulation_test;
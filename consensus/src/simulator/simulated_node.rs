@@ -0,0 +1,436 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    counters,
+    epoch_manager::{EpochManager, LivenessStorageData},
+    error::{MempoolError, StateSyncError},
+    network::NetworkTask,
+    network_interface::{ConsensusNetworkEvents, ConsensusNetworkSender},
+    network_tests::{NetworkPlayground, TwinId},
+    persistent_liveness_storage::{LedgerRecoveryData, PersistentLivenessStorage},
+    simulator::simulation::SimulationStats,
+    state_replication::{StateComputer, StateComputerCommitCallBackType, TxnManager},
+    test_utils::MockStorage,
+    util::time_service::{ScheduledTask, TimeService},
+};
+use anyhow::{ensure, Result};
+use aptos_config::config::NodeConfig;
+use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
+use aptos_time_service::{MockTimeService, TimeServiceTrait};
+use aptos_types::{
+    account_config::aptos_root_address,
+    account_state::AccountState,
+    block_metadata::{
+        new_block_event_key, BlockMetadata, FailedProposersEvent, FailedProposersResource,
+        NewBlockEvent,
+    },
+    contract_event::ContractEvent,
+    epoch_change::EpochChangeProof,
+    event::{EventHandle, EventKey},
+    ledger_info::LedgerInfoWithSignatures,
+    on_chain_config::{OnChainConfig, OnChainConfigPayload, OnChainConsensusConfig, ValidatorSet},
+    state_store::{state_key::StateKey, state_value::StateValue},
+    test_helpers::transaction_test_helpers::get_test_signed_transaction,
+    transaction::{Script, TransactionPayload, Version},
+    validator_signer::ValidatorSigner,
+};
+use channel::{self, aptos_channel, message_queues::QueueStyle};
+use consensus_types::{
+    block::Block, common::Payload, executed_block::ExecutedBlock, quorum_cert::QuorumCert,
+    timeout_2chain::TwoChainTimeoutCertificate, timeout_certificate::TimeoutCertificate,
+    vote::Vote,
+};
+use event_notifications::{ReconfigNotification, ReconfigNotificationListener};
+use executor_types::{Error as ExecutionError, StateComputeResult};
+use futures::{
+    future::{AbortHandle, Abortable, BoxFuture},
+    SinkExt, StreamExt,
+};
+use move_core_types::{language_storage::TypeTag, move_resource::MoveResource};
+use network::peer_manager::{
+    conn_notifs_channel, ConnectionRequestSender, PeerManagerRequestSender,
+};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use storage_interface::{DbReader, Order};
+use tokio::runtime::Handle;
+
+/// Runs the timers of consensus on the simulated clock.
+pub struct SimulatedTimeService {
+    time_service: MockTimeService,
+    executor: Handle,
+}
+
+impl SimulatedTimeService {
+    pub fn new(time_service: MockTimeService, executor: Handle) -> Self {
+        Self {
+            time_service,
+            executor,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TimeService for SimulatedTimeService {
+    fn run_after(&self, timeout: Duration, mut t: Box<dyn ScheduledTask>) -> AbortHandle {
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        // the sleep is registered as soon as it is created, not when the task is first polled
+        let sleep = self.time_service.sleep(timeout);
+        let task = Abortable::new(
+            async move {
+                sleep.await;
+                t.run().await;
+            },
+            abort_registration,
+        );
+        self.executor.spawn(task);
+        abort_handle
+    }
+
+    fn get_current_timestamp(&self) -> Duration {
+        self.time_service.now_unix_time()
+    }
+
+    /// Waits for the clock shared by the nodes to move forward, as it does when they are idle.
+    async fn sleep(&self, t: Duration) {
+        self.time_service.sleep(t).await;
+    }
+}
+
+/// Proposes blocks of the same transaction, as many as fit in the block (up to block_size).
+pub struct SimulatedTxnManager {
+    payload: Payload,
+}
+
+impl SimulatedTxnManager {
+    pub fn new(block_size: usize, txn_bytes: usize) -> Self {
+        let signer = ValidatorSigner::from_int(0);
+        let txn = get_test_signed_transaction(
+            signer.author(),
+            0,
+            signer.private_key(),
+            signer.public_key(),
+            Some(TransactionPayload::Script(Script::new(
+                vec![0; txn_bytes],
+                vec![],
+                vec![],
+            ))),
+            u64::max_value(),
+            0,
+            None,
+        );
+        Self {
+            payload: vec![txn; block_size],
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TxnManager for SimulatedTxnManager {
+    async fn pull_txns(
+        &self,
+        max_size: u64,
+        max_bytes: u64,
+        _exclude_txns: Vec<&Payload>,
+        _callback: BoxFuture<'static, ()>,
+        _pending_ordering: bool,
+    ) -> Result<Payload, MempoolError> {
+        let mut num_bytes = 0;
+        Ok(self
+            .payload
+            .iter()
+            .take(max_size as usize)
+            .take_while(|txn| {
+                num_bytes += txn.txn_bytes_len() as u64;
+                num_bytes <= max_bytes
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn notify_failed_txn(
+        &self,
+        _block: &Block,
+        _compute_results: &StateComputeResult,
+    ) -> Result<(), MempoolError> {
+        Ok(())
+    }
+}
+
+/// Commits the blocks without executing them, recording their metadata for the leader
+/// reputation and their commit in the stats of the simulation.
+pub struct SimulatedStateComputer {
+    storage: Arc<MockStorage>,
+    db: Arc<SimulatedDb>,
+    stats: Arc<Mutex<SimulationStats>>,
+    time_service: MockTimeService,
+}
+
+#[async_trait::async_trait]
+impl StateComputer for SimulatedStateComputer {
+    async fn compute(
+        &self,
+        _block: &Block,
+        _parent_block_id: HashValue,
+    ) -> Result<StateComputeResult, ExecutionError> {
+        Ok(StateComputeResult::new_dummy())
+    }
+
+    async fn commit(
+        &self,
+        blocks: &[Arc<ExecutedBlock>],
+        finality_proof: LedgerInfoWithSignatures,
+        callback: StateComputerCommitCallBackType,
+    ) -> Result<(), ExecutionError> {
+        self.storage
+            .commit_to_storage(finality_proof.ledger_info().clone());
+        let now = self.time_service.now_unix_time();
+        for block in blocks {
+            self.db.commit_block(block.block());
+            if let Some(payload) = block.payload() {
+                self.stats.lock().record_commit(
+                    block.id(),
+                    payload.len(),
+                    now.saturating_sub(Duration::from_micros(block.timestamp_usecs())),
+                );
+            }
+        }
+        callback(blocks, finality_proof);
+        Ok(())
+    }
+
+    async fn sync_to(&self, target: LedgerInfoWithSignatures) -> Result<(), StateSyncError> {
+        self.storage.commit_to_storage(target.ledger_info().clone());
+        Ok(())
+    }
+}
+
+/// The key of the failed proposers events, whose handle is published at genesis in the simulations.
+fn failed_proposers_event_key() -> EventKey {
+    EventKey::new_from_address(&aptos_root_address(), 6)
+}
+
+/// The NewBlockEvents and FailedProposersEvents of the committed blocks, the history read by the
+/// leader reputation. Each block is a version, the blocks that a node state syncs to are missing.
+#[derive(Default)]
+pub struct SimulatedDb {
+    events: Mutex<Vec<(Version, ContractEvent)>>,
+    latest_version: Mutex<Version>,
+}
+
+impl SimulatedDb {
+    fn commit_block(&self, block: &Block) {
+        let (round, timestamp, votes, proposer) = BlockMetadata::from(block).into_inner();
+        let mut latest_version = self.latest_version.lock();
+        *latest_version += 1;
+        self.emit(
+            *latest_version,
+            new_block_event_key(),
+            bcs::to_bytes(&NewBlockEvent::new(round, proposer, votes, timestamp)).unwrap(),
+        );
+        if let Some(failed_authors) = block.failed_authors() {
+            let failed_proposers = failed_authors.iter().map(|(_, author)| *author).collect();
+            self.emit(
+                *latest_version,
+                failed_proposers_event_key(),
+                bcs::to_bytes(&FailedProposersEvent::new(round, failed_proposers)).unwrap(),
+            );
+        }
+    }
+
+    fn emit(&self, version: Version, key: EventKey, event_data: Vec<u8>) {
+        let mut events = self.events.lock();
+        let sequence_number = events.iter().filter(|(_, e)| *e.key() == key).count() as u64;
+        // only the event data is read
+        events.push((
+            version,
+            ContractEvent::new(key, sequence_number, TypeTag::Bool, event_data),
+        ));
+    }
+}
+
+impl DbReader for SimulatedDb {
+    fn get_events(
+        &self,
+        event_key: &EventKey,
+        _start: u64,
+        order: Order,
+        limit: u64,
+    ) -> Result<Vec<(u64, ContractEvent)>> {
+        ensure!(
+            order == Order::Descending,
+            "Only the latest events can be read"
+        );
+        Ok(self
+            .events
+            .lock()
+            .iter()
+            .rev()
+            .filter(|(_, event)| event.key() == event_key)
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+
+    fn get_latest_version(&self) -> Result<Version> {
+        Ok(*self.latest_version.lock())
+    }
+
+    fn get_latest_state_value(&self, state_key: StateKey) -> Result<Option<StateValue>> {
+        ensure!(
+            state_key == StateKey::AccountAddressKey(aptos_root_address()),
+            "Only the aptos root account can be read"
+        );
+        // only the failed proposers handle is read
+        let handle = bcs::to_bytes(&EventHandle::new(failed_proposers_event_key(), 0))?;
+        let mut account_state = AccountState::default();
+        account_state.insert(FailedProposersResource::resource_path(), handle);
+        Ok(Some(StateValue::from(bcs::to_bytes(&account_state)?)))
+    }
+}
+
+/// A MockStorage backed by a SimulatedDb.
+pub struct SimulatedStorage {
+    storage: Arc<MockStorage>,
+    db: Arc<SimulatedDb>,
+}
+
+impl PersistentLivenessStorage for SimulatedStorage {
+    fn save_tree(&self, blocks: Vec<Block>, quorum_certs: Vec<QuorumCert>) -> Result<()> {
+        self.storage.save_tree(blocks, quorum_certs)
+    }
+
+    fn prune_tree(&self, block_ids: Vec<HashValue>) -> Result<()> {
+        self.storage.prune_tree(block_ids)
+    }
+
+    fn save_vote(&self, vote: &Vote) -> Result<()> {
+        self.storage.save_vote(vote)
+    }
+
+    fn recover_from_ledger(&self) -> LedgerRecoveryData {
+        self.storage.recover_from_ledger()
+    }
+
+    fn start(&self) -> LivenessStorageData {
+        self.storage.start()
+    }
+
+    fn save_highest_timeout_cert(&self, highest_timeout_cert: TimeoutCertificate) -> Result<()> {
+        self.storage.save_highest_timeout_cert(highest_timeout_cert)
+    }
+
+    fn save_highest_2chain_timeout_cert(
+        &self,
+        highest_timeout_cert: &TwoChainTimeoutCertificate,
+    ) -> Result<()> {
+        self.storage
+            .save_highest_2chain_timeout_cert(highest_timeout_cert)
+    }
+
+    fn retrieve_epoch_change_proof(&self, version: u64) -> Result<EpochChangeProof> {
+        self.storage.retrieve_epoch_change_proof(version)
+    }
+
+    fn aptos_db(&self) -> Arc<dyn DbReader> {
+        self.db.clone()
+    }
+}
+
+/// Starts a node on the executor of the simulation, connected to the other nodes through the
+/// playground.
+pub fn start_node(
+    playground: &mut NetworkPlayground,
+    executor: &Handle,
+    time_service: &MockTimeService,
+    config: NodeConfig,
+    twin_id: TwinId,
+    storage: Arc<MockStorage>,
+    onchain_consensus_config: &OnChainConsensusConfig,
+    txn_manager: Arc<SimulatedTxnManager>,
+    stats: Arc<Mutex<SimulationStats>>,
+) {
+    let (network_reqs_tx, network_reqs_rx) = aptos_channel::new(QueueStyle::FIFO, 1_024, None);
+    let (connection_reqs_tx, _) = aptos_channel::new(QueueStyle::FIFO, 8, None);
+    let (consensus_tx, consensus_rx) = aptos_channel::new(QueueStyle::FIFO, 1_024, None);
+    let (_conn_mgr_reqs_tx, conn_mgr_reqs_rx) = channel::new_test(8);
+    let (_, conn_notifs_channel) = conn_notifs_channel::new();
+    let mut network_sender = ConsensusNetworkSender::new(
+        PeerManagerRequestSender::new(network_reqs_tx),
+        ConnectionRequestSender::new(connection_reqs_tx),
+    );
+    network_sender.initialize(playground.peer_protocols());
+    let network_events = ConsensusNetworkEvents::new(consensus_rx, conn_notifs_channel);
+
+    playground.add_node(twin_id, consensus_tx, network_reqs_rx, conn_mgr_reqs_rx);
+
+    let db = Arc::new(SimulatedDb::default());
+    let state_computer = Arc::new(SimulatedStateComputer {
+        storage: storage.clone(),
+        db: db.clone(),
+        stats: stats.clone(),
+        time_service: time_service.clone(),
+    });
+    let storage = Arc::new(SimulatedStorage { storage, db });
+
+    let (reconfig_sender, reconfig_events) = aptos_channel::new(QueueStyle::LIFO, 1, None);
+    let reconfig_listener = ReconfigNotificationListener {
+        notification_receiver: reconfig_events,
+    };
+    let mut configs = HashMap::new();
+    configs.insert(
+        ValidatorSet::CONFIG_ID,
+        bcs::to_bytes(storage.storage.get_validator_set()).unwrap(),
+    );
+    // the on-chain consensus config is stored as serialized bytes in a Move resource
+    configs.insert(
+        OnChainConsensusConfig::CONFIG_ID,
+        bcs::to_bytes(&bcs::to_bytes(onchain_consensus_config).unwrap()).unwrap(),
+    );
+    let payload = OnChainConfigPayload::new(1, Arc::new(configs));
+    reconfig_sender
+        .push(
+            (),
+            ReconfigNotification {
+                version: 1,
+                on_chain_configs: payload,
+            },
+        )
+        .unwrap();
+
+    let time_service = Arc::new(SimulatedTimeService::new(
+        time_service.clone(),
+        executor.clone(),
+    ));
+
+    // the local timeouts are counted on their way to the node
+    let (timeout_sender, mut local_timeouts) = channel::new_test(1_024);
+    let (mut timeout_forwarder, timeout_receiver) =
+        channel::new(1_024, &counters::PENDING_ROUND_TIMEOUTS);
+    executor.spawn(async move {
+        while let Some(round) = local_timeouts.next().await {
+            stats.lock().record_timeout(twin_id.id, round);
+            if timeout_forwarder.send(round).await.is_err() {
+                break;
+            }
+        }
+    });
+    let (self_sender, self_receiver) = channel::new(1_024, &counters::PENDING_SELF_MESSAGES);
+
+    let epoch_mgr = EpochManager::new(
+        &config,
+        time_service,
+        self_sender,
+        network_sender,
+        timeout_sender,
+        txn_manager,
+        state_computer,
+        storage,
+        reconfig_listener,
+    );
+    let (network_task, network_receiver) = NetworkTask::new(network_events, self_receiver);
+
+    executor.spawn(network_task.start());
+    executor.spawn(epoch_mgr.start(timeout_receiver, network_receiver));
+}
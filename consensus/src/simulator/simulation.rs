@@ -0,0 +1,305 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    network_interface::{DIRECT_SEND, RPC},
+    network_tests::{NetworkPlayground, TwinId},
+    simulator::{
+        link_model::{Link, WanModel},
+        simulated_node::{start_node, SimulatedTxnManager},
+    },
+    test_utils::MockStorage,
+};
+use aptos_config::{
+    config::{ConsensusProposerType, NodeConfig, TestConfig, WaypointConfig},
+    generator::{self, ValidatorSwarm},
+    network_id::NetworkId,
+};
+use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
+use aptos_time_service::{MockTimeService, TimeServiceTrait};
+use aptos_types::{
+    on_chain_config::{ConsensusConfigV1, OnChainConsensusConfig, ValidatorSet},
+    validator_info::ValidatorInfo,
+    waypoint::Waypoint,
+};
+use consensus_types::common::{Author, Round};
+use network::{protocols::wire::handshake::v1::ProtocolIdSet, transport::ConnectionMetadata};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Display, Formatter},
+    iter::FromIterator,
+    sync::Arc,
+    time::Duration,
+};
+use tokio::{
+    runtime::Builder,
+    time::{sleep, Instant},
+};
+
+/// The granularity of the simulated clock
+const TIME_STEP: Duration = Duration::from_millis(1);
+
+/// The validators and the network of a simulation
+#[derive(Clone, Debug)]
+pub struct SimulationConfig {
+    pub num_validators: usize,
+    /// Simulated duration of the run
+    pub duration: Duration,
+    pub round_initial_timeout_ms: u64,
+    pub proposer_type: ConsensusProposerType,
+    pub enable_fast_commit: bool,
+    /// Number of transactions of the proposed blocks
+    pub block_size: usize,
+    /// Size of the scripts of the transactions
+    pub txn_bytes: usize,
+    /// The link from each region to each region, see `WanModel`
+    pub links: Vec<Vec<Link>>,
+    pub seed: [u8; 32],
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            num_validators: 10,
+            duration: Duration::from_secs(60),
+            round_initial_timeout_ms: 1000,
+            proposer_type: ConsensusProposerType::RotatingProposer,
+            enable_fast_commit: false,
+            block_size: 100,
+            txn_bytes: 200,
+            links: vec![vec![Link::new(20, 100 * 1024 * 1024, 0.0)]],
+            seed: [0; 32],
+        }
+    }
+}
+
+/// What the nodes observed during a simulation
+#[derive(Default)]
+pub struct SimulationStats {
+    /// Number of transactions of each committed block
+    committed_blocks: HashMap<HashValue, usize>,
+    /// Time from the proposal of a block to its commit, by each validator
+    commit_latencies: Vec<Duration>,
+    /// The rounds each validator timed out
+    timeouts: HashSet<(usize, Round)>,
+}
+
+impl SimulationStats {
+    pub fn record_commit(&mut self, block_id: HashValue, num_txns: usize, latency: Duration) {
+        self.committed_blocks.insert(block_id, num_txns);
+        self.commit_latencies.push(latency);
+    }
+
+    /// The timeout of a round is counted once per validator, even if it is broadcast again
+    pub fn record_timeout(&mut self, validator: usize, round: Round) {
+        self.timeouts.insert((validator, round));
+    }
+}
+
+/// The measurements of a simulation
+#[derive(Clone, Debug)]
+pub struct SimulationReport {
+    pub duration: Duration,
+    pub committed_blocks: usize,
+    pub committed_txns: usize,
+    /// Sorted commit latencies of the blocks, by each validator
+    pub commit_latencies: Vec<Duration>,
+    /// Number of the local timeouts of the validators
+    pub timeouts: usize,
+    /// Number of the rounds in which at least one validator timed out
+    pub timeout_rounds: usize,
+}
+
+impl SimulationReport {
+    fn new(duration: Duration, stats: &SimulationStats) -> Self {
+        let mut commit_latencies = stats.commit_latencies.clone();
+        commit_latencies.sort();
+        let timeout_rounds: HashSet<_> = stats.timeouts.iter().map(|(_, round)| round).collect();
+        Self {
+            duration,
+            committed_blocks: stats.committed_blocks.len(),
+            committed_txns: stats.committed_blocks.values().sum(),
+            commit_latencies,
+            timeouts: stats.timeouts.len(),
+            timeout_rounds: timeout_rounds.len(),
+        }
+    }
+
+    /// Committed transactions per simulated second
+    pub fn throughput(&self) -> f64 {
+        self.committed_txns as f64 / self.duration.as_secs_f64()
+    }
+
+    /// The commit latency below which the given fraction (between 0 and 1) of the commits are
+    pub fn latency_percentile(&self, fraction: f64) -> Option<Duration> {
+        assert!((0.0..=1.0).contains(&fraction));
+        let last = self.commit_latencies.len().checked_sub(1)?;
+        let index = (last as f64 * fraction).round() as usize;
+        Some(self.commit_latencies[index])
+    }
+}
+
+impl Display for SimulationReport {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} blocks and {} txns committed in {:?} ({:.1} txns/s)",
+            self.committed_blocks,
+            self.committed_txns,
+            self.duration,
+            self.throughput()
+        )?;
+        let latency = |fraction| {
+            self.latency_percentile(fraction)
+                .map_or_else(|| "-".to_string(), |latency| format!("{:?}", latency))
+        };
+        writeln!(
+            f,
+            "commit latency: p50 {}, p90 {}, p99 {}, max {}",
+            latency(0.5),
+            latency(0.9),
+            latency(0.99),
+            latency(1.0)
+        )?;
+        write!(
+            f,
+            "{} timeouts in {} rounds",
+            self.timeouts, self.timeout_rounds
+        )
+    }
+}
+
+fn author_from_config(config: &NodeConfig) -> Author {
+    config.validator_network.as_ref().unwrap().peer_id()
+}
+
+/// Runs the validators of the config on one thread, with the consensus timers and the network
+/// delays on a simulated clock. The clock moves forward by TIME_STEP whenever all the nodes are
+/// idle, so the computations take no simulated time and a run only takes as long as its
+/// computations.
+///
+/// The tasks run in the same order and the links delay and drop the same messages from the same
+/// seed, except for two choices that are drawn from the thread rng of the process:
+/// - the event loop of the EpochManager (a `tokio::select!`) polls its sources in a random order,
+/// so when several of them are ready in the same step (the consensus messages, the block
+/// retrieval requests, the quorum store messages, the batch retrieval requests and the local
+/// round timeouts) the one handled first varies, e.g., a proposal and the timeout of its round,
+/// - a block retrieval that failed on its preferred peer retries on a random signer of the QC.
+/// Once one of them differs, the rest of the run may differ too, so the reports of two runs
+/// from the same seed are close but not always equal.
+pub fn run_simulation(config: &SimulationConfig) -> SimulationReport {
+    // the tokio clock is paused and only moves forward when all the tasks are idle
+    let runtime = Builder::new_current_thread()
+        .enable_all()
+        .start_paused(true)
+        .build()
+        .unwrap();
+    let time_service = MockTimeService::new();
+    // the blocks must have a later timestamp than the genesis
+    time_service.advance(Duration::from_secs(1));
+    let start = time_service.now_unix_time();
+
+    let link_model = Arc::new(WanModel::new(
+        config.links.clone(),
+        config.seed,
+        time_service.clone(),
+    ));
+    let mut playground = NetworkPlayground::with_link_model(
+        runtime.handle().clone(),
+        link_model,
+        time_service.clone().into(),
+    );
+    let stats = Arc::new(Mutex::new(SimulationStats::default()));
+
+    let template = NodeConfig {
+        test: Some(TestConfig::open_module()),
+        ..Default::default()
+    };
+    let ValidatorSwarm {
+        nodes: mut node_configs,
+    } = generator::validator_swarm(&template, config.num_validators, config.seed, false);
+    let peer_metadata_storage = playground.peer_protocols();
+    for node_config in &node_configs {
+        let mut conn_meta = ConnectionMetadata::mock(author_from_config(node_config));
+        conn_meta.application_protocols =
+            ProtocolIdSet::from_iter(RPC.iter().chain(DIRECT_SEND.iter()).copied());
+        peer_metadata_storage.insert_connection(NetworkId::Validator, conn_meta);
+    }
+    let validator_set = ValidatorSet::new(
+        node_configs
+            .iter()
+            .map(|node_config| {
+                let sr_test_config = node_config.consensus.safety_rules.test.as_ref().unwrap();
+                ValidatorInfo::new_with_test_network_keys(
+                    sr_test_config.author,
+                    sr_test_config.consensus_key.as_ref().unwrap().public_key(),
+                    1,
+                )
+            })
+            .collect(),
+    );
+    // sort by the peer id, as the validators of the set
+    node_configs.sort_by_key(author_from_config);
+
+    let onchain_consensus_config =
+        OnChainConsensusConfig::V1(ConsensusConfigV1 { two_chain: true });
+    let txn_manager = Arc::new(SimulatedTxnManager::new(
+        config.block_size,
+        config.txn_bytes,
+    ));
+    for (id, mut node_config) in node_configs.into_iter().enumerate() {
+        let (_, storage) = MockStorage::start_for_testing(validator_set.clone());
+        let waypoint = Waypoint::new_epoch_boundary(&storage.get_ledger_info())
+            .expect("Unable to produce waypoint with the provided LedgerInfo");
+        node_config
+            .consensus
+            .safety_rules
+            .test
+            .as_mut()
+            .unwrap()
+            .waypoint = Some(waypoint);
+        node_config.base.waypoint = WaypointConfig::FromConfig(waypoint);
+        node_config.consensus.proposer_type = config.proposer_type.clone();
+        node_config.consensus.round_initial_timeout_ms = config.round_initial_timeout_ms;
+        node_config.consensus.enable_fast_commit = config.enable_fast_commit;
+        node_config
+            .consensus
+            .safety_rules
+            .verify_vote_proposal_signature = false;
+
+        let twin_id = TwinId {
+            id,
+            author: author_from_config(&node_config),
+        };
+        start_node(
+            &mut playground,
+            runtime.handle(),
+            &time_service,
+            node_config,
+            twin_id,
+            storage,
+            &onchain_consensus_config,
+            txn_manager.clone(),
+            stats.clone(),
+        );
+    }
+    runtime.spawn(playground.start());
+
+    let end = start + config.duration;
+    runtime.block_on(async {
+        let mut last_step = Instant::now();
+        while time_service.now_unix_time() < end {
+            sleep(TIME_STEP).await;
+            // the simulated clock follows the tokio one, so that the tokio timers (e.g., of the
+            // rpc timeouts) stay in step with it
+            let now = Instant::now();
+            time_service.advance(now - last_step);
+            last_step = now;
+        }
+    });
+    // the nodes keep running until the runtime is dropped, the stats are taken before
+    let report = SimulationReport::new(config.duration, &stats.lock());
+    drop(runtime);
+    report
+}
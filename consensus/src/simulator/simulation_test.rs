@@ -0,0 +1,108 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::simulator::{
+    link_model::Link,
+    simulation::{run_simulation, SimulationConfig},
+};
+use aptos_config::config::{ConsensusProposerType, LeaderReputationConfig};
+use std::time::Duration;
+
+const MB: u64 = 1024 * 1024;
+
+/// Two regions 10ms apart internally and 50ms apart from each other
+fn two_regions(drop_rate: f64) -> Vec<Vec<Link>> {
+    let local = Link::new(10, 100 * MB, drop_rate);
+    let remote = Link::new(50, 10 * MB, drop_rate);
+    vec![vec![local, remote], vec![remote, local]]
+}
+
+#[test]
+/// Run the test:
+/// cargo xtest -p consensus simulation_test -- --nocapture
+fn simulation_test() {
+    let config = SimulationConfig {
+        num_validators: 7,
+        duration: Duration::from_secs(10),
+        links: two_regions(0.0),
+        ..Default::default()
+    };
+    let report = run_simulation(&config);
+    println!("{}", report);
+
+    assert!(report.committed_blocks > 0);
+    assert_eq!(
+        report.committed_txns,
+        report.committed_blocks * config.block_size
+    );
+    // a block is committed once the next one is certified, i.e., after two rounds of votes
+    assert!(report.latency_percentile(0.0).unwrap() >= Duration::from_millis(20));
+}
+
+#[test]
+/// The nodes recover from the dropped proposals and votes with their timeouts.
+///
+/// Run the test:
+/// cargo xtest -p consensus lossy_simulation_test -- --nocapture
+fn lossy_simulation_test() {
+    let report = run_simulation(&SimulationConfig {
+        num_validators: 7,
+        duration: Duration::from_secs(30),
+        links: two_regions(0.05),
+        ..Default::default()
+    });
+    println!("{}", report);
+
+    assert!(report.committed_blocks > 0);
+}
+
+#[test]
+/// The leader reputation reads the committed blocks of the nodes.
+///
+/// Run the test:
+/// cargo xtest -p consensus leader_reputation_simulation_test -- --nocapture
+fn leader_reputation_simulation_test() {
+    let report = run_simulation(&SimulationConfig {
+        num_validators: 7,
+        duration: Duration::from_secs(10),
+        proposer_type: ConsensusProposerType::LeaderReputation(LeaderReputationConfig::default()),
+        links: two_regions(0.0),
+        ..Default::default()
+    });
+    println!("{}", report);
+
+    assert!(report.committed_blocks > 0);
+}
+
+#[test]
+#[ignore]
+/// Hundreds of validators spread over four regions, to be run manually when evaluating
+/// proposer election or pipeline changes.
+///
+/// Run the test:
+/// cargo xtest -p consensus multi_region_simulation -- --ignored --nocapture
+fn multi_region_simulation() {
+    let latencies_ms = [
+        [5, 40, 90, 140],
+        [40, 5, 70, 110],
+        [90, 70, 5, 80],
+        [140, 110, 80, 5],
+    ];
+    let links = latencies_ms
+        .iter()
+        .map(|to| {
+            to.iter()
+                .map(|latency_ms| Link::new(*latency_ms, 100 * MB, 0.001))
+                .collect()
+        })
+        .collect();
+    let report = run_simulation(&SimulationConfig {
+        num_validators: 200,
+        duration: Duration::from_secs(60),
+        round_initial_timeout_ms: 1500,
+        block_size: 1000,
+        links,
+        ..Default::default()
+    });
+    println!("{}", report);
+}
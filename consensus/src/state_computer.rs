@@ -4,21 +4,23 @@
 use crate::{
     counters,
     error::StateSyncError,
+    experimental::batch_store::BatchReader,
     state_replication::{StateComputer, StateComputerCommitCallBackType, TxnManager},
 };
 use anyhow::Result;
 use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
 use aptos_logger::prelude::*;
 use aptos_metrics::monitor;
 use aptos_types::{
     contract_event::ContractEvent, ledger_info::LedgerInfoWithSignatures, transaction::Transaction,
 };
 use consensus_notifications::ConsensusNotificationSender;
-use consensus_types::{block::Block, executed_block::ExecutedBlock};
+use consensus_types::{block::Block, common::Payload, executed_block::ExecutedBlock};
 use executor_types::{BlockExecutorTrait, Error as ExecutionError, StateComputeResult};
 use fail::fail_point;
 use futures::{SinkExt, StreamExt};
-use std::{boxed::Box, sync::Arc};
+use std::{boxed::Box, collections::HashSet, sync::Arc};
 
 type NotificationType = (
     Box<dyn FnOnce() + Send + Sync>,
@@ -33,6 +35,7 @@ pub struct ExecutionProxy {
     mempool_notifier: Arc<dyn TxnManager>,
     state_sync_notifier: Arc<dyn ConsensusNotificationSender>,
     async_state_sync_notifier: channel::Sender<NotificationType>,
+    batch_reader: Mutex<Option<Arc<dyn BatchReader>>>,
 }
 
 impl ExecutionProxy {
@@ -62,8 +65,37 @@ impl ExecutionProxy {
             mempool_notifier,
             state_sync_notifier,
             async_state_sync_notifier: tx,
+            batch_reader: Mutex::new(None),
         }
     }
+
+    /// The transactions of a block carrying proofs of store, read from the batches of the proofs
+    /// in order. A transaction included in several batches is only kept at its first occurrence,
+    /// as the batches of different authors may pull the same transactions from their mempools.
+    /// None for the blocks carrying their transactions.
+    async fn resolve_payload(&self, block: &Block) -> Result<Option<Payload>, ExecutionError> {
+        let proofs = match block.proofs() {
+            Some(proofs) => proofs,
+            None => return Ok(None),
+        };
+        let batch_reader =
+            self.batch_reader
+                .lock()
+                .clone()
+                .ok_or_else(|| ExecutionError::InternalError {
+                    error: format!("No batch reader to execute block {}", block.id()),
+                })?;
+        let mut payload = vec![];
+        let mut included = HashSet::new();
+        for proof in proofs {
+            for txn in batch_reader.get_batch(proof).await? {
+                if included.insert((txn.sender(), txn.sequence_number())) {
+                    payload.push(txn);
+                }
+            }
+        }
+        Ok(Some(payload))
+    }
 }
 
 #[async_trait::async_trait]
@@ -86,13 +118,16 @@ impl StateComputer for ExecutionProxy {
             "Executing block",
         );
 
+        let transactions = match self.resolve_payload(block).await? {
+            Some(payload) => block.transactions_to_execute_with_payload(&payload),
+            None => block.transactions_to_execute(),
+        };
+
         // TODO: figure out error handling for the prologue txn
         let compute_result = monitor!(
             "execute_block",
-            self.executor.execute_block(
-                (block.id(), block.transactions_to_execute()),
-                parent_block_id
-            )
+            self.executor
+                .execute_block((block.id(), transactions), parent_block_id)
         )?;
 
         // notify mempool about failed transaction
@@ -118,10 +153,17 @@ impl StateComputer for ExecutionProxy {
         let mut block_ids = Vec::new();
         let mut txns = Vec::new();
         let mut reconfig_events = Vec::new();
+        let mut proofs = Vec::new();
 
         for block in blocks {
             block_ids.push(block.id());
-            txns.extend(block.transactions_to_commit());
+            match self.resolve_payload(block.block()).await? {
+                Some(payload) => {
+                    txns.extend(block.transactions_to_commit_with_payload(&payload));
+                    proofs.extend(block.block().proofs().into_iter().flatten().cloned());
+                }
+                None => txns.extend(block.transactions_to_commit()),
+            }
             reconfig_events.extend(block.reconfig_event());
         }
 
@@ -130,6 +172,9 @@ impl StateComputer for ExecutionProxy {
             self.executor
                 .commit_blocks(block_ids, finality_proof.clone())?
         );
+        if let Some(batch_reader) = self.batch_reader.lock().as_ref() {
+            batch_reader.notify_commit(&proofs);
+        }
 
         let blocks = blocks.to_vec();
         let wrapped_callback = move || {
@@ -167,4 +212,8 @@ impl StateComputer for ExecutionProxy {
             anyhow_error.into()
         })
     }
+
+    fn set_batch_reader(&self, batch_reader: Option<Arc<dyn BatchReader>>) {
+        *self.batch_reader.lock() = batch_reader;
+    }
 }
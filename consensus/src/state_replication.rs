@@ -1,7 +1,10 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::error::{MempoolError, StateSyncError};
+use crate::{
+    error::{MempoolError, StateSyncError},
+    experimental::batch_store::BatchReader,
+};
 use anyhow::Result;
 use aptos_crypto::HashValue;
 use aptos_types::ledger_info::LedgerInfoWithSignatures;
@@ -19,12 +22,14 @@ pub trait TxnManager: Send + Sync {
     /// Brings new transactions to be applied.
     /// The `exclude_txns` list includes the transactions that are already pending in the
     /// branch of blocks consensus is trying to extend.
+    /// The transactions are bounded both by count (`max_size`) and total size (`max_bytes`).
     ///
     /// wait_callback is executed when there's no transactions available and it decides to wait.
     /// pending_ordering indicates if we should long poll mempool or propose empty blocks to help commit pending txns
     async fn pull_txns(
         &self,
         max_size: u64,
+        max_bytes: u64,
         exclude: Vec<&Payload>,
         wait_callback: BoxFuture<'static, ()>,
         pending_ordering: bool,
@@ -71,4 +76,8 @@ pub trait StateComputer: Send + Sync {
     /// In case of failure (`Result::Error`) the LI of storage remains unchanged, and the validator
     /// can assume there were no modifications to the storage made.
     async fn sync_to(&self, target: LedgerInfoWithSignatures) -> Result<(), StateSyncError>;
+
+    /// Sets where the transactions of the blocks carrying proofs of store are read from, for the
+    /// epoch starting. None when the quorum store is disabled.
+    fn set_batch_reader(&self, _batch_reader: Option<Arc<dyn BatchReader>>) {}
 }
@@ -0,0 +1,102 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::test_utils::mock_storage::MockStorage;
+use aptos_crypto::{hash::ACCUMULATOR_PLACEHOLDER_HASH, HashValue};
+use aptos_types::{
+    contract_event::ContractEvent,
+    ledger_info::LedgerInfoWithSignatures,
+    transaction::{Transaction, TransactionStatus},
+    vm_status::KeptVMStatus,
+};
+use consensus_notifications::{ConsensusNotificationSender, Error as NotificationError};
+use executor_types::{BlockExecutorTrait, Error, StateComputeResult};
+use futures::channel::mpsc;
+use std::sync::Arc;
+
+/// Stands for the executor and the state sync behind an `ExecutionProxy`, for the tests that run
+/// the real `StateComputer`. Every transaction is kept by the execution, and the transactions of
+/// each commit are sent out as state sync would be notified of them.
+#[derive(Clone)]
+pub struct MockExecutor {
+    consensus_db: Arc<MockStorage>,
+    commit_callback: mpsc::UnboundedSender<LedgerInfoWithSignatures>,
+    committed_txns: mpsc::UnboundedSender<Vec<Transaction>>,
+}
+
+impl MockExecutor {
+    pub fn new(
+        consensus_db: Arc<MockStorage>,
+        commit_callback: mpsc::UnboundedSender<LedgerInfoWithSignatures>,
+        committed_txns: mpsc::UnboundedSender<Vec<Transaction>>,
+    ) -> Self {
+        Self {
+            consensus_db,
+            commit_callback,
+            committed_txns,
+        }
+    }
+}
+
+impl BlockExecutorTrait for MockExecutor {
+    fn committed_block_id(&self) -> HashValue {
+        self.consensus_db.get_ledger_info().consensus_block_id()
+    }
+
+    fn reset(&self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn execute_block(
+        &self,
+        block: (HashValue, Vec<Transaction>),
+        _parent_block_id: HashValue,
+    ) -> Result<StateComputeResult, Error> {
+        let (_, transactions) = block;
+        Ok(StateComputeResult::new(
+            *ACCUMULATOR_PLACEHOLDER_HASH,
+            vec![],
+            0,
+            vec![],
+            0,
+            None,
+            vec![TransactionStatus::Keep(KeptVMStatus::Executed); transactions.len()],
+            vec![],
+            vec![],
+        ))
+    }
+
+    fn commit_blocks(
+        &self,
+        _block_ids: Vec<HashValue>,
+        ledger_info_with_sigs: LedgerInfoWithSignatures,
+    ) -> Result<(), Error> {
+        self.consensus_db
+            .commit_to_storage(ledger_info_with_sigs.ledger_info().clone());
+        // they may fail during shutdown
+        let _ = self.commit_callback.unbounded_send(ledger_info_with_sigs);
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ConsensusNotificationSender for MockExecutor {
+    async fn notify_new_commit(
+        &self,
+        transactions: Vec<Transaction>,
+        _reconfiguration_events: Vec<ContractEvent>,
+    ) -> Result<(), NotificationError> {
+        let _ = self.committed_txns.unbounded_send(transactions);
+        Ok(())
+    }
+
+    async fn sync_to_target(
+        &self,
+        target: LedgerInfoWithSignatures,
+    ) -> Result<(), NotificationError> {
+        self.consensus_db
+            .commit_to_storage(target.ledger_info().clone());
+        let _ = self.commit_callback.unbounded_send(target);
+        Ok(())
+    }
+}
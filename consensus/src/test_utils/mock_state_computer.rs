@@ -22,6 +22,8 @@ pub struct MockStateComputer {
     commit_callback: mpsc::UnboundedSender<LedgerInfoWithSignatures>,
     consensus_db: Arc<MockStorage>,
     block_cache: Mutex<HashMap<HashValue, Payload>>,
+    // The blocks of each commit, for the tests checking the committed chains
+    committed_blocks: Option<mpsc::UnboundedSender<Vec<Block>>>,
 }
 
 impl MockStateComputer {
@@ -35,8 +37,18 @@ impl MockStateComputer {
             commit_callback,
             consensus_db,
             block_cache: Mutex::new(HashMap::new()),
+            committed_blocks: None,
         }
     }
+
+    /// Sends the blocks of each commit, the blocks that a node syncs to are missing.
+    pub fn with_committed_blocks(
+        mut self,
+        committed_blocks: mpsc::UnboundedSender<Vec<Block>>,
+    ) -> Self {
+        self.committed_blocks = Some(committed_blocks);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -75,6 +87,10 @@ impl StateComputer for MockStateComputer {
         // they may fail during shutdown
         let _ = self.state_sync_client.unbounded_send(txns);
 
+        if let Some(committed_blocks) = &self.committed_blocks {
+            let _ = committed_blocks
+                .unbounded_send(blocks.iter().map(|block| block.block().clone()).collect());
+        }
         let _ = self.commit_callback.unbounded_send(commit.clone());
 
         call_back(blocks, commit);
@@ -48,6 +48,7 @@ impl TxnManager for MockTransactionManager {
     async fn pull_txns(
         &self,
         _max_size: u64,
+        _max_bytes: u64,
         _exclude_txns: Vec<&Payload>,
         _callback: BoxFuture<'static, ()>,
         _pending_ordering: bool,
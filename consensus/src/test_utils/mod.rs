@@ -15,6 +15,8 @@ use consensus_types::{
 use std::{future::Future, sync::Arc, time::Duration};
 use tokio::{runtime, time::timeout};
 
+#[cfg(test)]
+mod mock_executor;
 mod mock_state_computer;
 mod mock_storage;
 #[cfg(any(test, feature = "fuzzing"))]
@@ -23,6 +25,8 @@ mod mock_txn_manager;
 use crate::util::mock_time_service::SimulatedTimeService;
 use aptos_types::block_info::BlockInfo;
 use consensus_types::{block::block_test_utils::gen_test_certificate, common::Payload};
+#[cfg(test)]
+pub use mock_executor::MockExecutor;
 pub use mock_state_computer::{
     EmptyStateComputer, MockStateComputer, RandomComputeResultStateComputer,
 };
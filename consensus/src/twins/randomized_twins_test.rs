@@ -0,0 +1,236 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::twins::{
+    scenario::{partitions, shrink, RoundScenario, Scenario, ScenarioGenerator},
+    scenario_runner::ScenarioRunner,
+};
+use rand::{rngs::StdRng, SeedableRng};
+use std::{collections::HashSet, time::Duration};
+
+#[test]
+fn test_partitions() {
+    // Bell numbers restricted to at most k sets: S(4, 1) + S(4, 2) = 1 + 7
+    let two_partitions = partitions(4, 2);
+    assert_eq!(two_partitions.len(), 8);
+    assert_eq!(partitions(4, 4).len(), 15);
+    assert_eq!(two_partitions[0], vec![vec![0, 1, 2, 3]]);
+
+    let distinct: HashSet<_> = two_partitions.iter().collect();
+    assert_eq!(distinct.len(), two_partitions.len());
+    for partition in &two_partitions {
+        assert!(partition.len() <= 2);
+        let mut instances: Vec<_> = partition.iter().flatten().cloned().collect();
+        instances.sort_unstable();
+        assert_eq!(instances, vec![0, 1, 2, 3]);
+    }
+}
+
+#[test]
+fn test_scenario_generator() {
+    // 4 validators and the twin of n0, in at most 2 partitions
+    let generator = ScenarioGenerator::new(4, 1, 2, 2);
+    assert_eq!(generator.round_scenarios().len(), 4 * 16);
+    assert_eq!(generator.num_scenarios(), Some(64 * 64));
+
+    let scenarios: Vec<_> = generator.enumerate().take(100).collect();
+    let distinct: HashSet<_> = scenarios.iter().map(|s| format!("{}", s)).collect();
+    assert_eq!(distinct.len(), scenarios.len());
+    assert_eq!(scenarios[65], generator.scenario(65));
+    assert_eq!(scenarios[65].rounds[0], generator.round_scenarios()[1]);
+    assert_eq!(scenarios[65].rounds[1], generator.round_scenarios()[1]);
+
+    // sampling is reproducible from the seed
+    let sample = generator.sample(&mut StdRng::seed_from_u64(7));
+    assert_eq!(sample, generator.sample(&mut StdRng::seed_from_u64(7)));
+    assert_eq!(sample.rounds.len(), 2);
+    assert_eq!(sample.num_instances(), 5);
+}
+
+#[test]
+fn test_shrink() {
+    // Fails whenever n0 leads a round in which it is separated from its twin t0 (instance 4)
+    let fails = |scenario: &Scenario| {
+        scenario.num_twins > 0
+            && scenario.rounds.iter().any(|round| {
+                round.leader == 0
+                    && round
+                        .partitions
+                        .iter()
+                        .all(|partition| !(partition.contains(&0) && partition.contains(&4)))
+            })
+    };
+    let scenario = Scenario {
+        num_nodes: 4,
+        num_twins: 1,
+        rounds: vec![
+            RoundScenario {
+                leader: 1,
+                partitions: vec![vec![0, 1], vec![2, 3], vec![4]],
+            },
+            RoundScenario {
+                leader: 0,
+                partitions: vec![vec![0, 1], vec![2], vec![3, 4]],
+            },
+            RoundScenario {
+                leader: 0,
+                partitions: vec![vec![0, 1, 2, 3, 4]],
+            },
+        ],
+    };
+    assert!(fails(&scenario));
+
+    let minimal = shrink(scenario, fails);
+    assert_eq!(
+        minimal,
+        Scenario {
+            num_nodes: 4,
+            num_twins: 1,
+            rounds: vec![RoundScenario {
+                leader: 0,
+                partitions: vec![vec![0, 1, 2], vec![3, 4]],
+            }],
+        }
+    );
+}
+
+/// Runs the given number of scenarios sampled from the seed, and panics with the minimal
+/// reproducer of the first failing one.
+fn run_random_scenarios(
+    runner: &ScenarioRunner,
+    generator: &ScenarioGenerator,
+    seed: u64,
+    num_scenarios: usize,
+) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    for _ in 0..num_scenarios {
+        let scenario = generator.sample(&mut rng);
+        if let Err(failure) = runner.run(&scenario) {
+            let minimal = shrink(scenario.clone(), |candidate| runner.run(candidate).is_err());
+            panic!(
+                "[TwinsTest] {} in scenario\n{}minimal reproducer ({}):\n{}",
+                failure,
+                scenario,
+                runner
+                    .run(&minimal)
+                    .err()
+                    .map_or("not reproduced".to_string(), |f| f.to_string()),
+                minimal
+            );
+        }
+    }
+}
+
+#[test]
+/// This test checks that the safety and liveness invariants hold in randomly sampled
+/// scenarios of 4 nodes and 1 twin over 3 rounds with up to 2 partitions.
+///
+/// Run the test:
+/// cargo xtest -p consensus randomized_twins_test -- --nocapture
+fn randomized_twins_test() {
+    let generator = ScenarioGenerator::new(4, 1, 3, 2);
+    run_random_scenarios(&ScenarioRunner::default(), &generator, 0, 2);
+}
+
+#[test]
+/// This test checks that the safety and liveness invariants hold with the fast commit path in
+/// randomly sampled scenarios of 4 nodes and 1 twin over 4 rounds with up to 3 partitions.
+///
+/// Run the test:
+/// cargo xtest -p consensus randomized_twins_fast_commit_test -- --nocapture
+fn randomized_twins_fast_commit_test() {
+    let runner = ScenarioRunner::new(50, 500, Duration::from_secs(30), true);
+    let generator = ScenarioGenerator::new(4, 1, 4, 3);
+    run_random_scenarios(&runner, &generator, 0, 10);
+}
+
+#[test]
+#[ignore]
+/// The longer sweep with the fast commit path, to be run manually.
+///
+/// Run the test:
+/// cargo xtest -p consensus randomized_twins_fast_commit_sweep -- --ignored --nocapture
+fn randomized_twins_fast_commit_sweep() {
+    let runner = ScenarioRunner::new(50, 500, Duration::from_secs(30), true);
+    let generator = ScenarioGenerator::new(4, 1, 6, 3);
+    run_random_scenarios(&runner, &generator, 42, 100);
+}
+
+#[test]
+#[ignore]
+/// A longer sweep over scenarios of up to 3 partitions, to be run manually.
+///
+/// Run the test:
+/// cargo xtest -p consensus randomized_twins_sweep -- --ignored --nocapture
+fn randomized_twins_sweep() {
+    let generator = ScenarioGenerator::new(4, 1, 6, 3);
+    run_random_scenarios(&ScenarioRunner::default(), &generator, 42, 100);
+}
+
+#[test]
+/// A scenario with a partition isolating a twin leader from its validator in each round.
+///
+/// Run the test:
+/// cargo xtest -p consensus twins_equivocating_leader_test -- --nocapture
+fn twins_equivocating_leader_test() {
+    run_equivocating_leader_scenario(false);
+}
+
+#[test]
+/// The equivocating leader scenario with the fast commit path: the twins vote and fast vote
+/// for the blocks of both partitions, and the honest instances must not fast commit
+/// conflicting blocks.
+///
+/// Run the test:
+/// cargo xtest -p consensus twins_equivocating_leader_fast_commit_test -- --nocapture
+fn twins_equivocating_leader_fast_commit_test() {
+    run_equivocating_leader_scenario(true);
+}
+
+fn run_equivocating_leader_scenario(fast_commit: bool) {
+    let round = RoundScenario {
+        leader: 0,
+        partitions: vec![vec![0, 1, 2], vec![3, 4]],
+    };
+    let scenario = Scenario {
+        num_nodes: 4,
+        num_twins: 1,
+        rounds: vec![round.clone(), round.clone(), round],
+    };
+    let runner = ScenarioRunner::new(50, 500, Duration::from_secs(30), fast_commit);
+    if let Err(failure) = runner.run(&scenario) {
+        panic!("[TwinsTest] {} in scenario\n{}", failure, scenario);
+    }
+}
+
+#[test]
+/// A scenario in which the QC of a block only forms in the partition of its leader, whose
+/// instances fast commit it, while the other instances time out: the timeout certificate of the
+/// next round must carry the QC of a fast voter so that the block isn't forked.
+///
+/// Run the test:
+/// cargo xtest -p consensus twins_split_qc_fast_commit_test -- --nocapture
+fn twins_split_qc_fast_commit_test() {
+    let scenario = Scenario {
+        num_nodes: 4,
+        num_twins: 1,
+        rounds: vec![
+            RoundScenario {
+                leader: 1,
+                partitions: vec![vec![0, 1, 2], vec![3, 4]],
+            },
+            RoundScenario {
+                leader: 0,
+                partitions: vec![vec![0, 1], vec![2, 3, 4]],
+            },
+            RoundScenario {
+                leader: 3,
+                partitions: vec![vec![1, 2, 3], vec![0, 4]],
+            },
+        ],
+    };
+    let runner = ScenarioRunner::new(50, 500, Duration::from_secs(30), true);
+    if let Err(failure) = runner.run(&scenario) {
+        panic!("[TwinsTest] {} in scenario\n{}", failure, scenario);
+    }
+}
@@ -0,0 +1,262 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generation and shrinking of the Byzantine scenarios of the Twins approach
+//! (https://arxiv.org/abs/2004.10617): a Byzantine validator is emulated by running two honest
+//! instances (twins) with the same keys, and each round of a scenario picks a leader and a
+//! partition of the network among the instances.
+
+use rand::Rng;
+use std::fmt::{self, Display, Formatter};
+
+/// The leader and the network partitions of a round.
+/// The instances are indexed as in `SMRNode::start_num_nodes_with_twins`: the validators
+/// are 0..num_nodes and the twin of the validator i is num_nodes + i.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundScenario {
+    /// Index of the validator leading the round, both instances propose if it has a twin
+    pub leader: usize,
+    /// The messages of the round are only delivered within each partition
+    pub partitions: Vec<Vec<usize>>,
+}
+
+/// The rounds 1..=rounds.len() of a run, the later rounds are led by honest validators without
+/// any partition.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Scenario {
+    pub num_nodes: usize,
+    /// The validators 0..num_twins have a twin
+    pub num_twins: usize,
+    pub rounds: Vec<RoundScenario>,
+}
+
+impl Scenario {
+    /// Number of running instances, validators and twins
+    pub fn num_instances(&self) -> usize {
+        self.num_nodes + self.num_twins
+    }
+
+    /// Whether the instance runs a validator without twin
+    pub fn is_honest(&self, instance: usize) -> bool {
+        instance >= self.num_twins && instance < self.num_nodes
+    }
+
+    fn instance_name(&self, instance: usize) -> String {
+        if instance < self.num_nodes {
+            format!("n{}", instance)
+        } else {
+            format!("t{}", instance - self.num_nodes)
+        }
+    }
+
+    /// The simpler scenarios that are tried in turn when shrinking: each one has fewer rounds,
+    /// partitions or twins than this one.
+    fn simplifications(&self) -> Vec<Scenario> {
+        let mut candidates = vec![];
+        // drop a round, starting from the last one
+        for i in (0..self.rounds.len()).rev() {
+            let mut candidate = self.clone();
+            candidate.rounds.remove(i);
+            candidates.push(candidate);
+        }
+        // heal the network of a round, then merge two of its partitions
+        for (i, round) in self.rounds.iter().enumerate() {
+            if round.partitions.len() > 1 {
+                let mut candidate = self.clone();
+                candidate.rounds[i].partitions = vec![(0..self.num_instances()).collect()];
+                candidates.push(candidate);
+            }
+            if round.partitions.len() > 2 {
+                for first in 0..round.partitions.len() {
+                    for second in first + 1..round.partitions.len() {
+                        let mut candidate = self.clone();
+                        let partitions = &mut candidate.rounds[i].partitions;
+                        let merged = partitions.remove(second);
+                        partitions[first].extend(merged);
+                        partitions[first].sort_unstable();
+                        candidates.push(candidate);
+                    }
+                }
+            }
+        }
+        // remove the last twin
+        if self.num_twins > 0 {
+            let removed = self.num_instances() - 1;
+            let mut candidate = self.clone();
+            candidate.num_twins -= 1;
+            for round in &mut candidate.rounds {
+                for partition in &mut round.partitions {
+                    partition.retain(|instance| *instance != removed);
+                }
+                round.partitions.retain(|partition| !partition.is_empty());
+            }
+            candidates.push(candidate);
+        }
+        candidates
+    }
+}
+
+impl Display for Scenario {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, "{} nodes, {} twins", self.num_nodes, self.num_twins)?;
+        for (i, round) in self.rounds.iter().enumerate() {
+            let partitions: Vec<_> = round
+                .partitions
+                .iter()
+                .map(|partition| {
+                    let names: Vec<_> = partition
+                        .iter()
+                        .map(|instance| self.instance_name(*instance))
+                        .collect();
+                    format!("[{}]", names.join(" "))
+                })
+                .collect();
+            writeln!(
+                f,
+                "round {}: leader {}, partitions {}",
+                i + 1,
+                self.instance_name(round.leader),
+                partitions.join(" ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Enumerates or samples the scenarios of a given number of rounds, each round picking one of
+/// the leaders and one of the partitions of the instances into at most max_partitions sets.
+pub struct ScenarioGenerator {
+    num_nodes: usize,
+    num_twins: usize,
+    num_rounds: usize,
+    round_scenarios: Vec<RoundScenario>,
+}
+
+impl ScenarioGenerator {
+    pub fn new(
+        num_nodes: usize,
+        num_twins: usize,
+        num_rounds: usize,
+        max_partitions: usize,
+    ) -> Self {
+        assert!(
+            num_twins * 3 < num_nodes,
+            "The twins must not exceed the f Byzantine validators tolerated"
+        );
+        let partitions = partitions(num_nodes + num_twins, max_partitions);
+        let round_scenarios = (0..num_nodes)
+            .flat_map(|leader| {
+                partitions.iter().map(move |partitions| RoundScenario {
+                    leader,
+                    partitions: partitions.clone(),
+                })
+            })
+            .collect();
+        Self {
+            num_nodes,
+            num_twins,
+            num_rounds,
+            round_scenarios,
+        }
+    }
+
+    /// All the (leader, partitions) pairs of a round
+    pub fn round_scenarios(&self) -> &[RoundScenario] {
+        &self.round_scenarios
+    }
+
+    /// Number of the distinct scenarios, none if it overflows
+    pub fn num_scenarios(&self) -> Option<u128> {
+        (self.round_scenarios.len() as u128).checked_pow(self.num_rounds as u32)
+    }
+
+    /// The scenario at the given index of the enumeration, its rounds being the digits of the
+    /// index in base round_scenarios().len()
+    pub fn scenario(&self, mut index: u128) -> Scenario {
+        let base = self.round_scenarios.len() as u128;
+        let rounds = (0..self.num_rounds)
+            .map(|_| {
+                let round = self.round_scenarios[(index % base) as usize].clone();
+                index /= base;
+                round
+            })
+            .collect();
+        self.new_scenario(rounds)
+    }
+
+    /// All the scenarios, in the order of their index
+    pub fn enumerate(&self) -> impl Iterator<Item = Scenario> + '_ {
+        let num_scenarios = self
+            .num_scenarios()
+            .expect("Too many scenarios to enumerate");
+        (0..num_scenarios).map(move |index| self.scenario(index))
+    }
+
+    /// A scenario picking each round uniformly at random
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Scenario {
+        let rounds = (0..self.num_rounds)
+            .map(|_| self.round_scenarios[rng.gen_range(0..self.round_scenarios.len())].clone())
+            .collect();
+        self.new_scenario(rounds)
+    }
+
+    fn new_scenario(&self, rounds: Vec<RoundScenario>) -> Scenario {
+        Scenario {
+            num_nodes: self.num_nodes,
+            num_twins: self.num_twins,
+            rounds,
+        }
+    }
+}
+
+/// All the partitions of the instances 0..num_instances into at most max_partitions non-empty
+/// sets, the sets and their instances being sorted.
+pub fn partitions(num_instances: usize, max_partitions: usize) -> Vec<Vec<Vec<usize>>> {
+    // each partition is generated once as the restricted growth string of the set of each
+    // instance: an instance is either added to one of the existing sets or starts the next one
+    let mut result = vec![];
+    let mut current: Vec<Vec<usize>> = vec![];
+    extend_partitions(0, num_instances, max_partitions, &mut current, &mut result);
+    result
+}
+
+fn extend_partitions(
+    instance: usize,
+    num_instances: usize,
+    max_partitions: usize,
+    current: &mut Vec<Vec<usize>>,
+    result: &mut Vec<Vec<Vec<usize>>>,
+) {
+    if instance == num_instances {
+        result.push(current.clone());
+        return;
+    }
+    for i in 0..current.len() {
+        current[i].push(instance);
+        extend_partitions(instance + 1, num_instances, max_partitions, current, result);
+        current[i].pop();
+    }
+    if current.len() < max_partitions {
+        current.push(vec![instance]);
+        extend_partitions(instance + 1, num_instances, max_partitions, current, result);
+        current.pop();
+    }
+}
+
+/// Shrinks a failing scenario to a minimal one that still fails: the simplifications are
+/// applied greedily for as long as one of them keeps failing.
+/// As the runs are not deterministic, a candidate is only kept if it fails when run.
+pub fn shrink<F>(scenario: Scenario, mut fails: F) -> Scenario
+where
+    F: FnMut(&Scenario) -> bool,
+{
+    let mut current = scenario;
+    while let Some(simpler) = current
+        .simplifications()
+        .into_iter()
+        .find(|candidate| fails(candidate))
+    {
+        current = simpler;
+    }
+    current
+}
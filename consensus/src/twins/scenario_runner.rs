@@ -0,0 +1,323 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    network_tests::{NetworkPlayground, TwinId},
+    test_utils::consensus_runtime,
+    twins::{scenario::Scenario, twins_node::SMRNode},
+};
+use aptos_config::config::ConsensusProposerType::RoundProposer;
+use aptos_crypto::HashValue;
+use aptos_types::ledger_info::LedgerInfoWithSignatures;
+use consensus_types::{block::Block, common::Round};
+use futures::StreamExt;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{self, Display, Formatter},
+    time::Duration,
+};
+use tokio::time::{timeout_at, Instant};
+
+/// A violated invariant of a run
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ScenarioFailure {
+    /// Two honest instances committed different blocks in the same round
+    ConflictingCommits {
+        round: Round,
+        first: (usize, HashValue),
+        second: (usize, HashValue),
+    },
+    /// An honest instance committed a block that isn't in the chain committed by another one:
+    /// the block of a round that the other chain skipped or a block skipping a round of it
+    ForkedCommits {
+        first: (usize, Round, HashValue),
+        second: (usize, Round, HashValue),
+    },
+    /// An honest instance didn't commit any block of the rounds after the scenario
+    NoProgress { instance: usize },
+}
+
+impl Display for ScenarioFailure {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ScenarioFailure::ConflictingCommits {
+                round,
+                first,
+                second,
+            } => write!(
+                f,
+                "conflicting commits in round {}: {} by n{} and {} by n{}",
+                round, first.1, first.0, second.1, second.0
+            ),
+            ScenarioFailure::ForkedCommits { first, second } => write!(
+                f,
+                "forked commits: {} in round {} by n{} and {} in round {} by n{}",
+                first.2, first.1, first.0, second.2, second.1, second.0
+            ),
+            ScenarioFailure::NoProgress { instance } => {
+                write!(f, "n{} made no progress after the scenario", instance)
+            }
+        }
+    }
+}
+
+/// Runs the scenarios on SMRNodes connected through a NetworkPlayground and checks:
+/// - safety: the chains committed by the honest instances are prefixes of one another,
+/// - liveness: once the scenario is over, with honest leaders and without partitions, every
+/// honest instance commits a block of a later round.
+pub struct ScenarioRunner {
+    // Number of the rounds after the scenario that are led by honest validators
+    liveness_rounds: u64,
+    round_initial_timeout_ms: u64,
+    // How long the honest instances are given to commit after the scenario
+    max_duration: Duration,
+    // Whether the nodes run the 2-chain commit rule with the fast commit path
+    fast_commit: bool,
+}
+
+impl Default for ScenarioRunner {
+    fn default() -> Self {
+        Self {
+            liveness_rounds: 50,
+            round_initial_timeout_ms: 500,
+            max_duration: Duration::from_secs(30),
+            fast_commit: false,
+        }
+    }
+}
+
+impl ScenarioRunner {
+    pub fn new(
+        liveness_rounds: u64,
+        round_initial_timeout_ms: u64,
+        max_duration: Duration,
+        fast_commit: bool,
+    ) -> Self {
+        Self {
+            liveness_rounds,
+            round_initial_timeout_ms,
+            max_duration,
+            fast_commit,
+        }
+    }
+
+    pub fn run(&self, scenario: &Scenario) -> Result<(), ScenarioFailure> {
+        let runtime = consensus_runtime();
+        let mut playground = NetworkPlayground::new(runtime.handle().clone());
+
+        let last_round = scenario.rounds.len() as Round;
+        let mut round_proposers: HashMap<Round, usize> = scenario
+            .rounds
+            .iter()
+            .enumerate()
+            .map(|(i, round)| (i as Round + 1, round.leader))
+            .collect();
+        // The rounds after the scenario rotate among the validators without twin, the later
+        // rounds default to the first validator.
+        let num_honest = (scenario.num_nodes - scenario.num_twins) as Round;
+        for round in last_round + 1..=last_round + self.liveness_rounds {
+            round_proposers.insert(round, scenario.num_twins + (round % num_honest) as usize);
+        }
+
+        let mut nodes = SMRNode::start_num_nodes_with_twins_and_timeout(
+            scenario.num_nodes,
+            scenario.num_twins,
+            &mut playground,
+            RoundProposer(HashMap::new()),
+            Some(round_proposers),
+            self.round_initial_timeout_ms,
+            self.fast_commit,
+        );
+
+        let round_partitions: HashMap<Round, Vec<Vec<TwinId>>> = scenario
+            .rounds
+            .iter()
+            .enumerate()
+            .map(|(i, round)| {
+                let partitions = round
+                    .partitions
+                    .iter()
+                    .map(|partition| {
+                        partition
+                            .iter()
+                            .map(|instance| nodes[*instance].id)
+                            .collect()
+                    })
+                    .collect();
+                (i as Round + 1, partitions)
+            })
+            .collect();
+        assert!(playground.split_network_round(&round_partitions));
+        runtime.spawn(playground.start());
+
+        let deadline = Instant::now() + self.max_duration;
+        let result = runtime.block_on(async {
+            let mut commits = Commits::default();
+            for (instance, node) in nodes.iter_mut().enumerate() {
+                if !scenario.is_honest(instance) {
+                    continue;
+                }
+                let mut progress = false;
+                while !progress {
+                    let ledger_info =
+                        match timeout_at(deadline, node.commit_cb_receiver.next()).await {
+                            Ok(Some(ledger_info)) => ledger_info,
+                            _ => return Err(ScenarioFailure::NoProgress { instance }),
+                        };
+                    let round = ledger_info.ledger_info().commit_info().round();
+                    commits.check_ledger_info(instance, &ledger_info)?;
+                    while let Ok(Some(blocks)) = node.committed_blocks_receiver.try_next() {
+                        commits.check_blocks(instance, &blocks)?;
+                    }
+                    progress = round > last_round;
+                }
+            }
+            // the commits of the instances that made progress first, after they did
+            for (instance, node) in nodes.iter_mut().enumerate() {
+                if !scenario.is_honest(instance) {
+                    continue;
+                }
+                while let Ok(Some(ledger_info)) = node.commit_cb_receiver.try_next() {
+                    commits.check_ledger_info(instance, &ledger_info)?;
+                }
+                while let Ok(Some(blocks)) = node.committed_blocks_receiver.try_next() {
+                    commits.check_blocks(instance, &blocks)?;
+                }
+            }
+            Ok(())
+        });
+        // the runtimes of the nodes can't be dropped within an async context
+        drop(nodes);
+        result
+    }
+}
+
+/// The chain committed by the honest instances: the committed blocks by round, with the first
+/// instance committing them, and the rounds skipped by the chain, between the round of a committed
+/// block and the one of its parent. The chains of the instances are prefixes of one another if no
+/// two blocks are committed in the same round and no block is committed in a skipped round.
+#[derive(Default)]
+struct Commits {
+    blocks: BTreeMap<Round, (usize, HashValue)>,
+    // the rounds skipped before a block, exclusive: the round of its parent and the instance
+    // committing it, by the round of the block
+    skipped: BTreeMap<Round, (Round, usize)>,
+}
+
+impl Commits {
+    /// The blocks of a commit, the ledger info of a node syncing has no parent to check.
+    fn check_blocks(&mut self, instance: usize, blocks: &[Block]) -> Result<(), ScenarioFailure> {
+        for block in blocks {
+            let parent = block.quorum_cert().certified_block();
+            self.check(instance, parent.round(), parent.id())?;
+            self.check(instance, block.round(), block.id())?;
+            if let Some((round, (first, id))) =
+                self.blocks.range(parent.round() + 1..block.round()).next()
+            {
+                return Err(ScenarioFailure::ForkedCommits {
+                    first: (*first, *round, *id),
+                    second: (instance, block.round(), block.id()),
+                });
+            }
+            self.skipped
+                .insert(block.round(), (parent.round(), instance));
+        }
+        Ok(())
+    }
+
+    fn check_ledger_info(
+        &mut self,
+        instance: usize,
+        ledger_info: &LedgerInfoWithSignatures,
+    ) -> Result<(), ScenarioFailure> {
+        let commit_info = ledger_info.ledger_info().commit_info();
+        self.check(instance, commit_info.round(), commit_info.id())
+    }
+
+    fn check(
+        &mut self,
+        instance: usize,
+        round: Round,
+        id: HashValue,
+    ) -> Result<(), ScenarioFailure> {
+        let first = *self.blocks.entry(round).or_insert((instance, id));
+        if first.1 != id {
+            return Err(ScenarioFailure::ConflictingCommits {
+                round,
+                first,
+                second: (instance, id),
+            });
+        }
+        // the skipped rounds don't overlap, only the first ones ending after the round may
+        // contain it
+        if let Some((end, (start, other))) = self.skipped.range(round + 1..).next() {
+            if *start < round {
+                return Err(ScenarioFailure::ForkedCommits {
+                    first: (*other, *end, self.blocks[end].1),
+                    second: (instance, round, id),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Commits, ScenarioFailure};
+    use aptos_types::validator_signer::ValidatorSigner;
+    use consensus_types::block::{
+        block_test_utils::{certificate_for_genesis, placeholder_certificate_for_block},
+        Block,
+    };
+
+    fn child(signer: &ValidatorSigner, parent: &Block, round: u64) -> Block {
+        let qc = placeholder_certificate_for_block(
+            vec![signer],
+            parent.id(),
+            parent.round(),
+            parent.quorum_cert().certified_block().id(),
+            parent.quorum_cert().certified_block().round(),
+        );
+        Block::new_proposal(vec![], round, round, qc, signer)
+    }
+
+    #[test]
+    fn test_forked_commits() {
+        let signer = ValidatorSigner::random(None);
+        let a1 = Block::new_proposal(vec![], 1, 1, certificate_for_genesis(), &signer);
+        let a2 = child(&signer, &a1, 2);
+        let a3 = child(&signer, &a2, 3);
+        // skips the round of a2
+        let b3 = child(&signer, &a1, 3);
+
+        let mut commits = Commits::default();
+        commits.check_blocks(0, &[a1.clone(), a2.clone()]).unwrap();
+        commits.check_blocks(1, &[a1.clone()]).unwrap();
+        commits.check_blocks(1, &[a2.clone(), a3]).unwrap();
+        assert!(matches!(
+            commits.check_blocks(2, &[b3.clone()]),
+            Err(ScenarioFailure::ConflictingCommits { round: 3, .. })
+        ));
+
+        // a fork committing at different rounds, in both orders
+        let mut commits = Commits::default();
+        commits.check_blocks(0, &[a1.clone(), a2.clone()]).unwrap();
+        assert!(matches!(
+            commits.check_blocks(1, &[a1.clone(), b3.clone()]),
+            Err(ScenarioFailure::ForkedCommits {
+                first: (0, 2, _),
+                second: (1, 3, _)
+            })
+        ));
+        let mut commits = Commits::default();
+        commits.check_blocks(1, &[a1, b3]).unwrap();
+        assert!(matches!(
+            commits.check_blocks(0, &[a2]),
+            Err(ScenarioFailure::ForkedCommits {
+                first: (1, 3, _),
+                second: (0, 2, _)
+            })
+        ));
+    }
+}
@@ -7,7 +7,9 @@ use crate::{
     network::NetworkTask,
     network_interface::{ConsensusNetworkEvents, ConsensusNetworkSender},
     network_tests::{NetworkPlayground, TwinId},
-    test_utils::{MockStateComputer, MockStorage, MockTransactionManager},
+    state_computer::ExecutionProxy,
+    state_replication::{StateComputer, TxnManager},
+    test_utils::{MockExecutor, MockStateComputer, MockStorage, MockTransactionManager},
     util::time_service::ClockTimeService,
 };
 use aptos_config::{
@@ -21,12 +23,19 @@ use aptos_config::{
 use aptos_mempool::mocks::MockSharedMempool;
 use aptos_types::{
     ledger_info::LedgerInfoWithSignatures,
-    on_chain_config::{OnChainConfig, OnChainConfigPayload, ValidatorSet},
+    on_chain_config::{
+        ConsensusConfigV1, ConsensusConfigV3, OnChainConfig, OnChainConfigPayload,
+        OnChainConsensusConfig, ValidatorSet,
+    },
+    transaction::Transaction,
     validator_info::ValidatorInfo,
     waypoint::Waypoint,
 };
 use channel::{self, aptos_channel, message_queues::QueueStyle};
-use consensus_types::common::{Author, Payload, Round};
+use consensus_types::{
+    block::Block,
+    common::{Author, Payload, Round},
+};
 use event_notifications::{ReconfigNotification, ReconfigNotificationListener};
 use futures::channel::mpsc;
 use network::{
@@ -46,6 +55,10 @@ pub struct SMRNode {
     pub id: TwinId,
     pub storage: Arc<MockStorage>,
     pub commit_cb_receiver: mpsc::UnboundedReceiver<LedgerInfoWithSignatures>,
+    pub committed_blocks_receiver: mpsc::UnboundedReceiver<Vec<Block>>,
+    /// The transactions of each commit, only sent by the nodes executing through the
+    /// `ExecutionProxy`.
+    pub committed_txns_receiver: mpsc::UnboundedReceiver<Vec<Transaction>>,
     _runtime: Runtime,
     _shared_mempool: MockSharedMempool,
     _state_sync: mpsc::UnboundedReceiver<Payload>,
@@ -61,6 +74,7 @@ impl SMRNode {
         config: NodeConfig,
         storage: Arc<MockStorage>,
         twin_id: TwinId,
+        onchain_consensus_config: OnChainConsensusConfig,
     ) -> Self {
         let (network_reqs_tx, network_reqs_rx) = aptos_channel::new(QueueStyle::FIFO, 8, None);
         let (connection_reqs_tx, _) = aptos_channel::new(QueueStyle::FIFO, 8, None);
@@ -76,18 +90,47 @@ impl SMRNode {
 
         playground.add_node(twin_id, consensus_tx, network_reqs_rx, conn_mgr_reqs_rx);
 
+        let runtime = Builder::new_multi_thread()
+            .thread_name(format!(
+                "{}-node-{}",
+                twin_id.id,
+                std::thread::current().name().unwrap_or("")
+            ))
+            .enable_all()
+            .build()
+            .unwrap();
+
         let (state_sync_client, state_sync) = mpsc::unbounded();
         let (commit_cb_sender, commit_cb_receiver) = mpsc::unbounded::<LedgerInfoWithSignatures>();
+        let (committed_blocks_sender, committed_blocks_receiver) = mpsc::unbounded();
+        let (committed_txns_sender, committed_txns_receiver) = mpsc::unbounded();
         let shared_mempool = MockSharedMempool::new();
         let consensus_to_mempool_sender = shared_mempool.consensus_sender.clone();
-        let state_computer = Arc::new(MockStateComputer::new(
-            state_sync_client,
-            commit_cb_sender,
-            Arc::clone(&storage),
-        ));
-        let txn_manager = Arc::new(MockTransactionManager::new(Some(
+        let txn_manager: Arc<dyn TxnManager> = Arc::new(MockTransactionManager::new(Some(
             consensus_to_mempool_sender,
         )));
+        let state_computer: Arc<dyn StateComputer> = if onchain_consensus_config
+            .quorum_store_enabled()
+        {
+            // Only the execution proxy reads the transactions of the blocks from the batches
+            // of their proofs of store.
+            let executor = MockExecutor::new(
+                Arc::clone(&storage),
+                commit_cb_sender,
+                committed_txns_sender,
+            );
+            Arc::new(ExecutionProxy::new(
+                Box::new(executor.clone()),
+                txn_manager.clone(),
+                Arc::new(executor),
+                runtime.handle(),
+            ))
+        } else {
+            Arc::new(
+                MockStateComputer::new(state_sync_client, commit_cb_sender, Arc::clone(&storage))
+                    .with_committed_blocks(committed_blocks_sender),
+            )
+        };
         let (reconfig_sender, reconfig_events) = aptos_channel::new(QueueStyle::LIFO, 1, None);
         let reconfig_listener = ReconfigNotificationListener {
             notification_receiver: reconfig_events,
@@ -97,6 +140,11 @@ impl SMRNode {
             ValidatorSet::CONFIG_ID,
             bcs::to_bytes(storage.get_validator_set()).unwrap(),
         );
+        // the on-chain consensus config is stored as serialized bytes in a Move resource
+        configs.insert(
+            OnChainConsensusConfig::CONFIG_ID,
+            bcs::to_bytes(&bcs::to_bytes(&onchain_consensus_config).unwrap()).unwrap(),
+        );
         let payload = OnChainConfigPayload::new(1, Arc::new(configs));
         reconfig_sender
             .push(
@@ -108,16 +156,6 @@ impl SMRNode {
             )
             .unwrap();
 
-        let runtime = Builder::new_multi_thread()
-            .thread_name(format!(
-                "{}-node-{}",
-                twin_id.id,
-                std::thread::current().name().unwrap_or("")
-            ))
-            .enable_all()
-            .build()
-            .unwrap();
-
         let time_service = Arc::new(ClockTimeService::new(runtime.handle().clone()));
 
         let (timeout_sender, timeout_receiver) =
@@ -143,6 +181,8 @@ impl SMRNode {
             id: twin_id,
             _runtime: runtime,
             commit_cb_receiver,
+            committed_blocks_receiver,
+            committed_txns_receiver,
             storage,
             _shared_mempool: shared_mempool,
             _state_sync: state_sync,
@@ -157,6 +197,92 @@ impl SMRNode {
         proposer_type: ConsensusProposerType,
         round_proposers_idx: Option<HashMap<Round, usize>>,
     ) -> Vec<Self> {
+        // Disable timeout in twins test to avoid flakiness
+        Self::start_num_nodes_with_twins_and_timeout(
+            num_nodes,
+            num_twins,
+            playground,
+            proposer_type,
+            round_proposers_idx,
+            2_000_000,
+            false,
+        )
+    }
+
+    /// Starts a given number of nodes and their twins, the rounds timing out after the given
+    /// initial timeout. The twin of the node i is the node num_nodes + i. The fast commit path
+    /// requires the 2-chain commit rule, which is enabled with it.
+    pub fn start_num_nodes_with_twins_and_timeout(
+        num_nodes: usize,
+        num_twins: usize,
+        playground: &mut NetworkPlayground,
+        proposer_type: ConsensusProposerType,
+        round_proposers_idx: Option<HashMap<Round, usize>>,
+        round_initial_timeout_ms: u64,
+        fast_commit: bool,
+    ) -> Vec<Self> {
+        let (mut node_configs, validator_set) = Self::generate_configs(
+            num_nodes,
+            num_twins,
+            playground,
+            proposer_type,
+            round_proposers_idx,
+        );
+        for config in node_configs.iter_mut() {
+            config.consensus.round_initial_timeout_ms = round_initial_timeout_ms;
+            config.consensus.enable_fast_commit = fast_commit;
+        }
+        let onchain_consensus_config = OnChainConsensusConfig::V1(ConsensusConfigV1 {
+            two_chain: fast_commit,
+        });
+        Self::start_nodes(
+            playground,
+            node_configs,
+            validator_set,
+            onchain_consensus_config,
+        )
+    }
+
+    /// Starts a given number of nodes with the quorum store enabled on chain: the blocks carry
+    /// the proofs of store of the batches that the nodes disseminate, and are executed through
+    /// the `ExecutionProxy` in the decoupled execution pipeline.
+    pub fn start_num_nodes_with_quorum_store(
+        num_nodes: usize,
+        playground: &mut NetworkPlayground,
+    ) -> Vec<Self> {
+        let (node_configs, validator_set) = Self::generate_configs(
+            num_nodes,
+            0,
+            playground,
+            ConsensusProposerType::RotatingProposer,
+            None,
+        );
+        let onchain_consensus_config = OnChainConsensusConfig::V3(ConsensusConfigV3 {
+            two_chain: true,
+            decoupled_execution: true,
+            back_pressure_limit: 10,
+            exclude_round: 20,
+            max_block_bytes: u64::MAX,
+            quorum_store_enabled: true,
+            max_failed_authors_to_store: 0,
+        });
+        Self::start_nodes(
+            playground,
+            node_configs,
+            validator_set,
+            onchain_consensus_config,
+        )
+    }
+
+    /// Generates the configs of the given number of nodes followed by the ones of their twins,
+    /// along with the validator set, which doesn't include the twins.
+    fn generate_configs(
+        num_nodes: usize,
+        num_twins: usize,
+        playground: &mut NetworkPlayground,
+        proposer_type: ConsensusProposerType,
+        round_proposers_idx: Option<HashMap<Round, usize>>,
+    ) -> (Vec<NodeConfig>, ValidatorSet) {
         assert!(num_nodes >= num_twins);
         let ValidatorSwarm {
             nodes: mut node_configs,
@@ -165,6 +291,8 @@ impl SMRNode {
         node_configs.iter().for_each(|config| {
             let mut conn_meta = ConnectionMetadata::mock(author_from_config(config));
             conn_meta.application_protocols = ProtocolIdSet::from_iter([
+                ProtocolId::ConsensusDirectSendCompressed,
+                ProtocolId::ConsensusRpcCompressed,
                 ProtocolId::ConsensusDirectSendJson,
                 ProtocolId::ConsensusDirectSendBcs,
                 ProtocolId::ConsensusRpcBcs,
@@ -201,6 +329,9 @@ impl SMRNode {
             }
             _ => proposer_type,
         };
+        for config in node_configs.iter_mut() {
+            config.consensus.proposer_type = proposer_type.clone();
+        }
 
         // We don't add twins to ValidatorSet or round_proposers above
         // because a node with twins should be treated the same at the
@@ -209,7 +340,15 @@ impl SMRNode {
             let twin = node_configs[i].clone();
             node_configs.push(twin);
         }
+        (node_configs, validator_set)
+    }
 
+    fn start_nodes(
+        playground: &mut NetworkPlayground,
+        node_configs: Vec<NodeConfig>,
+        validator_set: ValidatorSet,
+        onchain_consensus_config: OnChainConsensusConfig,
+    ) -> Vec<Self> {
         let mut smr_nodes = vec![];
 
         for (smr_id, mut config) in node_configs.into_iter().enumerate() {
@@ -225,16 +364,19 @@ impl SMRNode {
                 .unwrap()
                 .waypoint = Some(waypoint);
             config.base.waypoint = WaypointConfig::FromConfig(waypoint);
-            config.consensus.proposer_type = proposer_type.clone();
             config.consensus.safety_rules.verify_vote_proposal_signature = false;
-            // Disable timeout in twins test to avoid flakiness
-            config.consensus.round_initial_timeout_ms = 2_000_000;
 
             let author = author_from_config(&config);
 
             let twin_id = TwinId { id: smr_id, author };
 
-            smr_nodes.push(Self::start(playground, config, storage, twin_id));
+            smr_nodes.push(Self::start(
+                playground,
+                config,
+                storage,
+                twin_id,
+                onchain_consensus_config.clone(),
+            ));
         }
         smr_nodes
     }
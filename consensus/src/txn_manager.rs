@@ -53,10 +53,12 @@ impl MempoolProxy {
     async fn pull_internal(
         &self,
         max_size: u64,
+        max_bytes: u64,
         exclude_txns: Vec<TransactionSummary>,
     ) -> Result<Payload, MempoolError> {
         let (callback, callback_rcv) = oneshot::channel();
-        let req = ConsensusRequest::GetBlockRequest(max_size, exclude_txns.clone(), callback);
+        let req =
+            ConsensusRequest::GetBlockRequest(max_size, max_bytes, exclude_txns.clone(), callback);
         // send to shared mempool
         self.consensus_to_mempool_sender
             .clone()
@@ -89,6 +91,7 @@ impl TxnManager for MempoolProxy {
     async fn pull_txns(
         &self,
         max_size: u64,
+        max_bytes: u64,
         exclude_payloads: Vec<&Payload>,
         wait_callback: BoxFuture<'static, ()>,
         pending_ordering: bool,
@@ -110,7 +113,9 @@ impl TxnManager for MempoolProxy {
         let mut count = self.poll_count;
         let txns = loop {
             count -= 1;
-            let txns = self.pull_internal(max_size, exclude_txns.clone()).await?;
+            let txns = self
+                .pull_internal(max_size, max_bytes, exclude_txns.clone())
+                .await?;
             if txns.is_empty() && !pending_ordering && count > 0 {
                 if let Some(callback) = callback_wrapper.take() {
                     callback.await;
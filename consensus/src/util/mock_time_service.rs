@@ -27,6 +27,7 @@ struct SimulatedTimeServiceInner {
     max: Duration,
 }
 
+#[async_trait::async_trait]
 impl TimeService for SimulatedTimeService {
     fn run_after(&self, timeout: Duration, mut t: Box<dyn ScheduledTask>) -> AbortHandle {
         let mut inner = self.inner.lock();
@@ -61,7 +62,7 @@ impl TimeService for SimulatedTimeService {
         self.inner.lock().now
     }
 
-    fn sleep(&self, t: Duration) {
+    async fn sleep(&self, t: Duration) {
         let inner = self.inner.clone();
         let mut inner = inner.lock();
         inner.now += t;
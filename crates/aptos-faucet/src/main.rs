@@ -1,7 +1,6 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use aptos::common::types::EncodingType;
 use aptos_crypto::ed25519;
 use aptos_logger::info;
 use aptos_sdk::types::{
@@ -67,9 +66,8 @@ async fn main() {
         args.maximum_amount,
     );
 
-    let key: ed25519::Ed25519PrivateKey = EncodingType::BCS
-        .load_key("Ed25519PrivateKey", Path::new(&args.mint_key_file_path))
-        .unwrap();
+    let key: ed25519::Ed25519PrivateKey =
+        bcs::from_bytes(&std::fs::read(Path::new(&args.mint_key_file_path)).unwrap()).unwrap();
 
     let faucet_address: AccountAddress =
         args.mint_account_address.unwrap_or_else(aptos_root_address);
@@ -114,8 +112,11 @@ async fn main() {
 
 #[cfg(test)]
 mod tests {
-    use aptos::op::key::GenerateKey;
-    use aptos_crypto::{ed25519::Ed25519PublicKey, hash::HashValue, PrivateKey};
+    use aptos_crypto::{
+        ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
+        hash::HashValue,
+        PrivateKey, Uniform,
+    };
     use aptos_faucet::{routes, Service};
     use aptos_infallible::RwLock;
     use aptos_rest_client::{
@@ -137,6 +138,7 @@ mod tests {
             LocalAccount,
         },
     };
+    use rand::{rngs::StdRng, SeedableRng};
     use serde::Serialize;
     use std::{
         collections::HashMap,
@@ -165,7 +167,7 @@ mod tests {
     }
 
     fn setup(maximum_amount: Option<u64>) -> (AccountStates, Arc<Service>) {
-        let key = GenerateKey::generate_ed25519_in_memory();
+        let key = Ed25519PrivateKey::generate(&mut StdRng::from_entropy());
         let account_address = AuthenticationKey::ed25519(&key.public_key()).derived_address();
 
         let faucet_account = LocalAccount::new(account_address, key, 0);
@@ -2,17 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::{anyhow, Result};
-pub use aptos_api_types::{self, MoveModuleBytecode, PendingTransaction, Transaction};
+pub use aptos_api_types::{self, Event, MoveModuleBytecode, PendingTransaction, Transaction};
 use aptos_crypto::HashValue;
 use aptos_types::{
     account_address::AccountAddress, account_config::aptos_root_address,
+    account_state::AccountState, account_state_blob::AccountStateBlob, event::EventKey,
     transaction::SignedTransaction,
 };
+use move_core_types::move_resource::MoveResource;
 use reqwest::{header::CONTENT_TYPE, Client as ReqwestClient, StatusCode};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::{json, Value};
 use state::State;
-use std::time::Duration;
+use std::{convert::TryFrom, time::Duration};
 use url::Url;
 pub mod error;
 pub mod faucet;
@@ -22,7 +24,7 @@ pub use response::Response;
 mod state;
 pub mod types;
 use crate::aptos::{AptosVersion, Balance};
-pub use types::{Account, Resource, RestError};
+pub use types::{Account, Resource, RestError, WaitForTransactionError};
 pub mod aptos;
 
 const BCS_CONTENT_TYPE: &str = "application/x.diem.signed_transaction+bcs";
@@ -102,6 +104,22 @@ impl Client {
         self.json(response).await
     }
 
+    /// Executes `txn` on the latest state of the node without submitting it.
+    pub async fn simulate(&self, txn: &SignedTransaction) -> Result<Response<Transaction>> {
+        let txn_payload = bcs::to_bytes(txn)?;
+        let url = self.base_url.join("transactions/simulate")?;
+
+        let response = self
+            .inner
+            .post(url)
+            .header(CONTENT_TYPE, BCS_CONTENT_TYPE)
+            .body(txn_payload)
+            .send()
+            .await?;
+
+        self.json(response).await
+    }
+
     pub async fn submit_and_wait(&self, txn: &SignedTransaction) -> Result<Response<Transaction>> {
         self.submit(txn).await?;
         self.wait_for_signed_transaction(txn).await
@@ -151,22 +169,22 @@ impl Client {
                 let (transaction, state) = txn_resp.into_parts();
                 if !transaction.is_pending() {
                     if !transaction.success() {
-                        return Err(anyhow!(
-                            "transaction execution failed: {}",
-                            transaction.vm_status()
-                        ));
+                        return Err(WaitForTransactionError::ExecutionFailed(
+                            transaction.vm_status(),
+                        )
+                        .into());
                     }
                     return Ok(Response::new(transaction, state));
                 }
                 if expiration_timestamp_secs <= state.timestamp_usecs / 1_000_000 {
-                    return Err(anyhow!("transaction expired"));
+                    return Err(WaitForTransactionError::Expired.into());
                 }
             }
 
             tokio::time::sleep(DEFAULT_DELAY).await;
         }
 
-        Err(anyhow!("timeout"))
+        Err(WaitForTransactionError::Timeout.into())
     }
 
     pub async fn get_transactions(
@@ -241,6 +259,28 @@ impl Client {
         self.json(response).await
     }
 
+    pub async fn get_events(
+        &self,
+        event_key: EventKey,
+        start: Option<u64>,
+        limit: Option<u64>,
+    ) -> Result<Response<Vec<Event>>> {
+        let url = self.base_url.join(&format!("events/{:#x}", event_key))?;
+
+        let mut request = self.inner.get(url);
+        if let Some(start) = start {
+            request = request.query(&[("start", start)])
+        }
+
+        if let Some(limit) = limit {
+            request = request.query(&[("limit", limit)])
+        }
+
+        let response = request.send().await?;
+
+        self.json(response).await
+    }
+
     pub async fn get_account_state_blob(
         &self,
         address: AccountAddress,
@@ -286,6 +326,19 @@ impl Client {
         })
     }
 
+    /// Fetches a resource as its typed Rust mirror, e.g. as generated by
+    /// `transaction-builder-generator` from the Move module defining it
+    pub async fn get_move_resource<T: MoveResource>(
+        &self,
+        address: AccountAddress,
+    ) -> Result<Response<Option<T>>> {
+        let response = self.get_account_state_blob(address).await?;
+        response.and_then(|blob| {
+            let state = AccountState::try_from(&AccountStateBlob::from(blob))?;
+            state.get_resource::<T>()
+        })
+    }
+
     pub async fn get_account_resource(
         &self,
         address: AccountAddress,
@@ -331,6 +384,23 @@ impl Client {
         self.json(response).await
     }
 
+    pub async fn view(
+        &self,
+        function: &str,
+        type_arguments: &[&str],
+        arguments: Vec<Value>,
+    ) -> Result<Response<Vec<Value>>> {
+        let url = self.base_url.join("view")?;
+        let data = json!({
+            "function": function,
+            "type_arguments": type_arguments,
+            "arguments": arguments,
+        });
+
+        let response = self.inner.post(url).json(&data).send().await?;
+        self.json(response).await
+    }
+
     pub async fn get_account(&self, address: AccountAddress) -> Result<Response<Account>> {
         let url = self.base_url.join(&format!("accounts/{}", address))?;
         let response = self.inner.get(url).send().await?;
@@ -342,8 +412,9 @@ impl Client {
         response: reqwest::Response,
     ) -> Result<(reqwest::Response, State)> {
         if !response.status().is_success() {
+            // Keep the `RestError` so that callers can inspect it through `downcast_ref`
             let error_response = response.json::<RestError>().await?;
-            return Err(anyhow::anyhow!("Request failed: {:?}", error_response));
+            return Err(error_response.into());
         }
         let state = State::from_headers(response.headers())?;
 
@@ -5,7 +5,7 @@ use aptos_api_types::{Address, U64};
 use aptos_types::transaction::authenticator::AuthenticationKey;
 use move_core_types::{language_storage::StructTag, parser::parse_struct_tag};
 use serde::{Deserialize, Deserializer, Serialize};
-use std::str::FromStr;
+use std::{fmt, str::FromStr};
 
 #[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct RestError {
@@ -14,6 +14,43 @@ pub struct RestError {
     pub aptos_ledger_version: Option<U64>,
 }
 
+impl fmt::Display for RestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Request failed with code {}: {}",
+            self.code, self.message
+        )
+    }
+}
+
+impl std::error::Error for RestError {}
+
+/// Why waiting for a transaction through the `Client` did not return it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WaitForTransactionError {
+    /// The transaction was committed, but its execution failed with the given VM status.
+    ExecutionFailed(String),
+    /// The transaction expired before being committed, it can't be committed anymore.
+    Expired,
+    /// The transaction was neither committed nor expired before the wait timed out.
+    Timeout,
+}
+
+impl fmt::Display for WaitForTransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WaitForTransactionError::ExecutionFailed(vm_status) => {
+                write!(f, "transaction execution failed: {}", vm_status)
+            }
+            WaitForTransactionError::Expired => write!(f, "transaction expired"),
+            WaitForTransactionError::Timeout => write!(f, "timeout"),
+        }
+    }
+}
+
+impl std::error::Error for WaitForTransactionError {}
+
 #[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct Resource {
     #[serde(rename = "type", deserialize_with = "deserialize_resource_type")]
@@ -0,0 +1,39 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A command to show the coin balance of an account
+//!
+
+use crate::common::types::{
+    account_or_profile_account, CliError, CliTypedResult, ProfileOptions, RestOptions,
+};
+use aptos_rest_client::Client;
+use aptos_types::account_address::AccountAddress;
+use clap::Parser;
+
+/// Command to show the coin balance of an account
+///
+#[derive(Debug, Parser)]
+pub struct Balance {
+    #[clap(flatten)]
+    rest_options: RestOptions,
+    #[clap(flatten)]
+    profile_options: ProfileOptions,
+
+    /// Address of the account, defaults to the account of the profile
+    #[clap(long)]
+    account: Option<AccountAddress>,
+}
+
+impl Balance {
+    pub async fn execute(self) -> CliTypedResult<u64> {
+        let account = account_or_profile_account(self.account, &self.profile_options.profile)?;
+        let client = Client::new(self.rest_options.url(&self.profile_options.profile)?);
+        let balance = client
+            .get_account_balance(account)
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?
+            .into_inner();
+        Ok(balance.get())
+    }
+}
@@ -15,7 +15,7 @@ use crate::common::{
 };
 use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey};
 use aptos_rest_client::{Client as RestClient, Response, Transaction};
-use aptos_sdk::{transaction_builder::TransactionFactory, types::LocalAccount};
+use aptos_sdk::types::LocalAccount;
 use aptos_transaction_builder::aptos_stdlib;
 use aptos_types::account_address::AccountAddress;
 use clap::Parser;
@@ -46,13 +46,15 @@ impl CreateAccount {
     pub async fn execute(self) -> CliTypedResult<String> {
         let public_key_to_create = self
             .public_key_options
-            .extract_public_key(self.encoding_options.encoding)?;
+            .extract_public_key(self.encoding_options.encoding, self.write_options.profile())?;
         let address = account_address_from_public_key(&public_key_to_create);
 
         if self.use_faucet {
             let faucet_url = if let Some(faucet_url) = self.faucet_url {
                 faucet_url
-            } else if let Some(url) = CliConfig::load()?.faucet_url {
+            } else if let Some(url) = CliConfig::load_profile(self.write_options.profile())?
+                .and_then(|profile_config| profile_config.faucet_url)
+            {
                 Url::parse(&url)
                     .map_err(|err| CliError::UnableToParse("config faucet_url", err.to_string()))?
             } else {
@@ -75,10 +77,8 @@ impl CreateAccount {
         sender_address: AccountAddress,
         sequence_number: u64,
     ) -> CliTypedResult<Response<Transaction>> {
-        let client = RestClient::new(reqwest::Url::clone(&self.write_options.rest_options.url()?));
-        let transaction_factory = TransactionFactory::new(self.write_options.chain_id().await?)
-            .with_gas_unit_price(1)
-            .with_max_gas_amount(self.write_options.max_gas);
+        let client = RestClient::new(self.write_options.url()?);
+        let transaction_factory = self.write_options.transaction_factory().await?;
         let sender_account = &mut LocalAccount::new(sender_address, sender_key, sequence_number);
         let transaction = sender_account.sign_with_transaction_builder(
             transaction_factory
@@ -116,11 +116,10 @@ impl CreateAccount {
     }
 
     async fn create_account_with_key(self, address: AccountAddress) -> CliTypedResult<()> {
-        let client = RestClient::new(reqwest::Url::clone(&self.write_options.rest_options.url()?));
+        let client = RestClient::new(self.write_options.url()?);
         let sender_private_key = self
             .write_options
-            .private_key_options
-            .extract_private_key(self.encoding_options.encoding)?;
+            .private_key(self.encoding_options.encoding)?;
         let sender_public_key = sender_private_key.public_key();
         let sender_address = account_address_from_public_key(&sender_public_key);
         let sequence_number = client
@@ -0,0 +1,44 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A command to list the transactions sent by an account
+//!
+
+use crate::common::types::{
+    account_or_profile_account, CliError, CliTypedResult, ProfileOptions, RestOptions,
+};
+use aptos_rest_client::{Client, Transaction};
+use aptos_types::account_address::AccountAddress;
+use clap::Parser;
+
+/// Command to list the transactions sent by an account, oldest first
+///
+#[derive(Debug, Parser)]
+pub struct History {
+    #[clap(flatten)]
+    rest_options: RestOptions,
+    #[clap(flatten)]
+    profile_options: ProfileOptions,
+
+    /// Address of the account, defaults to the account of the profile
+    #[clap(long)]
+    account: Option<AccountAddress>,
+    /// Sequence number of the first transaction to list
+    #[clap(long)]
+    start: Option<u64>,
+    /// Maximum number of transactions to list
+    #[clap(long)]
+    limit: Option<u64>,
+}
+
+impl History {
+    pub async fn execute(self) -> CliTypedResult<Vec<Transaction>> {
+        let account = account_or_profile_account(self.account, &self.profile_options.profile)?;
+        let client = Client::new(self.rest_options.url(&self.profile_options.profile)?);
+        Ok(client
+            .get_account_transactions(account, self.start, self.limit)
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?
+            .into_inner())
+    }
+}
@@ -7,9 +7,8 @@
 //!
 
 use crate::common::types::{
-    account_address_from_public_key, CliConfig, CliError, CliTypedResult, RestOptions,
+    account_or_profile_account, CliError, CliTypedResult, ProfileOptions, RestOptions,
 };
-use aptos_crypto::PrivateKey;
 use aptos_rest_client::{types::Resource, Client};
 use aptos_types::account_address::AccountAddress;
 use clap::Parser;
@@ -20,6 +19,8 @@ use clap::Parser;
 pub struct ListResources {
     #[clap(flatten)]
     rest_options: RestOptions,
+    #[clap(flatten)]
+    profile_options: ProfileOptions,
 
     /// Address of account you want to list resources for
     #[clap(long)]
@@ -30,18 +31,8 @@ impl ListResources {
     // TODO: Format this in a reasonable way while providing all information
     // add options like --tokens --nfts etc
     pub(crate) async fn execute(self) -> CliTypedResult<Vec<serde_json::Value>> {
-        let account = if let Some(account) = self.account {
-            account
-        } else if let Some(private_key) = CliConfig::load()?.private_key {
-            let public_key = private_key.public_key();
-            account_address_from_public_key(&public_key)
-        } else {
-            return Err(CliError::CommandArgumentError(
-                "Please provide an account using --account or run aptos init".to_string(),
-            ));
-        };
-
-        let client = Client::new(self.rest_options.url()?);
+        let account = account_or_profile_account(self.account, &self.profile_options.profile)?;
+        let client = Client::new(self.rest_options.url(&self.profile_options.profile)?);
         let response: Vec<Resource> = client
             .get_account_resources(account)
             .await
@@ -4,8 +4,12 @@
 use crate::common::{types::CliResult, utils::to_common_result};
 use clap::Subcommand;
 
+pub mod balance;
 pub mod create;
+pub mod history;
 pub mod list;
+pub mod rotate_key;
+pub mod transfer;
 
 /// CLI tool for interacting with accounts
 ///
@@ -13,6 +17,10 @@ pub mod list;
 pub enum AccountTool {
     Create(create::CreateAccount),
     List(list::ListResources),
+    Transfer(transfer::TransferCoins),
+    Balance(balance::Balance),
+    RotateKey(rotate_key::RotateKey),
+    History(history::History),
 }
 
 impl AccountTool {
@@ -20,6 +28,10 @@ impl AccountTool {
         match self {
             AccountTool::Create(tool) => to_common_result(tool.execute().await),
             AccountTool::List(tool) => to_common_result(tool.execute().await),
+            AccountTool::Transfer(tool) => to_common_result(tool.execute().await),
+            AccountTool::Balance(tool) => to_common_result(tool.execute().await),
+            AccountTool::RotateKey(tool) => to_common_result(tool.execute().await),
+            AccountTool::History(tool) => to_common_result(tool.execute().await),
         }
     }
 }
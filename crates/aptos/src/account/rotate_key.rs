@@ -0,0 +1,91 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A command to rotate the key of an account
+//!
+
+use crate::common::types::{
+    CliConfig, CliError, CliTypedResult, EncodingOptions, WriteTransactionOptions,
+};
+use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey};
+use aptos_rest_client::Transaction;
+use aptos_transaction_builder::aptos_stdlib;
+use aptos_types::{account_address::AccountAddress, transaction::authenticator::AuthenticationKey};
+use clap::Parser;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Command to rotate the authentication key of the sender to a new key
+///
+/// The account keeps its address.  When the sender is the account of the profile, the profile
+/// is updated to sign with the new key.
+#[derive(Debug, Parser)]
+pub struct RotateKey {
+    #[clap(flatten)]
+    encoding_options: EncodingOptions,
+    #[clap(flatten)]
+    write_options: WriteTransactionOptions,
+    /// New private key input file name
+    #[clap(long, group = "new_private_key_input", parse(from_os_str))]
+    new_private_key_file: Option<PathBuf>,
+    /// New private key encoded in a type as shown in `encoding`
+    #[clap(long, group = "new_private_key_input")]
+    new_private_key: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RotateKeySummary {
+    account: AccountAddress,
+    authentication_key: AuthenticationKey,
+    profile_updated: bool,
+    transaction: Transaction,
+}
+
+impl RotateKey {
+    pub async fn execute(self) -> CliTypedResult<RotateKeySummary> {
+        let encoding = self.encoding_options.encoding;
+        let new_private_key: Ed25519PrivateKey = if let Some(ref file) = self.new_private_key_file {
+            encoding.load_key("--new-private-key-file", file.as_path())?
+        } else if let Some(ref key) = self.new_private_key {
+            encoding.decode_key("--new-private-key", key.as_bytes().to_vec())?
+        } else {
+            return Err(CliError::CommandArgumentError(
+                "One of ['--new-private-key', '--new-private-key-file'] must be used".to_string(),
+            ));
+        };
+        let authentication_key = AuthenticationKey::ed25519(&new_private_key.public_key());
+
+        let sender_key = self.write_options.private_key(encoding)?;
+        let account = self.write_options.sender_address(&sender_key)?;
+        let transaction = self
+            .write_options
+            .submit_transaction(
+                encoding,
+                aptos_stdlib::encode_rotate_authentication_key_script_function(
+                    authentication_key.to_vec(),
+                ),
+            )
+            .await?;
+
+        // The old key can't sign for the account anymore, so keep the profile usable
+        let mut profile_updated = false;
+        if CliConfig::config_exists()? {
+            let mut config = CliConfig::load()?;
+            if let Some(profile_config) = config.profiles.get_mut(self.write_options.profile()) {
+                if profile_config.account_address() == Some(account) {
+                    profile_config.private_key = Some(new_private_key);
+                    profile_config.account = Some(account);
+                    config.save()?;
+                    profile_updated = true;
+                }
+            }
+        }
+
+        Ok(RotateKeySummary {
+            account,
+            authentication_key,
+            profile_updated,
+            transaction,
+        })
+    }
+}
@@ -0,0 +1,38 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A command to transfer coins between accounts
+//!
+
+use crate::common::types::{CliTypedResult, EncodingOptions, WriteTransactionOptions};
+use aptos_rest_client::Transaction;
+use aptos_transaction_builder::aptos_stdlib;
+use aptos_types::account_address::AccountAddress;
+use clap::Parser;
+
+/// Command to transfer coins from the sender to another account
+///
+#[derive(Debug, Parser)]
+pub struct TransferCoins {
+    #[clap(flatten)]
+    encoding_options: EncodingOptions,
+    #[clap(flatten)]
+    write_options: WriteTransactionOptions,
+    /// Address of the account receiving the coins
+    #[clap(long)]
+    receiver: AccountAddress,
+    /// Number of coins to transfer
+    #[clap(long)]
+    amount: u64,
+}
+
+impl TransferCoins {
+    pub async fn execute(self) -> CliTypedResult<Transaction> {
+        self.write_options
+            .submit_transaction(
+                self.encoding_options.encoding,
+                aptos_stdlib::encode_transfer_script_function(self.receiver, self.amount),
+            )
+            .await
+    }
+}
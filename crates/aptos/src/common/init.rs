@@ -4,80 +4,141 @@
 use crate::{
     account::create::CreateAccount,
     common::{
-        types::{account_address_from_public_key, CliConfig, CliError, CliTypedResult},
+        types::{
+            account_address_from_public_key, CliConfig, CliError, CliTypedResult, ProfileOptions,
+        },
         utils::prompt_yes,
     },
     op::key::GenerateKey,
 };
 use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, ValidCryptoMaterialStringExt};
-use clap::Parser;
+use clap::{ArgEnum, Parser};
+use std::str::FromStr;
 
 pub const DEFAULT_REST_URL: &str = "https://fullnode.devnet.aptoslabs.com";
 pub const DEFAULT_FAUCET_URL: &str = "https://faucet.devnet.aptoslabs.com";
+pub const DEFAULT_MAX_GAS: u64 = 1000;
+pub const DEFAULT_GAS_UNIT_PRICE: u64 = 1;
+const TESTNET_REST_URL: &str = "https://fullnode.testnet.aptoslabs.com";
+const TESTNET_FAUCET_URL: &str = "https://faucet.testnet.aptoslabs.com";
+const LOCAL_REST_URL: &str = "http://127.0.0.1:8080";
+const LOCAL_FAUCET_URL: &str = "http://127.0.0.1:8081";
 const NUM_DEFAULT_COINS: u64 = 10000;
 
-/// Tool to initialize current directory for the aptos tool
+/// Networks with well known endpoints
+#[derive(ArgEnum, Clone, Copy, Debug)]
+pub enum Network {
+    /// The Aptos devnet
+    Devnet,
+    /// The Aptos testnet
+    Testnet,
+    /// A local testnet started with `aptos node run-local-testnet`
+    Local,
+    /// Any other network, the endpoints are prompted for
+    Custom,
+}
+
+impl Network {
+    /// Rest and faucet endpoints of the network, if well known
+    fn endpoints(self) -> Option<(&'static str, &'static str)> {
+        match self {
+            Network::Devnet => Some((DEFAULT_REST_URL, DEFAULT_FAUCET_URL)),
+            Network::Testnet => Some((TESTNET_REST_URL, TESTNET_FAUCET_URL)),
+            Network::Local => Some((LOCAL_REST_URL, LOCAL_FAUCET_URL)),
+            Network::Custom => None,
+        }
+    }
+}
+
+impl FromStr for Network {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "devnet" => Ok(Network::Devnet),
+            "testnet" => Ok(Network::Testnet),
+            "local" => Ok(Network::Local),
+            "custom" => Ok(Network::Custom),
+            _ => Err("Invalid network"),
+        }
+    }
+}
+
+/// Tool to initialize a profile in the current directory for the aptos tool
 #[derive(Debug, Parser)]
-pub struct InitTool {}
+pub struct InitTool {
+    #[clap(flatten)]
+    profile_options: ProfileOptions,
+    /// Network of the profile: `devnet`, `testnet`, `local`, or `custom`
+    ///
+    /// The endpoints of `devnet`, `testnet` and `local` are filled in, otherwise they are prompted for
+    #[clap(long)]
+    network: Option<Network>,
+    /// Default maximum gas for transactions of the profile
+    #[clap(long)]
+    max_gas: Option<u64>,
+    /// Default gas unit price for transactions of the profile
+    #[clap(long)]
+    gas_unit_price: Option<u64>,
+}
 
 impl InitTool {
     pub async fn execute(self) -> CliTypedResult<()> {
+        let profile = self.profile_options.profile;
         let mut config = if CliConfig::config_exists()? {
-            if !prompt_yes(
-                "Aptos already initialized, do you want to overwrite the existing config?",
-            ) {
-                eprintln!("Exiting...");
-                return Ok(());
-            }
             CliConfig::load()?
         } else {
             CliConfig::default()
         };
-
-        // Rest Endpoint
-        eprintln!(
-            "Enter your rest endpoint [Current: {} No input: {}]",
-            config.rest_url.unwrap_or_else(|| "None".to_string()),
-            DEFAULT_REST_URL
-        );
-        let input = read_line("Rest endpoint")?;
-        let input = input.trim();
-        let rest_url = if input.is_empty() {
-            eprintln!("No rest url given, using {}...", DEFAULT_REST_URL);
-            reqwest::Url::parse(DEFAULT_REST_URL).map_err(|err| {
-                CliError::UnexpectedError(format!("Failed to parse default rest URL {}", err))
-            })?
+        let mut profile_config = if let Some(profile_config) = config.profiles.remove(&profile) {
+            if !prompt_yes(&format!(
+                "Profile {} already initialized, do you want to overwrite it?",
+                profile
+            )) {
+                eprintln!("Exiting...");
+                return Ok(());
+            }
+            profile_config
         } else {
-            reqwest::Url::parse(input)
-                .map_err(|err| CliError::UnableToParse("Rest Endpoint", err.to_string()))?
+            Default::default()
         };
-        config.rest_url = Some(rest_url.to_string());
 
-        // Faucet Endpoint
-        eprintln!(
-            "Enter your faucet endpoint [Current: {} No input: {}]",
-            config.faucet_url.unwrap_or_else(|| "None".to_string()),
-            DEFAULT_FAUCET_URL
-        );
-        let input = read_line("Faucet endpoint")?;
-        let input = input.trim();
-        let faucet_url = if input.is_empty() {
-            eprintln!("No faucet url given, using {}...", DEFAULT_FAUCET_URL);
-            reqwest::Url::parse(DEFAULT_FAUCET_URL).map_err(|err| {
-                CliError::UnexpectedError(format!("Failed to parse default faucet URL {}", err))
-            })?
+        let (rest_url, faucet_url) = if let Some((rest_url, faucet_url)) =
+            self.network.and_then(Network::endpoints)
+        {
+            eprintln!(
+                "Using rest endpoint {} and faucet endpoint {}",
+                rest_url, faucet_url
+            );
+            (
+                reqwest::Url::parse(rest_url)
+                    .map_err(|err| CliError::UnableToParse("Rest Endpoint", err.to_string()))?,
+                reqwest::Url::parse(faucet_url)
+                    .map_err(|err| CliError::UnableToParse("Faucet Endpoint", err.to_string()))?,
+            )
         } else {
-            reqwest::Url::parse(input)
-                .map_err(|err| CliError::UnableToParse("Faucet Endpoint", err.to_string()))?
+            (
+                prompt_url(
+                    "Rest endpoint",
+                    profile_config.rest_url.as_deref(),
+                    DEFAULT_REST_URL,
+                )?,
+                prompt_url(
+                    "Faucet endpoint",
+                    profile_config.faucet_url.as_deref(),
+                    DEFAULT_FAUCET_URL,
+                )?,
+            )
         };
-        config.faucet_url = Some(faucet_url.to_string());
+        profile_config.rest_url = Some(rest_url.to_string());
+        profile_config.faucet_url = Some(faucet_url.to_string());
 
         // Private key
-        eprintln!("Enter your private key as a hex literal (0x...) [Current: {} No input: Generate new key (or keep one if present)]", config.private_key.as_ref().map(|_| "Redacted").unwrap_or("None"));
+        eprintln!("Enter your private key as a hex literal (0x...) [Current: {} No input: Generate new key (or keep one if present)]", profile_config.private_key.as_ref().map(|_| "Redacted").unwrap_or("None"));
         let input = read_line("Private key")?;
         let input = input.trim();
         let private_key = if input.is_empty() {
-            if let Some(private_key) = config.private_key {
+            if let Some(private_key) = profile_config.private_key {
                 eprintln!("No key given, keeping existing key...");
                 private_key
             } else {
@@ -90,7 +151,16 @@ impl InitTool {
         };
         let public_key = private_key.public_key();
         let address = account_address_from_public_key(&public_key);
-        config.private_key = Some(private_key);
+        profile_config.private_key = Some(private_key);
+        profile_config.account = Some(address);
+
+        // Gas settings
+        if self.max_gas.is_some() {
+            profile_config.max_gas = self.max_gas;
+        }
+        if self.gas_unit_price.is_some() {
+            profile_config.gas_unit_price = self.gas_unit_price;
+        }
 
         // Create account if it doesn't exist
         let client = aptos_rest_client::Client::new(rest_url);
@@ -102,12 +172,42 @@ impl InitTool {
             CreateAccount::create_account_with_faucet(faucet_url, NUM_DEFAULT_COINS, address)
                 .await?;
         }
+        config.profiles.insert(profile.clone(), profile_config);
         config.save()?;
-        eprintln!("Aptos is now set up for account {}!  Run `aptos help` for more information about commands", address);
+        eprintln!("Aptos is now set up for account {} in profile {}!  Run `aptos help` for more information about commands", address, profile);
         Ok(())
     }
 }
 
+/// Prompts for an endpoint, falling back to `default` on empty input
+fn prompt_url(
+    input_name: &'static str,
+    current: Option<&str>,
+    default: &str,
+) -> CliTypedResult<reqwest::Url> {
+    eprintln!(
+        "Enter your {} [Current: {} No input: {}]",
+        input_name.to_lowercase(),
+        current.unwrap_or("None"),
+        default
+    );
+    let input = read_line(input_name)?;
+    let input = input.trim();
+    if input.is_empty() {
+        eprintln!(
+            "No {} given, using {}...",
+            input_name.to_lowercase(),
+            default
+        );
+        reqwest::Url::parse(default).map_err(|err| {
+            CliError::UnexpectedError(format!("Failed to parse default {} {}", input_name, err))
+        })
+    } else {
+        reqwest::Url::parse(input)
+            .map_err(|err| CliError::UnableToParse(input_name, err.to_string()))
+    }
+}
+
 /// Reads a line from input
 fn read_line(input_name: &'static str) -> CliTypedResult<String> {
     let mut input_buf = String::new();
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::common::{
-    init::DEFAULT_REST_URL,
+    init::{DEFAULT_GAS_UNIT_PRICE, DEFAULT_MAX_GAS, DEFAULT_REST_URL},
     utils::{check_if_file_exists, write_to_file},
 };
 use aptos_crypto::{
@@ -10,8 +10,12 @@ use aptos_crypto::{
     x25519, PrivateKey, ValidCryptoMaterial, ValidCryptoMaterialStringExt,
 };
 use aptos_logger::{debug, info};
-use aptos_rest_client::Client;
-use aptos_types::{chain_id::ChainId, transaction::authenticator::AuthenticationKey};
+use aptos_rest_client::{Client, Transaction};
+use aptos_sdk::{transaction_builder::TransactionFactory, types::LocalAccount};
+use aptos_types::{
+    chain_id::ChainId,
+    transaction::{authenticator::AuthenticationKey, SignedTransaction, TransactionPayload},
+};
 use clap::{ArgEnum, Parser};
 use itertools::Itertools;
 use move_core_types::account_address::AccountAddress;
@@ -54,29 +58,78 @@ pub enum CliError {
     AbortedError,
     #[error("Move compilation failed: {0}")]
     MoveCompilationError(String),
+    #[error("Move compatibility check failed:\n{0}")]
+    MoveCompatibilityError(String),
     #[error("Move unit tests failed: {0}")]
     MoveTestError(String),
 }
 
+/// Name of the profile used when `--profile` isn't given
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Config saved to `.aptos/config.yml`
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct CliConfig {
+    /// Map of profile name to profile config
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ProfileConfig>,
+}
+
+/// A single network and account setup, selected with `--profile`
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ProfileConfig {
     /// Private key for commands.  TODO: Add vault functionality
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub private_key: Option<Ed25519PrivateKey>,
+    /// Account of the private key, which differs from the address derived from the key once the
+    /// key has been rotated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<AccountAddress>,
     /// URL for the Aptos rest endpoint
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub rest_url: Option<String>,
     /// URL for the Faucet endpoint (if applicable)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub faucet_url: Option<String>,
+    /// Default maximum gas for transactions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_gas: Option<u64>,
+    /// Default gas unit price for transactions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_unit_price: Option<u64>,
+}
+
+impl ProfileConfig {
+    /// The account of the profile, if any
+    pub fn account_address(&self) -> Option<AccountAddress> {
+        self.account.or_else(|| {
+            self.private_key
+                .as_ref()
+                .map(|private_key| account_address_from_public_key(&private_key.public_key()))
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.private_key.is_none()
+            && self.account.is_none()
+            && self.rest_url.is_none()
+            && self.faucet_url.is_none()
+            && self.max_gas.is_none()
+            && self.gas_unit_price.is_none()
+    }
 }
 
 impl CliConfig {
     /// Checks if the config exists in the current working directory
     pub fn config_exists() -> CliTypedResult<bool> {
-        Self::aptos_folder().map(|folder| folder.exists())
+        Self::config_file().map(|file| file.exists())
     }
 
     /// Loads the config from the current working directory
+    ///
+    /// Configs written before profiles existed are loaded as the default profile.
     pub fn load() -> CliTypedResult<Self> {
-        let config_file = Self::aptos_folder()?.join("config.yml");
+        let config_file = Self::config_file()?;
         if !config_file.exists() {
             return Err(CliError::ConfigNotFoundError(format!("{:?}", config_file)));
         }
@@ -84,8 +137,37 @@ impl CliConfig {
         let bytes = std::fs::read(&config_file).map_err(|err| {
             CliError::ConfigLoadError(format!("{:?}", config_file), err.to_string())
         })?;
-        serde_yaml::from_slice(&bytes)
-            .map_err(|err| CliError::ConfigLoadError(format!("{:?}", config_file), err.to_string()))
+        let mut config: CliConfig = serde_yaml::from_slice(&bytes).map_err(|err| {
+            CliError::ConfigLoadError(format!("{:?}", config_file), err.to_string())
+        })?;
+        if config.profiles.is_empty() {
+            let legacy: ProfileConfig = serde_yaml::from_slice(&bytes).map_err(|err| {
+                CliError::ConfigLoadError(format!("{:?}", config_file), err.to_string())
+            })?;
+            if !legacy.is_empty() {
+                config.profiles.insert(DEFAULT_PROFILE.to_string(), legacy);
+            }
+        }
+        Ok(config)
+    }
+
+    /// Loads a single profile from the config
+    ///
+    /// A missing default profile isn't an error, so that commands fall back to their defaults
+    /// before `aptos init` is run.  Any other missing profile is.
+    pub fn load_profile(profile: &str) -> CliTypedResult<Option<ProfileConfig>> {
+        let profile_config = if Self::config_exists()? {
+            Self::load()?.profiles.remove(profile)
+        } else {
+            None
+        };
+        if profile_config.is_none() && profile != DEFAULT_PROFILE {
+            return Err(CliError::ConfigNotFoundError(format!(
+                "profile '{}', run `aptos init --profile {}` to create it",
+                profile, profile
+            )));
+        }
+        Ok(profile_config)
     }
 
     /// Saves the config to ./.aptos/config.yml
@@ -106,8 +188,7 @@ impl CliConfig {
         }
 
         // Save over previous config file
-        // TODO: Ask for saving over?
-        let config_file = aptos_folder.join("config.yml");
+        let config_file = Self::config_file()?;
         let config_bytes = serde_yaml::to_string(&self).map_err(|err| {
             CliError::UnexpectedError(format!("Failed to serialize config {}", err))
         })?;
@@ -123,6 +204,10 @@ impl CliConfig {
             })
             .map(|dir| dir.join(".aptos"))
     }
+
+    fn config_file() -> CliTypedResult<PathBuf> {
+        Self::aptos_folder().map(|folder| folder.join("config.yml"))
+    }
 }
 
 /// Types of Keys used by the blockchain
@@ -228,6 +313,14 @@ pub struct PromptOptions {
     pub assume_yes: bool,
 }
 
+/// An insertable option for selecting a profile of the config.
+#[derive(Debug, Parser)]
+pub struct ProfileOptions {
+    /// Profile to use from the config
+    #[clap(long, default_value = DEFAULT_PROFILE)]
+    pub profile: String,
+}
+
 /// An insertable option for use with encodings.
 #[derive(Debug, Parser)]
 pub struct EncodingOptions {
@@ -247,7 +340,11 @@ pub struct PublicKeyInputOptions {
 }
 
 impl ExtractPublicKey for PublicKeyInputOptions {
-    fn extract_public_key(&self, encoding: EncodingType) -> CliTypedResult<Ed25519PublicKey> {
+    fn extract_public_key(
+        &self,
+        encoding: EncodingType,
+        _profile: &str,
+    ) -> CliTypedResult<Ed25519PublicKey> {
         if let Some(ref file) = self.public_key_file {
             encoding.load_key("--public-key-file", file.as_path())
         } else if let Some(ref key) = self.public_key {
@@ -272,13 +369,19 @@ pub struct PrivateKeyInputOptions {
 }
 
 impl PrivateKeyInputOptions {
-    pub fn extract_private_key(&self, encoding: EncodingType) -> CliTypedResult<Ed25519PrivateKey> {
+    pub fn extract_private_key(
+        &self,
+        encoding: EncodingType,
+        profile: &str,
+    ) -> CliTypedResult<Ed25519PrivateKey> {
         if let Some(ref file) = self.private_key_file {
             encoding.load_key("--private-key-file", file.as_path())
         } else if let Some(ref key) = self.private_key {
             let key = key.as_bytes().to_vec();
             encoding.decode_key("--private-key", key)
-        } else if let Some(private_key) = CliConfig::load()?.private_key {
+        } else if let Some(private_key) =
+            CliConfig::load_profile(profile)?.and_then(|profile_config| profile_config.private_key)
+        {
             Ok(private_key)
         } else {
             Err(CliError::CommandArgumentError(
@@ -289,20 +392,30 @@ impl PrivateKeyInputOptions {
 }
 
 impl ExtractPublicKey for PrivateKeyInputOptions {
-    fn extract_public_key(&self, encoding: EncodingType) -> CliTypedResult<Ed25519PublicKey> {
-        self.extract_private_key(encoding)
+    fn extract_public_key(
+        &self,
+        encoding: EncodingType,
+        profile: &str,
+    ) -> CliTypedResult<Ed25519PublicKey> {
+        self.extract_private_key(encoding, profile)
             .map(|private_key| private_key.public_key())
     }
 }
 
 pub trait ExtractPublicKey {
-    fn extract_public_key(&self, encoding: EncodingType) -> CliTypedResult<Ed25519PublicKey>;
+    /// Extracts the key from the options, falling back to the key of `profile` if there is one
+    fn extract_public_key(
+        &self,
+        encoding: EncodingType,
+        profile: &str,
+    ) -> CliTypedResult<Ed25519PublicKey>;
 
     fn extract_x25519_public_key(
         &self,
         encoding: EncodingType,
+        profile: &str,
     ) -> CliTypedResult<x25519::PublicKey> {
-        let key = self.extract_public_key(encoding)?;
+        let key = self.extract_public_key(encoding, profile)?;
         x25519::PublicKey::from_ed25519_public_bytes(&key.to_bytes()).map_err(|err| {
             CliError::UnexpectedError(format!("Failed to convert ed25519 to x25519 {:?}", err))
         })
@@ -314,6 +427,24 @@ pub fn account_address_from_public_key(public_key: &Ed25519PublicKey) -> Account
     AccountAddress::new(*auth_key.derived_address())
 }
 
+/// Returns `account` if given, and the account of the profile otherwise
+pub fn account_or_profile_account(
+    account: Option<AccountAddress>,
+    profile: &str,
+) -> CliTypedResult<AccountAddress> {
+    if let Some(account) = account {
+        Ok(account)
+    } else if let Some(account) = CliConfig::load_profile(profile)?
+        .and_then(|profile_config| profile_config.account_address())
+    {
+        Ok(account)
+    } else {
+        Err(CliError::CommandArgumentError(
+            "Please provide an account using --account or run aptos init".to_string(),
+        ))
+    }
+}
+
 #[derive(Debug, Parser)]
 pub struct SaveFile {
     /// Output file name
@@ -341,16 +472,18 @@ impl SaveFile {
 pub struct RestOptions {
     /// URL to a fullnode on the network
     ///
-    /// Defaults to https://fullnode.devnet.aptoslabs.com
+    /// Defaults to the rest endpoint of the profile, or https://fullnode.devnet.aptoslabs.com
     #[clap(long, parse(try_from_str))]
     pub url: Option<reqwest::Url>,
 }
 
 impl RestOptions {
-    pub fn url(&self) -> CliTypedResult<reqwest::Url> {
+    pub fn url(&self, profile: &str) -> CliTypedResult<reqwest::Url> {
         if let Some(ref url) = self.url {
             Ok(url.clone())
-        } else if let Some(url) = CliConfig::load()?.rest_url {
+        } else if let Some(url) =
+            CliConfig::load_profile(profile)?.and_then(|profile_config| profile_config.rest_url)
+        {
             reqwest::Url::parse(&url)
                 .map_err(|err| CliError::UnableToParse("Rest URL", err.to_string()))
         } else {
@@ -368,16 +501,129 @@ pub struct WriteTransactionOptions {
     pub private_key_options: PrivateKeyInputOptions,
     #[clap(flatten)]
     pub rest_options: RestOptions,
-    /// Maximum gas to be used to publish the package
+    #[clap(flatten)]
+    pub profile_options: ProfileOptions,
+    /// Maximum gas to be used to send the transaction
     ///
-    /// Defaults to 1000 gas units
-    #[clap(long, default_value_t = 1000)]
-    pub max_gas: u64,
+    /// Defaults to the max gas of the profile, or 1000 gas units
+    #[clap(long)]
+    pub max_gas: Option<u64>,
+    /// Gas unit price to be used to send the transaction
+    ///
+    /// Defaults to the gas unit price of the profile, or 1
+    #[clap(long)]
+    pub gas_unit_price: Option<u64>,
 }
 
 impl WriteTransactionOptions {
+    /// Name of the selected profile
+    pub fn profile(&self) -> &str {
+        &self.profile_options.profile
+    }
+
+    /// Extracts the sender key from the options, or from the profile
+    pub fn private_key(&self, encoding: EncodingType) -> CliTypedResult<Ed25519PrivateKey> {
+        self.private_key_options
+            .extract_private_key(encoding, self.profile())
+    }
+
+    /// URL of the rest endpoint from the options, or from the profile
+    pub fn url(&self) -> CliTypedResult<reqwest::Url> {
+        self.rest_options.url(self.profile())
+    }
+
+    /// Address of the account signing with `private_key`
+    ///
+    /// This is the account of the profile when signing with its key, as the key may have been
+    /// rotated, and the address derived from the key otherwise.
+    pub fn sender_address(
+        &self,
+        private_key: &Ed25519PrivateKey,
+    ) -> CliTypedResult<AccountAddress> {
+        if let Some(profile_config) = CliConfig::load_profile(self.profile())? {
+            let is_profile_key = profile_config
+                .private_key
+                .as_ref()
+                .map(|key| key.public_key() == private_key.public_key())
+                .unwrap_or(false);
+            if let (true, Some(account)) = (is_profile_key, profile_config.account) {
+                return Ok(account);
+            }
+        }
+        Ok(account_address_from_public_key(&private_key.public_key()))
+    }
+
+    /// A [`TransactionFactory`] for the chain with the gas settings of the options or profile
+    pub async fn transaction_factory(&self) -> CliTypedResult<TransactionFactory> {
+        let profile_config = CliConfig::load_profile(self.profile())?.unwrap_or_default();
+        let max_gas = self
+            .max_gas
+            .or(profile_config.max_gas)
+            .unwrap_or(DEFAULT_MAX_GAS);
+        let gas_unit_price = self
+            .gas_unit_price
+            .or(profile_config.gas_unit_price)
+            .unwrap_or(DEFAULT_GAS_UNIT_PRICE);
+        Ok(TransactionFactory::new(self.chain_id().await?)
+            .with_gas_unit_price(gas_unit_price)
+            .with_max_gas_amount(max_gas))
+    }
+
+    /// Submits a [`TransactionPayload`] as signed by the sender key and waits for it
+    pub async fn submit_transaction(
+        &self,
+        encoding: EncodingType,
+        payload: TransactionPayload,
+    ) -> CliTypedResult<Transaction> {
+        let (client, transaction) = self.sign_transaction(encoding, payload).await?;
+        let response = client
+            .submit_and_wait(&transaction)
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?;
+        Ok(response.into_inner())
+    }
+
+    /// Executes a [`TransactionPayload`] signed by the sender key on the node without submitting
+    /// it, to check that it would succeed
+    pub async fn simulate_transaction(
+        &self,
+        encoding: EncodingType,
+        payload: TransactionPayload,
+    ) -> CliTypedResult<Transaction> {
+        let (client, transaction) = self.sign_transaction(encoding, payload).await?;
+        let response = client
+            .simulate(&transaction)
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?;
+        Ok(response.into_inner())
+    }
+
+    async fn sign_transaction(
+        &self,
+        encoding: EncodingType,
+        payload: TransactionPayload,
+    ) -> CliTypedResult<(Client, SignedTransaction)> {
+        let sender_key = self.private_key(encoding)?;
+        let sender_address = self.sender_address(&sender_key)?;
+        let client = Client::new(self.url()?);
+
+        // Get account to get the sequence number
+        let sequence_number = client
+            .get_account(sender_address)
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?
+            .into_inner()
+            .sequence_number;
+
+        let transaction_factory = self.transaction_factory().await?;
+        let sender_account = &mut LocalAccount::new(sender_address, sender_key, sequence_number);
+        let transaction =
+            sender_account.sign_with_transaction_builder(transaction_factory.payload(payload));
+        Ok((client, transaction))
+    }
+
     pub async fn chain_id(&self) -> CliTypedResult<ChainId> {
-        let client = Client::new(self.rest_options.url()?);
+        let client = Client::new(self.url()?);
         let state = client
             .get_ledger_information()
             .await
@@ -405,6 +651,9 @@ pub struct MovePackageDir {
     /// Note: This will fail if there are duplicates in the Move.toml file remove those first.
     #[clap(long, parse(try_from_str = parse_map), default_value = "")]
     pub named_addresses: BTreeMap<String, AccountAddress>,
+    /// Download the `[on-chain-dependencies]` of the package again instead of using the cache
+    #[clap(long)]
+    pub refresh_dependencies: bool,
 }
 
 const PARSE_MAP_SYNTAX_MSG: &str = "Invalid syntax for map.  Example: Name=Value,Name2=Value";
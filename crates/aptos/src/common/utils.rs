@@ -118,3 +118,9 @@ pub fn append_file_extension(
         Ok(file.with_extension(appended_extension))
     }
 }
+
+/// Read a `Vec<u8>` from a file
+pub fn read_from_file(path: &Path) -> CliTypedResult<Vec<u8>> {
+    std::fs::read(path)
+        .map_err(|err| CliError::UnableToReadFile(format!("{}", path.display()), err.to_string()))
+}
@@ -0,0 +1,133 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::{
+    types::{
+        CliConfig, CliError, CliResult, CliTypedResult, ProfileConfig, ProfileOptions,
+        PromptOptions,
+    },
+    utils::{prompt_yes, to_common_result},
+};
+use aptos_types::account_address::AccountAddress;
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+
+/// CLI tool for managing the profiles of the config
+///
+/// Profiles are created with `aptos init --profile <name>`
+#[derive(Debug, Subcommand)]
+pub enum ConfigTool {
+    ListProfiles(ListProfiles),
+    ShowProfile(ShowProfile),
+    RemoveProfile(RemoveProfile),
+}
+
+impl ConfigTool {
+    pub async fn execute(self) -> CliResult {
+        match self {
+            ConfigTool::ListProfiles(tool) => to_common_result(tool.execute()),
+            ConfigTool::ShowProfile(tool) => to_common_result(tool.execute()),
+            ConfigTool::RemoveProfile(tool) => to_common_result(tool.execute()),
+        }
+    }
+}
+
+/// A profile as shown to users, without its private key
+#[derive(Debug, Serialize)]
+pub struct ProfileSummary {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account: Option<AccountAddress>,
+    has_private_key: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rest_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    faucet_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_gas: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gas_unit_price: Option<u64>,
+}
+
+impl ProfileSummary {
+    fn new(name: String, profile_config: ProfileConfig) -> Self {
+        ProfileSummary {
+            name,
+            account: profile_config.account_address(),
+            has_private_key: profile_config.private_key.is_some(),
+            rest_url: profile_config.rest_url,
+            faucet_url: profile_config.faucet_url,
+            max_gas: profile_config.max_gas,
+            gas_unit_price: profile_config.gas_unit_price,
+        }
+    }
+}
+
+/// List the profiles of the config
+#[derive(Debug, Parser)]
+pub struct ListProfiles {}
+
+impl ListProfiles {
+    pub fn execute(self) -> CliTypedResult<Vec<ProfileSummary>> {
+        Ok(CliConfig::load()?
+            .profiles
+            .into_iter()
+            .map(|(name, profile_config)| ProfileSummary::new(name, profile_config))
+            .collect())
+    }
+}
+
+/// Show a profile of the config
+#[derive(Debug, Parser)]
+pub struct ShowProfile {
+    #[clap(flatten)]
+    profile_options: ProfileOptions,
+}
+
+impl ShowProfile {
+    pub fn execute(self) -> CliTypedResult<ProfileSummary> {
+        let name = self.profile_options.profile;
+        let profile_config = CliConfig::load()?
+            .profiles
+            .remove(&name)
+            .ok_or_else(|| profile_not_found(&name))?;
+        Ok(ProfileSummary::new(name, profile_config))
+    }
+}
+
+/// Remove a profile from the config
+///
+/// The private key of the profile is deleted with it, so back it up first if it's still needed.
+#[derive(Debug, Parser)]
+pub struct RemoveProfile {
+    #[clap(flatten)]
+    profile_options: ProfileOptions,
+    #[clap(flatten)]
+    prompt_options: PromptOptions,
+}
+
+impl RemoveProfile {
+    pub fn execute(self) -> CliTypedResult<String> {
+        let name = self.profile_options.profile;
+        let mut config = CliConfig::load()?;
+        if !config.profiles.contains_key(&name) {
+            return Err(profile_not_found(&name));
+        }
+        if !self.prompt_options.assume_yes
+            && !prompt_yes(&format!(
+                "Remove profile {} and its private key from the config?",
+                name
+            ))
+        {
+            return Err(CliError::AbortedError);
+        }
+
+        config.profiles.remove(&name);
+        config.save()?;
+        Ok(format!("Removed profile {}", name))
+    }
+}
+
+fn profile_not_found(name: &str) -> CliError {
+    CliError::CommandArgumentError(format!("Profile {} doesn't exist in the config", name))
+}
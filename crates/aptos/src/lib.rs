@@ -5,8 +5,11 @@
 
 pub mod account;
 pub mod common;
+pub mod config;
 pub mod move_tool;
+pub mod node;
 pub mod op;
+pub mod transaction;
 
 use crate::common::{types::CliResult, utils::to_common_success_result};
 use clap::Parser;
@@ -18,20 +21,29 @@ use clap::Parser;
 pub enum Tool {
     #[clap(subcommand)]
     Account(account::AccountTool),
+    #[clap(subcommand)]
+    Config(config::ConfigTool),
     Init(common::init::InitTool),
     #[clap(subcommand)]
     Move(move_tool::MoveTool),
     #[clap(subcommand)]
     Key(op::key::KeyTool),
+    #[clap(subcommand)]
+    Node(node::NodeTool),
+    #[clap(subcommand)]
+    Transaction(transaction::TransactionTool),
 }
 
 impl Tool {
     pub async fn execute(self) -> CliResult {
         match self {
             Tool::Account(tool) => tool.execute().await,
+            Tool::Config(tool) => tool.execute().await,
             Tool::Init(tool) => to_common_success_result(tool.execute().await),
             Tool::Move(tool) => tool.execute().await,
             Tool::Key(tool) => tool.execute().await,
+            Tool::Node(tool) => tool.execute().await,
+            Tool::Transaction(tool) => tool.execute().await,
         }
     }
 }
@@ -0,0 +1,159 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generates typed Rust bindings of Move modules, either compiled locally or published on chain
+//!
+
+use crate::{
+    common::types::{parse_map, CliError, CliTypedResult, ProfileOptions, RestOptions, SaveFile},
+    move_tool::{compile_move, dependencies},
+};
+use aptos_rest_client::Client;
+use clap::Parser;
+use move_binary_format::CompiledModule;
+use move_compiler::compiled_unit::{CompiledUnitEnum, NamedCompiledModule};
+use move_core_types::account_address::AccountAddress;
+use move_package::BuildConfig;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+
+/// Generates Rust structs for the structs of Move modules, and builders for their script functions
+///
+/// The structs are BCS-compatible with their Move values and can be read with
+/// `Client::get_move_resource` of the Rust SDK.
+#[derive(Parser)]
+pub struct GenerateRustBindings {
+    /// Path to a move package (the folder with a Move.toml file) to compile and generate
+    /// bindings for
+    #[clap(long, parse(from_os_str))]
+    package_dir: Option<PathBuf>,
+    /// Named addresses for compiling the package
+    ///
+    /// Example: alice=0x1234, bob=0x5678
+    #[clap(long, parse(try_from_str = parse_map), default_value = "")]
+    named_addresses: BTreeMap<String, AccountAddress>,
+    /// Account to generate bindings for the published modules of, instead of a package
+    #[clap(long, conflicts_with = "package-dir")]
+    account: Option<AccountAddress>,
+    #[clap(flatten)]
+    rest_options: RestOptions,
+    #[clap(flatten)]
+    profile_options: ProfileOptions,
+    #[clap(flatten)]
+    save_file: SaveFile,
+}
+
+impl GenerateRustBindings {
+    pub async fn execute(self) -> CliTypedResult<Vec<String>> {
+        self.save_file.check_file()?;
+        let (modules, dependencies) = if let Some(account) = self.account {
+            self.published_modules(account).await?
+        } else if let Some(ref package_dir) = self.package_dir {
+            self.compiled_modules(package_dir).await?
+        } else {
+            return Err(CliError::CommandArgumentError(
+                "One of ['--package-dir', '--account'] must be used".to_string(),
+            ));
+        };
+
+        let mut bindings = vec![];
+        transaction_builder_generator::rust_bindings::output(
+            &mut bindings,
+            &modules,
+            &dependencies,
+        )
+        .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        self.save_file.save_to_file("Rust bindings", &bindings)?;
+        Ok(modules
+            .iter()
+            .map(|module| module.self_id().to_string())
+            .collect())
+    }
+
+    /// Compiles the package, its dependencies provide the structs it uses
+    async fn compiled_modules(
+        &self,
+        package_dir: &Path,
+    ) -> CliTypedResult<(Vec<CompiledModule>, Vec<CompiledModule>)> {
+        dependencies::resolve_on_chain_dependencies(
+            package_dir,
+            false,
+            &self.profile_options.profile,
+        )
+        .await?;
+        let build_config = BuildConfig {
+            additional_named_addresses: self.named_addresses.clone(),
+            ..Default::default()
+        };
+        let package = compile_move(build_config, package_dir)?;
+        let modules = package
+            .compiled_modules()
+            .iter_modules()
+            .into_iter()
+            .cloned()
+            .collect();
+        let dependencies = package
+            .deps_compiled_units
+            .iter()
+            .filter_map(|(_, unit)| match &unit.unit {
+                CompiledUnitEnum::Module(NamedCompiledModule { module, .. }) => {
+                    Some(module.clone())
+                }
+                CompiledUnitEnum::Script(_) => None,
+            })
+            .collect();
+        Ok((modules, dependencies))
+    }
+
+    /// Fetches the modules published at `account`, along with the modules of the other accounts
+    /// they depend on
+    async fn published_modules(
+        &self,
+        account: AccountAddress,
+    ) -> CliTypedResult<(Vec<CompiledModule>, Vec<CompiledModule>)> {
+        let client = Client::new(self.rest_options.url(&self.profile_options.profile)?);
+        let modules = fetch_modules(&client, account).await?;
+        if modules.is_empty() {
+            return Err(CliError::CommandArgumentError(format!(
+                "No modules are published at {}",
+                account
+            )));
+        }
+
+        let mut fetched = BTreeSet::from([account]);
+        let mut pending: Vec<_> = modules
+            .iter()
+            .flat_map(|module| module.immediate_dependencies())
+            .collect();
+        let mut dependencies = vec![];
+        while let Some(module_id) = pending.pop() {
+            if !fetched.insert(*module_id.address()) {
+                continue;
+            }
+            for module in fetch_modules(&client, *module_id.address()).await? {
+                pending.extend(module.immediate_dependencies());
+                dependencies.push(module);
+            }
+        }
+        Ok((modules, dependencies))
+    }
+}
+
+async fn fetch_modules(
+    client: &Client,
+    address: AccountAddress,
+) -> CliTypedResult<Vec<CompiledModule>> {
+    client
+        .get_account_modules(address)
+        .await
+        .map_err(|err| CliError::ApiError(err.to_string()))?
+        .into_inner()
+        .iter()
+        .map(|module| {
+            CompiledModule::deserialize(module.bytecode.inner())
+                .map_err(|err| CliError::UnableToParse("Published module", err.to_string()))
+        })
+        .collect()
+}
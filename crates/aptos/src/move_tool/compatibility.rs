@@ -0,0 +1,178 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks that upgrading published modules won't break their dependents or their stored data
+//!
+
+use move_binary_format::{
+    compatibility::Compatibility,
+    file_format::Visibility,
+    normalized::{Function, Module},
+    CompiledModule,
+};
+use move_core_types::{identifier::Identifier, language_storage::ModuleId};
+use std::collections::BTreeMap;
+
+/// Lists the changes of `new` that are incompatible with the published version of the module
+///
+/// Structs must keep their abilities, type parameters and fields, and public functions must
+/// keep their signatures.  Friend functions are free to change.
+pub fn incompatible_changes(old: &CompiledModule, new: &CompiledModule) -> Vec<String> {
+    let (old, new) = (Module::new(old), Module::new(new));
+    let compatibility = Compatibility::check(&old, &new);
+    if compatibility.is_fully_compatible() {
+        return vec![];
+    }
+
+    let module_id = ModuleId::new(new.address, new.name.clone());
+    let mut changes = vec![];
+    for (name, old_struct) in &old.structs {
+        match new.structs.get(name) {
+            None => changes.push(format!("{}::{}: struct was removed", module_id, name)),
+            Some(new_struct) => {
+                if old_struct.abilities != new_struct.abilities
+                    || old_struct.type_parameters != new_struct.type_parameters
+                {
+                    changes.push(format!(
+                        "{}::{}: struct abilities or type parameters changed",
+                        module_id, name
+                    ));
+                }
+                if old_struct.fields != new_struct.fields {
+                    changes.push(format!(
+                        "{}::{}: struct fields changed, published values can't be read anymore",
+                        module_id, name
+                    ));
+                }
+            }
+        }
+    }
+    for (name, old_function) in public_functions(&old.exposed_functions) {
+        match new.exposed_functions.get(name) {
+            Some(new_function)
+                if new_function.visibility != Visibility::Friend
+                    && new_function.type_parameters == old_function.type_parameters
+                    && new_function.parameters == old_function.parameters
+                    && new_function.return_ == old_function.return_ => {}
+            Some(_) => changes.push(format!(
+                "{}::{}: public function signature or visibility changed",
+                module_id, name
+            )),
+            None => changes.push(format!(
+                "{}::{}: public function was removed",
+                module_id, name
+            )),
+        }
+    }
+
+    // Fall back to the flags of the checker, in case it rejects a change not covered above
+    if changes.is_empty() {
+        if !compatibility.struct_and_function_linking {
+            changes.push(format!(
+                "{}: linking API of structs or functions changed",
+                module_id
+            ));
+        }
+        if !compatibility.struct_layout {
+            changes.push(format!("{}: layout of structs changed", module_id));
+        }
+    }
+    changes
+}
+
+fn public_functions(
+    functions: &BTreeMap<Identifier, Function>,
+) -> impl Iterator<Item = (&Identifier, &Function)> {
+    functions
+        .iter()
+        .filter(|(_, function)| function.visibility != Visibility::Friend)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_ir_compiler::Compiler;
+
+    const STRUCT: &str = "struct S has copy, drop { f: u64 }";
+    const FUNCTION: &str = "public f(x: u64): u64 { label b0: return move(x); }";
+
+    fn compile(members: &[&str]) -> CompiledModule {
+        Compiler { deps: vec![] }
+            .into_compiled_module(&format!("module 0x1.M {{\n{}\n}}", members.join("\n")))
+            .unwrap()
+    }
+
+    fn changes(new_members: &[&str]) -> Vec<String> {
+        incompatible_changes(&compile(&[STRUCT, FUNCTION]), &compile(new_members))
+    }
+
+    fn assert_change(new_members: &[&str], expected: &str) {
+        let changes = changes(new_members);
+        assert!(
+            changes.iter().any(|change| change.contains(expected)),
+            "expected '{}' in {:?}",
+            expected,
+            changes
+        );
+    }
+
+    #[test]
+    fn test_compatible_additions() {
+        assert!(changes(&[
+            STRUCT,
+            FUNCTION,
+            "struct T { g: bool }",
+            "public g(): u64 { label b0: return 0; }",
+        ])
+        .is_empty());
+    }
+
+    #[test]
+    fn test_struct_changes() {
+        assert_change(
+            &["struct S has copy, drop { f: u64, g: bool }", FUNCTION],
+            "struct fields changed",
+        );
+        assert_change(
+            &["struct S has copy, drop { f: bool }", FUNCTION],
+            "struct fields changed",
+        );
+        assert_change(
+            &["struct S has copy { f: u64 }", FUNCTION],
+            "struct abilities or type parameters changed",
+        );
+        assert_change(&[FUNCTION], "::M::S: struct was removed");
+    }
+
+    #[test]
+    fn test_function_changes() {
+        assert_change(
+            &[
+                STRUCT,
+                "public f(x: u64, y: u64): u64 { label b0: return move(x); }",
+            ],
+            "public function signature or visibility changed",
+        );
+        assert_change(
+            &[STRUCT, "public f(x: u64): bool { label b0: return true; }"],
+            "public function signature or visibility changed",
+        );
+        assert_change(&[STRUCT], "::M::f: public function was removed");
+    }
+
+    #[test]
+    fn test_visibility_changes() {
+        assert_change(
+            &[
+                STRUCT,
+                "public(friend) f(x: u64): u64 { label b0: return move(x); }",
+            ],
+            "public function signature or visibility changed",
+        );
+        // A private function is not visible to other modules anymore
+        assert_change(
+            &[STRUCT, "f(x: u64): u64 { label b0: return move(x); }"],
+            "::M::f: public function was removed",
+        );
+    }
+}
@@ -0,0 +1,308 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Dependencies on modules published on chain
+//!
+//! A package can depend on the modules published at an address without having their source:
+//!
+//! ```toml
+//! [dependencies]
+//! Marketplace = { local = ".aptos/dependencies/Marketplace" }
+//!
+//! [on-chain-dependencies]
+//! Marketplace = { address = "0xCAFE", url = "https://fullnode.devnet.aptoslabs.com" }
+//! ```
+//!
+//! Before compiling, the bytecode of the modules is downloaded and turned into a package of
+//! interface sources at the `local` path, which the compiler builds against like any other
+//! dependency.  The interface package depends on the other dependencies of the package, e.g. the
+//! framework.  Functions of the interfaces are declared `native`, so they can be linked against
+//! but not run by unit tests.
+//!
+//! Modules published at other addresses that the downloaded modules depend on are downloaded the
+//! same way from the same `url`, into packages named `OnChain_<address>` next to the declared ones.
+//! Modules at `0x1` are expected to come from the regular dependencies, i.e. the framework.
+//!
+//! Downloads are cached until `--refresh-dependencies` is given.  `url` defaults to the rest
+//! endpoint of the profile.
+//!
+
+use crate::common::{
+    types::{CliError, CliTypedResult, RestOptions},
+    utils::write_to_file,
+};
+use aptos_rest_client::Client;
+use move_binary_format::{access::ModuleAccess, CompiledModule};
+use move_compiler::interface_generator;
+use move_core_types::{account_address::AccountAddress, language_storage::CORE_CODE_ADDRESS};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    path::{Path, PathBuf},
+};
+
+const MANIFEST_FILE: &str = "Move.toml";
+const ON_CHAIN_DEPENDENCIES: &str = "on-chain-dependencies";
+const DEPENDENCIES_DIR: &str = ".aptos/dependencies";
+/// Records where the cached interfaces were downloaded from
+const METADATA_FILE: &str = "on-chain-dependency.toml";
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct OnChainDependency {
+    address: String,
+    url: Option<String>,
+}
+
+/// What a cached download was made from, and which addresses its modules depend on
+#[derive(Debug, Deserialize, Serialize)]
+struct Metadata {
+    address: String,
+    url: Option<String>,
+    dependencies: Vec<String>,
+}
+
+/// Downloads the on-chain dependencies declared in the manifest of `package_dir`, and the on-chain
+/// dependencies of those, recursively
+///
+/// Dependencies without a `url` are downloaded from the rest endpoint of `profile`.
+pub async fn resolve_on_chain_dependencies(
+    package_dir: &Path,
+    refresh: bool,
+    profile: &str,
+) -> CliTypedResult<()> {
+    let package_dir = std::fs::canonicalize(package_dir)
+        .map_err(|err| CliError::IO(format!("{}", package_dir.display()), err))?;
+    let manifest_path = package_dir.join(MANIFEST_FILE);
+    let manifest = std::fs::read_to_string(&manifest_path)
+        .map_err(|err| CliError::IO(format!("{}", manifest_path.display()), err))?;
+    let manifest: toml::Value = toml::from_str(&manifest)
+        .map_err(|err| CliError::UnableToParse("Move.toml", err.to_string()))?;
+
+    let on_chain_dependencies: BTreeMap<String, OnChainDependency> =
+        match manifest.get(ON_CHAIN_DEPENDENCIES) {
+            Some(value) => value
+                .clone()
+                .try_into()
+                .map_err(|err| CliError::UnableToParse("Move.toml", err.to_string()))?,
+            None => return Ok(()),
+        };
+    let dependencies = manifest
+        .get("dependencies")
+        .and_then(|dependencies| dependencies.as_table())
+        .cloned()
+        .unwrap_or_default();
+
+    let mut packages = BTreeMap::new();
+    for (name, dependency) in &on_chain_dependencies {
+        // The compiler only knows about the dependency through the regular `[dependencies]`
+        let local_path = format!("{}/{}", DEPENDENCIES_DIR, name);
+        let declared_path = dependencies
+            .get(name)
+            .and_then(|dependency| dependency.get("local"))
+            .and_then(|local| local.as_str());
+        if declared_path.map(|path| path.trim_end_matches('/')) != Some(local_path.as_str()) {
+            return Err(CliError::CommandArgumentError(format!(
+                "On-chain dependency {} must also be declared in [dependencies] as: {} = {{ local = \"{}\" }}",
+                name, name, local_path
+            )));
+        }
+        packages.insert(parse_address(&dependency.address)?, name.clone());
+    }
+    let other_dependencies: toml::value::Table = dependencies
+        .iter()
+        .filter(|(name, _)| !on_chain_dependencies.contains_key(*name))
+        .map(|(name, dependency)| (name.clone(), absolute_dependency(&package_dir, dependency)))
+        .collect();
+
+    let mut resolved = BTreeSet::new();
+    let mut pending: VecDeque<_> = on_chain_dependencies.into_iter().collect();
+    while let Some((name, dependency)) = pending.pop_front() {
+        if !resolved.insert(name.clone()) {
+            continue;
+        }
+        let dependency_dir = package_dir.join(DEPENDENCIES_DIR).join(&name);
+        let addresses = match cached_dependencies(&dependency_dir, &dependency, refresh)? {
+            Some(addresses) => addresses,
+            None => {
+                download_dependency(
+                    &name,
+                    &dependency,
+                    &dependency_dir,
+                    &package_dir,
+                    &packages,
+                    &other_dependencies,
+                    profile,
+                )
+                .await?
+            }
+        };
+
+        // Modules at other addresses are fetched from the chain the dependency came from
+        for address in addresses {
+            let name = packages
+                .entry(address)
+                .or_insert_with(|| package_name(address))
+                .clone();
+            pending.push_back((
+                name,
+                OnChainDependency {
+                    address: address.to_hex_literal(),
+                    url: dependency.url.clone(),
+                },
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn parse_address(address: &str) -> CliTypedResult<AccountAddress> {
+    AccountAddress::from_hex_literal(address)
+        .map_err(|err| CliError::UnableToParse("on-chain dependency address", err.to_string()))
+}
+
+/// Name of the package of a dependency that isn't declared in the manifest
+fn package_name(address: AccountAddress) -> String {
+    format!("OnChain_{}", address.short_str_lossless())
+}
+
+/// Returns the addresses the cached modules of `dependency` depend on, or `None` if they have to
+/// be downloaded
+fn cached_dependencies(
+    dependency_dir: &Path,
+    dependency: &OnChainDependency,
+    refresh: bool,
+) -> CliTypedResult<Option<Vec<AccountAddress>>> {
+    let metadata_path = dependency_dir.join(METADATA_FILE);
+    if refresh || !metadata_path.exists() {
+        return Ok(None);
+    }
+    let metadata = std::fs::read_to_string(&metadata_path)
+        .map_err(|err| CliError::IO(format!("{}", metadata_path.display()), err))?;
+    match toml::from_str::<Metadata>(&metadata) {
+        Ok(metadata)
+            if metadata.address == dependency.address && metadata.url == dependency.url =>
+        {
+            let addresses = metadata
+                .dependencies
+                .iter()
+                .map(|address| parse_address(address))
+                .collect::<CliTypedResult<_>>()?;
+            Ok(Some(addresses))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Makes `local` paths of a dependency usable from another directory
+fn absolute_dependency(package_dir: &Path, dependency: &toml::Value) -> toml::Value {
+    let mut dependency = dependency.clone();
+    if let Some(table) = dependency.as_table_mut() {
+        if let Some(local) = table.get("local").and_then(|local| local.as_str()) {
+            let local = package_dir.join(local).display().to_string();
+            table.insert("local".to_string(), toml::Value::String(local));
+        }
+    }
+    dependency
+}
+
+/// Downloads the modules of `dependency` into an interface package, and returns the addresses of
+/// the on-chain modules they depend on
+async fn download_dependency(
+    name: &str,
+    dependency: &OnChainDependency,
+    dependency_dir: &Path,
+    package_dir: &Path,
+    packages: &BTreeMap<AccountAddress, String>,
+    other_dependencies: &toml::value::Table,
+    profile: &str,
+) -> CliTypedResult<Vec<AccountAddress>> {
+    let address = parse_address(&dependency.address)?;
+    let url = match dependency.url {
+        Some(ref url) => reqwest::Url::parse(url)
+            .map_err(|err| CliError::UnableToParse("on-chain dependency url", err.to_string()))?,
+        None => RestOptions { url: None }.url(profile)?,
+    };
+
+    let modules = Client::new(url)
+        .get_account_modules(address)
+        .await
+        .map_err(|err| CliError::ApiError(err.to_string()))?
+        .into_inner();
+    if modules.is_empty() {
+        return Err(CliError::CommandArgumentError(format!(
+            "No modules are published at {} for on-chain dependency {}",
+            address, name
+        )));
+    }
+
+    // Start from scratch so that modules removed from the chain don't linger
+    if dependency_dir.exists() {
+        std::fs::remove_dir_all(dependency_dir)
+            .map_err(|err| CliError::IO(format!("{}", dependency_dir.display()), err))?;
+    }
+    let sources_dir = dependency_dir.join("sources");
+    std::fs::create_dir_all(&sources_dir)
+        .map_err(|err| CliError::IO(format!("{}", sources_dir.display()), err))?;
+
+    let mut addresses = BTreeSet::new();
+    for module in modules {
+        let module = CompiledModule::deserialize(module.bytecode.inner())
+            .map_err(|err| CliError::UnableToParse("Published module", err.to_string()))?;
+        addresses.extend(
+            module
+                .immediate_dependencies()
+                .iter()
+                .map(|module_id| *module_id.address())
+                .filter(|dependency| *dependency != address && *dependency != CORE_CODE_ADDRESS),
+        );
+        let (module_id, interface) =
+            interface_generator::write_module_to_string(&BTreeMap::new(), &module)
+                .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        let path: PathBuf = sources_dir.join(format!("{}.move", module_id.name()));
+        write_to_file(&path, &module_id.to_string(), interface.as_bytes())?;
+    }
+
+    let mut dependencies = other_dependencies.clone();
+    for address in &addresses {
+        let name = packages
+            .get(address)
+            .cloned()
+            .unwrap_or_else(|| package_name(*address));
+        let mut dependency = toml::value::Table::new();
+        let local = package_dir.join(DEPENDENCIES_DIR).join(&name);
+        dependency.insert(
+            "local".to_string(),
+            toml::Value::String(local.display().to_string()),
+        );
+        dependencies.insert(name, toml::Value::Table(dependency));
+    }
+
+    let mut package = toml::value::Table::new();
+    package.insert("name".to_string(), toml::Value::String(name.to_string()));
+    package.insert(
+        "version".to_string(),
+        toml::Value::String("0.0.0".to_string()),
+    );
+    let mut manifest = toml::value::Table::new();
+    manifest.insert("package".to_string(), toml::Value::Table(package));
+    manifest.insert("dependencies".to_string(), toml::Value::Table(dependencies));
+    write_toml(&dependency_dir.join(MANIFEST_FILE), &manifest)?;
+
+    // Written last, so that an interrupted download isn't mistaken for a cached one
+    let metadata = Metadata {
+        address: dependency.address.clone(),
+        url: dependency.url.clone(),
+        dependencies: addresses
+            .iter()
+            .map(|address| address.to_hex_literal())
+            .collect(),
+    };
+    write_toml(&dependency_dir.join(METADATA_FILE), &metadata)?;
+    Ok(addresses.into_iter().collect())
+}
+
+fn write_toml<T: Serialize>(path: &Path, value: &T) -> CliTypedResult<()> {
+    let contents = toml::to_string(value)
+        .map_err(|err| CliError::UnexpectedError(format!("Failed to serialize toml {}", err)))?;
+    write_to_file(path, &format!("{}", path.display()), contents.as_bytes())
+}
@@ -9,33 +9,35 @@
 use crate::{
     common::{
         types::{
-            CliError, CliTypedResult, EncodingOptions, MovePackageDir, WriteTransactionOptions,
+            CliError, CliTypedResult, EncodingOptions, MovePackageDir, ProfileOptions,
+            WriteTransactionOptions,
         },
         utils::to_common_result,
     },
     CliResult,
 };
-use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey};
 use aptos_rest_client::{Client, Transaction};
-use aptos_sdk::{transaction_builder::TransactionFactory, types::LocalAccount};
-use aptos_types::{
-    chain_id::ChainId,
-    transaction::{authenticator::AuthenticationKey, ModuleBundle, TransactionPayload},
-};
+use aptos_types::transaction::{ModuleBundle, TransactionPayload};
 use aptos_vm::natives::aptos_natives;
 use clap::{Parser, Subcommand};
+use move_binary_format::CompiledModule;
 use move_cli::package::cli::{run_move_unit_tests, UnitTestResult};
-use move_core_types::account_address::AccountAddress;
+use move_core_types::{account_address::AccountAddress, language_storage::ModuleId};
 use move_package::{compilation::compiled_package::CompiledPackage, BuildConfig};
 use move_unit_test::UnitTestingConfig;
-use reqwest::Url;
-use std::path::Path;
+use serde::Serialize;
+use std::{collections::BTreeMap, path::Path};
+
+pub mod bindings;
+mod compatibility;
+mod dependencies;
 
 /// CLI tool for performing Move tasks
 ///
 #[derive(Subcommand)]
 pub enum MoveTool {
     Compile(CompilePackage),
+    GenerateRustBindings(bindings::GenerateRustBindings),
     Publish(PublishPackage),
     Test(TestPackage),
 }
@@ -44,6 +46,7 @@ impl MoveTool {
     pub async fn execute(self) -> CliResult {
         match self {
             MoveTool::Compile(tool) => to_common_result(tool.execute().await),
+            MoveTool::GenerateRustBindings(tool) => to_common_result(tool.execute().await),
             MoveTool::Publish(tool) => to_common_result(tool.execute().await),
             MoveTool::Test(tool) => to_common_result(tool.execute().await),
         }
@@ -55,10 +58,15 @@ impl MoveTool {
 pub struct CompilePackage {
     #[clap(flatten)]
     move_options: MovePackageDir,
+    #[clap(flatten)]
+    profile_options: ProfileOptions,
 }
 
 impl CompilePackage {
     pub async fn execute(self) -> CliTypedResult<Vec<String>> {
+        self.move_options
+            .resolve_on_chain_dependencies(&self.profile_options.profile)
+            .await?;
         let build_config = BuildConfig {
             additional_named_addresses: self.move_options.named_addresses.clone(),
             generate_docs: true,
@@ -81,10 +89,15 @@ impl CompilePackage {
 pub struct TestPackage {
     #[clap(flatten)]
     move_options: MovePackageDir,
+    #[clap(flatten)]
+    profile_options: ProfileOptions,
 }
 
 impl TestPackage {
     pub async fn execute(self) -> CliTypedResult<&'static str> {
+        self.move_options
+            .resolve_on_chain_dependencies(&self.profile_options.profile)
+            .await?;
         let config = BuildConfig {
             additional_named_addresses: self.move_options.named_addresses.clone(),
             test_mode: true,
@@ -108,6 +121,20 @@ impl TestPackage {
     }
 }
 
+impl MovePackageDir {
+    /// Downloads the modules of the `[on-chain-dependencies]` of the package
+    ///
+    /// Dependencies without a `url` are downloaded from the rest endpoint of `profile`.
+    async fn resolve_on_chain_dependencies(&self, profile: &str) -> CliTypedResult<()> {
+        dependencies::resolve_on_chain_dependencies(
+            self.package_dir.as_path(),
+            self.refresh_dependencies,
+            profile,
+        )
+        .await
+    }
+}
+
 /// Compiles a Move package dir, and returns the compiled modules.
 fn compile_move(build_config: BuildConfig, package_dir: &Path) -> CliTypedResult<CompiledPackage> {
     // TODO: Add caching
@@ -117,6 +144,9 @@ fn compile_move(build_config: BuildConfig, package_dir: &Path) -> CliTypedResult
 }
 
 /// Publishes the modules in a Move package
+///
+/// Before submitting, the modules are checked against the versions already published at the
+/// sender's address, and upgrades that would break dependents or stored data are rejected.
 #[derive(Parser)]
 pub struct PublishPackage {
     #[clap(flatten)]
@@ -125,10 +155,27 @@ pub struct PublishPackage {
     move_options: MovePackageDir,
     #[clap(flatten)]
     write_options: WriteTransactionOptions,
+    /// Run the checks and simulate the publish transaction without submitting it
+    #[clap(long)]
+    dry_run: bool,
+}
+
+/// The outcome of publishing a package
+#[derive(Debug, Serialize)]
+pub struct PublishSummary {
+    /// Modules of the package
+    modules: Vec<String>,
+    /// Modules of the package that replace an already published version
+    upgraded_modules: Vec<String>,
+    /// The committed transaction, or the simulated one for a dry run
+    transaction: Transaction,
 }
 
 impl PublishPackage {
-    pub async fn execute(self) -> CliTypedResult<aptos_rest_client::Transaction> {
+    pub async fn execute(self) -> CliTypedResult<PublishSummary> {
+        self.move_options
+            .resolve_on_chain_dependencies(self.write_options.profile())
+            .await?;
         let build_config = BuildConfig {
             additional_named_addresses: self.move_options.named_addresses.clone(),
             generate_abis: false,
@@ -137,62 +184,102 @@ impl PublishPackage {
             ..Default::default()
         };
         let package = compile_move(build_config, self.move_options.package_dir.as_path())?;
-        let compiled_units: Vec<Vec<u8>> = package
-            .compiled_units
-            .iter()
-            .map(|unit_with_source| unit_with_source.unit.serialize())
+        let modules: Vec<CompiledModule> = package
+            .compiled_modules()
+            .iter_modules()
+            .into_iter()
+            .cloned()
             .collect();
-        let compiled_payload = TransactionPayload::ModuleBundle(ModuleBundle::new(compiled_units));
 
-        // Now that it's compiled, lets send it
         let sender_key = self
             .write_options
-            .private_key_options
-            .extract_private_key(self.encoding_options.encoding)?;
-
-        submit_transaction(
-            self.write_options.rest_options.url()?,
-            self.write_options.chain_id().await?,
-            sender_key,
-            compiled_payload,
-            self.write_options.max_gas,
-        )
-        .await
-    }
-}
+            .private_key(self.encoding_options.encoding)?;
+        let sender_address = self.write_options.sender_address(&sender_key)?;
+        if let Some(module) = modules
+            .iter()
+            .find(|module| *module.self_id().address() != sender_address)
+        {
+            return Err(CliError::CommandArgumentError(format!(
+                "Module {} can't be published by {}, check the named addresses of the package",
+                module.self_id(),
+                sender_address
+            )));
+        }
 
-/// Submits a [`TransactionPayload`] as signed by the `sender_key`
-async fn submit_transaction(
-    url: Url,
-    chain_id: ChainId,
-    sender_key: Ed25519PrivateKey,
-    payload: TransactionPayload,
-    max_gas: u64,
-) -> CliTypedResult<Transaction> {
-    let client = Client::new(url);
-
-    // Get sender address
-    let sender_address = AuthenticationKey::ed25519(&sender_key.public_key()).derived_address();
-    let sender_address = AccountAddress::new(*sender_address);
-
-    // Get account to get the sequence number
-    let account_response = client
-        .get_account(sender_address)
-        .await
-        .map_err(|err| CliError::ApiError(err.to_string()))?;
-    let account = account_response.inner();
-    let sequence_number = account.sequence_number;
-
-    let transaction_factory = TransactionFactory::new(chain_id)
-        .with_gas_unit_price(1)
-        .with_max_gas_amount(max_gas);
-    let sender_account = &mut LocalAccount::new(sender_address, sender_key, sequence_number);
-    let transaction =
-        sender_account.sign_with_transaction_builder(transaction_factory.payload(payload));
-    let response = client
-        .submit_and_wait(&transaction)
-        .await
-        .map_err(|err| CliError::ApiError(err.to_string()))?;
+        // Check the upgrade against what is published on chain
+        let published_modules = self.published_modules(sender_address).await?;
+        let mut upgraded_modules = vec![];
+        let mut incompatible_changes = vec![];
+        for module in &modules {
+            if let Some(old_module) = published_modules.get(&module.self_id()) {
+                upgraded_modules.push(module.self_id().to_string());
+                incompatible_changes
+                    .extend(compatibility::incompatible_changes(old_module, module));
+            }
+        }
+        if !incompatible_changes.is_empty() {
+            return Err(CliError::MoveCompatibilityError(
+                incompatible_changes.join("\n"),
+            ));
+        }
 
-    Ok(response.inner().clone())
+        let mut compiled_units = vec![];
+        for module in &modules {
+            let mut bytes = vec![];
+            module
+                .serialize(&mut bytes)
+                .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+            compiled_units.push(bytes);
+        }
+        let compiled_payload = TransactionPayload::ModuleBundle(ModuleBundle::new(compiled_units));
+
+        // Now that it's compiled and checked, lets send it
+        let encoding = self.encoding_options.encoding;
+        let transaction = if self.dry_run {
+            let transaction = self
+                .write_options
+                .simulate_transaction(encoding, compiled_payload)
+                .await?;
+            if !transaction.success() {
+                return Err(CliError::UnexpectedError(format!(
+                    "Publishing the package would fail: {}",
+                    transaction.vm_status()
+                )));
+            }
+            transaction
+        } else {
+            self.write_options
+                .submit_transaction(encoding, compiled_payload)
+                .await?
+        };
+        Ok(PublishSummary {
+            modules: modules
+                .iter()
+                .map(|module| module.self_id().to_string())
+                .collect(),
+            upgraded_modules,
+            transaction,
+        })
+    }
+
+    /// Fetches the modules currently published at `address`
+    async fn published_modules(
+        &self,
+        address: AccountAddress,
+    ) -> CliTypedResult<BTreeMap<ModuleId, CompiledModule>> {
+        let client = Client::new(self.write_options.url()?);
+        let modules = client
+            .get_account_modules(address)
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?
+            .into_inner();
+        modules
+            .iter()
+            .map(|module| {
+                CompiledModule::deserialize(module.bytecode.inner())
+                    .map(|module| (module.self_id(), module))
+                    .map_err(|err| CliError::UnableToParse("Published module", err.to_string()))
+            })
+            .collect()
+    }
 }
@@ -0,0 +1,77 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A command to wait for an epoch change
+//!
+
+use crate::common::types::{CliError, CliTypedResult, ProfileOptions, RestOptions};
+use aptos_rest_client::{Client, Event};
+use aptos_types::on_chain_config::new_epoch_event_key;
+use clap::Parser;
+use std::time::{Duration, Instant};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Wait for the reconfiguration event of an epoch
+///
+/// The changes to the validator set and to the validator configs take effect at the start of the
+/// next epoch.  Returns the `NewEpochEvent` of the epoch once it is committed.
+#[derive(Debug, Parser)]
+pub struct WatchEpoch {
+    #[clap(flatten)]
+    rest_options: RestOptions,
+    #[clap(flatten)]
+    profile_options: ProfileOptions,
+
+    /// Epoch to wait for, defaults to the epoch after the current one
+    #[clap(long)]
+    epoch: Option<u64>,
+    /// Number of seconds to wait for the epoch before giving up
+    #[clap(long, default_value_t = 3600)]
+    timeout_secs: u64,
+}
+
+impl WatchEpoch {
+    pub async fn execute(self) -> CliTypedResult<Event> {
+        let client = Client::new(self.rest_options.url(&self.profile_options.profile)?);
+        let current_epoch = client
+            .get_ledger_information()
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?
+            .into_inner()
+            .epoch;
+        let epoch = self.epoch.unwrap_or(current_epoch + 1);
+        if epoch > current_epoch {
+            eprintln!(
+                "Current epoch is {}, waiting for epoch {}",
+                current_epoch, epoch
+            );
+        }
+
+        // Genesis emits the event of epoch 1, and every reconfiguration the one of the next epoch,
+        // so the event of an epoch has the sequence number `epoch - 1`
+        let timeout = Duration::from_secs(self.timeout_secs);
+        let start = Instant::now();
+        loop {
+            let events = client
+                .get_events(
+                    new_epoch_event_key(),
+                    Some(epoch.saturating_sub(1)),
+                    Some(1),
+                )
+                .await
+                .map_err(|err| CliError::ApiError(err.to_string()))?
+                .into_inner();
+            if let Some(event) = events.into_iter().next() {
+                return Ok(event);
+            }
+            if start.elapsed() > timeout {
+                return Err(CliError::UnexpectedError(format!(
+                    "Epoch {} didn't start within {:?}",
+                    epoch, timeout
+                )));
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
@@ -0,0 +1,140 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tools for running nodes and operating validators
+//!
+
+pub mod epoch;
+pub mod validator;
+
+use crate::common::{
+    types::{CliError, CliResult, CliTypedResult, EncodingType},
+    utils::{to_common_result, to_common_success_result},
+};
+use aptos_crypto::ed25519::Ed25519PrivateKey;
+use aptos_faucet::Service;
+use aptos_rest_client::Client;
+use aptos_sdk::types::LocalAccount;
+use aptos_types::{account_config::aptos_root_address, chain_id::ChainId};
+use clap::{Parser, Subcommand};
+use rand::{rngs::StdRng, SeedableRng};
+use reqwest::Url;
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+const NODE_STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// CLI tool for running nodes and operating validators
+///
+#[derive(Debug, Subcommand)]
+pub enum NodeTool {
+    RunLocalTestnet(RunLocalTestnet),
+    RegisterValidatorCandidate(validator::RegisterValidatorCandidate),
+    UpdateValidatorNetworkAddresses(validator::UpdateValidatorNetworkAddresses),
+    RotateConsensusKey(validator::RotateConsensusKey),
+    AddStake(validator::AddStake),
+    JoinValidatorSet(validator::JoinValidatorSet),
+    LeaveValidatorSet(validator::LeaveValidatorSet),
+    WatchEpoch(epoch::WatchEpoch),
+}
+
+impl NodeTool {
+    pub async fn execute(self) -> CliResult {
+        match self {
+            NodeTool::RunLocalTestnet(tool) => to_common_success_result(tool.execute().await),
+            NodeTool::RegisterValidatorCandidate(tool) => to_common_result(tool.execute().await),
+            NodeTool::UpdateValidatorNetworkAddresses(tool) => {
+                to_common_result(tool.execute().await)
+            }
+            NodeTool::RotateConsensusKey(tool) => to_common_result(tool.execute().await),
+            NodeTool::AddStake(tool) => to_common_result(tool.execute().await),
+            NodeTool::JoinValidatorSet(tool) => to_common_result(tool.execute().await),
+            NodeTool::LeaveValidatorSet(tool) => to_common_result(tool.execute().await),
+            NodeTool::WatchEpoch(tool) => to_common_result(tool.execute().await),
+        }
+    }
+}
+
+/// Runs a single validator testnet along with a faucet, until interrupted
+///
+/// The state of the testnet is kept in `test-dir`, so that it survives restarts.
+#[derive(Debug, Parser)]
+pub struct RunLocalTestnet {
+    /// Directory to keep the configuration and the state of the testnet in
+    #[clap(long, parse(from_os_str), default_value = ".aptos/testnet")]
+    test_dir: PathBuf,
+    /// Delete the state of a previous run and start from genesis
+    #[clap(long)]
+    reset: bool,
+    /// Port of the faucet
+    #[clap(long, default_value_t = 8081)]
+    faucet_port: u16,
+}
+
+impl RunLocalTestnet {
+    pub async fn execute(self) -> CliTypedResult<()> {
+        if self.reset && self.test_dir.exists() {
+            std::fs::remove_dir_all(&self.test_dir)
+                .map_err(|err| CliError::IO(format!("{}", self.test_dir.display()), err))?;
+        }
+
+        let (config, test_dir) = aptos_node::load_or_create_test_config(
+            &self.test_dir,
+            false,
+            false,
+            None,
+            cached_framework_packages::module_blobs().to_vec(),
+            StdRng::from_entropy(),
+        );
+
+        // The node runs its own runtimes, keep them off the runtime of the CLI
+        let log_file = test_dir.join("validator.log");
+        let node_config = config.clone();
+        std::thread::spawn(move || aptos_node::start(&node_config, Some(log_file)));
+
+        let rest_url = Url::parse(&format!("http://127.0.0.1:{}", config.api.address.port()))
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        let client = Client::new(rest_url.clone());
+        let start = Instant::now();
+        while client.get_ledger_information().await.is_err() {
+            if start.elapsed() > NODE_STARTUP_TIMEOUT {
+                return Err(CliError::UnexpectedError(format!(
+                    "Node didn't start within {:?}, see {}",
+                    NODE_STARTUP_TIMEOUT,
+                    test_dir.join("validator.log").display()
+                )));
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+
+        // The faucet mints from the root account, which may have sent transactions in a previous run
+        let mint_key: Ed25519PrivateKey =
+            EncodingType::BCS.load_key("mint.key", test_dir.join("mint.key").as_path())?;
+        let sequence_number = client
+            .get_account(aptos_root_address())
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?
+            .into_inner()
+            .sequence_number;
+        let faucet_account = LocalAccount::new(aptos_root_address(), mint_key, sequence_number);
+        let service = Arc::new(Service::new(
+            rest_url.to_string(),
+            ChainId::test(),
+            faucet_account,
+            None,
+        ));
+
+        eprintln!("Local testnet is running, press ctrl-c to exit");
+        eprintln!("\tREST API endpoint: {}", rest_url);
+        eprintln!("\tFaucet endpoint: http://127.0.0.1:{}", self.faucet_port);
+        eprintln!("\tChain id: {}", ChainId::test());
+        eprintln!("\tTest directory: {}", test_dir.display());
+        warp::serve(aptos_faucet::routes(service))
+            .run(([127, 0, 0, 1], self.faucet_port))
+            .await;
+        Ok(())
+    }
+}
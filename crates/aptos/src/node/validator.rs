@@ -0,0 +1,270 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Commands for the lifecycle of a validator: registering it, updating its config, staking and
+//! joining or leaving the validator set
+//!
+//! The changes to the validator set and to the configs of its validators take effect at the next
+//! epoch, see `aptos node watch-epoch`.
+
+use crate::common::types::{CliError, CliTypedResult, EncodingOptions, WriteTransactionOptions};
+use aptos_crypto::ed25519::Ed25519PublicKey;
+use aptos_rest_client::{Client, Transaction};
+use aptos_transaction_builder::aptos_stdlib;
+use aptos_types::{account_address::AccountAddress, network_address::NetworkAddress};
+use clap::Parser;
+use std::path::PathBuf;
+
+/// The lockup of a stake must be longer than a day, see `Stake.move`
+const DEFAULT_LOCKUP_DURATION_SECS: u64 = 7 * 24 * 3600;
+
+#[derive(Debug, Parser)]
+pub struct ConsensusKeyInputOptions {
+    /// Consensus public key input file name
+    #[clap(long, group = "consensus_public_key_input", parse(from_os_str))]
+    consensus_public_key_file: Option<PathBuf>,
+    /// Consensus public key encoded in a type as shown in `encoding`
+    #[clap(long, group = "consensus_public_key_input")]
+    consensus_public_key: Option<String>,
+}
+
+impl ConsensusKeyInputOptions {
+    /// The consensus public key as stored in the `ValidatorConfig`
+    fn encoded_key(&self, encoding_options: &EncodingOptions) -> CliTypedResult<Vec<u8>> {
+        let encoding = encoding_options.encoding;
+        let key: Ed25519PublicKey = if let Some(ref file) = self.consensus_public_key_file {
+            encoding.load_key("--consensus-public-key-file", file.as_path())?
+        } else if let Some(ref key) = self.consensus_public_key {
+            encoding.decode_key("--consensus-public-key", key.as_bytes().to_vec())?
+        } else {
+            return Err(CliError::CommandArgumentError(
+                "One of ['--consensus-public-key', '--consensus-public-key-file'] must be used"
+                    .to_string(),
+            ));
+        };
+        Ok(key.to_bytes().to_vec())
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct NetworkAddressInputOptions {
+    /// Address the other validators connect to, with the x25519 key of the validator
+    ///
+    /// e.g. `/dns4/validator.example.com/tcp/6180/ln-noise-ik/<x25519 public key>/ln-handshake/0`
+    #[clap(long)]
+    validator_network_address: NetworkAddress,
+    /// Address the fullnodes connect to, with the x25519 key of the validator fullnode
+    #[clap(long)]
+    fullnode_network_address: NetworkAddress,
+}
+
+impl NetworkAddressInputOptions {
+    /// The validator and fullnode addresses as stored in the `ValidatorConfig`
+    fn encoded_addresses(&self) -> CliTypedResult<(Vec<u8>, Vec<u8>)> {
+        Ok((
+            encode_address(
+                "--validator-network-address",
+                &self.validator_network_address,
+            )?,
+            encode_address("--fullnode-network-address", &self.fullnode_network_address)?,
+        ))
+    }
+}
+
+/// The peers authenticate the validator with the noise key of its address, so the address is
+/// checked before it is stored on chain
+fn encode_address(name: &'static str, address: &NetworkAddress) -> CliTypedResult<Vec<u8>> {
+    if address.find_noise_proto().is_none() {
+        return Err(CliError::CommandArgumentError(format!(
+            "{} must contain the x25519 public key of the node e.g. '/ln-noise-ik/<key>', got '{}'",
+            name, address
+        )));
+    }
+    bcs::to_bytes(&vec![address.clone()]).map_err(|err| CliError::BCS(name, err))
+}
+
+/// Register the sender as a validator candidate
+///
+/// The account can join the validator set once it has enough stake.
+#[derive(Debug, Parser)]
+pub struct RegisterValidatorCandidate {
+    #[clap(flatten)]
+    encoding_options: EncodingOptions,
+    #[clap(flatten)]
+    write_options: WriteTransactionOptions,
+    #[clap(flatten)]
+    consensus_key_options: ConsensusKeyInputOptions,
+    #[clap(flatten)]
+    network_address_options: NetworkAddressInputOptions,
+}
+
+impl RegisterValidatorCandidate {
+    pub async fn execute(self) -> CliTypedResult<Transaction> {
+        let consensus_pubkey = self
+            .consensus_key_options
+            .encoded_key(&self.encoding_options)?;
+        let (network_address, fullnode_address) =
+            self.network_address_options.encoded_addresses()?;
+        self.write_options
+            .submit_transaction(
+                self.encoding_options.encoding,
+                aptos_stdlib::encode_register_validator_candidate_script_function(
+                    consensus_pubkey,
+                    network_address,
+                    fullnode_address,
+                ),
+            )
+            .await
+    }
+}
+
+/// Update the network addresses of the sender's validator, from the next epoch on
+///
+#[derive(Debug, Parser)]
+pub struct UpdateValidatorNetworkAddresses {
+    #[clap(flatten)]
+    encoding_options: EncodingOptions,
+    #[clap(flatten)]
+    write_options: WriteTransactionOptions,
+    #[clap(flatten)]
+    network_address_options: NetworkAddressInputOptions,
+}
+
+impl UpdateValidatorNetworkAddresses {
+    pub async fn execute(self) -> CliTypedResult<Transaction> {
+        let (network_address, fullnode_address) =
+            self.network_address_options.encoded_addresses()?;
+        self.write_options
+            .submit_transaction(
+                self.encoding_options.encoding,
+                aptos_stdlib::encode_update_network_addresses_script_function(
+                    network_address,
+                    fullnode_address,
+                ),
+            )
+            .await
+    }
+}
+
+/// Rotate the consensus key of the sender's validator, from the next epoch on
+///
+/// The validator must keep signing with the old key until the epoch changes.
+#[derive(Debug, Parser)]
+pub struct RotateConsensusKey {
+    #[clap(flatten)]
+    encoding_options: EncodingOptions,
+    #[clap(flatten)]
+    write_options: WriteTransactionOptions,
+    #[clap(flatten)]
+    consensus_key_options: ConsensusKeyInputOptions,
+}
+
+impl RotateConsensusKey {
+    pub async fn execute(self) -> CliTypedResult<Transaction> {
+        let consensus_pubkey = self
+            .consensus_key_options
+            .encoded_key(&self.encoding_options)?;
+        self.write_options
+            .submit_transaction(
+                self.encoding_options.encoding,
+                aptos_stdlib::encode_rotate_consensus_key_script_function(consensus_pubkey),
+            )
+            .await
+    }
+}
+
+/// Stake coins of the sender on a validator
+///
+/// The stake of a validator in the validator set is only counted from the next epoch on.
+#[derive(Debug, Parser)]
+pub struct AddStake {
+    #[clap(flatten)]
+    encoding_options: EncodingOptions,
+    #[clap(flatten)]
+    write_options: WriteTransactionOptions,
+    /// Address of the validator, defaults to the sender
+    #[clap(long)]
+    validator: Option<AccountAddress>,
+    /// Number of coins to stake
+    #[clap(long)]
+    amount: u64,
+    /// Number of seconds from now the stake stays locked for
+    #[clap(long, default_value_t = DEFAULT_LOCKUP_DURATION_SECS)]
+    lockup_duration_secs: u64,
+}
+
+impl AddStake {
+    pub async fn execute(self) -> CliTypedResult<Transaction> {
+        let encoding = self.encoding_options.encoding;
+        let validator = if let Some(validator) = self.validator {
+            validator
+        } else {
+            let sender_key = self.write_options.private_key(encoding)?;
+            self.write_options.sender_address(&sender_key)?
+        };
+
+        // The lockup is checked against the time of the chain rather than the local clock
+        let client = Client::new(self.write_options.url()?);
+        let now_secs = client
+            .get_ledger_information()
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?
+            .into_inner()
+            .timestamp_usecs
+            / 1_000_000;
+        self.write_options
+            .submit_transaction(
+                encoding,
+                aptos_stdlib::encode_delegate_stake_script_function(
+                    validator,
+                    self.amount,
+                    now_secs + self.lockup_duration_secs,
+                ),
+            )
+            .await
+    }
+}
+
+/// Add the sender's validator to the validator set from the next epoch on
+///
+/// The validator must be registered and have the minimum stake.
+#[derive(Debug, Parser)]
+pub struct JoinValidatorSet {
+    #[clap(flatten)]
+    encoding_options: EncodingOptions,
+    #[clap(flatten)]
+    write_options: WriteTransactionOptions,
+}
+
+impl JoinValidatorSet {
+    pub async fn execute(self) -> CliTypedResult<Transaction> {
+        self.write_options
+            .submit_transaction(
+                self.encoding_options.encoding,
+                aptos_stdlib::encode_join_validator_set_script_function(),
+            )
+            .await
+    }
+}
+
+/// Remove the sender's validator from the validator set from the next epoch on
+///
+/// The validator must keep running until the epoch changes.
+#[derive(Debug, Parser)]
+pub struct LeaveValidatorSet {
+    #[clap(flatten)]
+    encoding_options: EncodingOptions,
+    #[clap(flatten)]
+    write_options: WriteTransactionOptions,
+}
+
+impl LeaveValidatorSet {
+    pub async fn execute(self) -> CliTypedResult<Transaction> {
+        self.write_options
+            .submit_transaction(
+                self.encoding_options.encoding,
+                aptos_stdlib::encode_leave_validator_set_script_function(),
+            )
+            .await
+    }
+}
@@ -5,7 +5,7 @@ use crate::{
     common::{
         types::{
             CliError, CliTypedResult, EncodingOptions, EncodingType, ExtractPublicKey, KeyType,
-            PrivateKeyInputOptions, SaveFile,
+            PrivateKeyInputOptions, ProfileOptions, SaveFile,
         },
         utils::{append_file_extension, check_if_file_exists, to_common_result, write_to_file},
     },
@@ -42,7 +42,8 @@ impl KeyTool {
 /// CLI tool for extracting full peer information for an upstream peer
 ///
 /// A `private-key` or `public-key` can be given encoded on the command line, or
-/// a `private-key-file` or a `public-key-file` can be given to read from.
+/// a `private-key-file` or a `public-key-file` can be given to read from.  Otherwise the
+/// private key of the `profile` is used.
 /// The `output_file` will be a YAML serialized peer information for use in network config.
 #[derive(Debug, Parser)]
 pub struct ExtractPeer {
@@ -52,6 +53,8 @@ pub struct ExtractPeer {
     output_file_options: SaveFile,
     #[clap(flatten)]
     encoding_options: EncodingOptions,
+    #[clap(flatten)]
+    profile_options: ProfileOptions,
 }
 
 impl ExtractPeer {
@@ -60,9 +63,10 @@ impl ExtractPeer {
         self.output_file_options.check_file()?;
 
         // Load key based on public or private
-        let public_key = self
-            .private_key_input_options
-            .extract_x25519_public_key(self.encoding_options.encoding)?;
+        let public_key = self.private_key_input_options.extract_x25519_public_key(
+            self.encoding_options.encoding,
+            &self.profile_options.profile,
+        )?;
 
         // Build peer info
         // TODO: Take in an address?
@@ -0,0 +1,134 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A command to build an unsigned transaction to be signed offline
+//!
+
+use crate::{
+    common::{
+        init::{DEFAULT_GAS_UNIT_PRICE, DEFAULT_MAX_GAS},
+        types::{CliConfig, CliError, CliTypedResult, ProfileOptions, RestOptions, SaveFile},
+    },
+    transaction::read_bcs_file,
+};
+use aptos_rest_client::Client;
+use aptos_sdk::transaction_builder::TransactionFactory;
+use aptos_types::{
+    chain_id::ChainId,
+    transaction::{RawTransaction, TransactionPayload},
+};
+use clap::Parser;
+use move_core_types::account_address::AccountAddress;
+use std::path::PathBuf;
+
+/// Command to build an unsigned `RawTransaction` and write it to a file
+///
+/// The sequence number and chain id are fetched from the network unless they are given.
+#[derive(Debug, Parser)]
+pub struct BuildTransaction {
+    #[clap(flatten)]
+    rest_options: RestOptions,
+    #[clap(flatten)]
+    profile_options: ProfileOptions,
+    /// Address of the account sending the transaction
+    #[clap(long)]
+    sender: AccountAddress,
+    /// Sequence number of the transaction
+    ///
+    /// Defaults to the current sequence number of the sender
+    #[clap(long)]
+    sequence_number: Option<u64>,
+    /// Chain id of the network the transaction is for
+    ///
+    /// Defaults to the chain id of the network at `url`
+    #[clap(long)]
+    chain_id: Option<ChainId>,
+    /// File containing a BCS encoded `TransactionPayload`
+    #[clap(long, group = "payload", parse(from_os_str))]
+    payload_file: Option<PathBuf>,
+    /// Recipient of a coin transfer, as an alternative to `payload-file`
+    #[clap(long, group = "payload", requires = "amount")]
+    transfer_to: Option<AccountAddress>,
+    /// Amount of coins to transfer to `transfer-to`
+    #[clap(long)]
+    amount: Option<u64>,
+    /// Maximum gas to be used by the transaction
+    ///
+    /// Defaults to the max gas of the profile, or 1000 gas units
+    #[clap(long)]
+    max_gas: Option<u64>,
+    /// Price of a unit of gas
+    ///
+    /// Defaults to the gas unit price of the profile, or 1
+    #[clap(long)]
+    gas_unit_price: Option<u64>,
+    /// Number of seconds from now until the transaction expires
+    ///
+    /// Defaults to an hour, to leave time for signing offline
+    #[clap(long, default_value_t = 3600)]
+    expiration_secs: u64,
+    #[clap(flatten)]
+    output_file_options: SaveFile,
+}
+
+impl BuildTransaction {
+    pub async fn execute(self) -> CliTypedResult<RawTransaction> {
+        self.output_file_options.check_file()?;
+
+        let payload = if let Some(ref file) = self.payload_file {
+            read_bcs_file::<TransactionPayload>("Transaction payload", file)?
+        } else if let (Some(to), Some(amount)) = (self.transfer_to, self.amount) {
+            aptos_transaction_builder::aptos_stdlib::encode_transfer_script_function(to, amount)
+        } else {
+            return Err(CliError::CommandArgumentError(
+                "One of ['--payload-file', '--transfer-to'] must be used".to_string(),
+            ));
+        };
+
+        let profile_config =
+            CliConfig::load_profile(&self.profile_options.profile)?.unwrap_or_default();
+        let client = Client::new(self.rest_options.url(&self.profile_options.profile)?);
+        let chain_id = if let Some(chain_id) = self.chain_id {
+            chain_id
+        } else {
+            let state = client
+                .get_ledger_information()
+                .await
+                .map_err(|err| CliError::ApiError(err.to_string()))?
+                .into_inner();
+            ChainId::new(state.chain_id)
+        };
+        let sequence_number = if let Some(sequence_number) = self.sequence_number {
+            sequence_number
+        } else {
+            client
+                .get_account(self.sender)
+                .await
+                .map_err(|err| CliError::ApiError(err.to_string()))?
+                .inner()
+                .sequence_number
+        };
+
+        let raw_txn = TransactionFactory::new(chain_id)
+            .with_gas_unit_price(
+                self.gas_unit_price
+                    .or(profile_config.gas_unit_price)
+                    .unwrap_or(DEFAULT_GAS_UNIT_PRICE),
+            )
+            .with_max_gas_amount(
+                self.max_gas
+                    .or(profile_config.max_gas)
+                    .unwrap_or(DEFAULT_MAX_GAS),
+            )
+            .with_transaction_expiration_time(self.expiration_secs)
+            .payload(payload)
+            .sender(self.sender)
+            .sequence_number(sequence_number)
+            .build();
+
+        let bytes = bcs::to_bytes(&raw_txn).map_err(|err| CliError::BCS("Raw transaction", err))?;
+        self.output_file_options
+            .save_to_file("Raw transaction", &bytes)?;
+        Ok(raw_txn)
+    }
+}
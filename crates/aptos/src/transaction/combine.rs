@@ -0,0 +1,87 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A command to combine the signature shares of a multisig account
+//!
+
+use crate::{
+    common::types::{CliError, CliTypedResult, EncodingOptions, SaveFile},
+    transaction::{read_bcs_file, SignatureShare},
+};
+use aptos_crypto::ed25519::Ed25519PublicKey;
+use aptos_sdk::types::{MultiEd25519AccountKey, MultiEd25519PendingTransaction};
+use aptos_types::transaction::RawTransaction;
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Command to combine signature shares into a `SignedTransaction` of a multisig account
+///
+/// Every share is checked against the transaction and the keys of the account.  This command
+/// never accesses the network.
+#[derive(Debug, Parser)]
+pub struct CombineSignatures {
+    #[clap(flatten)]
+    encoding_options: EncodingOptions,
+    /// File containing the BCS encoded `RawTransaction` that was signed
+    #[clap(long, parse(from_os_str))]
+    raw_transaction_file: PathBuf,
+    /// Public keys of the multisig account, in the order they were used to create it
+    ///
+    /// Encoded in a type as shown in `encoding`
+    #[clap(long, required = true, multiple_values = true)]
+    public_keys: Vec<String>,
+    /// Number of signatures required by the multisig account
+    #[clap(long)]
+    threshold: u8,
+    /// Files containing the signature shares written by `aptos transaction sign --multisig-share`
+    #[clap(long, required = true, multiple_values = true, parse(from_os_str))]
+    signature_files: Vec<PathBuf>,
+    #[clap(flatten)]
+    output_file_options: SaveFile,
+}
+
+impl CombineSignatures {
+    pub fn execute(self) -> CliTypedResult<String> {
+        self.output_file_options.check_file()?;
+
+        let raw_txn: RawTransaction =
+            read_bcs_file("Raw transaction", self.raw_transaction_file.as_path())?;
+        let public_keys = self
+            .public_keys
+            .iter()
+            .map(|key| {
+                self.encoding_options
+                    .encoding
+                    .decode_key::<Ed25519PublicKey>("--public-keys", key.as_bytes().to_vec())
+            })
+            .collect::<CliTypedResult<Vec<_>>>()?;
+        let key = MultiEd25519AccountKey::new(public_keys, self.threshold)
+            .map_err(|err| CliError::CommandArgumentError(err.to_string()))?;
+
+        let mut txn = MultiEd25519PendingTransaction::new(raw_txn, &key);
+        for file in &self.signature_files {
+            let share: SignatureShare = read_bcs_file("Signature share", file.as_path())?;
+            let index = key.index_of(&share.public_key).ok_or_else(|| {
+                CliError::CommandArgumentError(format!(
+                    "Signature share {:?} is from a key outside of the multisig account",
+                    file
+                ))
+            })?;
+            txn.add_signature(index, share.signature).map_err(|err| {
+                CliError::CommandArgumentError(format!(
+                    "Invalid signature share {:?}: {}",
+                    file, err
+                ))
+            })?;
+        }
+        let signed_txn = txn
+            .into_signed_transaction()
+            .map_err(|err| CliError::CommandArgumentError(err.to_string()))?;
+
+        let bytes =
+            bcs::to_bytes(&signed_txn).map_err(|err| CliError::BCS("Signed transaction", err))?;
+        self.output_file_options
+            .save_to_file("Signed transaction", &bytes)?;
+        Ok(signed_txn.committed_hash().to_hex_literal())
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Commands to build, sign and submit transactions in separate steps
+//!
+//! This allows keys to be kept on a machine that never touches the network:
+//!
+//! 1. `aptos transaction build` writes an unsigned `RawTransaction` to a file
+//! 2. `aptos transaction sign` signs it offline, either fully or as one share of a multisig
+//! 3. `aptos transaction combine` assembles the multisig shares into a signed transaction
+//! 4. `aptos transaction submit` sends the signed transaction to the network
+//!
+//! All files are BCS encoded.
+//!
+
+use crate::common::{
+    types::{CliError, CliResult, CliTypedResult},
+    utils::{read_from_file, to_common_result},
+};
+use aptos_crypto::ed25519::{Ed25519PublicKey, Ed25519Signature};
+use clap::Subcommand;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::path::Path;
+
+pub mod build;
+pub mod combine;
+pub mod sign;
+pub mod submit;
+
+/// CLI tool for building, signing and submitting transactions separately
+///
+#[derive(Debug, Subcommand)]
+pub enum TransactionTool {
+    Build(build::BuildTransaction),
+    Sign(sign::SignTransaction),
+    Combine(combine::CombineSignatures),
+    Submit(submit::SubmitTransaction),
+}
+
+impl TransactionTool {
+    pub async fn execute(self) -> CliResult {
+        match self {
+            TransactionTool::Build(tool) => to_common_result(tool.execute().await),
+            TransactionTool::Sign(tool) => to_common_result(tool.execute()),
+            TransactionTool::Combine(tool) => to_common_result(tool.execute()),
+            TransactionTool::Submit(tool) => to_common_result(tool.execute().await),
+        }
+    }
+}
+
+/// The signature of one of the keys of a multisig account, to be combined with the others
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SignatureShare {
+    pub public_key: Ed25519PublicKey,
+    pub signature: Ed25519Signature,
+}
+
+/// Reads a BCS encoded value from a file
+pub(crate) fn read_bcs_file<T: DeserializeOwned>(
+    name: &'static str,
+    path: &Path,
+) -> CliTypedResult<T> {
+    bcs::from_bytes(&read_from_file(path)?).map_err(|err| CliError::BCS(name, err))
+}
@@ -0,0 +1,73 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A command to sign a transaction without network access
+//!
+
+use crate::{
+    common::types::{
+        CliError, CliTypedResult, EncodingOptions, PrivateKeyInputOptions, ProfileOptions, SaveFile,
+    },
+    transaction::{read_bcs_file, SignatureShare},
+};
+use aptos_crypto::{PrivateKey, SigningKey};
+use aptos_types::transaction::RawTransaction;
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Command to sign a `RawTransaction` built with `aptos transaction build`
+///
+/// By default, the output file contains a `SignedTransaction` ready to be submitted.  With
+/// `--multisig-share`, it contains the signature of this key only, to be combined with the
+/// shares of the other keys of a multisig account with `aptos transaction combine`.
+///
+/// This command never accesses the network.
+#[derive(Debug, Parser)]
+pub struct SignTransaction {
+    #[clap(flatten)]
+    encoding_options: EncodingOptions,
+    #[clap(flatten)]
+    private_key_options: PrivateKeyInputOptions,
+    #[clap(flatten)]
+    profile_options: ProfileOptions,
+    /// File containing the BCS encoded `RawTransaction` to sign
+    #[clap(long, parse(from_os_str))]
+    raw_transaction_file: PathBuf,
+    /// Write a signature share of a multisig account instead of a signed transaction
+    #[clap(long)]
+    multisig_share: bool,
+    #[clap(flatten)]
+    output_file_options: SaveFile,
+}
+
+impl SignTransaction {
+    pub fn execute(self) -> CliTypedResult<RawTransaction> {
+        self.output_file_options.check_file()?;
+
+        let raw_txn: RawTransaction =
+            read_bcs_file("Raw transaction", self.raw_transaction_file.as_path())?;
+        let private_key = self.private_key_options.extract_private_key(
+            self.encoding_options.encoding,
+            &self.profile_options.profile,
+        )?;
+
+        let bytes = if self.multisig_share {
+            let share = SignatureShare {
+                public_key: private_key.public_key(),
+                signature: private_key.sign(&raw_txn),
+            };
+            bcs::to_bytes(&share).map_err(|err| CliError::BCS("Signature share", err))?
+        } else {
+            let signed_txn = raw_txn
+                .clone()
+                .sign(&private_key, private_key.public_key())
+                .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+                .into_inner();
+            bcs::to_bytes(&signed_txn).map_err(|err| CliError::BCS("Signed transaction", err))?
+        };
+        self.output_file_options.save_to_file("Signature", &bytes)?;
+
+        // Return the transaction so that what was signed can be reviewed
+        Ok(raw_txn)
+    }
+}
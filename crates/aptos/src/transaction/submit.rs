@@ -0,0 +1,46 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A command to submit a transaction that was signed beforehand
+//!
+
+use crate::{
+    common::types::{CliError, CliTypedResult, ProfileOptions, RestOptions},
+    transaction::read_bcs_file,
+};
+use aptos_rest_client::{Client, Transaction};
+use aptos_types::transaction::SignedTransaction;
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Command to submit a `SignedTransaction` and wait for it to be committed
+///
+#[derive(Debug, Parser)]
+pub struct SubmitTransaction {
+    #[clap(flatten)]
+    rest_options: RestOptions,
+    #[clap(flatten)]
+    profile_options: ProfileOptions,
+    /// File containing the BCS encoded `SignedTransaction`
+    #[clap(long, parse(from_os_str))]
+    signed_transaction_file: PathBuf,
+}
+
+impl SubmitTransaction {
+    pub async fn execute(self) -> CliTypedResult<Transaction> {
+        let signed_txn: SignedTransaction =
+            read_bcs_file("Signed transaction", self.signed_transaction_file.as_path())?;
+        // Catch bad signatures before they reach the network
+        let signed_txn = signed_txn
+            .check_signature()
+            .map_err(|err| CliError::CommandArgumentError(err.to_string()))?
+            .into_inner();
+
+        let client = Client::new(self.rest_options.url(&self.profile_options.profile)?);
+        let response = client
+            .submit_and_wait(&signed_txn)
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?;
+        Ok(response.inner().clone())
+    }
+}
@@ -334,7 +334,7 @@ fn ensure_txn_valid_for_vacant_entry(transaction: &Transaction) -> Result<()> {
     // maybe other writeset transactions).
     match transaction {
         Transaction::GenesisTransaction(_) => (),
-        Transaction::BlockMetadata(_) => {
+        Transaction::BlockMetadata(_) | Transaction::BlockMetadataV2(_) => {
             bail!("Write set should be a subset of read set.")
         }
         Transaction::UserTransaction(txn) => match txn.payload() {
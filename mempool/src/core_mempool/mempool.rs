@@ -38,6 +38,8 @@ pub struct Mempool {
     // takes to pick it up by consensus.
     pub(crate) metrics_cache: TtlCache<(AccountAddress, u64), SystemTime>,
     pub system_transaction_timeout: Duration,
+    // Transactions larger than this can't fit in a block, so they aren't accepted.
+    max_txn_bytes: u64,
 }
 
 impl Mempool {
@@ -49,6 +51,7 @@ impl Mempool {
             system_transaction_timeout: Duration::from_secs(
                 config.mempool.system_transaction_timeout_secs,
             ),
+            max_txn_bytes: config.consensus.max_block_bytes,
         }
     }
 
@@ -160,11 +163,6 @@ impl Mempool {
 
         let expiration_time =
             aptos_infallible::duration_since_epoch() + self.system_transaction_timeout;
-        if timeline_state != TimelineState::NonQualified {
-            self.metrics_cache
-                .insert((txn.sender(), txn.sequence_number()), SystemTime::now());
-        }
-
         let txn_info = MempoolTransaction::new(
             txn,
             expiration_time,
@@ -173,18 +171,33 @@ impl Mempool {
             timeline_state,
             sequence_number,
         );
+        if txn_info.txn_bytes_len as u64 > self.max_txn_bytes {
+            return MempoolStatus::new(MempoolStatusCode::TooLarge).with_message(format!(
+                "transaction size is {} bytes, max size is {} bytes",
+                txn_info.txn_bytes_len, self.max_txn_bytes,
+            ));
+        }
+
+        if timeline_state != TimelineState::NonQualified {
+            self.metrics_cache.insert(
+                (txn_info.get_sender(), txn_info.txn.sequence_number()),
+                SystemTime::now(),
+            );
+        }
 
         self.transactions.insert(txn_info)
     }
 
     /// Fetches next block of transactions for consensus.
     /// `batch_size` - size of requested block.
+    /// `max_bytes` - max total size of the transactions of the requested block.
     /// `seen_txns` - transactions that were sent to Consensus but were not committed yet,
     ///  mempool should filter out such transactions.
     #[allow(clippy::explicit_counter_loop)]
     pub(crate) fn get_block(
         &self,
         batch_size: u64,
+        max_bytes: u64,
         mut seen: HashSet<TxnPointer>,
     ) -> Vec<SignedTransaction> {
         let mut result = vec![];
@@ -238,12 +251,26 @@ impl Mempool {
         }
         let result_size = result.len();
         // convert transaction pointers to real values
+        // A transaction that doesn't fit in `max_bytes` is left out along with the following
+        // transactions of its account, which can't be executed without it
         let mut block_log = TxnsLog::new();
+        let mut block_bytes = 0;
+        let mut oversized_accounts = HashSet::new();
         let block: Vec<_> = result
             .into_iter()
             .filter_map(|(address, tx_seq)| {
+                if oversized_accounts.contains(&address) {
+                    return None;
+                }
+                let (txn, txn_bytes) = self.transactions.get_with_bytes_len(&address, tx_seq)?;
+                let txn_bytes = txn_bytes as u64;
+                if block_bytes + txn_bytes > max_bytes {
+                    oversized_accounts.insert(address);
+                    return None;
+                }
+                block_bytes += txn_bytes;
                 block_log.add(address, tx_seq);
-                self.transactions.get(&address, tx_seq)
+                Some(txn)
             })
             .collect();
 
@@ -253,7 +280,8 @@ impl Mempool {
             walked = txn_walked,
             seen_after = seen.len(),
             result_size = result_size,
-            block_size = block.len()
+            block_size = block.len(),
+            block_bytes = block_bytes
         );
         for transaction in &block {
             self.log_latency(
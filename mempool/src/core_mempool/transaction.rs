@@ -12,6 +12,8 @@ use std::time::Duration;
 #[derive(Clone, Debug)]
 pub struct MempoolTransaction {
     pub txn: SignedTransaction,
+    // Size of the serialized transaction, computed once on insertion.
+    pub txn_bytes_len: usize,
     // System expiration time of the transaction. It should be removed from mempool by that time.
     pub expiration_time: Duration,
     pub gas_amount: u64,
@@ -34,6 +36,7 @@ impl MempoolTransaction {
                 transaction_sequence_number: txn.sequence_number(),
                 account_sequence_number_type: seqno_type,
             },
+            txn_bytes_len: txn.txn_bytes_len(),
             txn,
             expiration_time,
             gas_amount,
@@ -80,6 +80,18 @@ impl TransactionStore {
     }
 
     /// Fetch transaction by account address + sequence_number.
+    /// Returns the transaction along with its size in bytes.
+    pub(crate) fn get_with_bytes_len(
+        &self,
+        address: &AccountAddress,
+        sequence_number: u64,
+    ) -> Option<(SignedTransaction, usize)> {
+        self.transactions
+            .get(address)
+            .and_then(|txns| txns.get(&sequence_number))
+            .map(|txn| (txn.txn.clone(), txn.txn_bytes_len))
+    }
+
     pub(crate) fn get(
         &self,
         address: &AccountAddress,
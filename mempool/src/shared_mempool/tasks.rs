@@ -370,7 +370,12 @@ pub(crate) fn process_consensus_request<V: TransactionValidation>(
     debug!(LogSchema::event_log(LogEntry::Consensus, LogEvent::Received).consensus_msg(&req));
 
     let (resp, callback, counter_label) = match req {
-        ConsensusRequest::GetBlockRequest(max_block_size, transactions, callback) => {
+        ConsensusRequest::GetBlockRequest(
+            max_block_size,
+            max_block_bytes,
+            transactions,
+            callback,
+        ) => {
             let exclude_transactions: HashSet<TxnPointer> = transactions
                 .iter()
                 .map(|txn| (txn.sender, txn.sequence_number))
@@ -383,7 +388,7 @@ pub(crate) fn process_consensus_request<V: TransactionValidation>(
                 let curr_time = aptos_infallible::duration_since_epoch();
                 mempool.gc_by_expiration_time(curr_time);
                 let block_size = cmp::max(max_block_size, 1);
-                txns = mempool.get_block(block_size, exclude_transactions);
+                txns = mempool.get_block(block_size, max_block_bytes, exclude_transactions);
             }
             counters::mempool_service_transactions(counters::GET_BLOCK_LABEL, txns.len());
             txns.len();
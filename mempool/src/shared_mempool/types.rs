@@ -151,6 +151,8 @@ pub enum ConsensusRequest {
     GetBlockRequest(
         // max block size
         u64,
+        // max block size in bytes
+        u64,
         // transactions to exclude from the requested block
         Vec<TransactionSummary>,
         // callback to respond to
@@ -168,14 +170,14 @@ pub enum ConsensusRequest {
 impl fmt::Display for ConsensusRequest {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let payload = match self {
-            ConsensusRequest::GetBlockRequest(block_size, excluded_txns, _) => {
+            ConsensusRequest::GetBlockRequest(block_size, block_bytes, excluded_txns, _) => {
                 let mut txns_str = "".to_string();
                 for tx in excluded_txns.iter() {
                     txns_str += &format!("{} ", tx);
                 }
                 format!(
-                    "GetBlockRequest [block_size: {}, excluded_txns: {}]",
-                    block_size, txns_str
+                    "GetBlockRequest [block_size: {}, block_bytes: {}, excluded_txns: {}]",
+                    block_size, block_bytes, txns_str
                 )
             }
             ConsensusRequest::RejectNotification(rejected_txns, _) => {
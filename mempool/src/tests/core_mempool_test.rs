@@ -10,7 +10,10 @@ use crate::{
 };
 use aptos_config::config::NodeConfig;
 use aptos_crypto::HashValue;
-use aptos_types::{account_config::AccountSequenceInfo, transaction::SignedTransaction};
+use aptos_types::{
+    account_config::AccountSequenceInfo, mempool_status::MempoolStatusCode,
+    transaction::SignedTransaction,
+};
 use std::{
     collections::HashSet,
     time::{Duration, SystemTime},
@@ -375,7 +378,7 @@ fn test_system_ttl() {
 
     // GC routine should clear transaction from first insert but keep last one.
     mempool.gc();
-    let batch = mempool.get_block(1, HashSet::new());
+    let batch = mempool.get_block(1, u64::MAX, HashSet::new());
     assert_eq!(vec![transaction.make_signed_transaction()], batch);
 }
 
@@ -387,11 +390,11 @@ fn test_commit_callback() {
     let txns = add_txns_to_mempool(&mut pool, vec![TestTransaction::new(1, 6, 1)]);
 
     // Check that pool is empty.
-    assert!(pool.get_block(1, HashSet::new()).is_empty());
+    assert!(pool.get_block(1, u64::MAX, HashSet::new()).is_empty());
     // Transaction 5 got back from consensus.
     pool.remove_transaction(&TestTransaction::get_address(1), 5, false);
     // Verify that we can execute transaction 6.
-    assert_eq!(pool.get_block(1, HashSet::new())[0], txns[0]);
+    assert_eq!(pool.get_block(1, u64::MAX, HashSet::new())[0], txns[0]);
 }
 
 #[test]
@@ -405,7 +408,7 @@ fn test_sequence_number_cache() {
     // for AC is 0).
     add_txns_to_mempool(&mut pool, vec![TestTransaction::new(1, 6, 1)]);
     // Verify that we can execute transaction 6.
-    assert_eq!(pool.get_block(1, HashSet::new()).len(), 1);
+    assert_eq!(pool.get_block(1, u64::MAX, HashSet::new()).len(), 1);
 }
 
 #[test]
@@ -502,7 +505,7 @@ fn test_parking_lot_eviction() {
     }
     // Make sure that we have correct txns in Mempool.
     let mut txns: Vec<_> = pool
-        .get_block(5, HashSet::new())
+        .get_block(5, u64::MAX, HashSet::new())
         .iter()
         .map(SignedTransaction::sequence_number)
         .collect();
@@ -531,7 +534,7 @@ fn test_parking_lot_evict_only_for_ready_txn_insertion() {
 
     // Make sure that we have correct txns in Mempool.
     let mut txns: Vec<_> = pool
-        .get_block(5, HashSet::new())
+        .get_block(5, u64::MAX, HashSet::new())
         .iter()
         .map(SignedTransaction::sequence_number)
         .collect();
@@ -573,7 +576,7 @@ fn test_gc_ready_transaction() {
     pool.gc_by_expiration_time(Duration::from_secs(1));
 
     // Make sure txns 2 and 3 became not ready and we can't read them from any API.
-    let block = pool.get_block(10, HashSet::new());
+    let block = pool.get_block(10, u64::MAX, HashSet::new());
     assert_eq!(block.len(), 1);
     assert_eq!(block[0].sequence_number(), 0);
 
@@ -597,11 +600,50 @@ fn test_clean_stuck_transactions() {
         AccountSequenceInfo::Sequential(db_sequence_number),
         TimelineState::NotReady,
     );
-    let block = pool.get_block(10, HashSet::new());
+    let block = pool.get_block(10, u64::MAX, HashSet::new());
     assert_eq!(block.len(), 1);
     assert_eq!(block[0].sequence_number(), 10);
 }
 
+#[test]
+fn test_get_block_max_bytes() {
+    let (mut pool, _) = setup_mempool();
+    let txns = add_txns_to_mempool(
+        &mut pool,
+        vec![
+            TestTransaction::new(0, 0, 1),
+            TestTransaction::new(0, 1, 1),
+            TestTransaction::new(0, 2, 1),
+        ],
+    );
+    let txn_bytes = txns[0].txn_bytes_len() as u64;
+
+    // Only the transactions fitting in the byte limit are returned, in sequence order.
+    let block = pool.get_block(10, 2 * txn_bytes, HashSet::new());
+    assert_eq!(block, txns[..2].to_vec());
+
+    // A limit below the size of a single transaction gives an empty block.
+    assert!(pool.get_block(10, txn_bytes - 1, HashSet::new()).is_empty());
+}
+
+#[test]
+fn test_reject_txn_larger_than_block() {
+    let txn = TestTransaction::new(0, 0, 1).make_signed_transaction();
+    let mut config = NodeConfig::random();
+    config.consensus.max_block_bytes = txn.txn_bytes_len() as u64 - 1;
+    let mut pool = CoreMempool::new(&config);
+
+    let status = pool.add_txn(
+        txn,
+        0,
+        1,
+        AccountSequenceInfo::Sequential(0),
+        TimelineState::NotReady,
+    );
+    assert_eq!(status.code, MempoolStatusCode::TooLarge);
+    assert!(pool.get_block(10, u64::MAX, HashSet::new()).is_empty());
+}
+
 #[test]
 fn test_ttl_cache() {
     let mut cache = TtlCache::new(2, Duration::from_secs(1));
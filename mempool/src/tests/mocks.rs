@@ -164,7 +164,7 @@ impl MockSharedMempool {
 
     pub fn get_txns(&self, size: u64) -> Vec<SignedTransaction> {
         let pool = self.mempool.lock();
-        pool.get_block(size, HashSet::new())
+        pool.get_block(size, u64::MAX, HashSet::new())
     }
 
     pub fn remove_txn(&self, txn: &SignedTransaction) {
@@ -152,7 +152,7 @@ impl MempoolNode {
     /// Asynchronously waits for up to 1 second for txns to appear in mempool
     pub async fn wait_on_txns_in_mempool(&self, txns: &[TestTransaction]) {
         for _ in 0..10 {
-            let block = self.mempool.lock().get_block(100, HashSet::new());
+            let block = self.mempool.lock().get_block(100, u64::MAX, HashSet::new());
 
             if block_contains_all_transactions(&block, txns) {
                 break;
@@ -202,7 +202,7 @@ impl MempoolNode {
         txns: &[TestTransaction],
         condition: Condition,
     ) -> Result<(), (Vec<(AccountAddress, u64)>, Vec<(AccountAddress, u64)>)> {
-        let block = self.mempool.lock().get_block(100, HashSet::new());
+        let block = self.mempool.lock().get_block(100, u64::MAX, HashSet::new());
         if !condition(&block, txns) {
             let actual: Vec<_> = block
                 .iter()
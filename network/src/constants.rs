@@ -13,6 +13,9 @@ pub const INBOUND_RPC_TIMEOUT_MS: u64 = 10_000;
 pub const MAX_CONCURRENT_OUTBOUND_RPCS: u32 = 100;
 /// Limit on concurrent Inbound RPC requests before backpressure is applied
 pub const MAX_CONCURRENT_INBOUND_RPCS: u32 = 100;
+/// Limit on the decompressed size of the compressed messages, to protect against decompression
+/// bombs. It leaves room for the messages larger than the frame size once compressed.
+pub const MAX_DECOMPRESSED_MESSAGE_SIZE: usize = 64 * 1024 * 1024; /* 64 MiB */
 
 // These are only used in tests
 // TODO: Fix this so the tests and the defaults in config are the same
@@ -12,12 +12,14 @@
 //!
 //! [AptosNet Handshake v1 Specification]: https://github.com/aptos-labs/aptos-core/blob/main/specifications/network/handshake-v1.md
 
-use anyhow::anyhow;
+use crate::constants::MAX_DECOMPRESSED_MESSAGE_SIZE;
+use anyhow::{anyhow, ensure};
 use aptos_config::network_id::NetworkId;
 use aptos_types::chain_id::ChainId;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
+    convert::TryInto,
     fmt,
     iter::{FromIterator, Iterator},
     ops::{BitAnd, BitOr},
@@ -51,14 +53,21 @@ pub enum ProtocolId {
     ConsensusRpcJson = 7,
     StorageServiceRpc = 8,
     MempoolRpc = 9,
+    // lz4 compressed bcs, for the large consensus messages carrying the transactions
+    ConsensusRpcCompressed = 10,
+    ConsensusDirectSendCompressed = 11,
 }
 
 /// The encoding types for Protocols
 enum Encoding {
     Bcs,
+    CompressedBcs,
     Json,
 }
 
+/// Length of the uncompressed size prefix of the compressed messages
+const SIZE_PREFIX_LEN: usize = 4;
+
 impl ProtocolId {
     pub fn as_str(self) -> &'static str {
         use ProtocolId::*;
@@ -73,6 +82,8 @@ impl ProtocolId {
             ConsensusRpcJson => "ConsensusRpcJson",
             StorageServiceRpc => "StorageServiceRpc",
             MempoolRpc => "MempoolRpc",
+            ConsensusRpcCompressed => "ConsensusRpcCompressed",
+            ConsensusDirectSendCompressed => "ConsensusDirectSendCompressed",
         }
     }
 
@@ -88,6 +99,8 @@ impl ProtocolId {
             ProtocolId::ConsensusRpcJson,
             ProtocolId::StorageServiceRpc,
             ProtocolId::MempoolRpc,
+            ProtocolId::ConsensusRpcCompressed,
+            ProtocolId::ConsensusDirectSendCompressed,
         ]
     }
 
@@ -95,6 +108,9 @@ impl ProtocolId {
     fn encoding(self) -> Encoding {
         match self {
             ProtocolId::ConsensusDirectSendJson | ProtocolId::ConsensusRpcJson => Encoding::Json,
+            ProtocolId::ConsensusDirectSendCompressed | ProtocolId::ConsensusRpcCompressed => {
+                Encoding::CompressedBcs
+            }
             _ => Encoding::Bcs,
         }
     }
@@ -108,17 +124,64 @@ impl ProtocolId {
         match self.encoding() {
             Encoding::Json => serde_json::to_vec(value).map_err(|e| anyhow!("{:?}", e)),
             Encoding::Bcs => bcs::to_bytes(value).map_err(|e| anyhow! {"{:?}", e}),
+            Encoding::CompressedBcs => {
+                let bytes = bcs::to_bytes(value).map_err(|e| anyhow! {"{:?}", e})?;
+                compress(&bytes)
+            }
         }
     }
 
-    pub fn from_bytes<'a, T: Deserialize<'a>>(&self, bytes: &'a [u8]) -> anyhow::Result<T> {
+    pub fn from_bytes<T: DeserializeOwned>(&self, bytes: &[u8]) -> anyhow::Result<T> {
         match self.encoding() {
             Encoding::Json => serde_json::from_slice(bytes).map_err(|e| anyhow!("{:?}", e)),
             Encoding::Bcs => bcs::from_bytes(bytes).map_err(|e| anyhow! {"{:?}", e}),
+            Encoding::CompressedBcs => {
+                let bytes = decompress(bytes)?;
+                bcs::from_bytes(&bytes).map_err(|e| anyhow! {"{:?}", e})
+            }
         }
     }
 }
 
+/// Compresses the bytes with lz4, prefixed by their uncompressed size.
+fn compress(bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+    ensure!(
+        bytes.len() <= MAX_DECOMPRESSED_MESSAGE_SIZE,
+        "Message of {} bytes exceeds the maximum size of a compressed message {}",
+        bytes.len(),
+        MAX_DECOMPRESSED_MESSAGE_SIZE
+    );
+    lz4::block::compress(bytes, None, true).map_err(|e| anyhow!("{:?}", e))
+}
+
+/// Decompresses the bytes of [`compress`]. The uncompressed size is checked against the maximum
+/// size before anything is allocated, so that a small message can't expand into an arbitrarily
+/// large buffer.
+fn decompress(bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+    ensure!(
+        bytes.len() >= SIZE_PREFIX_LEN,
+        "Compressed message of {} bytes is missing its size prefix",
+        bytes.len()
+    );
+    let (prefix, compressed) = bytes.split_at(SIZE_PREFIX_LEN);
+    let size = u32::from_le_bytes(prefix.try_into()?) as usize;
+    ensure!(
+        size <= MAX_DECOMPRESSED_MESSAGE_SIZE,
+        "Compressed message of {} bytes exceeds the maximum size {}",
+        size,
+        MAX_DECOMPRESSED_MESSAGE_SIZE
+    );
+    let decompressed =
+        lz4::block::decompress(compressed, Some(size as i32)).map_err(|e| anyhow!("{:?}", e))?;
+    ensure!(
+        decompressed.len() == size,
+        "Compressed message of {} bytes decompressed into {} bytes",
+        size,
+        decompressed.len()
+    );
+    Ok(decompressed)
+}
+
 impl fmt::Debug for ProtocolId {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self)
@@ -182,3 +182,39 @@ fn ignore_unknown_protocols() {
         ProtocolIdSet::empty(),
     );
 }
+
+#[test]
+fn compressed_encoding() {
+    let protocol = ProtocolId::ConsensusDirectSendCompressed;
+    let message = vec![7u8; 100_000];
+    let bytes = protocol.to_bytes(&message).unwrap();
+    assert!(bytes.len() < message.len());
+    assert_eq!(protocol.from_bytes::<Vec<u8>>(&bytes).unwrap(), message);
+
+    // the compressed bytes are not bcs
+    ProtocolId::ConsensusDirectSendBcs
+        .from_bytes::<Vec<u8>>(&bytes)
+        .unwrap_err();
+    // truncated messages
+    protocol.from_bytes::<Vec<u8>>(&bytes[..2]).unwrap_err();
+    protocol
+        .from_bytes::<Vec<u8>>(&bytes[..bytes.len() / 2])
+        .unwrap_err();
+}
+
+#[test]
+fn decompression_bomb() {
+    let protocol = ProtocolId::ConsensusRpcCompressed;
+    let message = vec![0u8; MAX_DECOMPRESSED_MESSAGE_SIZE + 1];
+    protocol.to_bytes(&message).unwrap_err();
+
+    // a small message claiming a huge uncompressed size is refused before decompression
+    let mut bytes = protocol.to_bytes(&vec![0u8; 1024]).unwrap();
+    bytes[..SIZE_PREFIX_LEN].copy_from_slice(&u32::MAX.to_le_bytes());
+    protocol.from_bytes::<Vec<u8>>(&bytes).unwrap_err();
+    // or a size larger than the actual one
+    let mut bytes = protocol.to_bytes(&vec![0u8; 1024]).unwrap();
+    let size = (MAX_DECOMPRESSED_MESSAGE_SIZE as u32).to_le_bytes();
+    bytes[..SIZE_PREFIX_LEN].copy_from_slice(&size);
+    protocol.from_bytes::<Vec<u8>>(&bytes).unwrap_err();
+}
@@ -8,6 +8,7 @@
 //! This SDK provides all the necessary components for building on top of the Diem Blockchain. Some of the important modules are:
 //!
 //! * `crypto` - Types used for signing and verifying
+//! * `pipeline` - Helpers for submitting many concurrent transactions from one account
 //! * `transaction_builder` - Includes helpers for constructing transactions
 //! * `types` - Includes types for Diem on-chain data structures
 //!
@@ -21,6 +22,12 @@ pub mod crypto {
     pub use aptos_crypto::*;
 }
 
+pub mod pipeline;
+
+pub mod rest_client {
+    pub use aptos_rest_client::*;
+}
+
 pub mod transaction_builder;
 
 pub mod types;
@@ -0,0 +1,604 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Submission of many concurrent transactions from a single account.
+//!
+//! `SequenceNumberManager` leases sequence numbers to in-flight transactions and resyncs with the
+//! chain when a submission shows that it drifted. `TransactionPipeline` builds on it to sign,
+//! submit and wait for transactions, handing back one future per transaction.
+
+use crate::{
+    rest_client::{Client, RestError, Transaction, WaitForTransactionError},
+    transaction_builder::TransactionBuilder,
+    types::{account_address::AccountAddress, transaction::SignedTransaction, LocalAccount},
+};
+use anyhow::{Error, Result};
+use std::{
+    collections::BTreeSet,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{sync::Mutex, task::JoinHandle, time::Instant};
+
+const DEFAULT_MAX_IN_FLIGHT: u64 = 100;
+const DEFAULT_SYNC_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_MAX_RETRIES: usize = 5;
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// A sequence number handed out by a `SequenceNumberManager`.
+///
+/// Every lease must be given back through `SequenceNumberManager::complete` or
+/// `SequenceNumberManager::release` once the fate of its transaction is known.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Lease {
+    sequence_number: u64,
+}
+
+impl Lease {
+    pub fn sequence_number(&self) -> u64 {
+        self.sequence_number
+    }
+}
+
+/// Where a `SequenceNumberManager` reads the sequence number of its account on chain from.
+pub trait SequenceNumberSource: Clone + Send + Sync + 'static {
+    fn sequence_number(
+        &self,
+        address: AccountAddress,
+    ) -> Pin<Box<dyn Future<Output = Result<u64>> + Send + '_>>;
+}
+
+impl SequenceNumberSource for Client {
+    fn sequence_number(
+        &self,
+        address: AccountAddress,
+    ) -> Pin<Box<dyn Future<Output = Result<u64>> + Send + '_>> {
+        Box::pin(async move {
+            Ok(self
+                .get_account(address)
+                .await?
+                .into_inner()
+                .sequence_number)
+        })
+    }
+}
+
+#[derive(Debug)]
+struct SequenceNumberState {
+    /// Next sequence number to lease, unless one of `free` is available.
+    next: u64,
+    /// Latest sequence number of the account seen on chain.
+    on_chain: u64,
+    /// Sequence numbers leased and not given back yet.
+    outstanding: BTreeSet<u64>,
+    /// Sequence numbers below `next` whose transactions never made it or expired, leased again
+    /// before `next` so that they don't leave a gap.
+    free: BTreeSet<u64>,
+}
+
+impl SequenceNumberState {
+    /// Catches up with the sequence number on chain, everything below it is used.
+    fn observe_on_chain(&mut self, on_chain: u64) {
+        self.on_chain = self.on_chain.max(on_chain);
+        self.next = self.next.max(self.on_chain);
+        self.free = self.free.split_off(&self.on_chain);
+    }
+}
+
+/// Leases the sequence numbers of one account to concurrent transactions.
+///
+/// At most `max_in_flight` sequence numbers are leased ahead of the sequence number on chain. The
+/// chain is never queried while the state is locked, so leasing is not held up by a slow node.
+#[derive(Clone, Debug)]
+pub struct SequenceNumberManager<S = Client> {
+    source: S,
+    address: AccountAddress,
+    state: Arc<Mutex<SequenceNumberState>>,
+    max_in_flight: u64,
+    sync_timeout: Duration,
+}
+
+impl<S: SequenceNumberSource> SequenceNumberManager<S> {
+    pub fn new(source: S, address: AccountAddress, sequence_number: u64) -> Self {
+        Self {
+            source,
+            address,
+            state: Arc::new(Mutex::new(SequenceNumberState {
+                next: sequence_number,
+                on_chain: sequence_number,
+                outstanding: BTreeSet::new(),
+                free: BTreeSet::new(),
+            })),
+            max_in_flight: DEFAULT_MAX_IN_FLIGHT,
+            sync_timeout: DEFAULT_SYNC_TIMEOUT,
+        }
+    }
+
+    pub fn with_max_in_flight(mut self, max_in_flight: u64) -> Self {
+        self.max_in_flight = max_in_flight;
+        self
+    }
+
+    pub fn with_sync_timeout(mut self, sync_timeout: Duration) -> Self {
+        self.sync_timeout = sync_timeout;
+        self
+    }
+
+    pub fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    /// Leases the lowest sequence number that is not in use, waiting for in-flight transactions
+    /// to be committed if `max_in_flight` of them are pending.
+    pub async fn lease(&self) -> Result<Lease> {
+        let deadline = Instant::now() + self.sync_timeout;
+        loop {
+            {
+                let mut state = self.state.lock().await;
+                let sequence_number = match state.free.iter().next().copied() {
+                    Some(free) => {
+                        state.free.remove(&free);
+                        Some(free)
+                    }
+                    None if state.next < state.on_chain + self.max_in_flight => {
+                        state.next += 1;
+                        Some(state.next - 1)
+                    }
+                    None => None,
+                };
+                if let Some(sequence_number) = sequence_number {
+                    state.outstanding.insert(sequence_number);
+                    return Ok(Lease { sequence_number });
+                }
+            }
+            if Instant::now() >= deadline {
+                return Err(anyhow::anyhow!(
+                    "Timed out waiting for in-flight transactions of {} to be committed",
+                    self.address
+                ));
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+            self.refresh().await?;
+        }
+    }
+
+    /// Gives back `lease` once its transaction was accepted, whether it was committed or not.
+    pub async fn complete(&self, lease: Lease) {
+        self.state
+            .lock()
+            .await
+            .outstanding
+            .remove(&lease.sequence_number);
+    }
+
+    /// Gives back `lease` when its transaction was rejected or expired, so that the sequence
+    /// number is leased again rather than leaving a gap.
+    pub async fn release(&self, lease: Lease) {
+        let mut state = self.state.lock().await;
+        state.outstanding.remove(&lease.sequence_number);
+        if lease.sequence_number >= state.on_chain {
+            state.free.insert(lease.sequence_number);
+        }
+    }
+
+    /// Catches up with the sequence number on chain, e.g. after a `SEQUENCE_NUMBER_TOO_OLD`
+    /// when transactions were sent from the account by someone else.
+    pub async fn refresh(&self) -> Result<()> {
+        let on_chain = self.fetch_sequence_number().await?;
+        self.state.lock().await.observe_on_chain(on_chain);
+        Ok(())
+    }
+
+    /// Fills the gaps left by transactions that were lost, e.g. after a `SEQUENCE_NUMBER_TOO_NEW`
+    /// or when a transaction expired.
+    ///
+    /// Every sequence number from the one on chain up to `next` that is not held by an
+    /// outstanding lease is leased again. The outstanding leases are kept, as their transactions
+    /// may still be in mempool.
+    pub async fn resync(&self) -> Result<()> {
+        let on_chain = self.fetch_sequence_number().await?;
+        let mut state = self.state.lock().await;
+        state.observe_on_chain(on_chain);
+        let lost: BTreeSet<_> = (state.on_chain..state.next)
+            .filter(|sequence_number| !state.outstanding.contains(sequence_number))
+            .collect();
+        state.free = lost;
+        Ok(())
+    }
+
+    async fn fetch_sequence_number(&self) -> Result<u64> {
+        self.source.sequence_number(self.address).await
+    }
+}
+
+/// How a failed submission is handled by the `TransactionPipeline`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum SubmissionFailure {
+    /// The sequence number was used already, or is taken by another transaction in mempool,
+    /// lease a new one.
+    SequenceNumberTooOld,
+    /// Transactions before this one were lost, resync and lease a new one.
+    SequenceNumberTooNew,
+    /// Submit the same transaction again after a delay.
+    Transient,
+    Permanent,
+}
+
+impl SubmissionFailure {
+    fn from_error(error: &Error) -> Self {
+        let error = match error.downcast_ref::<RestError>() {
+            Some(error) => error,
+            // Failed before getting a response from the node, e.g. a timeout
+            None => return SubmissionFailure::Transient,
+        };
+        if error.message.contains("SEQUENCE_NUMBER_TOO_OLD")
+            || error.message.contains("InvalidSeqNumber")
+            || error.message.contains("InvalidUpdate")
+        {
+            SubmissionFailure::SequenceNumberTooOld
+        } else if error.message.contains("SEQUENCE_NUMBER_TOO_NEW") {
+            SubmissionFailure::SequenceNumberTooNew
+        } else if error.code >= 500
+            || error.message.contains("MempoolIsFull")
+            || error.message.contains("TooManyTransactions")
+        {
+            SubmissionFailure::Transient
+        } else {
+            SubmissionFailure::Permanent
+        }
+    }
+}
+
+/// Signs, submits and waits for the transactions of one account concurrently.
+///
+/// The pipeline owns the sequence numbers of the account: the sequence number of the
+/// `LocalAccount` is only used as a starting point and is not kept up to date.
+#[derive(Clone, Debug)]
+pub struct TransactionPipeline {
+    client: Client,
+    account: Arc<LocalAccount>,
+    sequence_numbers: SequenceNumberManager,
+    max_retries: usize,
+}
+
+impl TransactionPipeline {
+    pub fn new(client: Client, account: LocalAccount) -> Self {
+        let sequence_numbers = SequenceNumberManager::new(
+            client.clone(),
+            account.address(),
+            account.sequence_number(),
+        );
+        Self {
+            client,
+            account: Arc::new(account),
+            sequence_numbers,
+            max_retries: DEFAULT_MAX_RETRIES,
+        }
+    }
+
+    pub fn with_max_in_flight(mut self, max_in_flight: u64) -> Self {
+        self.sequence_numbers = self.sequence_numbers.with_max_in_flight(max_in_flight);
+        self
+    }
+
+    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn account(&self) -> &LocalAccount {
+        &self.account
+    }
+
+    pub fn sequence_numbers(&self) -> &SequenceNumberManager {
+        &self.sequence_numbers
+    }
+
+    /// Starts submitting the transaction built by `builder` in the background. The returned
+    /// future resolves once the transaction is committed.
+    pub fn submit(&self, builder: TransactionBuilder) -> PendingTransactionHandle {
+        let pipeline = self.clone();
+        PendingTransactionHandle {
+            handle: tokio::spawn(async move { pipeline.submit_and_wait(builder).await }),
+        }
+    }
+
+    async fn submit_and_wait(&self, builder: TransactionBuilder) -> Result<Transaction> {
+        let mut lease = self.sequence_numbers.lease().await?;
+        let mut retries = 0;
+        loop {
+            let txn = self.account.sign_transaction(
+                builder
+                    .clone()
+                    .sender(self.account.address())
+                    .sequence_number(lease.sequence_number())
+                    .build(),
+            );
+            let error = match self.client.submit(&txn).await {
+                Ok(_) => return self.wait(&txn, lease).await,
+                Err(error) => error,
+            };
+
+            retries += 1;
+            match SubmissionFailure::from_error(&error) {
+                SubmissionFailure::Transient if retries <= self.max_retries => {
+                    tokio::time::sleep(RETRY_DELAY * retries as u32).await;
+                }
+                SubmissionFailure::SequenceNumberTooOld if retries <= self.max_retries => {
+                    // A submission that failed earlier may have been accepted all the same, in
+                    // which case the sequence number is used by this very transaction. Signing
+                    // the payload again would execute it twice.
+                    if self.is_known(&txn).await? {
+                        return self.wait(&txn, lease).await;
+                    }
+                    // Someone else used the sequence number, it won't be leased again
+                    self.sequence_numbers.complete(lease).await;
+                    self.sequence_numbers.refresh().await?;
+                    lease = self.sequence_numbers.lease().await?;
+                }
+                SubmissionFailure::SequenceNumberTooNew if retries <= self.max_retries => {
+                    self.sequence_numbers.release(lease).await;
+                    self.sequence_numbers.resync().await?;
+                    lease = self.sequence_numbers.lease().await?;
+                }
+                _ => {
+                    // The sequence number was never used, don't leave a gap behind
+                    self.sequence_numbers.release(lease).await;
+                    return Err(error);
+                }
+            }
+        }
+    }
+
+    /// Whether the node knows `txn`, either committed or pending in mempool.
+    async fn is_known(&self, txn: &SignedTransaction) -> Result<bool> {
+        match self
+            .client
+            .get_transaction(txn.clone().committed_hash())
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(error) => match error.downcast_ref::<RestError>() {
+                Some(rest_error) if rest_error.code == 404 => Ok(false),
+                _ => Err(error),
+            },
+        }
+    }
+
+    /// Waits for `txn`, which was accepted by mempool, and gives back its lease.
+    async fn wait(&self, txn: &SignedTransaction, lease: Lease) -> Result<Transaction> {
+        match self.client.wait_for_signed_transaction(txn).await {
+            Ok(response) => {
+                self.sequence_numbers.complete(lease).await;
+                Ok(response.into_inner())
+            }
+            // An expired transaction can't be committed anymore, and the transactions after it
+            // can't be committed until its sequence number is used again
+            Err(error)
+                if matches!(
+                    error.downcast_ref::<WaitForTransactionError>(),
+                    Some(WaitForTransactionError::Expired)
+                ) =>
+            {
+                self.sequence_numbers.release(lease).await;
+                self.sequence_numbers.resync().await?;
+                Err(error)
+            }
+            Err(error) => {
+                self.sequence_numbers.complete(lease).await;
+                Err(error)
+            }
+        }
+    }
+}
+
+/// A transaction submitted through a `TransactionPipeline`, resolving once it is committed.
+#[derive(Debug)]
+pub struct PendingTransactionHandle {
+    handle: JoinHandle<Result<Transaction>>,
+}
+
+impl Future for PendingTransactionHandle {
+    type Output = Result<Transaction>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.handle)
+            .poll(cx)
+            .map(|result| result.map_err(Error::from).and_then(|result| result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use tokio::sync::Notify;
+
+    #[derive(Clone, Debug, Default)]
+    struct MockChain {
+        sequence_number: Arc<AtomicU64>,
+        /// If set, every query waits for a notification before answering
+        gate: Option<Arc<Notify>>,
+    }
+
+    impl MockChain {
+        fn set_sequence_number(&self, sequence_number: u64) {
+            self.sequence_number
+                .store(sequence_number, Ordering::SeqCst);
+        }
+    }
+
+    impl SequenceNumberSource for MockChain {
+        fn sequence_number(
+            &self,
+            _address: AccountAddress,
+        ) -> Pin<Box<dyn Future<Output = Result<u64>> + Send + '_>> {
+            Box::pin(async move {
+                if let Some(gate) = &self.gate {
+                    gate.notified().await;
+                }
+                Ok(self.sequence_number.load(Ordering::SeqCst))
+            })
+        }
+    }
+
+    fn manager(chain: &MockChain) -> SequenceNumberManager<MockChain> {
+        SequenceNumberManager::new(
+            chain.clone(),
+            AccountAddress::ONE,
+            chain.sequence_number.load(Ordering::SeqCst),
+        )
+    }
+
+    async fn lease(manager: &SequenceNumberManager<MockChain>) -> u64 {
+        manager.lease().await.unwrap().sequence_number()
+    }
+
+    #[tokio::test]
+    async fn test_released_sequence_numbers_are_leased_again() {
+        let chain = MockChain::default();
+        chain.set_sequence_number(5);
+        let manager = manager(&chain);
+
+        let leases = [
+            manager.lease().await.unwrap(),
+            manager.lease().await.unwrap(),
+            manager.lease().await.unwrap(),
+        ];
+        assert_eq!(leases[2].sequence_number(), 7);
+        manager.release(leases[1]).await;
+        assert_eq!(lease(&manager).await, 6);
+        assert_eq!(lease(&manager).await, 8);
+    }
+
+    #[tokio::test]
+    async fn test_resync_keeps_outstanding_leases() {
+        let chain = MockChain::default();
+        let manager = manager(&chain);
+
+        let mut held = vec![];
+        for _ in 0..4 {
+            held.push(manager.lease().await.unwrap());
+        }
+        // 0 is committed, 1 and 3 are still in mempool, 2 was lost without its failure being
+        // noticed, e.g. the wait for it timed out.
+        chain.set_sequence_number(1);
+        manager.complete(held[0]).await;
+        manager.complete(held[2]).await;
+
+        manager.resync().await.unwrap();
+        assert_eq!(lease(&manager).await, 2);
+        assert_eq!(lease(&manager).await, 4);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_skips_sequence_numbers_used_on_chain() {
+        let chain = MockChain::default();
+        let manager = manager(&chain);
+
+        let first = manager.lease().await.unwrap();
+        manager.release(first).await;
+        // Someone else sent transactions from the account
+        chain.set_sequence_number(10);
+        manager.refresh().await.unwrap();
+        assert_eq!(lease(&manager).await, 10);
+        assert_eq!(lease(&manager).await, 11);
+    }
+
+    #[tokio::test]
+    async fn test_max_in_flight() {
+        let chain = MockChain::default();
+        let manager = manager(&chain)
+            .with_max_in_flight(2)
+            .with_sync_timeout(Duration::from_secs(0));
+
+        assert_eq!(lease(&manager).await, 0);
+        assert_eq!(lease(&manager).await, 1);
+        assert!(manager.lease().await.is_err());
+
+        chain.set_sequence_number(1);
+        manager.refresh().await.unwrap();
+        assert_eq!(lease(&manager).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_chain_is_not_queried_under_lock() {
+        let gate = Arc::new(Notify::new());
+        let chain = MockChain {
+            gate: Some(gate.clone()),
+            ..MockChain::default()
+        };
+        let manager = manager(&chain);
+
+        let resync = {
+            let manager = manager.clone();
+            tokio::spawn(async move { manager.resync().await })
+        };
+        tokio::task::yield_now().await;
+        // The pending query doesn't hold up leasing
+        let first = tokio::time::timeout(Duration::from_secs(5), manager.lease())
+            .await
+            .expect("lease is not blocked by the resync")
+            .unwrap();
+        assert_eq!(first.sequence_number(), 0);
+
+        gate.notify_one();
+        resync.await.unwrap().unwrap();
+        // The outstanding lease survives the resync
+        assert_eq!(lease(&manager).await, 1);
+    }
+
+    fn rest_error(code: u32, message: &str) -> Error {
+        RestError {
+            code,
+            message: message.to_string(),
+            aptos_ledger_version: None,
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_submission_failure_from_error() {
+        let cases = [
+            (
+                rest_error(400, "invalid transaction: SEQUENCE_NUMBER_TOO_OLD"),
+                SubmissionFailure::SequenceNumberTooOld,
+            ),
+            (
+                rest_error(400, "transaction is rejected: InvalidSeqNumber - seq 1"),
+                SubmissionFailure::SequenceNumberTooOld,
+            ),
+            (
+                rest_error(400, "invalid transaction: SEQUENCE_NUMBER_TOO_NEW"),
+                SubmissionFailure::SequenceNumberTooNew,
+            ),
+            (
+                rest_error(400, "transaction is rejected: InvalidUpdate - same seq"),
+                SubmissionFailure::SequenceNumberTooOld,
+            ),
+            (
+                rest_error(400, "transaction is rejected: MempoolIsFull"),
+                SubmissionFailure::Transient,
+            ),
+            (rest_error(503, "unavailable"), SubmissionFailure::Transient),
+            (
+                anyhow::anyhow!("connection reset"),
+                SubmissionFailure::Transient,
+            ),
+            (
+                rest_error(
+                    400,
+                    "invalid transaction: INSUFFICIENT_BALANCE_FOR_TRANSACTION_FEE",
+                ),
+                SubmissionFailure::Permanent,
+            ),
+        ];
+        for (error, expected) in cases.iter() {
+            assert_eq!(SubmissionFailure::from_error(error), *expected, "{}", error);
+        }
+    }
+}
@@ -16,6 +16,7 @@ use aptos_types::transaction::{
     WriteSetPayload,
 };
 
+#[derive(Clone, Debug)]
 pub struct TransactionBuilder {
     sender: Option<AccountAddress>,
     sequence_number: Option<u64>,
@@ -140,6 +141,19 @@ impl TransactionFactory {
         self.payload(aptos_stdlib::encode_transfer_script_function(to, amount))
     }
 
+    /// Rotates the authentication key of the sender, e.g. to the key of a
+    /// `MultiEd25519AccountKey` to turn a single key account into a multisig one.
+    pub fn rotate_authentication_key(
+        &self,
+        new_authentication_key: AuthenticationKey,
+    ) -> TransactionBuilder {
+        self.payload(
+            aptos_stdlib::encode_rotate_authentication_key_script_function(
+                new_authentication_key.to_vec(),
+            ),
+        )
+    }
+
     pub fn mint(&self, to: AccountAddress, amount: u64) -> TransactionBuilder {
         self.payload(aptos_stdlib::encode_mint_script_function(to, amount))
     }
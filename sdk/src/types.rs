@@ -3,8 +3,9 @@
 
 use crate::{
     crypto::{
-        ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
-        traits::Uniform,
+        ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
+        multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
+        traits::{CryptoMaterialError, Signature, SigningKey, Uniform},
     },
     transaction_builder::TransactionBuilder,
     types::{
@@ -12,6 +13,8 @@ use crate::{
         transaction::{authenticator::AuthenticationKey, RawTransaction, SignedTransaction},
     },
 };
+use anyhow::{bail, ensure, format_err, Result};
+use std::collections::BTreeMap;
 
 pub use aptos_types::*;
 
@@ -117,6 +120,12 @@ impl LocalAccount {
     pub fn rotate_key<T: Into<AccountKey>>(&mut self, new_key: T) -> AccountKey {
         std::mem::replace(&mut self.key, new_key.into())
     }
+
+    /// Turns this account into a multisig account once a transaction built with
+    /// `TransactionFactory::rotate_authentication_key` for `new_key` has been committed.
+    pub fn into_multi_ed25519(self, new_key: MultiEd25519AccountKey) -> MultiEd25519Account {
+        MultiEd25519Account::new(self.address, new_key, self.sequence_number)
+    }
 }
 
 #[derive(Debug)]
@@ -164,3 +173,262 @@ impl From<Ed25519PrivateKey> for AccountKey {
         Self::from_private_key(private_key)
     }
 }
+
+/// The K-of-N key of a `MultiEd25519` account. Only the public keys are held here, the private
+/// keys stay with their holders who each contribute a signature to a
+/// `MultiEd25519PendingTransaction`.
+#[derive(Clone, Debug)]
+pub struct MultiEd25519AccountKey {
+    public_key: MultiEd25519PublicKey,
+    authentication_key: AuthenticationKey,
+}
+
+impl MultiEd25519AccountKey {
+    pub fn new(
+        public_keys: Vec<Ed25519PublicKey>,
+        threshold: u8,
+    ) -> std::result::Result<Self, CryptoMaterialError> {
+        let public_key = MultiEd25519PublicKey::new(public_keys, threshold)?;
+        let authentication_key = AuthenticationKey::multi_ed25519(&public_key);
+
+        Ok(Self {
+            public_key,
+            authentication_key,
+        })
+    }
+
+    pub fn public_key(&self) -> &MultiEd25519PublicKey {
+        &self.public_key
+    }
+
+    pub fn threshold(&self) -> u8 {
+        *self.public_key.threshold()
+    }
+
+    pub fn authentication_key(&self) -> AuthenticationKey {
+        self.authentication_key
+    }
+
+    /// Position of `public_key` among the keys of the account, used as the signature index.
+    pub fn index_of(&self, public_key: &Ed25519PublicKey) -> Option<u8> {
+        self.public_key
+            .public_keys()
+            .iter()
+            .position(|key| key == public_key)
+            .map(|index| index as u8)
+    }
+}
+
+impl From<MultiEd25519PublicKey> for MultiEd25519AccountKey {
+    fn from(public_key: MultiEd25519PublicKey) -> Self {
+        let authentication_key = AuthenticationKey::multi_ed25519(&public_key);
+        Self {
+            public_key,
+            authentication_key,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MultiEd25519Account {
+    /// Address of the account.
+    address: AccountAddress,
+    /// Authentication key of the account.
+    key: MultiEd25519AccountKey,
+    /// Latest known sequence number of the account, it can be different from validator.
+    sequence_number: u64,
+}
+
+impl MultiEd25519Account {
+    pub fn new(address: AccountAddress, key: MultiEd25519AccountKey, sequence_number: u64) -> Self {
+        Self {
+            address,
+            key,
+            sequence_number,
+        }
+    }
+
+    /// Creates an account whose address is derived from the authentication key of `key`.
+    pub fn from_key(key: MultiEd25519AccountKey) -> Self {
+        let address = key.authentication_key().derived_address();
+        Self::new(address, key, 0)
+    }
+
+    /// Builds the next transaction of this account, ready to collect signatures from the key
+    /// holders.
+    pub fn transaction_with_transaction_builder(
+        &mut self,
+        builder: TransactionBuilder,
+    ) -> MultiEd25519PendingTransaction {
+        let raw_txn = builder
+            .sender(self.address())
+            .sequence_number(self.sequence_number())
+            .build();
+        *self.sequence_number_mut() += 1;
+        MultiEd25519PendingTransaction::new(raw_txn, &self.key)
+    }
+
+    pub fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    pub fn key(&self) -> &MultiEd25519AccountKey {
+        &self.key
+    }
+
+    pub fn public_key(&self) -> &MultiEd25519PublicKey {
+        self.key.public_key()
+    }
+
+    pub fn authentication_key(&self) -> AuthenticationKey {
+        self.key.authentication_key()
+    }
+
+    pub fn sequence_number(&self) -> u64 {
+        self.sequence_number
+    }
+
+    pub fn sequence_number_mut(&mut self) -> &mut u64 {
+        &mut self.sequence_number
+    }
+
+    pub fn rotate_key(&mut self, new_key: MultiEd25519AccountKey) -> MultiEd25519AccountKey {
+        std::mem::replace(&mut self.key, new_key)
+    }
+}
+
+/// A transaction of a `MultiEd25519` account collecting the signatures of the key holders.
+///
+/// Holders with access to their `AccountKey` sign through `sign`. Holders signing elsewhere sign
+/// `raw_txn()` with their private key and hand the signature back through `add_signature`.
+#[derive(Clone, Debug)]
+pub struct MultiEd25519PendingTransaction {
+    raw_txn: RawTransaction,
+    public_key: MultiEd25519AccountKey,
+    signatures: BTreeMap<u8, Ed25519Signature>,
+}
+
+impl MultiEd25519PendingTransaction {
+    pub fn new(raw_txn: RawTransaction, key: &MultiEd25519AccountKey) -> Self {
+        Self {
+            raw_txn,
+            public_key: key.clone(),
+            signatures: BTreeMap::new(),
+        }
+    }
+
+    pub fn raw_txn(&self) -> &RawTransaction {
+        &self.raw_txn
+    }
+
+    /// Signs the transaction with `key`, which must be one of the keys of the account.
+    pub fn sign(&mut self, key: &AccountKey) -> Result<()> {
+        let index = self
+            .public_key
+            .index_of(key.public_key())
+            .ok_or_else(|| format_err!("Key is not part of the multisig account"))?;
+        let signature = key.private_key().sign(&self.raw_txn);
+        self.add_signature(index, signature)
+    }
+
+    /// Adds the signature of the key at `index`, after checking it against the transaction.
+    pub fn add_signature(&mut self, index: u8, signature: Ed25519Signature) -> Result<()> {
+        let public_key = self
+            .public_key
+            .public_key()
+            .public_keys()
+            .get(index as usize)
+            .ok_or_else(|| format_err!("Signature index {} is out of range", index))?;
+        if self.signatures.contains_key(&index) {
+            bail!("Key at index {} has already signed", index);
+        }
+        signature.verify(&self.raw_txn, public_key)?;
+        self.signatures.insert(index, signature);
+        Ok(())
+    }
+
+    pub fn num_signatures(&self) -> usize {
+        self.signatures.len()
+    }
+
+    /// Whether enough holders have signed for the transaction to be submitted.
+    pub fn is_complete(&self) -> bool {
+        self.signatures.len() >= self.public_key.threshold() as usize
+    }
+
+    /// Assembles the `TransactionAuthenticator::MultiEd25519` from the collected signatures.
+    pub fn into_signed_transaction(self) -> Result<SignedTransaction> {
+        ensure!(
+            self.is_complete(),
+            "Only {} of the {} required signatures have been collected",
+            self.signatures.len(),
+            self.public_key.threshold()
+        );
+        let signatures = self
+            .signatures
+            .into_iter()
+            .map(|(index, signature)| (signature, index))
+            .collect();
+        let signature = MultiEd25519Signature::new(signatures)?;
+        Ok(SignedTransaction::new_multisig(
+            self.raw_txn,
+            self.public_key.public_key().clone(),
+            signature,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{transaction_builder::TransactionFactory, types::chain_id::ChainId};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_multi_ed25519_account() {
+        let mut rng = StdRng::from_seed([0u8; 32]);
+        let holders: Vec<_> = (0..3).map(|_| AccountKey::generate(&mut rng)).collect();
+        let key = MultiEd25519AccountKey::new(
+            holders.iter().map(|key| key.public_key().clone()).collect(),
+            2,
+        )
+        .unwrap();
+
+        // Rotating a single key account to the multisig key keeps its address.
+        let factory = TransactionFactory::new(ChainId::test());
+        let mut account = LocalAccount::generate(&mut rng);
+        let address = account.address();
+        account
+            .sign_with_transaction_builder(
+                factory.rotate_authentication_key(key.authentication_key()),
+            )
+            .check_signature()
+            .unwrap();
+        let mut account = account.into_multi_ed25519(key.clone());
+        assert_eq!(account.address(), address);
+        assert_eq!(account.sequence_number(), 1);
+
+        let mut txn =
+            account.transaction_with_transaction_builder(factory.transfer(AccountAddress::ONE, 1));
+        assert_eq!(account.sequence_number(), 2);
+        txn.sign(&holders[2]).unwrap();
+        assert!(!txn.is_complete());
+        assert!(txn.clone().into_signed_transaction().is_err());
+        // The same holder can't sign twice, outsiders can't sign at all.
+        assert!(txn.sign(&holders[2]).is_err());
+        assert!(txn.sign(&AccountKey::generate(&mut rng)).is_err());
+        assert_eq!(txn.num_signatures(), 1);
+        // Signatures produced elsewhere are checked against the key at their index.
+        let signature = holders[0].private_key().sign(txn.raw_txn());
+        assert!(txn.add_signature(1, signature.clone()).is_err());
+        txn.add_signature(0, signature).unwrap();
+        assert!(txn.is_complete());
+
+        let signed_txn = txn.into_signed_transaction().unwrap();
+        assert_eq!(
+            signed_txn.authenticator().sender().authentication_key(),
+            key.authentication_key()
+        );
+        signed_txn.check_signature().unwrap();
+    }
+}
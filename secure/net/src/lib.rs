@@ -14,10 +14,19 @@
 //!
 //! Internally both the client and server leverage a NetworkStream that communications in blocks
 //! where a block is a length prefixed array of bytes.
+//!
+//! The authenticated client and server run a Noise IK handshake (see `aptos_crypto::noise`) on
+//! each new connection: each end holds a static x25519 key and only accepts the public key
+//! configured for the other end. The blocks are then encrypted and authenticated.
 
+use aptos_crypto::{
+    noise::{self, NoiseConfig, NoiseError, NoiseSession},
+    x25519,
+};
 use aptos_logger::{info, trace, warn, Schema};
 use aptos_secure_push_metrics::{register_int_counter_vec, IntCounterVec};
 use once_cell::sync::Lazy;
+use rand::rngs::OsRng;
 use serde::Serialize;
 use std::{
     io::{Read, Write},
@@ -55,6 +64,7 @@ enum LogEvent {
     ConnectionAttempt,
     ConnectionSuccessful,
     ConnectionFailed,
+    HandshakeFailed,
     DisconnectedPeerOnRead,
     DisconnectedPeerOnWrite,
     Shutdown,
@@ -140,12 +150,32 @@ pub enum Error {
     DataTooLarge(usize),
     #[error("Internal network error:")]
     NetworkError(#[from] std::io::Error),
+    #[error("Noise error: {0}")]
+    NoiseError(#[from] NoiseError),
     #[error("No active stream")]
     NoActiveStream,
     #[error("Overflow error: {0}")]
     OverflowError(String),
     #[error("Remote stream cleanly closed")]
     RemoteStreamClosed,
+    #[error("Unexpected remote public key: {0}")]
+    UnexpectedRemoteKey(x25519::PublicKey),
+}
+
+/// The static key of this end of an authenticated link and the public key expected from the
+/// other end.
+struct NoiseLink {
+    config: NoiseConfig,
+    remote_public_key: x25519::PublicKey,
+}
+
+impl NoiseLink {
+    fn new(private_key: x25519::PrivateKey, remote_public_key: x25519::PublicKey) -> Self {
+        Self {
+            config: NoiseConfig::new(private_key),
+            remote_public_key,
+        }
+    }
 }
 
 pub struct NetworkClient {
@@ -154,6 +184,7 @@ pub struct NetworkClient {
     stream: Option<NetworkStream>,
     /// Read, Write, Connect timeout in milliseconds.
     timeout_ms: u64,
+    noise: Option<NoiseLink>,
 }
 
 impl NetworkClient {
@@ -163,6 +194,22 @@ impl NetworkClient {
             server,
             stream: None,
             timeout_ms,
+            noise: None,
+        }
+    }
+
+    /// A client that only talks to the server holding the private key of `server_public_key`,
+    /// authenticating itself with `private_key`.
+    pub fn new_authenticated(
+        service: &'static str,
+        server: SocketAddr,
+        timeout_ms: u64,
+        private_key: x25519::PrivateKey,
+        server_public_key: x25519::PublicKey,
+    ) -> Self {
+        Self {
+            noise: Some(NoiseLink::new(private_key, server_public_key)),
+            ..Self::new(service, server, timeout_ms)
         }
     }
 
@@ -257,7 +304,21 @@ impl NetworkClient {
 
             let stream = stream?;
             stream.set_nodelay(true)?;
-            self.stream = Some(NetworkStream::new(stream, self.server, self.timeout_ms));
+            let mut stream = NetworkStream::new(stream, self.server, self.timeout_ms);
+            if let Some(noise) = &self.noise {
+                if let Err(err) = stream.initiate_handshake(noise, self.service.as_bytes()) {
+                    self.increment_counter(Method::Connect, MethodResult::Failure);
+                    warn!(SecureNetLogSchema::new(
+                        self.service,
+                        NetworkMode::Client,
+                        LogEvent::HandshakeFailed,
+                    )
+                    .error(&err)
+                    .remote_peer(&self.server));
+                    return Err(err);
+                }
+            }
+            self.stream = Some(stream);
             self.increment_counter(Method::Connect, MethodResult::Success);
             info!(SecureNetLogSchema::new(
                 self.service,
@@ -277,6 +338,7 @@ pub struct NetworkServer {
     stream: Option<NetworkStream>,
     /// Read, Write, Connect timeout in milliseconds.
     timeout_ms: u64,
+    noise: Option<NoiseLink>,
 }
 
 impl NetworkServer {
@@ -287,6 +349,22 @@ impl NetworkServer {
             listener: Some(listener.unwrap()),
             stream: None,
             timeout_ms,
+            noise: None,
+        }
+    }
+
+    /// A server that authenticates itself with `private_key` and only accepts the client holding
+    /// the private key of `client_public_key`.
+    pub fn new_authenticated(
+        service: &'static str,
+        listen: SocketAddr,
+        timeout_ms: u64,
+        private_key: x25519::PrivateKey,
+        client_public_key: x25519::PublicKey,
+    ) -> Self {
+        Self {
+            noise: Some(NoiseLink::new(private_key, client_public_key)),
+            ..Self::new(service, listen, timeout_ms)
         }
     }
 
@@ -399,18 +477,36 @@ impl NetworkServer {
             .remote_peer(&stream_addr));
 
             stream.set_nodelay(true)?;
-            self.stream = Some(NetworkStream::new(stream, stream_addr, self.timeout_ms));
+            let mut stream = NetworkStream::new(stream, stream_addr, self.timeout_ms);
+            if let Some(noise) = &self.noise {
+                if let Err(err) = stream.respond_to_handshake(noise, self.service.as_bytes()) {
+                    warn!(SecureNetLogSchema::new(
+                        self.service,
+                        NetworkMode::Server,
+                        LogEvent::HandshakeFailed,
+                    )
+                    .error(&err)
+                    .remote_peer(&stream_addr));
+                    return Err(err);
+                }
+            }
+            self.stream = Some(stream);
         }
 
         self.stream.as_mut().ok_or(Error::NoActiveStream)
     }
 }
 
+/// The largest block of data encrypted as a single Noise message.
+const MAX_NOISE_PLAINTEXT_LEN: usize = noise::MAX_SIZE_NOISE_MSG - noise::AES_GCM_TAGLEN;
+
 struct NetworkStream {
     stream: TcpStream,
     remote: SocketAddr,
     buffer: Vec<u8>,
     temp_buffer: [u8; 1024],
+    /// Encrypts the blocks once the handshake of an authenticated link completed.
+    session: Option<NoiseSession>,
 }
 
 impl NetworkStream {
@@ -425,11 +521,90 @@ impl NetworkStream {
             remote,
             buffer: Vec::new(),
             temp_buffer: [0; 1024],
+            session: None,
+        }
+    }
+
+    /// Runs the client side of the handshake, the following blocks are encrypted.
+    fn initiate_handshake(&mut self, noise: &NoiseLink, prologue: &[u8]) -> Result<(), Error> {
+        let mut init_message = vec![0; noise::handshake_init_msg_len(0)];
+        let state = noise.config.initiate_connection(
+            &mut OsRng,
+            prologue,
+            noise.remote_public_key,
+            None,
+            &mut init_message,
+        )?;
+        self.write_block(&init_message)?;
+        let response = self.read_block()?;
+        let (_, session) = noise.config.finalize_connection(state, &response)?;
+        self.session = Some(session);
+        Ok(())
+    }
+
+    /// Runs the server side of the handshake, the following blocks are encrypted.
+    fn respond_to_handshake(&mut self, noise: &NoiseLink, prologue: &[u8]) -> Result<(), Error> {
+        let init_message = self.read_block()?;
+        let (remote_public_key, state, _) = noise
+            .config
+            .parse_client_init_message(prologue, &init_message)?;
+        if remote_public_key != noise.remote_public_key {
+            return Err(Error::UnexpectedRemoteKey(remote_public_key));
         }
+        let mut response = vec![0; noise::handshake_resp_msg_len(0)];
+        let session = noise
+            .config
+            .respond_to_client(&mut OsRng, state, None, &mut response)?;
+        self.write_block(&response)?;
+        self.session = Some(session);
+        Ok(())
     }
 
     /// Blocking read until able to successfully read an entire message
     pub fn read(&mut self) -> Result<Vec<u8>, Error> {
+        let mut block = self.read_block()?;
+        let session = match &mut self.session {
+            Some(session) => session,
+            None => return Ok(block),
+        };
+        // the block is a sequence of Noise messages, all but the last one of the maximum size, each
+        // with its authentication tag: even an empty message isn't shorter than the tag
+        let last_message_len = block.len() % noise::MAX_SIZE_NOISE_MSG;
+        if block.is_empty() || (last_message_len != 0 && last_message_len < noise::AES_GCM_TAGLEN) {
+            return Err(NoiseError::MsgTooShort.into());
+        }
+        let mut data = Vec::with_capacity(block.len());
+        for message in block.chunks_mut(noise::MAX_SIZE_NOISE_MSG) {
+            data.extend_from_slice(session.read_message_in_place(message)?);
+        }
+        Ok(data)
+    }
+
+    /// Blocking write until able to successfully send an entire message
+    pub fn write(&mut self, data: &[u8]) -> Result<(), Error> {
+        let session = match &mut self.session {
+            Some(session) => session,
+            None => return self.write_block(data),
+        };
+        let mut block = Vec::with_capacity(
+            data.len() + (data.len() / MAX_NOISE_PLAINTEXT_LEN + 1) * noise::AES_GCM_TAGLEN,
+        );
+        // an empty message is still sent as an (empty) Noise message
+        let mut chunks: Vec<&[u8]> = data.chunks(MAX_NOISE_PLAINTEXT_LEN).collect();
+        if chunks.is_empty() {
+            chunks.push(&[]);
+        }
+        for chunk in chunks {
+            let start = block.len();
+            block.extend_from_slice(chunk);
+            let auth_tag = session.write_message_in_place(&mut block[start..])?;
+            block.extend_from_slice(&auth_tag);
+        }
+        self.write_block(&block)
+    }
+
+    /// Blocking read until able to successfully read an entire block
+    fn read_block(&mut self) -> Result<Vec<u8>, Error> {
         let result = self.read_buffer();
         if !result.is_empty() {
             return Ok(result);
@@ -457,8 +632,8 @@ impl NetworkStream {
         Ok(self.stream.shutdown(Shutdown::Both)?)
     }
 
-    /// Blocking write until able to successfully send an entire message
-    pub fn write(&mut self, data: &[u8]) -> Result<(), Error> {
+    /// Blocking write until able to successfully send an entire block
+    fn write_block(&mut self, data: &[u8]) -> Result<(), Error> {
         let u32_max = u32::max_value() as usize;
         if u32_max <= data.len() {
             return Err(Error::DataTooLarge(data.len()));
@@ -518,6 +693,7 @@ impl NetworkStream {
 mod test {
     use super::*;
     use aptos_config::utils;
+    use aptos_crypto::Uniform;
     use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
     /// Read, Write, Connect timeout in milliseconds.
@@ -656,4 +832,127 @@ mod test {
         let result2 = server2.read().unwrap();
         assert_eq!(data2, result2);
     }
+
+    #[test]
+    fn test_authenticated_ping() {
+        let server_port = utils::get_available_port();
+        let server_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), server_port);
+        let server_key = x25519::PrivateKey::generate(&mut OsRng);
+        let client_key = x25519::PrivateKey::generate(&mut OsRng);
+        let server_public_key = server_key.public_key();
+        let mut server = NetworkServer::new_authenticated(
+            "test",
+            server_addr,
+            TIMEOUT,
+            server_key,
+            client_key.public_key(),
+        );
+        let mut client = NetworkClient::new_authenticated(
+            "test",
+            server_addr,
+            TIMEOUT,
+            client_key,
+            server_public_key,
+        );
+
+        // The handshake completes once the server accepts the connection on its first read
+        let data = vec![0, 1, 2, 3];
+        let expected = data.clone();
+        let server_thread = thread::spawn(move || {
+            let result = server.read().unwrap();
+            assert_eq!(expected, result);
+
+            // Larger than a single Noise message
+            let data: Vec<u8> = (0..3 * noise::MAX_SIZE_NOISE_MSG)
+                .map(|i| i as u8)
+                .collect();
+            server.write(&data).unwrap();
+            server.write(&[]).unwrap();
+            server
+        });
+        client.write(&data).unwrap();
+        let result = client.read().unwrap();
+        assert_eq!(result.len(), 3 * noise::MAX_SIZE_NOISE_MSG);
+        assert!(result.iter().enumerate().all(|(i, byte)| *byte == i as u8));
+        assert!(client.read().unwrap().is_empty());
+        server_thread.join().unwrap();
+    }
+
+    #[test]
+    fn test_authenticated_short_block() {
+        let server_port = utils::get_available_port();
+        let server_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), server_port);
+        let server_key = x25519::PrivateKey::generate(&mut OsRng);
+        let client_key = x25519::PrivateKey::generate(&mut OsRng);
+        let server_public_key = server_key.public_key();
+        let mut server = NetworkServer::new_authenticated(
+            "test",
+            server_addr,
+            TIMEOUT,
+            server_key,
+            client_key.public_key(),
+        );
+        let mut client = NetworkClient::new_authenticated(
+            "test",
+            server_addr,
+            TIMEOUT,
+            client_key,
+            server_public_key,
+        );
+        let server_thread = thread::spawn(move || {
+            assert_eq!(server.read().unwrap(), vec![0, 1, 2, 3]);
+            let err = server.read().unwrap_err();
+            assert!(matches!(err, Error::NoiseError(NoiseError::MsgTooShort)));
+        });
+
+        // An empty block bypassing the session would otherwise be read as an empty message
+        client.write(&[0, 1, 2, 3]).unwrap();
+        client.server().unwrap().write_block(&[]).unwrap();
+        server_thread.join().unwrap();
+    }
+
+    #[test]
+    fn test_authenticated_unexpected_client() {
+        let server_port = utils::get_available_port();
+        let server_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), server_port);
+        let server_key = x25519::PrivateKey::generate(&mut OsRng);
+        let server_public_key = server_key.public_key();
+        let client_public_key = x25519::PrivateKey::generate(&mut OsRng).public_key();
+        let other_key = x25519::PrivateKey::generate(&mut OsRng);
+        let mut server = NetworkServer::new_authenticated(
+            "test",
+            server_addr,
+            TIMEOUT,
+            server_key,
+            client_public_key,
+        );
+        let server_thread = thread::spawn(move || {
+            let err = server.read().unwrap_err();
+            assert!(matches!(err, Error::UnexpectedRemoteKey(_)));
+            server
+        });
+
+        // The server rejects a client authenticated with another key
+        let mut client = NetworkClient::new_authenticated(
+            "test",
+            server_addr,
+            TIMEOUT,
+            other_key,
+            server_public_key,
+        );
+        client.write(&[0, 1, 2, 3]).unwrap_err();
+        server_thread.join().unwrap();
+
+        // An unauthenticated client can't talk to an authenticated server either
+        let mut server = NetworkServer::new_authenticated(
+            "test",
+            server_addr,
+            TIMEOUT,
+            x25519::PrivateKey::generate(&mut OsRng),
+            client_public_key,
+        );
+        let mut client = NetworkClient::new("test", server_addr, TIMEOUT);
+        client.write(&[0, 1, 2, 3]).unwrap();
+        server.read().unwrap_err();
+    }
 }
@@ -73,6 +73,7 @@ use aptos_types::{
     state_proof::StateProof,
     state_store::{
         state_key::StateKey,
+        state_key_prefix::StateKeyPrefix,
         state_value::{
             StateKeyAndValue, StateValue, StateValueChunkWithProof, StateValueWithProof,
         },
@@ -98,6 +99,8 @@ use std::{
 use storage_interface::{DbReader, DbWriter, Order, StartupInfo, StateSnapshotReceiver, TreeState};
 
 const MAX_LIMIT: u64 = 5000;
+// Bounds the work of a page of state values under a key prefix when most of them were deleted.
+const MAX_KEYS_SCANNED_FOR_KEY_PREFIX: usize = 20_000;
 
 // TODO: Either implement an iteration API to allow a very old client to loop through a long history
 // or guarantee that there is always a recent enough waypoint and client knows to boot from there.
@@ -244,7 +247,9 @@ impl AptosDB {
             JELLYFISH_MERKLE_NODE_CF_NAME,
             LEDGER_COUNTERS_CF_NAME,
             STALE_NODE_INDEX_CF_NAME,
+            STALE_TABLE_SIZE_INDEX_CF_NAME,
             STATE_VALUE_INDEX_CF_NAME,
+            TABLE_SIZE_CF_NAME,
             TRANSACTION_CF_NAME,
             TRANSACTION_ACCUMULATOR_CF_NAME,
             TRANSACTION_BY_ACCOUNT_CF_NAME,
@@ -317,6 +322,11 @@ impl AptosDB {
         };
 
         let ret = Self::new_with_db(db, storage_pruner_config);
+        if !readonly {
+            if let Some((version, _)) = ret.ledger_store.get_latest_transaction_info_option()? {
+                ret.state_store.backfill_table_sizes(version)?;
+            }
+        }
         info!(
             path = path,
             time_ms = %instant.elapsed().as_millis(),
@@ -597,6 +607,8 @@ impl AptosDB {
                 .iter()
                 .map(|txn_to_commit| txn_to_commit.jf_node_hashes())
                 .collect::<Option<Vec<_>>>();
+            self.state_store
+                .put_table_sizes(&account_state_sets, first_version, cs)?;
             self.state_store
                 .put_value_sets(account_state_sets, node_hashes, first_version, cs)?;
         }
@@ -1174,6 +1186,31 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_state_values_by_key_prefix(
+        &self,
+        key_prefix: &StateKeyPrefix,
+        start_after: Option<&StateKey>,
+        limit: u64,
+        version: Version,
+    ) -> Result<(Vec<(StateKey, StateValue)>, Option<StateKey>)> {
+        gauged_api("get_state_values_by_key_prefix", || {
+            error_if_too_many_requested(limit, MAX_LIMIT)?;
+            self.state_store.get_values_by_key_prefix_paginated(
+                key_prefix,
+                start_after,
+                limit as usize,
+                MAX_KEYS_SCANNED_FOR_KEY_PREFIX,
+                version,
+            )
+        })
+    }
+
+    fn get_table_size(&self, handle: u128, version: Version) -> Result<Option<u64>> {
+        gauged_api("get_table_size", || {
+            self.state_store.get_table_size(handle, version)
+        })
+    }
+
     fn get_state_leaf_count(&self, version: Version) -> Result<usize> {
         gauged_api("get_state_leaf_count", || {
             self.state_store.get_value_count(version)
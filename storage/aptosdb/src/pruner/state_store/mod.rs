@@ -4,6 +4,7 @@
 use crate::{
     jellyfish_merkle_node::JellyfishMerkleNodeSchema, metrics::APTOS_PRUNER_LEAST_READABLE_VERSION,
     pruner::db_pruner::DBPruner, stale_node_index::StaleNodeIndexSchema,
+    stale_table_size_index::StaleTableSizeIndexSchema, table_size::TableSizeSchema,
     APTOS_STORAGE_OTHER_TIMERS_SECONDS,
 };
 use aptos_infallible::Mutex;
@@ -182,11 +183,40 @@ pub fn prune_state_store(
         indices
             .into_iter()
             .try_for_each(|index| batch.delete::<JellyfishMerkleNodeSchema>(&index.node_key))?;
+        prune_table_sizes(
+            &db,
+            least_readable_version,
+            new_least_readable_version,
+            &mut batch,
+        )?;
         db.write_schemas(batch)?;
         Ok(new_least_readable_version)
     }
 }
 
+/// Deletes the table size records that became stale between the two versions, together with
+/// their index entries. A table size only changes with the table items, so these versions always
+/// have stale nodes as well.
+fn prune_table_sizes(
+    db: &DB,
+    least_readable_version: Version,
+    new_least_readable_version: Version,
+    batch: &mut SchemaBatch,
+) -> anyhow::Result<()> {
+    let mut iter = db.iter::<StaleTableSizeIndexSchema>(ReadOptions::default())?;
+    iter.seek(&least_readable_version)?;
+    for res in iter {
+        let (index, _) = res?;
+        let (stale_since_version, handle, version) = index;
+        if stale_since_version > new_least_readable_version {
+            break;
+        }
+        batch.delete::<TableSizeSchema>(&(handle, version))?;
+        batch.delete::<StaleTableSizeIndexSchema>(&index)?;
+    }
+    Ok(())
+}
+
 struct StaleNodeIndicesByVersionIterator<'a> {
     inner: Peekable<SchemaIterator<'a, StaleNodeIndexSchema>>,
     target_least_readable_version: Version,
@@ -15,7 +15,9 @@ pub(crate) mod jellyfish_merkle_node;
 pub(crate) mod ledger_counters;
 pub(crate) mod ledger_info;
 pub(crate) mod stale_node_index;
+pub(crate) mod stale_table_size_index;
 pub(crate) mod state_value_index;
+pub(crate) mod table_size;
 pub(crate) mod transaction;
 pub(crate) mod transaction_accumulator;
 pub(crate) mod transaction_by_account;
@@ -34,7 +36,9 @@ pub const EVENT_CF_NAME: ColumnFamilyName = "event";
 pub const JELLYFISH_MERKLE_NODE_CF_NAME: ColumnFamilyName = "jellyfish_merkle_node";
 pub const LEDGER_COUNTERS_CF_NAME: ColumnFamilyName = "ledger_counters";
 pub const STALE_NODE_INDEX_CF_NAME: ColumnFamilyName = "stale_node_index";
+pub const STALE_TABLE_SIZE_INDEX_CF_NAME: ColumnFamilyName = "stale_table_size_index";
 pub const STATE_VALUE_INDEX_CF_NAME: ColumnFamilyName = "state_value_index";
+pub const TABLE_SIZE_CF_NAME: ColumnFamilyName = "table_size";
 pub const TRANSACTION_CF_NAME: ColumnFamilyName = "transaction";
 pub const TRANSACTION_ACCUMULATOR_CF_NAME: ColumnFamilyName = "transaction_accumulator";
 pub const TRANSACTION_BY_ACCOUNT_CF_NAME: ColumnFamilyName = "transaction_by_account";
@@ -80,6 +84,10 @@ pub mod fuzzing {
             assert_no_panic_decoding::<super::ledger_counters::LedgerCountersSchema>(data);
             assert_no_panic_decoding::<super::ledger_info::LedgerInfoSchema>(data);
             assert_no_panic_decoding::<super::stale_node_index::StaleNodeIndexSchema>(data);
+            assert_no_panic_decoding::<super::stale_table_size_index::StaleTableSizeIndexSchema>(
+                data,
+            );
+            assert_no_panic_decoding::<super::table_size::TableSizeSchema>(data);
             assert_no_panic_decoding::<super::transaction::TransactionSchema>(data);
             assert_no_panic_decoding::<super::transaction_accumulator::TransactionAccumulatorSchema>(
                 data,
@@ -0,0 +1,78 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines the physical storage schema for table sizes (see the `table_size` schema)
+//! that are outdated, which are ready to be pruned after being old enough.
+//!
+//! An index entry in this data set has 2 pieces of information:
+//!     1. The version since which a size record becomes stale, meaning, replaced by the size of
+//! the table at a later version.
+//!     2. The table handle and the version of the stale size record.
+//!
+//! ```text
+//! |<-----------------key----------------->|
+//! | stale_since_version | handle, version |
+//! ```
+//!
+//! All fields are serialized in big endian so that records in RocksDB will be in order of
+//! `stale_since_version`.
+
+use crate::schema::{ensure_slice_len_eq, STALE_TABLE_SIZE_INDEX_CF_NAME};
+use anyhow::Result;
+use aptos_types::transaction::Version;
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use schemadb::{
+    define_schema,
+    schema::{KeyCodec, SeekKeyCodec, ValueCodec},
+};
+use std::mem::size_of;
+
+type Key = (Version, u128, Version);
+
+define_schema!(
+    StaleTableSizeIndexSchema,
+    Key,
+    (),
+    STALE_TABLE_SIZE_INDEX_CF_NAME
+);
+
+impl KeyCodec<StaleTableSizeIndexSchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let mut encoded = vec![];
+        encoded.write_u64::<BigEndian>(self.0)?;
+        encoded.write_u128::<BigEndian>(self.1)?;
+        encoded.write_u64::<BigEndian>(self.2)?;
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        const VERSION_SIZE: usize = size_of::<Version>();
+        const HANDLE_SIZE: usize = size_of::<u128>();
+
+        ensure_slice_len_eq(data, 2 * VERSION_SIZE + HANDLE_SIZE)?;
+        let stale_since_version = (&data[..VERSION_SIZE]).read_u64::<BigEndian>()?;
+        let handle = (&data[VERSION_SIZE..VERSION_SIZE + HANDLE_SIZE]).read_u128::<BigEndian>()?;
+        let version = (&data[VERSION_SIZE + HANDLE_SIZE..]).read_u64::<BigEndian>()?;
+        Ok((stale_since_version, handle, version))
+    }
+}
+
+impl ValueCodec<StaleTableSizeIndexSchema> for () {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, 0)?;
+        Ok(())
+    }
+}
+
+impl SeekKeyCodec<StaleTableSizeIndexSchema> for Version {
+    fn encode_seek_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,22 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use proptest::prelude::*;
+use schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        stale_since_version in any::<Version>(),
+        handle in any::<u128>(),
+        version in any::<Version>(),
+    ) {
+        assert_encode_decode::<StaleTableSizeIndexSchema>(
+            &(stale_since_version, handle, version),
+            &(),
+        );
+    }
+}
+
+test_no_panic_decoding!(StaleTableSizeIndexSchema);
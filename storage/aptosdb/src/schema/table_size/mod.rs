@@ -0,0 +1,60 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for the number of items in each table created
+//! through the Move table extension.
+//!
+//! A row is written for every version that changes the size of a table, so the size at a given
+//! version is found by seeking to the last row at or before it.
+//! ```text
+//! |<------key------>|<-value->|
+//! | handle, version |  size   |
+//! ```
+//!
+//! `handle` and `version` are serialized in big endian so that records of the same table are
+//! adjacent in RocksDB and ordered by version.
+
+use crate::schema::{ensure_slice_len_eq, TABLE_SIZE_CF_NAME};
+use anyhow::Result;
+use aptos_types::transaction::Version;
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use std::mem::size_of;
+
+type Key = (u128, Version);
+
+define_schema!(TableSizeSchema, Key, u64, TABLE_SIZE_CF_NAME);
+
+impl KeyCodec<TableSizeSchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let mut encoded = self.0.to_be_bytes().to_vec();
+        encoded.write_u64::<BigEndian>(self.1)?;
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        const HANDLE_SIZE: usize = size_of::<u128>();
+
+        ensure_slice_len_eq(data, HANDLE_SIZE + size_of::<Version>())?;
+        let handle = (&data[..HANDLE_SIZE]).read_u128::<BigEndian>()?;
+        let version = (&data[HANDLE_SIZE..]).read_u64::<BigEndian>()?;
+        Ok((handle, version))
+    }
+}
+
+impl ValueCodec<TableSizeSchema> for u64 {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+
+    fn decode_value(mut data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, size_of::<u64>())?;
+        Ok(data.read_u64::<BigEndian>()?)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,19 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use proptest::prelude::*;
+use schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        handle in any::<u128>(),
+        version in any::<Version>(),
+        size in any::<u64>(),
+    ) {
+        assert_encode_decode::<TableSizeSchema>(&(handle, version), &size);
+    }
+}
+
+test_no_panic_decoding!(TableSizeSchema);
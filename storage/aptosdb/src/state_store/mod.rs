@@ -11,6 +11,7 @@ use crate::{
     ledger_counters::LedgerCounter,
     schema::{
         jellyfish_merkle_node::JellyfishMerkleNodeSchema, stale_node_index::StaleNodeIndexSchema,
+        stale_table_size_index::StaleTableSizeIndexSchema, table_size::TableSizeSchema,
     },
     state_value_index::StateValueIndexSchema,
     AptosDbError,
@@ -23,13 +24,12 @@ use aptos_jellyfish_merkle::{
     iterator::JellyfishMerkleIterator, node_type::NodeKey, restore::JellyfishMerkleRestore,
     JellyfishMerkleTree, TreeReader, TreeWriter,
 };
-#[cfg(test)]
-use aptos_types::state_store::state_key_prefix::StateKeyPrefix;
 use aptos_types::{
     nibble::{nibble_path::NibblePath, ROOT_NIBBLE_HEIGHT},
     proof::{SparseMerkleProof, SparseMerkleRangeProof},
     state_store::{
-        state_key::StateKey,
+        state_key::{StateKey, StateKeyTag},
+        state_key_prefix::StateKeyPrefix,
         state_value::{StateKeyAndValue, StateValue, StateValueChunkWithProof},
     },
     transaction::Version,
@@ -38,7 +38,10 @@ use itertools::process_results;
 use schemadb::{SchemaBatch, DB};
 #[cfg(test)]
 use std::cmp::Ordering;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{hash_map::Entry, BTreeSet, HashMap},
+    sync::Arc,
+};
 use storage_interface::StateSnapshotReceiver;
 
 type LeafNode = aptos_jellyfish_merkle::node_type::LeafNode<StateKeyAndValue>;
@@ -154,6 +157,216 @@ impl StateStore {
         Ok(result)
     }
 
+    /// Returns up to `limit` key, value pairs for a particular state key prefix at the desired
+    /// version, in the order of the encoded state keys. If `start_after` is given, only keys
+    /// after it are returned, so a caller can page through all values under a prefix, e.g. all
+    /// items of a table. Deleted values are skipped.
+    ///
+    /// At most `max_keys_scanned` keys are looked at, deleted ones included, so that a prefix
+    /// whose values were mostly deleted doesn't turn a page into a scan of its whole history.
+    /// Along with the values, returns the key to page from if the scan stopped before the end of
+    /// the prefix, which is not necessarily the last key returned.
+    pub fn get_values_by_key_prefix_paginated(
+        &self,
+        key_prefix: &StateKeyPrefix,
+        start_after: Option<&StateKey>,
+        limit: usize,
+        max_keys_scanned: usize,
+        version: Version,
+    ) -> Result<(Vec<(StateKey, StateValue)>, Option<StateKey>)> {
+        let mut result = vec![];
+        let mut num_scanned = 0;
+        let mut next_start_after = None;
+        self.for_each_key_by_prefix(key_prefix, start_after, |state_key| {
+            num_scanned += 1;
+            if let Some(state_value) = self.get_value(&state_key, version)? {
+                result.push((state_key.clone(), state_value));
+            }
+            let more = result.len() < limit && num_scanned < max_keys_scanned;
+            if !more {
+                next_start_after = Some(state_key);
+            }
+            Ok(more)
+        })?;
+        Ok((result, next_start_after))
+    }
+
+    /// Calls `f` with every state key that ever had a value under `key_prefix`, after
+    /// `start_after` if given and in the order of the encoded state keys, until `f` returns
+    /// false.
+    fn for_each_key_by_prefix(
+        &self,
+        key_prefix: &StateKeyPrefix,
+        start_after: Option<&StateKey>,
+        mut f: impl FnMut(StateKey) -> Result<bool>,
+    ) -> Result<()> {
+        let mut iter = self.db.iter::<StateValueIndexSchema>(Default::default())?;
+        match start_after {
+            // Skip all versions of the cursor key.
+            Some(state_key) => iter.seek(&(state_key.clone(), u64::MAX))?,
+            None => iter.seek(&key_prefix)?,
+        }
+
+        while let Some(((state_key, _), _)) = iter.next().transpose()? {
+            if !key_prefix.is_prefix(&state_key)? {
+                break;
+            }
+            // Seek to the next key - this can be done by seeking to the current key with max version
+            iter.seek(&(state_key.clone(), u64::MAX))?;
+            if !f(state_key)? {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the value of `state_key` at the desired version read from the Merkle tree, or
+    /// `None` if the key doesn't exist or was deleted at that version.
+    fn get_value(&self, state_key: &StateKey, version: Version) -> Result<Option<StateValue>> {
+        Ok(JellyfishMerkleTree::new(self)
+            .get(state_key.hash(), version)?
+            .map(|key_and_value| key_and_value.value)
+            .filter(|value| value.maybe_bytes.is_some()))
+    }
+
+    /// Returns the number of items in the table `handle` at the desired version, or `None` if the
+    /// size of the table was never recorded, which is the case for tables that haven't changed
+    /// since the DB started to track their sizes.
+    pub fn get_table_size(&self, handle: u128, version: Version) -> Result<Option<u64>> {
+        Ok(self
+            .get_table_size_record(handle, version)?
+            .map(|(_version, size)| size))
+    }
+
+    /// Returns the version and the size of the last size record of table `handle` at or before
+    /// `version`.
+    fn get_table_size_record(
+        &self,
+        handle: u128,
+        version: Version,
+    ) -> Result<Option<(Version, u64)>> {
+        let mut iter = self.db.iter::<TableSizeSchema>(Default::default())?;
+        iter.seek_for_prev(&(handle, version))?;
+        Ok(match iter.next().transpose()? {
+            Some(((db_handle, db_version), size)) if db_handle == handle => {
+                Some((db_version, size))
+            }
+            _ => None,
+        })
+    }
+
+    /// Records the sizes of all tables at `version` by counting their items. Used after a state
+    /// snapshot is restored at `version`, as the restore writes the items without their sizes.
+    pub fn rebuild_table_sizes(&self, version: Version) -> Result<()> {
+        let mut sizes: HashMap<u128, u64> = HashMap::new();
+        let all_tables = StateKeyPrefix::new(StateKeyTag::TableItem, vec![]);
+        self.for_each_key_by_prefix(&all_tables, None, |state_key| {
+            if let StateKey::TableItem { handle, .. } = &state_key {
+                let size = sizes.entry(*handle).or_default();
+                if self.get_value(&state_key, version)?.is_some() {
+                    *size += 1;
+                }
+            }
+            Ok(true)
+        })?;
+
+        let mut batch = SchemaBatch::new();
+        for (handle, size) in sizes {
+            batch.put::<TableSizeSchema>(&(handle, version), &size)?;
+        }
+        self.db.write_schemas(batch)
+    }
+
+    /// Records the sizes of all tables at `version`, the latest version of the DB, if it has no
+    /// size records at all, i.e. it was created before table sizes were tracked. This is done
+    /// once, from then on the sizes are kept up to date by every commit.
+    pub fn backfill_table_sizes(&self, version: Version) -> Result<()> {
+        let mut iter = self.db.iter::<TableSizeSchema>(Default::default())?;
+        iter.seek_to_first();
+        if iter.next().transpose()?.is_some() {
+            return Ok(());
+        }
+        self.rebuild_table_sizes(version)
+    }
+
+    /// Put the sizes of all tables whose items are created or deleted by `value_state_sets`, the
+    /// state updates of consecutive versions starting at `first_version`, to `cs`. Must be called
+    /// before the value sets themselves are put, as the previous state of every table item is
+    /// read from the DB.
+    ///
+    /// The size of each table is a counter moved by the items created and deleted, a table with
+    /// no record yet is new and starts from zero, as the sizes of the tables that predate the
+    /// tracking are backfilled when the DB is opened. Every record replaced by a newer one is added
+    /// to the stale index so that the pruner can delete it.
+    pub fn put_table_sizes(
+        &self,
+        value_state_sets: &[&HashMap<StateKey, StateValue>],
+        first_version: Version,
+        cs: &mut ChangeSet,
+    ) -> Result<()> {
+        let base_version = first_version.checked_sub(1);
+        // Items written earlier in this batch are not in the DB yet.
+        let mut item_exists: HashMap<&StateKey, bool> = HashMap::new();
+        // The current size of each table and the version of its last record.
+        let mut sizes: HashMap<u128, (u64, Option<Version>)> = HashMap::new();
+
+        for (idx, value_set) in value_state_sets.iter().enumerate() {
+            let version = first_version + idx as u64;
+            let mut resized = BTreeSet::new();
+            for (state_key, state_value) in value_set.iter() {
+                let handle = match state_key {
+                    StateKey::TableItem { handle, .. } => *handle,
+                    _ => continue,
+                };
+                let existed = match (item_exists.get(state_key), base_version) {
+                    (Some(exists), _) => *exists,
+                    (None, Some(base_version)) => {
+                        self.get_value(state_key, base_version)?.is_some()
+                    }
+                    (None, None) => false,
+                };
+                let exists = state_value.maybe_bytes.is_some();
+                item_exists.insert(state_key, exists);
+                if exists == existed {
+                    continue;
+                }
+
+                let (size, _) = match sizes.entry(handle) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => entry.insert(
+                        match base_version
+                            .map(|base_version| self.get_table_size_record(handle, base_version))
+                            .transpose()?
+                            .flatten()
+                        {
+                            Some((record_version, size)) => (size, Some(record_version)),
+                            None => (0, None),
+                        },
+                    ),
+                };
+                // The size is exact as long as every write to the table went through here, so
+                // it can't go below zero. Should a record ever be off, it is corrected rather than
+                // failing the commit.
+                *size = if exists {
+                    *size + 1
+                } else {
+                    size.saturating_sub(1)
+                };
+                resized.insert(handle);
+            }
+
+            for handle in resized {
+                let (size, last_record) = sizes.get_mut(&handle).expect("resized tables exist");
+                cs.batch.put::<TableSizeSchema>(&(handle, version), size)?;
+                if let Some(last_record) = last_record.replace(version) {
+                    cs.batch
+                        .put::<StaleTableSizeIndexSchema>(&(version, handle, last_record), &())?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Get the state value given the state key and root hash of state Merkle tree by using the
     /// state value index. Only used for testing for now but should replace the
     /// `get_value_with_proof_by_version` call for VM execution to fetch the value without proof.
@@ -349,11 +562,42 @@ impl StateStore {
         version: Version,
         expected_root_hash: HashValue,
     ) -> Result<Box<dyn StateSnapshotReceiver<StateKeyAndValue>>> {
-        Ok(Box::new(JellyfishMerkleRestore::new_overwrite(
-            Arc::clone(self),
+        Ok(Box::new(StateSnapshotRestore {
+            tree_restore: JellyfishMerkleRestore::new_overwrite(
+                Arc::clone(self),
+                version,
+                expected_root_hash,
+            )?,
+            store: Arc::clone(self),
             version,
-            expected_root_hash,
-        )?))
+        }))
+    }
+}
+
+/// Restores a state snapshot to the Merkle tree, then records the sizes of the restored tables
+/// once all of the values are in.
+struct StateSnapshotRestore {
+    tree_restore: JellyfishMerkleRestore<StateKeyAndValue>,
+    store: Arc<StateStore>,
+    version: Version,
+}
+
+impl StateSnapshotReceiver<StateKeyAndValue> for StateSnapshotRestore {
+    fn add_chunk(
+        &mut self,
+        chunk: Vec<(HashValue, StateKeyAndValue)>,
+        proof: SparseMerkleRangeProof,
+    ) -> Result<()> {
+        self.tree_restore.add_chunk(chunk, proof)
+    }
+
+    fn finish(self) -> Result<()> {
+        self.tree_restore.finish()?;
+        self.store.rebuild_table_sizes(self.version)
+    }
+
+    fn finish_box(self: Box<Self>) -> Result<()> {
+        (*self).finish()
     }
 }
 
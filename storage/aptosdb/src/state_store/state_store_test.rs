@@ -12,7 +12,6 @@ use aptos_jellyfish_merkle::restore::JellyfishMerkleRestore;
 use aptos_temppath::TempPath;
 use aptos_types::{
     access_path::AccessPath, account_address::AccountAddress, account_state_blob::AccountStateBlob,
-    state_store::state_key::StateKeyTag,
 };
 use storage_interface::StateSnapshotReceiver;
 
@@ -296,6 +295,213 @@ fn test_get_values_by_key_prefix() {
     assert_eq!(*key_value_map.get(&key5).unwrap(), value5_v2);
 }
 
+fn put_table_value_set(
+    state_store: &StateStore,
+    value_set: Vec<(StateKey, StateValue)>,
+    version: Version,
+) {
+    let mut cs = ChangeSet::new();
+    let value_set: HashMap<_, _> = value_set.into_iter().collect();
+    state_store
+        .put_table_sizes(&[&value_set], version, &mut cs)
+        .unwrap();
+    state_store
+        .put_value_sets(vec![&value_set], None, version, &mut cs)
+        .unwrap();
+    state_store.db.write_schemas(cs.batch).unwrap();
+}
+
+#[test]
+fn test_table_size_and_items() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let store = &db.state_store;
+    let handle = 7u128;
+    let item = |key: u8| StateKey::table_item(handle, vec![key]);
+    let value = |value: &str| StateValue::from(value.as_bytes().to_vec());
+    let table_prefix = StateKeyPrefix::new(StateKeyTag::TableItem, handle.to_be_bytes().to_vec());
+
+    put_table_value_set(
+        store,
+        vec![
+            (item(1), value("a")),
+            (item(2), value("b")),
+            (item(3), value("c")),
+            (StateKey::table_item(handle + 1, vec![1]), value("other")),
+        ],
+        0,
+    );
+    // An update does not change the size, a deletion and an insertion do.
+    put_table_value_set(
+        store,
+        vec![
+            (item(1), value("a2")),
+            (item(2), StateValue::empty()),
+            (item(4), value("d")),
+            (item(5), value("e")),
+        ],
+        1,
+    );
+
+    assert_eq!(store.get_table_size(handle, 0).unwrap(), Some(3));
+    assert_eq!(store.get_table_size(handle, 1).unwrap(), Some(4));
+    assert_eq!(store.get_table_size(handle + 1, 1).unwrap(), Some(1));
+    assert_eq!(store.get_table_size(handle + 2, 1).unwrap(), None);
+
+    let page = store
+        .get_values_by_key_prefix_paginated(&table_prefix, None, 2, 100, 1)
+        .unwrap();
+    assert_eq!(
+        page,
+        (
+            vec![(item(1), value("a2")), (item(3), value("c"))],
+            Some(item(3))
+        )
+    );
+    let page = store
+        .get_values_by_key_prefix_paginated(&table_prefix, Some(&item(3)), 10, 100, 1)
+        .unwrap();
+    assert_eq!(
+        page,
+        (vec![(item(4), value("d")), (item(5), value("e"))], None)
+    );
+
+    let page = store
+        .get_values_by_key_prefix_paginated(&table_prefix, None, 10, 100, 0)
+        .unwrap();
+    assert_eq!(
+        page,
+        (
+            vec![
+                (item(1), value("a")),
+                (item(2), value("b")),
+                (item(3), value("c"))
+            ],
+            None
+        )
+    );
+
+    // The scan stops at the deleted item, the next page picks up after it.
+    let page = store
+        .get_values_by_key_prefix_paginated(&table_prefix, None, 10, 2, 1)
+        .unwrap();
+    assert_eq!(page, (vec![(item(1), value("a2"))], Some(item(2))));
+    let page = store
+        .get_values_by_key_prefix_paginated(&table_prefix, Some(&item(2)), 10, 2, 1)
+        .unwrap();
+    assert_eq!(
+        page,
+        (
+            vec![(item(3), value("c")), (item(4), value("d"))],
+            Some(item(4))
+        )
+    );
+}
+
+#[test]
+fn test_backfill_table_sizes() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let store = &db.state_store;
+    let handle = 7u128;
+    let item = |key: u8| StateKey::table_item(handle, vec![key]);
+    let value = |value: &str| StateValue::from(value.as_bytes().to_vec());
+
+    // Items written without their table size, as by a DB that didn't track table sizes yet.
+    let mut cs = ChangeSet::new();
+    let value_set: HashMap<_, _> = vec![(item(1), value("a")), (item(2), value("b"))]
+        .into_iter()
+        .collect();
+    store
+        .put_value_sets(vec![&value_set], None, 0, &mut cs)
+        .unwrap();
+    store.db.write_schemas(cs.batch).unwrap();
+    assert_eq!(store.get_table_size(handle, 0).unwrap(), None);
+
+    // The sizes are backfilled once, the commits move them from there.
+    store.backfill_table_sizes(0).unwrap();
+    assert_eq!(store.get_table_size(handle, 0).unwrap(), Some(2));
+    put_table_value_set(store, vec![(item(1), StateValue::empty())], 1);
+    assert_eq!(store.get_table_size(handle, 1).unwrap(), Some(1));
+
+    // Once there are size records, the backfill doesn't count the items again.
+    let mut cs = ChangeSet::new();
+    let value_set: HashMap<_, _> = vec![(item(3), value("c"))].into_iter().collect();
+    store
+        .put_value_sets(vec![&value_set], None, 2, &mut cs)
+        .unwrap();
+    store.db.write_schemas(cs.batch).unwrap();
+    store.backfill_table_sizes(2).unwrap();
+    assert_eq!(store.get_table_size(handle, 2).unwrap(), Some(1));
+}
+
+#[test]
+fn test_table_size_pruning() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let store = &db.state_store;
+    let handle = 7u128;
+    let item = |key: u8| StateKey::table_item(handle, vec![key]);
+    let value = |value: &str| StateValue::from(value.as_bytes().to_vec());
+
+    put_table_value_set(store, vec![(item(1), value("a"))], 0);
+    put_table_value_set(store, vec![(item(2), value("b"))], 1);
+    put_table_value_set(store, vec![(item(3), value("c"))], 2);
+
+    prune_stale_indices(store, 0, 1, 10);
+    assert!(store
+        .db
+        .get::<TableSizeSchema>(&(handle, 0))
+        .unwrap()
+        .is_none());
+    assert_eq!(store.get_table_size(handle, 1).unwrap(), Some(2));
+    assert_eq!(store.get_table_size(handle, 2).unwrap(), Some(3));
+
+    prune_stale_indices(store, 1, 2, 10);
+    assert!(store
+        .db
+        .get::<TableSizeSchema>(&(handle, 1))
+        .unwrap()
+        .is_none());
+    assert_eq!(store.get_table_size(handle, 2).unwrap(), Some(3));
+}
+
+#[test]
+fn test_restore_table_sizes() {
+    let handle = 7u128;
+    let item = |key: u8| StateKey::table_item(handle, vec![key]);
+    let value = |value: &str| StateValue::from(value.as_bytes().to_vec());
+
+    let tmp_dir1 = TempPath::new();
+    let db1 = AptosDB::new_for_test(&tmp_dir1);
+    let store1 = &db1.state_store;
+    put_table_value_set(
+        store1,
+        vec![
+            (item(1), value("a")),
+            (item(2), value("b")),
+            (StateKey::table_item(handle + 1, vec![1]), value("other")),
+        ],
+        0,
+    );
+    put_table_value_set(store1, vec![(item(1), StateValue::empty())], 1);
+    let version = 1;
+    let expected_root_hash = store1.get_root_hash(version).unwrap();
+
+    let tmp_dir2 = TempPath::new();
+    let db2 = AptosDB::new_for_test(&tmp_dir2);
+    let store2 = &db2.state_store;
+    let mut restore = store2
+        .get_snapshot_receiver(version, expected_root_hash)
+        .unwrap();
+    let chunk = store1.get_value_chunk_with_proof(version, 0, 10).unwrap();
+    restore.add_chunk(chunk.raw_values, chunk.proof).unwrap();
+    restore.finish_box().unwrap();
+
+    assert_eq!(store2.get_table_size(handle, version).unwrap(), Some(1));
+    assert_eq!(store2.get_table_size(handle + 1, version).unwrap(), Some(1));
+}
+
 #[test]
 fn test_retired_records() {
     let address1 = AccountAddress::new([1u8; AccountAddress::LENGTH]);
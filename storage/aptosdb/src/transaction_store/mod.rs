@@ -140,10 +140,13 @@ impl TransactionStore {
         iter.seek(&version)?;
         for res in iter.take(MAX_VERSIONS_TO_SEARCH) {
             let (v, txn) = res?;
-            if let Transaction::BlockMetadata(block_meta) = txn {
-                return Ok(Some((v, block_meta)));
-            } else if v == 0 {
-                return Ok(None);
+            match txn {
+                Transaction::BlockMetadata(block_meta) => return Ok(Some((v, block_meta))),
+                Transaction::BlockMetadataV2(block_meta) => {
+                    return Ok(Some((v, block_meta.into_inner().0)))
+                }
+                _ if v == 0 => return Ok(None),
+                _ => (),
             }
         }
 
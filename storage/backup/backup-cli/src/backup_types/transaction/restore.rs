@@ -21,12 +21,14 @@ use crate::{
 };
 use anyhow::{anyhow, ensure, Result};
 use aptos_logger::prelude::*;
+use aptos_transaction_replay::{AptosDebugger, VerificationReport};
 use aptos_types::{
     contract_event::ContractEvent,
     ledger_info::LedgerInfoWithSignatures,
     proof::{TransactionAccumulatorRangeProof, TransactionInfoListWithProof},
     transaction::{Transaction, TransactionInfo, TransactionListWithProof, Version},
 };
+use aptos_validator_interface::DBDebuggerInterface;
 use aptos_vm::AptosVM;
 use aptosdb::backup::restore_handler::RestoreHandler;
 use executor::{chunk_executor::ChunkExecutor, components::apply_chunk_output::IntoLedgerView};
@@ -199,18 +201,38 @@ impl TransactionRestoreBatchController {
         let name = self.name();
         info!("{} started.", name);
         let res = self
-            .run_impl()
+            .run_impl(None)
             .await
             .map_err(|e| anyhow!("{} failed: {}", name, e))?;
         info!("{} succeeded.", name);
         Ok(res)
     }
 
+    /// Like `run`, but each chunk of transactions is re-executed with the local VM and compared to
+    /// the backup before it's replayed. The replay stops at the first chunk that diverges, as its
+    /// state can't be committed.
+    pub async fn run_and_verify_execution(self) -> Result<VerificationReport> {
+        let name = self.name();
+        info!("{} with execution verification started.", name);
+        let mut report = VerificationReport::new(self.replay_from_version.unwrap_or(0));
+        self.run_impl(Some(&mut report))
+            .await
+            .map_err(|e| anyhow!("{} failed: {}", name, e))?;
+        report.finalize();
+        info!(
+            num_transactions = report.num_transactions,
+            num_diverged_transactions = report.num_diverged_transactions,
+            "{} finished.",
+            name
+        );
+        Ok(report)
+    }
+
     fn name(&self) -> String {
         format!("transaction {}", self.global_opt.run_mode.name())
     }
 
-    async fn run_impl(self) -> Result<()> {
+    async fn run_impl(self, report: Option<&mut VerificationReport>) -> Result<()> {
         if self.manifest_handles.is_empty() {
             return Ok(());
         }
@@ -226,8 +248,17 @@ impl TransactionRestoreBatchController {
                 .await?;
 
             if let Some(txns_to_execute_stream) = txns_to_execute_stream {
-                self.replay_transactions(restore_handler, txns_to_execute_stream)
+                if let Some(report) = report {
+                    self.verify_and_replay_transactions(
+                        restore_handler,
+                        txns_to_execute_stream,
+                        report,
+                    )
                     .await?;
+                } else {
+                    self.replay_transactions(restore_handler, txns_to_execute_stream)
+                        .await?;
+                }
             }
         } else {
             Self::go_through_verified_chunks(loaded_chunk_stream, first_version).await?;
@@ -453,6 +484,63 @@ impl TransactionRestoreBatchController {
             .await
     }
 
+    async fn verify_and_replay_transactions(
+        &self,
+        restore_handler: &RestoreHandler,
+        txns_to_execute_stream: impl Stream<Item = Result<(Transaction, TransactionInfo)>>,
+        report: &mut VerificationReport,
+    ) -> Result<()> {
+        let mut version = self.replay_from_version.unwrap();
+        let db = DbReaderWriter::from_arc(Arc::clone(&restore_handler.aptosdb));
+        let persisted_view = restore_handler
+            .get_tree_state(version)?
+            .into_ledger_view(&db.reader)?;
+        let chunk_replayer = Arc::new(ChunkExecutor::<AptosVM>::new_with_view(db, persisted_view));
+        let debugger = Arc::new(AptosDebugger::new(Box::new(DBDebuggerInterface::new(
+            restore_handler.aptosdb.clone(),
+        ))));
+
+        // Each chunk is executed on top of the committed state of the previous one, so unlike
+        // `replay_transactions` the chunks are handled one at a time.
+        let mut chunk_stream = Box::pin(
+            txns_to_execute_stream
+                .try_chunks(BATCH_SIZE)
+                .err_into::<anyhow::Error>(),
+        );
+        while let Some(chunk) = chunk_stream.try_next().await? {
+            let (txns, txn_infos): (Vec<_>, Vec<_>) = chunk.into_iter().unzip();
+            let num_txns = txns.len() as Version;
+
+            let chunk_debugger = debugger.clone();
+            let (chunk_report, txns, txn_infos) = tokio::task::spawn_blocking(move || {
+                chunk_debugger
+                    .verify_transactions(version, txns.clone(), &txn_infos)
+                    .map(|chunk_report| (chunk_report, txns, txn_infos))
+            })
+            .await??;
+            let diverged = !chunk_report.is_ok();
+            report.merge(chunk_report);
+            if diverged {
+                warn!(
+                    version = version,
+                    "Transactions diverged, stopping the replay."
+                );
+                return Ok(());
+            }
+
+            let chunk_replayer = chunk_replayer.clone();
+            tokio::task::spawn_blocking(move || {
+                chunk_replayer.replay(txns, txn_infos)?;
+                chunk_replayer.commit().map(|_| ())
+            })
+            .await??;
+            version += num_txns;
+            TRANSACTION_REPLAY_VERSION.set(version as i64 - 1);
+            info!(version = version - 1, "Transactions verified and replayed.");
+        }
+        Ok(())
+    }
+
     async fn go_through_verified_chunks(
         loaded_chunk_stream: impl Stream<Item = Result<LoadedChunk>>,
         first_version: Version,
@@ -40,6 +40,14 @@ struct Opt {
                 to be replayed and verified (if present in the backup)."
     )]
     end_version: Option<Version>,
+    #[structopt(
+        long,
+        parse(from_os_str),
+        help = "Re-execute each chunk with the local VM before replaying it, and write the \
+                divergences from the backup, grouped by cause, as JSON to this file. The replay \
+                stops at the first chunk that diverges."
+    )]
+    divergence_report: Option<PathBuf>,
 }
 
 #[tokio::main]
@@ -69,6 +77,7 @@ async fn main_impl() -> Result<()> {
         restore_handler,
         opt.start_version.unwrap_or(0),
         opt.end_version.unwrap_or(Version::MAX),
+        opt.divergence_report,
     )?
     .run()
     .await
@@ -15,7 +15,7 @@ use anyhow::{ensure, Result};
 use aptos_logger::prelude::*;
 use aptos_types::transaction::Version;
 use aptosdb::backup::restore_handler::RestoreHandler;
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 pub struct ReplayVerifyCoordinator {
     storage: Arc<dyn BackupStorage>,
@@ -25,6 +25,7 @@ pub struct ReplayVerifyCoordinator {
     restore_handler: RestoreHandler,
     start_version: Version,
     end_version: Version,
+    divergence_report_path: Option<PathBuf>,
 }
 
 impl ReplayVerifyCoordinator {
@@ -36,6 +37,7 @@ impl ReplayVerifyCoordinator {
         restore_handler: RestoreHandler,
         start_version: Version,
         end_version: Version,
+        divergence_report_path: Option<PathBuf>,
     ) -> Result<Self> {
         Ok(Self {
             storage,
@@ -45,6 +47,7 @@ impl ReplayVerifyCoordinator {
             restore_handler,
             start_version,
             end_version,
+            divergence_report_path,
         })
     }
 
@@ -113,15 +116,27 @@ impl ReplayVerifyCoordinator {
         }
 
         let txn_manifests = transactions.into_iter().map(|b| b.manifest).collect();
-        TransactionRestoreBatchController::new(
+        let txn_restore = TransactionRestoreBatchController::new(
             global_opt,
             self.storage,
             txn_manifests,
             Some(replay_transactions_from_version), /* replay_from_version */
             None,                                   /* epoch_history */
-        )
-        .run()
-        .await?;
+        );
+        match self.divergence_report_path {
+            None => txn_restore.run().await?,
+            Some(path) => {
+                let report = txn_restore.run_and_verify_execution().await?;
+                std::fs::write(&path, serde_json::to_string_pretty(&report)?)?;
+                ensure!(
+                    report.is_ok(),
+                    "{} out of {} transactions diverged, see {}",
+                    report.num_diverged_transactions,
+                    report.num_transactions,
+                    path.display()
+                );
+            }
+        }
 
         Ok(())
     }
@@ -416,6 +416,16 @@ impl DB {
 
     /// Writes a group of records wrapped in a [`SchemaBatch`].
     pub fn write_schemas(&self, batch: SchemaBatch) -> Result<()> {
+        self.write_schemas_opt(batch, &default_write_options())
+    }
+
+    /// Writes a group of records without waiting for them to be synced to disk, for the
+    /// non-critical data which can be lost when the machine crashes.
+    pub fn write_schemas_relaxed(&self, batch: SchemaBatch) -> Result<()> {
+        self.write_schemas_opt(batch, &rocksdb::WriteOptions::default())
+    }
+
+    fn write_schemas_opt(&self, batch: SchemaBatch, opts: &rocksdb::WriteOptions) -> Result<()> {
         let _timer = APTOS_SCHEMADB_BATCH_COMMIT_LATENCY_SECONDS
             .with_label_values(&[self.name])
             .start_timer();
@@ -439,7 +449,7 @@ impl DB {
         }
         let serialized_size = db_batch.size_in_bytes();
 
-        self.inner.write_opt(db_batch, &default_write_options())?;
+        self.inner.write_opt(db_batch, opts)?;
 
         // Bump counters only after DB write succeeds.
         for (cf_name, rows) in &batch.rows {
@@ -511,9 +521,9 @@ impl DB {
     }
 }
 
-/// For now we always use synchronous writes. This makes sure that once the operation returns
-/// `Ok(())` the data is persisted even if the machine crashes. In the future we might consider
-/// selectively turning this off for some non-critical writes to improve performance.
+/// Synchronous writes are used by default. This makes sure that once the operation returns
+/// `Ok(())` the data is persisted even if the machine crashes. Non-critical writes can opt out
+/// with `write_schemas_relaxed` to improve performance.
 fn default_write_options() -> rocksdb::WriteOptions {
     let mut opts = rocksdb::WriteOptions::default();
     opts.set_sync(true);
@@ -22,6 +22,7 @@ use aptos_types::{
     state_proof::StateProof,
     state_store::{
         state_key::StateKey,
+        state_key_prefix::StateKeyPrefix,
         state_value::{
             StateKeyAndValue, StateValue, StateValueChunkWithProof, StateValueWithProof,
         },
@@ -494,6 +495,26 @@ pub trait DbReader: Send + Sync {
         )
     }
 
+    /// Returns up to `limit` state values under `key_prefix` at given version, ordered by key and
+    /// starting after `start_after` if given, along with the key to start the next page after if
+    /// there may be more. A page can be short while there are more values, as the number of
+    /// deleted values skipped to fill it is bounded.
+    fn get_state_values_by_key_prefix(
+        &self,
+        key_prefix: &StateKeyPrefix,
+        start_after: Option<&StateKey>,
+        limit: u64,
+        version: Version,
+    ) -> Result<(Vec<(StateKey, StateValue)>, Option<StateKey>)> {
+        unimplemented!()
+    }
+
+    /// Returns the number of items in the table `handle` at given version, or `None` if the table
+    /// doesn't exist at that version or its size wasn't tracked yet.
+    fn get_table_size(&self, handle: u128, version: Version) -> Result<Option<u64>> {
+        unimplemented!()
+    }
+
     /// Returns total number of leaves in state store at given version.
     fn get_state_leaf_count(&self, version: Version) -> Result<usize> {
         unimplemented!()
@@ -6,7 +6,7 @@ use crate::{
     account_address::AccountAddress,
     account_config::{AccountResource, BalanceResource, CRSNResource, ChainIdResource},
     account_state_blob::AccountStateBlob,
-    block_metadata::BlockResource,
+    block_metadata::{BlockResource, FailedProposersResource},
     on_chain_config::{
         access_path_for_config, dpn_access_path_for_config, ConfigurationResource, OnChainConfig,
         VMPublishingOption, ValidatorSet, Version,
@@ -90,6 +90,10 @@ impl AccountState {
         self.get_resource::<BlockResource>()
     }
 
+    pub fn get_failed_proposers_resource(&self) -> Result<Option<FailedProposersResource>> {
+        self.get_resource::<FailedProposersResource>()
+    }
+
     pub fn get(&self, key: &[u8]) -> Option<&Vec<u8>> {
         self.0.get(key)
     }
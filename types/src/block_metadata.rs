@@ -83,6 +83,36 @@ impl BlockMetadata {
     }
 }
 
+/// The block metadata with the proposers of the rounds that failed since the previous block, in
+/// round order. It replaces the BlockMetadata once the on-chain consensus config enables recording
+/// the failed proposers.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockMetadataV2 {
+    metadata: BlockMetadata,
+    failed_proposers: Vec<AccountAddress>,
+}
+
+impl BlockMetadataV2 {
+    pub fn new(metadata: BlockMetadata, failed_proposers: Vec<AccountAddress>) -> Self {
+        Self {
+            metadata,
+            failed_proposers,
+        }
+    }
+
+    pub fn metadata(&self) -> &BlockMetadata {
+        &self.metadata
+    }
+
+    pub fn failed_proposers(&self) -> &Vec<AccountAddress> {
+        &self.failed_proposers
+    }
+
+    pub fn into_inner(self) -> (BlockMetadata, Vec<AccountAddress>) {
+        (self.metadata, self.failed_proposers)
+    }
+}
+
 pub fn new_block_event_key() -> EventKey {
     EventKey::new_from_address(&aptos_root_address(), 5)
 }
@@ -118,12 +148,35 @@ impl MoveStructType for BlockResource {
 
 impl MoveResource for BlockResource {}
 
+/// The handle of the events with the failed proposers. Its key isn't known in advance, as the
+/// chains created before the failed proposers were recorded publish it after genesis.
+#[derive(Deserialize, Serialize)]
+pub struct FailedProposersResource {
+    failed_proposer_events: EventHandle,
+}
+
+impl FailedProposersResource {
+    pub fn failed_proposer_events(&self) -> &EventHandle {
+        &self.failed_proposer_events
+    }
+}
+
+impl MoveStructType for FailedProposersResource {
+    const MODULE_NAME: &'static IdentStr = ident_str!("Block");
+    const STRUCT_NAME: &'static IdentStr = ident_str!("FailedProposers");
+}
+
+impl MoveResource for FailedProposersResource {}
+
 #[derive(Clone, Deserialize, Serialize)]
 pub struct NewBlockEvent {
     round: u64,
     proposer: AccountAddress,
     votes: Vec<AccountAddress>,
     timestamp: u64,
+    // Not part of the event, joined from the FailedProposersEvent emitted with it if any
+    #[serde(skip)]
+    failed_proposers: Vec<AccountAddress>,
 }
 
 impl NewBlockEvent {
@@ -138,8 +191,15 @@ impl NewBlockEvent {
             proposer,
             votes,
             timestamp,
+            failed_proposers: vec![],
         }
     }
+
+    pub fn with_failed_proposers(mut self, failed_proposers: Vec<AccountAddress>) -> Self {
+        self.failed_proposers = failed_proposers;
+        self
+    }
+
     pub fn round(&self) -> u64 {
         self.round
     }
@@ -151,4 +211,34 @@ impl NewBlockEvent {
     pub fn votes(&self) -> Vec<AccountAddress> {
         self.votes.clone()
     }
+
+    /// The proposers of the rounds that timed out before the block
+    pub fn failed_proposers(&self) -> &Vec<AccountAddress> {
+        &self.failed_proposers
+    }
+}
+
+/// Emitted by the block prologue along with the NewBlockEvent, once the on-chain consensus config
+/// enables recording the failed proposers.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct FailedProposersEvent {
+    round: u64,
+    failed_proposers: Vec<AccountAddress>,
+}
+
+impl FailedProposersEvent {
+    pub fn new(round: u64, failed_proposers: Vec<AccountAddress>) -> Self {
+        Self {
+            round,
+            failed_proposers,
+        }
+    }
+
+    pub fn round(&self) -> u64 {
+        self.round
+    }
+
+    pub fn failed_proposers(&self) -> &Vec<AccountAddress> {
+        &self.failed_proposers
+    }
 }
@@ -10,6 +10,7 @@ use serde::{Deserialize, Serialize};
 pub enum OnChainConsensusConfig {
     V1(ConsensusConfigV1),
     V2(ConsensusConfigV2),
+    V3(ConsensusConfigV3),
 }
 
 /// The public interface that exposes all values with safe fallback.
@@ -19,6 +20,7 @@ impl OnChainConsensusConfig {
         match &self {
             OnChainConsensusConfig::V1(config) => config.two_chain,
             OnChainConsensusConfig::V2(config) => config.two_chain,
+            OnChainConsensusConfig::V3(config) => config.two_chain,
         }
     }
 
@@ -26,6 +28,7 @@ impl OnChainConsensusConfig {
     pub fn leader_reputation_exclude_round(&self) -> u64 {
         match &self {
             OnChainConsensusConfig::V2(config) => config.exclude_round,
+            OnChainConsensusConfig::V3(config) => config.exclude_round,
             // default value before onchain config
             _ => 4,
         }
@@ -35,6 +38,7 @@ impl OnChainConsensusConfig {
     pub fn decoupled_execution(&self) -> bool {
         match &self {
             OnChainConsensusConfig::V2(config) => config.decoupled_execution,
+            OnChainConsensusConfig::V3(config) => config.decoupled_execution,
             _ => false,
         }
     }
@@ -48,9 +52,39 @@ impl OnChainConsensusConfig {
         }
         match &self {
             OnChainConsensusConfig::V2(config) => config.back_pressure_limit,
+            OnChainConsensusConfig::V3(config) => config.back_pressure_limit,
             _ => 10,
         }
     }
+
+    /// Max total size in bytes of the transactions of a block, larger proposals are rejected.
+    pub fn max_block_bytes(&self) -> u64 {
+        match &self {
+            OnChainConsensusConfig::V3(config) => config.max_block_bytes,
+            // no limit before onchain config
+            _ => u64::MAX,
+        }
+    }
+
+    /// Whether the proposals carry proofs of store of batches instead of transactions.
+    /// The quorum store requires decoupled execution.
+    pub fn quorum_store_enabled(&self) -> bool {
+        match &self {
+            OnChainConsensusConfig::V3(config) => {
+                config.quorum_store_enabled && config.decoupled_execution
+            }
+            _ => false,
+        }
+    }
+
+    /// Max number of the failed proposers of the previous rounds recorded in a block, the blocks
+    /// record none in their legacy format when 0.
+    pub fn max_failed_authors_to_store(&self) -> usize {
+        match &self {
+            OnChainConsensusConfig::V3(config) => config.max_failed_authors_to_store,
+            _ => 0,
+        }
+    }
 }
 
 /// This is used when on-chain config is not initialized.
@@ -73,6 +107,17 @@ pub struct ConsensusConfigV2 {
     pub exclude_round: u64,
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ConsensusConfigV3 {
+    pub two_chain: bool,
+    pub decoupled_execution: bool,
+    pub back_pressure_limit: u64,
+    pub exclude_round: u64,
+    pub max_block_bytes: u64,
+    pub quorum_store_enabled: bool,
+    pub max_failed_authors_to_store: usize,
+}
+
 impl OnChainConfig for OnChainConsensusConfig {
     const IDENTIFIER: &'static str = "ConsensusConfig";
 
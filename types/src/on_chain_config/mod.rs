@@ -29,7 +29,9 @@ pub use self::{
     aptos_version::{
         Version, APTOS_MAX_KNOWN_VERSION, APTOS_VERSION_2, APTOS_VERSION_3, APTOS_VERSION_4,
     },
-    consensus_config::{ConsensusConfigV1, ConsensusConfigV2, OnChainConsensusConfig},
+    consensus_config::{
+        ConsensusConfigV1, ConsensusConfigV2, ConsensusConfigV3, OnChainConsensusConfig,
+    },
     parallel_execution_config::{ParallelExecutionConfig, ReadWriteSetAnalysis},
     registered_currencies::RegisteredCurrencies,
     validator_set::ValidatorSet,
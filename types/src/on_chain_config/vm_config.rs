@@ -36,6 +36,21 @@ impl CostTableInner {
     }
 }
 
+impl VMConfig {
+    /// Serializes the config in the layout of the on-chain `VMConfig` resource, the inverse of
+    /// `deserialize_into_config`.
+    pub fn serialize_into_config(&self) -> Result<Vec<u8>> {
+        let raw_vm_config = VMConfigInner {
+            gas_schedule: CostTableInner {
+                instruction_table: bcs::to_bytes(&self.gas_schedule.instruction_table)?,
+                native_table: bcs::to_bytes(&self.gas_schedule.native_table)?,
+                gas_constants: self.gas_schedule.gas_constants.clone(),
+            },
+        };
+        Ok(bcs::to_bytes(&raw_vm_config)?)
+    }
+}
+
 impl OnChainConfig for VMConfig {
     const IDENTIFIER: &'static str = "VMConfig";
 
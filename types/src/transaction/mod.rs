@@ -3,7 +3,7 @@
 
 use crate::{
     account_address::AccountAddress,
-    block_metadata::BlockMetadata,
+    block_metadata::{BlockMetadata, BlockMetadataV2},
     chain_id::ChainId,
     contract_event::ContractEvent,
     ledger_info::LedgerInfo,
@@ -615,6 +615,13 @@ impl SignedTransaction {
             .len()
     }
 
+    /// Size of the transaction including its authenticator, as sent over the network
+    pub fn txn_bytes_len(&self) -> usize {
+        bcs::to_bytes(self)
+            .expect("Unable to serialize SignedTransaction")
+            .len()
+    }
+
     /// Checks that the signature of given transaction. Returns `Ok(SignatureCheckedTransaction)` if
     /// the signature is valid.
     pub fn check_signature(self) -> Result<SignatureCheckedTransaction> {
@@ -1431,6 +1438,10 @@ pub enum Transaction {
     /// Transaction to let the executor update the global state tree and record the root hash
     /// in the TransactionInfo
     StateCheckpoint,
+
+    /// Transaction to update the block metadata resource at the beginning of a block, which also
+    /// records the proposers of the rounds that failed since the previous block.
+    BlockMetadataV2(BlockMetadataV2),
 }
 
 impl Transaction {
@@ -1452,6 +1463,8 @@ impl Transaction {
             Transaction::BlockMetadata(_block_metadata) => String::from("block_metadata"),
             // TODO: display proper information for client
             Transaction::StateCheckpoint => String::from("state_checkpoint"),
+            // TODO: display proper information for client
+            Transaction::BlockMetadataV2(_block_metadata) => String::from("block_metadata"),
         }
     }
 }